@@ -0,0 +1,76 @@
+//! Benchmarks for rewriting a large Steam config.
+//!
+//! The fixture is a generated `config.vdf` of about 4 MB with a `CompatToolMapping` entry for every installed app,
+//! which is the size of the config of a huge Steam library. `into_bytes` measures the conversion into a `Vec<u8>` that
+//! is written into the file afterwards, `write_to` streams the lines into the file instead.
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use assert_fs::prelude::*;
+use assert_fs::NamedTempFile;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use ge_man_lib::config::SteamConfig;
+
+const FIXTURE_SIZE: usize = 4 * 1024 * 1024;
+
+fn generate_config() -> String {
+    let mut config = String::from(
+        "\"InstallConfigStore\"\n{\n\t\"Software\"\n\t{\n\t\t\"Valve\"\n\t\t{\n\t\t\t\"Steam\"\n\t\t\t{\n\t\t\t\t\"CompatToolMapping\"\n\t\t\t\t{\n",
+    );
+    let mut app_id = 0;
+    while config.len() < FIXTURE_SIZE {
+        let name = if app_id == 0 { "Proton-6.21-GE-2" } else { "proton_63" };
+        config.push_str(&format!(
+            "\t\t\t\t\t\"{}\"\n\t\t\t\t\t{{\n\t\t\t\t\t\t\"name\"\t\t\"{}\"\n\t\t\t\t\t\t\"config\"\t\t\"\"\n\t\t\t\t\t\t\"Priority\"\t\t\"250\"\n\t\t\t\t\t}}\n",
+            app_id, name
+        ));
+        app_id += 1;
+    }
+    config.push_str("\t\t\t\t}\n\t\t\t}\n\t\t}\n\t}\n}\n");
+    config
+}
+
+fn rewrite(c: &mut Criterion) {
+    let fixture = NamedTempFile::new("config.vdf").unwrap();
+    fixture.write_str(&generate_config()).unwrap();
+    let output = NamedTempFile::new("config.vdf.tmp").unwrap();
+
+    let mut group = c.benchmark_group("steam_config");
+    group.throughput(Throughput::Bytes(FIXTURE_SIZE as u64));
+    group.sample_size(20);
+
+    group.bench_function("create_copy", |b| {
+        b.iter(|| SteamConfig::create_copy(fixture.path()).unwrap())
+    });
+    group.bench_function("into_bytes", |b| {
+        b.iter_batched(
+            || modified_config(&fixture),
+            |config| {
+                let bytes: Vec<u8> = config.into();
+                std::fs::write(output.path(), bytes).unwrap();
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("write_to", |b| {
+        b.iter_batched(
+            || modified_config(&fixture),
+            |config| {
+                let mut writer = BufWriter::new(File::create(output.path()).unwrap());
+                config.write_to(&mut writer).unwrap();
+                writer.flush().unwrap();
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn modified_config(fixture: &NamedTempFile) -> SteamConfig {
+    let mut config = SteamConfig::create_copy(fixture.path()).unwrap();
+    config.set_proton_version("GE-Proton8-26");
+    config
+}
+
+criterion_group!(benches, rewrite);
+criterion_main!(benches);
@@ -0,0 +1,166 @@
+//! `proptest` support for property based tests.
+//!
+//! With the `proptest` feature `Arbitrary` is implemented for `TagKind`, `SemVer` and `Tag`. Generated tags use the
+//! naming schemes of the upstream releases of their kind, e.g. `GE-Proton7-8`, `Proton-5.0-rc5-GE-1`, `5.11-GE-1-MF`,
+//! `7.0rc3-GE-1`, `lutris-GE-Proton8-26-x86_64` or `6.16-2-GE-LoL`, so parsing them produces the version they were
+//! generated from.
+//!
+//! # Examples
+//!
+//! ```
+//! use ge_man_lib::tag::{Tag, TagKind};
+//! use proptest::prelude::*;
+//! use proptest::test_runner::TestRunner;
+//!
+//! let mut runner = TestRunner::default();
+//! runner
+//!     .run(&any_with::<Tag>(Some(TagKind::Proton)), |tag| {
+//!         prop_assert_eq!(tag.kind(), Some(TagKind::Proton));
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! ```
+use proptest::prelude::*;
+
+use crate::tag::{SemVer, Tag, TagKind};
+
+impl Arbitrary for TagKind {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![Just(TagKind::Proton), Just(TagKind::wine()), Just(TagKind::lol())].boxed()
+    }
+}
+
+impl Arbitrary for SemVer {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Generate versions with up to two build numbers and an optional release candidate, `LoL` or `MF` identifier.
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        let identifier = prop_oneof![
+            Just(None),
+            any::<u32>().prop_map(|rc| Some(format!("rc{}", rc))),
+            Just(Some(String::from("LoL"))),
+            Just(Some(String::from("MF"))),
+        ];
+        (
+            any::<(u8, u8, u8)>(),
+            prop::collection::vec(any::<u32>(), 0..=2),
+            identifier,
+        )
+            .prop_map(|((major, minor, patch), build, identifier)| {
+                let mut version = format!("{}.{}.{}", major, minor, patch);
+                if let Some(identifier) = identifier {
+                    version.push_str(&format!("-{}", identifier));
+                }
+                if !build.is_empty() {
+                    let build: Vec<String> = build.iter().map(u32::to_string).collect();
+                    version.push_str(&format!("+{}", build.join(".")));
+                }
+                version.parse().expect("Generated version is valid")
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Tag {
+    /// Generate tags of this kind only. Tags of any kind are generated for `None`.
+    type Parameters = Option<TagKind>;
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Generate tags with an upstream naming scheme of their kind. The tags are bound to their kind.
+    fn arbitrary_with(kind: Self::Parameters) -> Self::Strategy {
+        let kind = match kind {
+            Some(kind) => Just(kind).boxed(),
+            None => any::<TagKind>(),
+        };
+        kind.prop_flat_map(|kind| {
+            upstream_tag(kind)
+                .prop_map(move |(tag, _)| Tag::with_kind(tag, kind).expect("Generated tag matches its kind"))
+        })
+        .boxed()
+    }
+}
+
+/// Generate an upstream tag string of `kind` together with the version string it represents.
+fn upstream_tag(kind: TagKind) -> BoxedStrategy<(String, String)> {
+    let numbers = any::<(u8, u8, u8)>();
+    match kind {
+        TagKind::Proton => prop_oneof![
+            numbers.prop_map(|(ma, mi, _)| (format!("GE-Proton{}-{}", ma, mi), format!("{}.{}.0", ma, mi))),
+            numbers.prop_map(|(ma, mi, pa)| (
+                format!("Proton-{}.{}-GE-{}", ma, mi, pa),
+                format!("{}.{}.{}", ma, mi, pa)
+            )),
+            numbers.prop_map(|(ma, mi, pa)| (
+                format!("{}.{}-GE-{}-MF", ma, mi, pa),
+                format!("{}.{}.{}-MF", ma, mi, pa)
+            )),
+            (numbers, any::<u32>()).prop_map(|((ma, mi, pa), rc)| (
+                format!("Proton-{}.{}-rc{}-GE-{}", ma, mi, rc, pa),
+                format!("{}.{}.{}-rc{}", ma, mi, pa, rc)
+            )),
+        ]
+        .boxed(),
+        TagKind::Wine { .. } if kind == TagKind::lol() => prop_oneof![
+            numbers.prop_map(|(ma, mi, pa)| (
+                format!("{}.{}-GE-{}-LoL", ma, mi, pa),
+                format!("{}.{}.{}-LoL", ma, mi, pa)
+            )),
+            numbers.prop_map(|(ma, mi, pa)| (
+                format!("{}.{}-{}-GE-LoL", ma, mi, pa),
+                format!("{}.{}.{}-LoL", ma, mi, pa)
+            )),
+        ]
+        .boxed(),
+        TagKind::Wine { .. } => prop_oneof![
+            numbers.prop_map(|(ma, mi, pa)| (format!("{}.{}-GE-{}", ma, mi, pa), format!("{}.{}.{}", ma, mi, pa))),
+            numbers.prop_map(|(ma, mi, pa)| (
+                format!("lutris-ge-{}.{}-{}-x86_64", ma, mi, pa),
+                format!("{}.{}.{}", ma, mi, pa)
+            )),
+            numbers.prop_map(|(ma, mi, _)| (
+                format!("lutris-GE-Proton{}-{}-x86_64", ma, mi),
+                format!("{}.{}.0", ma, mi)
+            )),
+            (numbers, any::<u32>()).prop_map(|((ma, mi, pa), rc)| (
+                format!("{}.{}rc{}-GE-{}", ma, mi, rc, pa),
+                format!("{}.{}.{}-rc{}", ma, mi, pa, rc)
+            )),
+        ]
+        .boxed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind_and_upstream_tag() -> impl Strategy<Value = (TagKind, (String, String))> {
+        any::<TagKind>().prop_flat_map(|kind| (Just(kind), upstream_tag(kind)))
+    }
+
+    proptest! {
+        #[test]
+        fn upstream_tags_are_parsed_into_their_version((kind, (tag, version)) in kind_and_upstream_tag()) {
+            let expected: SemVer = version.parse().unwrap();
+            let parsed = Tag::new(&tag);
+            prop_assert_eq!(parsed.semver(), &expected);
+            prop_assert_eq!(Tag::with_kind(tag, kind).unwrap().kind(), Some(kind));
+        }
+
+        #[test]
+        fn canonical_tags_are_parsed_into_their_version(semver in any::<SemVer>(), kind in any::<TagKind>()) {
+            let tag = Tag::from_semver(&semver, kind);
+            prop_assert_eq!(tag.semver(), &semver);
+        }
+
+        #[test]
+        fn generated_tags_are_bound_to_the_requested_kind(tag in any_with::<Tag>(Some(TagKind::lol()))) {
+            prop_assert_eq!(tag.kind(), Some(TagKind::lol()));
+            prop_assert!(tag.is_lol());
+        }
+    }
+}
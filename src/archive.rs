@@ -2,17 +2,48 @@
 //!
 //! This module defines operations that work with compressed archives from GloriousEgroll's Proton and Wine
 //! releases.
+use std::collections::HashSet;
+use std::fs;
+use std::fs::File;
 use std::io;
 use std::io::Read;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
 
-use data_encoding::HEXLOWER;
 use flate2::read::GzDecoder;
-use tar::Archive;
+use tar::{Archive, EntryType};
 use xz2::read::XzDecoder;
+use zstd::stream::read::Decoder as ZstdDecoder;
 
+use crate::digest;
+use crate::digest::{ChecksumFile, Digest};
+use crate::download::response::AssetContentType;
+use crate::error::{ChecksumError, ExtractError};
 use crate::tag::TagKind;
 
+const SETUID_SETGID_MASK: u32 = 0o6000;
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const XZ_MAGIC: [u8; 6] = [0xfd, b'7', b'z', b'X', b'Z', 0x00];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Options for extracting a compressed archive with `extract_compressed_with_options`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ExtractOptions {
+    /// Remove the setuid and setgid bits from the modes of extracted files and directories.
+    pub sanitize_modes: bool,
+    /// Set the modification times of extracted files and directories to the times stored in the archive.
+    pub preserve_mtime: bool,
+}
+
+impl Default for ExtractOptions {
+    fn default() -> Self {
+        ExtractOptions {
+            sanitize_modes: false,
+            preserve_mtime: true,
+        }
+    }
+}
+
 /// Compares a given checksum to the checksum generated from the provided compressed archive file.
 ///
 /// Generates a checksum from the provided `compressed_archive` file and compares it to the `expected_sum`. This method
@@ -20,6 +51,8 @@ use crate::tag::TagKind;
 /// `compressed_archive`. This is done because GE releases provide checksums with the sha512sum tool which also outputs
 /// the file name additionally to the generated sum.
 ///
+/// The checksum is generated with the `digest::DefaultDigester`.
+///
 /// # Examples
 ///
 /// Comparing checksums where `expected_sum` contains no file name.
@@ -41,14 +74,93 @@ pub fn checksums_match(compressed_tar: &[u8], expected_sum: &[u8]) -> bool {
         .split_whitespace()
         .next()
         .map(String::from)
-        .unwrap_or(String::new());
+        .unwrap_or_default();
 
-    let digest = ring::digest::digest(&ring::digest::SHA512, compressed_tar);
-    let sum = HEXLOWER.encode(digest.as_ref());
+    let sum = digest::sha512(compressed_tar).to_hex();
 
     expected_sum.eq(&sum)
 }
 
+/// Verifies a compressed archive against the content of its `sha512sum` checksum file.
+///
+/// The checksum file is parsed with `ChecksumFile::parse`, see `ChecksumFile` for the accepted format. Digests are
+/// compared case-insensitively. Use `verify_named` to also check the file name.
+///
+/// Invalid UTF-8 in `checksum_file` is replaced before parsing it.
+///
+/// # Errors
+///
+/// * `ChecksumError::MalformedChecksumFile` if the checksum file can not be parsed
+/// * `ChecksumError::Mismatch` if the digest of `compressed_archive` is not the expected digest
+///
+/// # Examples
+///
+/// ```ignore
+/// let archive = std::fs::read("GE-Proton7-8.tar.gz").unwrap();
+/// let checksum_file = std::fs::read("GE-Proton7-8.sha512sum").unwrap();
+/// archive::verify(&archive, &checksum_file)?;
+/// ```
+pub fn verify(compressed_archive: &[u8], checksum_file: &[u8]) -> Result<(), ChecksumError> {
+    let checksum_file = ChecksumFile::parse(&String::from_utf8_lossy(checksum_file))?;
+    verify_digest(&digest::sha512(compressed_archive), &checksum_file)
+}
+
+/// Verifies a compressed archive named `file_name` against the content of its `sha512sum` checksum file.
+///
+/// Works like `verify`, but additionally checks that the file name in the checksum file is `file_name`. Directories
+/// in the file name of the checksum file are ignored. A checksum file without a file name is accepted.
+///
+/// # Errors
+///
+/// The errors of `verify` and `ChecksumError::FileNameMismatch` if the checksum file is for another file.
+pub fn verify_named(file_name: &str, compressed_archive: &[u8], checksum_file: &[u8]) -> Result<(), ChecksumError> {
+    verify_named_digest(file_name, &digest::sha512(compressed_archive), checksum_file)
+}
+
+/// Verifies the already computed `digest` of a compressed archive named `file_name` against the content of its
+/// `sha512sum` checksum file.
+///
+/// Works like `verify_named` for a digest that was computed with `digest::Sha512Hasher`, e.g. while the archive was
+/// downloaded.
+///
+/// # Errors
+///
+/// The errors of `verify_named`.
+pub fn verify_named_digest(file_name: &str, digest: &Digest, checksum_file: &[u8]) -> Result<(), ChecksumError> {
+    let checksum_file = ChecksumFile::parse(&String::from_utf8_lossy(checksum_file))?;
+    verify_checksum_file(file_name, digest, &checksum_file)
+}
+
+/// Verifies the `digest` of a compressed archive named `file_name` against an already parsed `checksum_file`, like
+/// `verify_named_digest`.
+pub(crate) fn verify_checksum_file(
+    file_name: &str,
+    digest: &Digest,
+    checksum_file: &ChecksumFile,
+) -> Result<(), ChecksumError> {
+    if let Some(found) = checksum_file.file_name() {
+        let found_name = Path::new(found).file_name().and_then(|name| name.to_str());
+        if found_name != Some(file_name) {
+            return Err(ChecksumError::FileNameMismatch {
+                expected: String::from(file_name),
+                found: String::from(found),
+            });
+        }
+    }
+    verify_digest(digest, checksum_file)
+}
+
+fn verify_digest(actual: &Digest, checksum_file: &ChecksumFile) -> Result<(), ChecksumError> {
+    if actual == checksum_file.digest() {
+        Ok(())
+    } else {
+        Err(ChecksumError::Mismatch {
+            expected: checksum_file.digest_hex(),
+            actual: actual.to_hex(),
+        })
+    }
+}
+
 /// Extracts a compressed archive for a tag kind into the given `extract_destination` and returns a `PathBuf` to the
 /// extracted location.
 ///
@@ -95,37 +207,231 @@ pub fn extract_compressed(
     compressed_tar: impl Read,
     extract_destination: &Path,
 ) -> Result<PathBuf, io::Error> {
-    let extracted_dst = match kind {
-        TagKind::Proton => {
-            let decoder = GzDecoder::new(compressed_tar);
-            extract_tar(decoder, extract_destination)?
+    extract_compressed_with_options(kind, compressed_tar, extract_destination, &ExtractOptions::default())
+}
+
+/// Extracts a compressed archive for a tag kind like `extract_compressed` with the given `options`.
+///
+/// The modes of all files and directories are applied exactly as stored in the archive and are not filtered through
+/// the umask of the process. Only the setuid and setgid bits are removed if `ExtractOptions::sanitize_modes` is set.
+/// Directories are extracted after their contents, so that read-only directory modes and directory modification
+/// times are not affected by the files written into them.
+///
+/// # Errors
+///
+/// This method returns the same errors as `extract_compressed`.
+pub fn extract_compressed_with_options(
+    kind: &TagKind,
+    compressed_tar: impl Read,
+    extract_destination: &Path,
+    options: &ExtractOptions,
+) -> Result<PathBuf, io::Error> {
+    let compression = match kind {
+        TagKind::Proton => Compression::Gzip,
+        TagKind::Wine { .. } => Compression::Xz,
+    };
+
+    extract_with_compression(compression, compressed_tar, extract_destination, options).map_err(|err| match err {
+        ExtractError::IoError { source } => source,
+        err => io::Error::new(io::ErrorKind::InvalidData, err),
+    })
+}
+
+/// Extracts a `compression` compressed archive into the given `extract_destination` and returns a `PathBuf` to the
+/// top-level directory of the archive.
+///
+/// The archive is extracted like with `extract_compressed_with_options`. Entries with absolute paths or paths that
+/// contain `..` are rejected. If the extraction fails, every top-level file or directory that was created by the
+/// extraction is removed again.
+///
+/// # Errors
+///
+/// * `ExtractError::PathTraversal` if an entry would be extracted outside of `extract_destination`
+/// * `ExtractError::EmptyArchive` if the archive contains no entries
+/// * `ExtractError::IoError` if decompressing the archive or writing its contents fails
+pub fn extract_with_compression(
+    compression: Compression,
+    compressed_tar: impl Read,
+    extract_destination: &Path,
+    options: &ExtractOptions,
+) -> Result<PathBuf, ExtractError> {
+    let mut created = Vec::new();
+    let result = extract_tracked(compression, compressed_tar, extract_destination, options, &mut created);
+    if result.is_err() {
+        remove_created(&created);
+    }
+    result
+}
+
+/// Extracts an archive like `extract_with_compression`, but leaves the cleanup to the caller.
+///
+/// Every top-level file or directory that is created by the extraction is added to `created`, also if the extraction
+/// fails. This allows a caller to remove the extracted files if a later step fails, e.g. the verification of the
+/// archive.
+pub(crate) fn extract_tracked(
+    compression: Compression,
+    compressed_tar: impl Read,
+    extract_destination: &Path,
+    options: &ExtractOptions,
+    created: &mut Vec<PathBuf>,
+) -> Result<PathBuf, ExtractError> {
+    let decoder: Box<dyn Read> = match compression {
+        Compression::Gzip => Box::new(GzDecoder::new(compressed_tar)),
+        Compression::Xz => Box::new(XzDecoder::new(compressed_tar)),
+        Compression::Zstd => Box::new(ZstdDecoder::new(compressed_tar)?),
+    };
+    extract_tar(decoder, extract_destination, options, created)
+}
+
+/// Remove the `created` files and directories of a failed extraction.
+pub(crate) fn remove_created(created: &[PathBuf]) {
+    for path in created {
+        // Cleanup is best effort, the extraction error is more relevant than a failed removal.
+        let _ = if path.is_dir() {
+            fs::remove_dir_all(path)
+        } else {
+            fs::remove_file(path)
+        };
+    }
+}
+
+/// Compression format of a release archive.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Compression {
+    /// A `.tar.gz` archive, used by GE Proton releases.
+    Gzip,
+    /// A `.tar.xz` archive, used by Wine GE releases.
+    Xz,
+    /// A `.tar.zst` archive, used by some forks.
+    Zstd,
+}
+
+impl Compression {
+    /// Get the compression format from the extension of an archive's file name.
+    ///
+    /// The extensions `.tar.gz`, `.tgz`, `.tar.xz`, `.txz`, `.tar.zst` and `.tzst` are recognized.
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        let file_name = file_name.to_ascii_lowercase();
+        if file_name.ends_with(".tar.gz") || file_name.ends_with(".tgz") {
+            Some(Compression::Gzip)
+        } else if file_name.ends_with(".tar.xz") || file_name.ends_with(".txz") {
+            Some(Compression::Xz)
+        } else if file_name.ends_with(".tar.zst") || file_name.ends_with(".tzst") {
+            Some(Compression::Zstd)
+        } else {
+            None
         }
-        TagKind::Wine { .. } => {
-            let decoder = XzDecoder::new(compressed_tar);
-            extract_tar(decoder, extract_destination)?
+    }
+
+    /// Get the compression format from the content type of a release asset, e.g. `application/gzip`.
+    ///
+    /// The content type is parsed with `AssetContentType::from`. Generic content types like `application/octet-stream`
+    /// tell nothing about the compression and return `None`.
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        AssetContentType::from(content_type).compression()
+    }
+
+    /// Get the compression format from the magic bytes at the start of a compressed archive.
+    pub fn from_magic_bytes(compressed_tar: &[u8]) -> Option<Self> {
+        if compressed_tar.starts_with(&GZIP_MAGIC) {
+            Some(Compression::Gzip)
+        } else if compressed_tar.starts_with(&XZ_MAGIC) {
+            Some(Compression::Xz)
+        } else if compressed_tar.starts_with(&ZSTD_MAGIC) {
+            Some(Compression::Zstd)
+        } else {
+            None
         }
-    };
+    }
+}
 
-    Ok(extracted_dst)
+/// A compatibility tool that was extracted from a release archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractedTool {
+    /// Path of the top-level directory that was created by the extraction.
+    pub path: PathBuf,
+    /// Compression format of the extracted archive.
+    pub compression: Compression,
 }
 
-fn extract_tar(decoder: impl Read, extract_destination: &Path) -> Result<PathBuf, std::io::Error> {
+fn extract_tar(
+    decoder: impl Read,
+    extract_destination: &Path,
+    options: &ExtractOptions,
+    created: &mut Vec<PathBuf>,
+) -> Result<PathBuf, ExtractError> {
     let mut archive = Archive::new(decoder);
+    archive.set_preserve_permissions(true);
+    archive.set_preserve_mtime(options.preserve_mtime);
+    if options.sanitize_modes {
+        archive.set_mask(SETUID_SETGID_MASK);
+    }
 
-    let mut iter = archive.entries()?;
-    let first_entry = &mut iter.next().unwrap()?;
+    let mut dir_name = None;
+    let mut top_level_paths = HashSet::new();
+    let mut directories = Vec::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        if is_traversal(&path) {
+            return Err(ExtractError::PathTraversal { path });
+        }
+        if entry.header().entry_type().is_hard_link() {
+            if let Some(link_name) = entry.link_name()? {
+                if is_traversal(&link_name) {
+                    return Err(ExtractError::PathTraversal {
+                        path: link_name.into_owned(),
+                    });
+                }
+            }
+        }
 
-    let dir_name = first_entry.path().unwrap().into_owned();
+        if let Some(top_level) = top_level_component(&path) {
+            if top_level_paths.insert(top_level.clone()) {
+                let top_level_path = extract_destination.join(&top_level);
+                if fs::symlink_metadata(&top_level_path).is_err() {
+                    created.push(top_level_path);
+                }
+            }
+            dir_name.get_or_insert(top_level);
+        }
 
-    first_entry.unpack_in(extract_destination)?;
-    for entry in iter {
-        let mut entry = entry?;
-        entry.unpack_in(extract_destination)?;
+        // Directories are created on demand for their contents and get their final mode and mtime at the end.
+        if entry.header().entry_type() == EntryType::Directory {
+            directories.push(entry);
+        } else {
+            entry.unpack_in(extract_destination)?;
+        }
+    }
+
+    // Extract nested directories before their parents, so that a parent's mtime is set last.
+    directories.sort_by(|a, b| b.path_bytes().cmp(&a.path_bytes()));
+    for mut directory in directories {
+        if directory.unpack_in(extract_destination)? && options.preserve_mtime {
+            // The tar crate only restores the mtime of files, so the mtime of directories is set here.
+            let mtime = UNIX_EPOCH + Duration::from_secs(directory.header().mtime()?);
+            File::open(extract_destination.join(directory.path()?))?.set_modified(mtime)?;
+        }
     }
 
+    let dir_name = dir_name.ok_or(ExtractError::EmptyArchive)?;
     Ok(extract_destination.join(dir_name))
 }
 
+/// Would `path` be extracted outside of the destination directory, because it is absolute or contains `..`.
+fn is_traversal(path: &Path) -> bool {
+    path.components()
+        .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+/// Get the first normal component of a path, i.e. the top-level file or directory it is extracted into.
+fn top_level_component(path: &Path) -> Option<PathBuf> {
+    path.components().find_map(|component| match component {
+        Component::Normal(name) => Some(PathBuf::from(name)),
+        _ => None,
+    })
+}
+
 #[cfg(test)]
 mod checksum_tests {
     use std::fs;
@@ -162,6 +468,76 @@ mod checksum_tests {
     }
 }
 
+#[cfg(test)]
+mod verify_tests {
+    use std::fs;
+
+    use test_case::test_case;
+
+    use super::*;
+
+    const TEST_GZ_SUM: &str = "f2ad7b96bb24ae5fa71398127927b22c8c11eba2d3578df5a47e6ad5b5a06b0c4c66d25cf53bed0d9ed0864b76aea73794cc4be7f01249f43b796f70d068f972";
+
+    fn archive() -> Vec<u8> {
+        fs::read("test_resources/assets/test.tar.gz").unwrap()
+    }
+
+    #[test]
+    fn verify_checksum_file() {
+        let checksum_file = fs::read("test_resources/assets/test-gz.sha512sum").unwrap();
+        assert_eq!(verify(&archive(), &checksum_file), Ok(()));
+        assert_eq!(verify_named("test.tar.gz", &archive(), &checksum_file), Ok(()));
+    }
+
+    #[test_case(format!("{}  test.tar.gz\n", TEST_GZ_SUM.to_uppercase()); "uppercase digest")]
+    #[test_case(format!("{} *test.tar.gz", TEST_GZ_SUM); "binary mode")]
+    #[test_case(format!("{}  ./assets/test.tar.gz", TEST_GZ_SUM); "file name with directory")]
+    #[test_case(format!("\n{}\n", TEST_GZ_SUM); "without file name")]
+    fn verify_accepts_format_variants(checksum_file: String) {
+        assert_eq!(
+            verify_named("test.tar.gz", &archive(), checksum_file.as_bytes()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn verify_detects_mismatch() {
+        let checksum_file = format!("{}  test.tar.gz", "0".repeat(128));
+        let err = verify(&archive(), checksum_file.as_bytes()).unwrap_err();
+        assert_eq!(
+            err,
+            ChecksumError::Mismatch {
+                expected: "0".repeat(128),
+                actual: String::from(TEST_GZ_SUM),
+            }
+        );
+    }
+
+    #[test]
+    fn verify_named_detects_file_name_mismatch() {
+        let checksum_file = format!("{}  other.tar.gz", TEST_GZ_SUM);
+        let err = verify_named("test.tar.gz", &archive(), checksum_file.as_bytes()).unwrap_err();
+        assert_eq!(
+            err,
+            ChecksumError::FileNameMismatch {
+                expected: String::from("test.tar.gz"),
+                found: String::from("other.tar.gz"),
+            }
+        );
+    }
+
+    #[test_case(b""; "empty")]
+    #[test_case(b"  \n\n"; "whitespace only")]
+    #[test_case(b"unreal-checksum  test.tar.gz"; "not hex")]
+    #[test_case(b"abcdef  test.tar.gz"; "too short")]
+    #[test_case(b"\xff\xfe"; "not utf8")]
+    #[test_case(b"unreal-checksum  test.tar.gz\nunreal-checksum  test.tar.xz"; "multiple lines")]
+    fn verify_rejects_malformed_checksum_file(checksum_file: &[u8]) {
+        let err = verify(&archive(), checksum_file).unwrap_err();
+        assert!(matches!(err, ChecksumError::MalformedChecksumFile { .. }), "{:?}", err);
+    }
+}
+
 #[cfg(test)]
 mod extraction_tests {
     use std::fs::File;
@@ -247,6 +623,209 @@ mod extraction_tests {
         tmp_dir.close().unwrap();
     }
 
+    const MTIME: u64 = 1647801012;
+
+    fn append_entry(builder: &mut tar::Builder<Vec<u8>>, path: &str, entry_type: EntryType, mode: u32) {
+        let data: &[u8] = if entry_type == EntryType::Directory {
+            b""
+        } else {
+            b"#!/bin/sh\n"
+        };
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(entry_type);
+        header.set_mode(mode);
+        header.set_mtime(MTIME);
+        header.set_size(data.len() as u64);
+        builder.append_data(&mut header, path, data).unwrap();
+    }
+
+    fn archive_with_modes() -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        append_entry(&mut builder, "modes/", EntryType::Directory, 0o755);
+        append_entry(&mut builder, "modes/bin/", EntryType::Directory, 0o555);
+        append_entry(&mut builder, "modes/bin/wine", EntryType::Regular, 0o777);
+        append_entry(&mut builder, "modes/bin/wineserver", EntryType::Regular, 0o4755);
+        append_entry(&mut builder, "modes/shared/", EntryType::Directory, 0o2775);
+        append_entry(&mut builder, "modes/shared/notes.txt", EntryType::Regular, 0o640);
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        std::io::Write::write_all(&mut encoder, &builder.into_inner().unwrap()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn mode(path: &Path) -> u32 {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::metadata(path).unwrap().permissions().mode() & 0o7777
+    }
+
+    fn mtime(path: &Path) -> u64 {
+        let modified = std::fs::metadata(path).unwrap().modified().unwrap();
+        modified.duration_since(std::time::UNIX_EPOCH).unwrap().as_secs()
+    }
+
+    #[test]
+    fn extract_with_exact_modes_and_mtimes() {
+        let tmp_dir = TempDir::new().unwrap();
+        let archive = archive_with_modes();
+
+        let dst = extract_compressed(&TagKind::Proton, archive.as_slice(), tmp_dir.path()).unwrap();
+
+        assert_eq!(dst, tmp_dir.join("modes"));
+        assert_eq!(mode(&dst), 0o755);
+        assert_eq!(mode(&dst.join("bin")), 0o555);
+        assert_eq!(mode(&dst.join("bin/wine")), 0o777);
+        assert_eq!(mode(&dst.join("bin/wineserver")), 0o4755);
+        assert_eq!(mode(&dst.join("shared")), 0o2775);
+        assert_eq!(mode(&dst.join("shared/notes.txt")), 0o640);
+        for path in ["", "bin", "bin/wine", "shared", "shared/notes.txt"] {
+            assert_eq!(mtime(&dst.join(path)), MTIME, "Unexpected mtime for {}", path);
+        }
+
+        // Allow the temporary directory to be removed.
+        std::fs::set_permissions(dst.join("bin"), std::os::unix::fs::PermissionsExt::from_mode(0o755)).unwrap();
+        tmp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn extract_with_sanitized_modes_and_without_mtimes() {
+        let tmp_dir = TempDir::new().unwrap();
+        let archive = archive_with_modes();
+        let options = ExtractOptions {
+            sanitize_modes: true,
+            preserve_mtime: false,
+        };
+
+        let dst =
+            extract_compressed_with_options(&TagKind::Proton, archive.as_slice(), tmp_dir.path(), &options).unwrap();
+
+        assert_eq!(mode(&dst.join("bin/wine")), 0o777);
+        assert_eq!(mode(&dst.join("bin/wineserver")), 0o755);
+        assert_eq!(mode(&dst.join("shared")), 0o775);
+        assert_ne!(mtime(&dst.join("shared/notes.txt")), MTIME);
+
+        std::fs::set_permissions(dst.join("bin"), std::os::unix::fs::PermissionsExt::from_mode(0o755)).unwrap();
+        tmp_dir.close().unwrap();
+    }
+
+    fn gzip(tar: Vec<u8>) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        std::io::Write::write_all(&mut encoder, &tar).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    /// Append an entry without the path validation of `tar::Builder`.
+    fn append_raw_entry(builder: &mut tar::Builder<Vec<u8>>, path: &str) {
+        let data = b"evil";
+        let mut header = tar::Header::new_old();
+        header.as_old_mut().name[..path.len()].copy_from_slice(path.as_bytes());
+        header.set_entry_type(EntryType::Regular);
+        header.set_mode(0o644);
+        header.set_size(data.len() as u64);
+        header.set_cksum();
+        builder.append(&header, data.as_slice()).unwrap();
+    }
+
+    #[test_case("../evil.txt"; "parent directory")]
+    #[test_case("tool/../../evil.txt"; "nested parent directory")]
+    #[test_case("/tmp/evil.txt"; "absolute path")]
+    fn reject_path_traversal_and_clean_up(path: &str) {
+        let tmp_dir = TempDir::new().unwrap();
+        let dst = tmp_dir.child("compatibilitytools.d");
+        std::fs::create_dir(&dst).unwrap();
+        std::fs::write(dst.join("existing.txt"), "keep").unwrap();
+
+        let mut builder = tar::Builder::new(Vec::new());
+        append_entry(&mut builder, "tool/", EntryType::Directory, 0o755);
+        append_entry(&mut builder, "tool/proton", EntryType::Regular, 0o755);
+        append_entry(&mut builder, "other.txt", EntryType::Regular, 0o644);
+        append_raw_entry(&mut builder, path);
+        let archive = gzip(builder.into_inner().unwrap());
+
+        let err = extract_with_compression(Compression::Gzip, archive.as_slice(), &dst, &ExtractOptions::default())
+            .unwrap_err();
+
+        assert!(matches!(err, ExtractError::PathTraversal { path: ref found } if found == Path::new(path)));
+        let remaining: Vec<_> = std::fs::read_dir(&dst)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(remaining, vec!["existing.txt"]);
+        tmp_dir.child("evil.txt").assert(predicates::path::missing());
+        tmp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn extract_symlinks() {
+        let tmp_dir = TempDir::new().unwrap();
+        let mut builder = tar::Builder::new(Vec::new());
+        append_entry(&mut builder, "tool/", EntryType::Directory, 0o755);
+        append_entry(&mut builder, "tool/files/", EntryType::Directory, 0o755);
+        append_entry(&mut builder, "tool/files/wine64", EntryType::Regular, 0o755);
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(EntryType::Symlink);
+        header.set_size(0);
+        builder.append_link(&mut header, "tool/wine", "files/wine64").unwrap();
+        let archive = gzip(builder.into_inner().unwrap());
+
+        let dst = extract_with_compression(
+            Compression::Gzip,
+            archive.as_slice(),
+            tmp_dir.path(),
+            &ExtractOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(dst, tmp_dir.join("tool"));
+        assert_eq!(std::fs::read_link(dst.join("wine")).unwrap(), Path::new("files/wine64"));
+        tmp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn reject_empty_archive() {
+        let tmp_dir = TempDir::new().unwrap();
+        let archive = gzip(tar::Builder::new(Vec::new()).into_inner().unwrap());
+
+        let err = extract_with_compression(
+            Compression::Gzip,
+            archive.as_slice(),
+            tmp_dir.path(),
+            &ExtractOptions::default(),
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, ExtractError::EmptyArchive));
+        tmp_dir.close().unwrap();
+    }
+
+    #[test_case("GE-Proton7-8.tar.gz" => Some(Compression::Gzip))]
+    #[test_case("GE-PROTON7-8.TGZ" => Some(Compression::Gzip))]
+    #[test_case("wine-lutris-GE-Proton8-26-x86_64.tar.xz" => Some(Compression::Xz))]
+    #[test_case("wine.txz" => Some(Compression::Xz))]
+    #[test_case("GE-Proton9-1.tar.zst" => Some(Compression::Zstd))]
+    #[test_case("GE-Proton9-1.tzst" => Some(Compression::Zstd))]
+    #[test_case("GE-Proton7-8.sha512sum" => None)]
+    fn compression_from_file_name(file_name: &str) -> Option<Compression> {
+        Compression::from_file_name(file_name)
+    }
+
+    #[test_case("test_resources/assets/test.tar.gz" => Some(Compression::Gzip))]
+    #[test_case("test_resources/assets/test.tar.xz" => Some(Compression::Xz))]
+    #[test_case("test_resources/assets/test.tar.zst" => Some(Compression::Zstd))]
+    #[test_case("test_resources/assets/test-gz.sha512sum" => None)]
+    fn compression_from_magic_bytes(path: &str) -> Option<Compression> {
+        Compression::from_magic_bytes(&std::fs::read(path).unwrap())
+    }
+
+    #[test_case("application/gzip" => Some(Compression::Gzip))]
+    #[test_case("application/x-gzip" => Some(Compression::Gzip))]
+    #[test_case("application/x-xz" => Some(Compression::Xz))]
+    #[test_case("application/zstd" => Some(Compression::Zstd))]
+    #[test_case("Application/X-Zstd; charset=binary" => Some(Compression::Zstd); "parameters")]
+    #[test_case("application/octet-stream" => None)]
+    fn compression_from_content_type(content_type: &str) -> Option<Compression> {
+        Compression::from_content_type(content_type)
+    }
+
     #[test]
     fn extract_wine_ge_release_with_wrong_tag_kind() {
         let tmp_dir = TempDir::new().unwrap();
@@ -0,0 +1,90 @@
+//! Query the optional functionality this crate was compiled with.
+//!
+//! Cargo features are only visible at compile time. This module exposes them at runtime, so that a consumer that
+//! links against a prebuilt version of this crate can adapt to the available functionality.
+use crate::digest::{DefaultDigester, Digester};
+
+/// The crate version and the optional features this crate was compiled with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Version of this crate.
+    pub version: &'static str,
+    /// Is the `default` feature enabled. This feature enables TLS support for `reqwest` through the platform's
+    /// native TLS library.
+    pub default_tls: bool,
+    /// Is the `vendored-openssl` feature enabled. This feature compiles and statically links OpenSSL.
+    pub vendored_openssl: bool,
+    /// Name of the backend that computes checksums, see `digest::DefaultDigester`.
+    pub digest_backend: &'static str,
+    /// Is the `async` feature enabled. This feature provides `download::nonblocking::AsyncGeDownloader`.
+    pub async_api: bool,
+}
+
+impl Capabilities {
+    /// Can this crate download releases from GitHub.
+    ///
+    /// GitHub is only reachable over HTTPS, so this is `false` if the crate was compiled without TLS support.
+    pub fn supports_https(&self) -> bool {
+        self.default_tls
+    }
+}
+
+/// Get the capabilities of this crate.
+pub fn capabilities() -> Capabilities {
+    Capabilities {
+        version: env!("CARGO_PKG_VERSION"),
+        default_tls: cfg!(feature = "default"),
+        vendored_openssl: cfg!(feature = "vendored-openssl"),
+        digest_backend: DefaultDigester::NAME,
+        async_api: cfg!(feature = "async"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_crate_version() {
+        assert_eq!(capabilities().version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[cfg(feature = "default")]
+    #[test]
+    fn report_default_features() {
+        let capabilities = capabilities();
+        assert!(capabilities.default_tls);
+        assert!(capabilities.supports_https());
+    }
+
+    #[cfg(not(feature = "default"))]
+    #[test]
+    fn report_without_default_features() {
+        let capabilities = capabilities();
+        assert!(!capabilities.default_tls);
+        assert!(!capabilities.supports_https());
+    }
+
+    #[cfg(not(any(feature = "ring", feature = "openssl")))]
+    #[test]
+    fn report_sha2_digest_backend() {
+        assert_eq!(capabilities().digest_backend, "sha2");
+    }
+
+    #[cfg(feature = "vendored-openssl")]
+    #[test]
+    fn report_vendored_openssl() {
+        assert!(capabilities().vendored_openssl);
+    }
+
+    #[cfg(not(feature = "vendored-openssl"))]
+    #[test]
+    fn report_without_vendored_openssl() {
+        assert!(!capabilities().vendored_openssl);
+    }
+
+    #[test]
+    fn report_async_api() {
+        assert_eq!(capabilities().async_api, cfg!(feature = "async"));
+    }
+}
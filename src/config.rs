@@ -4,7 +4,7 @@
 //! global Wine version in Lutris.
 //!
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Write};
 use std::path::Path;
 
 use crate::error::{LutrisConfigError, SteamConfigError};
@@ -65,8 +65,6 @@ impl SteamConfig {
         // Create in memory copy and find default value for CompatTool attribute.
         for (idx, line) in steam_config.lines().enumerate() {
             if let Ok(line) = line {
-                lines.push(line.clone());
-
                 if line.contains("CompatToolMapping") {
                     passed_compat_tool_attribute = true;
                 }
@@ -77,6 +75,8 @@ impl SteamConfig {
                 {
                     default_compat_tool_dir_name_line_idx = Some(idx + 2);
                 }
+
+                lines.push(line);
             }
         }
 
@@ -114,16 +114,32 @@ impl SteamConfig {
             self.compat_tool_value_start_idx..self.compat_tool_value_end_idx,
             proton_dir_name,
         );
-        self.lines.splice(
-            self.compat_tool_line_idx..=self.compat_tool_line_idx,
-            [self.compat_tool_line.clone()],
-        );
+        self.lines[self.compat_tool_line_idx].clone_from(&self.compat_tool_line);
+    }
+
+    /// Write the content of this file copy to `writer`.
+    ///
+    /// The lines are written one after another, so the content is never assembled in memory as a whole. This allows
+    /// large config files to be streamed directly into the file they are saved to. The written bytes are identical to
+    /// the bytes of the `Into<Vec<u8>>` conversion.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if writing to `writer` fails.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        for (idx, line) in self.lines.iter().enumerate() {
+            if idx > 0 {
+                writer.write_all(b"\n")?;
+            }
+            writer.write_all(line.as_bytes())?;
+        }
+        Ok(())
     }
 }
 
-impl Into<Vec<u8>> for SteamConfig {
-    fn into(self) -> Vec<u8> {
-        self.lines.join("\n").into_bytes()
+impl From<SteamConfig> for Vec<u8> {
+    fn from(config: SteamConfig) -> Self {
+        config.lines.join("\n").into_bytes()
     }
 }
 
@@ -225,9 +241,9 @@ impl LutrisConfig {
     }
 }
 
-impl Into<Vec<u8>> for LutrisConfig {
-    fn into(self) -> Vec<u8> {
-        self.lines.join("\n").into_bytes()
+impl From<LutrisConfig> for Vec<u8> {
+    fn from(config: LutrisConfig) -> Self {
+        config.lines.join("\n").into_bytes()
     }
 }
 
@@ -236,6 +252,10 @@ mod tests {
     use std::io::BufRead;
     use std::path::PathBuf;
 
+    use assert_fs::prelude::*;
+    use assert_fs::NamedTempFile;
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
@@ -251,7 +271,7 @@ mod tests {
     #[test]
     fn create_lutris_config_from_file_with_no_version_property() {
         let config_path = Path::new("test_resources/assets/wine-no-version.yml");
-        let result = LutrisConfig::create_copy(&config_path);
+        let result = LutrisConfig::create_copy(config_path);
         assert!(result.is_err());
 
         let err = result.err().unwrap();
@@ -318,6 +338,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn write_steam_config_copy_to_writer() {
+        let config_file_path = Path::new("test_resources/assets/config.vdf");
+        let mut steam_config = SteamConfig::create_copy(config_file_path).unwrap();
+        steam_config.set_proton_version("Proton-6.20-GE-1");
+
+        let mut written = Vec::new();
+        steam_config.write_to(&mut written).unwrap();
+
+        let bytes_copy: Vec<u8> = steam_config.into();
+        assert_eq!(written, bytes_copy);
+    }
+
+    #[test]
+    fn write_unchanged_steam_config_copy_to_writer() {
+        let config_file_path = Path::new("test_resources/assets/config.vdf");
+        let steam_config = SteamConfig::create_copy(config_file_path).unwrap();
+
+        let mut written = Vec::new();
+        steam_config.write_to(&mut written).unwrap();
+
+        let original = std::fs::read_to_string(config_file_path).unwrap();
+        assert_eq!(String::from_utf8(written).unwrap(), original.trim_end_matches('\n'));
+    }
+
     #[test]
     fn read_proton_version_from_steam_config() {
         let config_file = Path::new("test_resources/assets/config.vdf");
@@ -331,7 +376,7 @@ mod tests {
     #[test]
     fn create_steam_config_copy_from_file_with_no_compat_tool_attribute() {
         let config_file = Path::new("test_resources/assets/config-no-compat-tool-attr.vdf");
-        let result = SteamConfig::create_copy(&config_file);
+        let result = SteamConfig::create_copy(config_file);
         assert!(result.is_err());
 
         let err = result.err().unwrap();
@@ -341,7 +386,7 @@ mod tests {
     #[test]
     fn create_steam_config_copy_from_file_with_no_default_proton_version() {
         let config_file = Path::new("test_resources/assets/config-no-default-version.vdf");
-        let result = SteamConfig::create_copy(&config_file);
+        let result = SteamConfig::create_copy(config_file);
         assert!(result.is_err());
 
         let err = result.err().unwrap();
@@ -357,4 +402,64 @@ mod tests {
         let err = result.err().unwrap();
         assert!(matches!(err, SteamConfigError::IoError { .. }));
     }
+
+    fn steam_config_document() -> impl Strategy<Value = String> {
+        let app = (any::<u32>(), "[A-Za-z0-9._-]{0,24}", any::<u16>());
+        let extra_line =
+            "[ -~]{0,40}".prop_filter("must not start the mapping", |line| !line.contains("CompatToolMapping"));
+        (
+            "[A-Za-z0-9._-]{1,24}",
+            prop::collection::vec(app, 0..16),
+            prop::collection::vec(extra_line, 0..8),
+        )
+            .prop_map(|(default_name, apps, extra_lines)| {
+                let mut document = String::from("\"InstallConfigStore\"\n{\n");
+                for line in extra_lines {
+                    document.push_str(&line);
+                    document.push('\n');
+                }
+                document.push_str("\t\"CompatToolMapping\"\n\t{\n");
+                let default_app = (0, default_name, 75);
+                for (app_id, name, priority) in std::iter::once(default_app).chain(apps) {
+                    document.push_str(&format!(
+                        "\t\t\"{}\"\n\t\t{{\n\t\t\t\"name\"\t\t\"{}\"\n\t\t\t\"config\"\t\t\"\"\n\t\t\t\"Priority\"\t\t\"{}\"\n\t\t}}\n",
+                        app_id, name, priority
+                    ));
+                }
+                document.push_str("\t}\n}\n");
+                document
+            })
+    }
+
+    fn steam_config_copy(document: &str) -> SteamConfig {
+        let file = NamedTempFile::new("config.vdf").unwrap();
+        file.write_str(document).unwrap();
+        SteamConfig::create_copy(file.path()).unwrap()
+    }
+
+    proptest! {
+        #[test]
+        fn unchanged_steam_config_is_written_byte_identical(document in steam_config_document()) {
+            let steam_config = steam_config_copy(&document);
+
+            let mut written = Vec::new();
+            steam_config.write_to(&mut written).unwrap();
+            prop_assert_eq!(&written, document.trim_end_matches('\n').as_bytes());
+
+            let bytes: Vec<u8> = steam_config.into();
+            prop_assert_eq!(bytes, written);
+        }
+
+        #[test]
+        fn setting_the_same_proton_version_keeps_the_steam_config(document in steam_config_document()) {
+            let mut steam_config = steam_config_copy(&document);
+            let version = steam_config.proton_version();
+
+            steam_config.set_proton_version(&version);
+
+            let mut written = Vec::new();
+            steam_config.write_to(&mut written).unwrap();
+            prop_assert_eq!(&written, document.trim_end_matches('\n').as_bytes());
+        }
+    }
 }
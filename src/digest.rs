@@ -0,0 +1,758 @@
+//! Compute checksums of release assets.
+//!
+//! The hashing implementation is selected at compile time with the following features:
+//! * `ring`: the implementation of the `ring` crate
+//! * `openssl`: the implementation of the system's OpenSSL library (or a vendored one with `vendored-openssl`)
+//!
+//! If both features are enabled, `ring` is used. Without either of them the pure Rust implementation of the `sha2`
+//! crate is used, so the crate builds without default features. Every backend produces the same
+//! `Digest`, so the verification logic does not depend on the selected backend.
+//!
+//! `ChecksumFile` parses the `sha512sum` files that are published with every release. `AssetDigest` parses the
+//! `sha256:<hex>` digests the GitHub API reports for release assets, which verify an archive if its release has no
+//! checksum file.
+use std::fmt::{Display, Formatter};
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use data_encoding::{HEXLOWER, HEXLOWER_PERMISSIVE};
+
+use crate::error::{ChecksumError, ChecksumParseError};
+
+/// The digest backend this crate was compiled with.
+#[cfg(not(any(feature = "ring", feature = "openssl")))]
+pub type DefaultDigester = Sha2Digester;
+/// The digest backend this crate was compiled with.
+#[cfg(feature = "ring")]
+pub type DefaultDigester = RingDigester;
+/// The digest backend this crate was compiled with.
+#[cfg(all(not(feature = "ring"), feature = "openssl"))]
+pub type DefaultDigester = OpensslDigester;
+
+/// The result of hashing data with a `Digester`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Digest {
+    bytes: Vec<u8>,
+}
+
+impl Digest {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Digest { bytes }
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Get the lowercase hex representation of this digest as printed by tools like `sha512sum`.
+    pub fn to_hex(&self) -> String {
+        HEXLOWER.encode(&self.bytes)
+    }
+}
+
+impl Display for Digest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+/// Hash algorithms that are used to verify release assets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    /// SHA-256, used by the asset digests of the GitHub API and the `sha256sum` checksum files of some forks.
+    Sha256,
+    /// SHA-512, used by the `sha512sum` checksum files of GE releases.
+    Sha512,
+}
+
+impl Algorithm {
+    /// Get the lowercase name of the algorithm as used in the prefix of an `AssetDigest`, e.g. `sha256`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Algorithm::Sha256 => "sha256",
+            Algorithm::Sha512 => "sha512",
+        }
+    }
+
+    /// Number of bytes of a digest of this algorithm.
+    pub fn digest_len(&self) -> usize {
+        match self {
+            Algorithm::Sha256 => 32,
+            Algorithm::Sha512 => 64,
+        }
+    }
+
+    /// Detect the algorithm of a checksum file from its `file_name`, e.g. SHA-512 for `GE-Proton7-8.sha512sum` and
+    /// SHA-256 for `sha256sums.txt`.
+    ///
+    /// The name of the algorithm is searched case-insensitively. If the file name contains the names of both
+    /// algorithms, the last one wins, so the extension of `sha512-fix.sha256sum` decides. Returns `None` if the file
+    /// name contains neither.
+    pub fn from_checksum_file_name(file_name: &str) -> Option<Algorithm> {
+        let file_name = file_name.to_ascii_lowercase();
+        [Algorithm::Sha256, Algorithm::Sha512]
+            .into_iter()
+            .filter_map(|algorithm| file_name.rfind(algorithm.name()).map(|position| (position, algorithm)))
+            .max_by_key(|(position, _)| *position)
+            .map(|(_, algorithm)| algorithm)
+    }
+}
+
+impl Display for Algorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A hashing implementation.
+pub trait Digester {
+    /// Name of the backend, e.g. `sha2`.
+    const NAME: &'static str;
+
+    /// Compute the SHA-256 digest of `data`.
+    fn sha256(data: &[u8]) -> Digest;
+
+    /// Compute the SHA-512 digest of `data`.
+    fn sha512(data: &[u8]) -> Digest;
+}
+
+/// `Digester` backed by the pure Rust `sha2` crate.
+pub struct Sha2Digester;
+
+impl Digester for Sha2Digester {
+    const NAME: &'static str = "sha2";
+
+    fn sha256(data: &[u8]) -> Digest {
+        use sha2::Digest as _;
+        Digest::new(sha2::Sha256::digest(data).to_vec())
+    }
+
+    fn sha512(data: &[u8]) -> Digest {
+        use sha2::Digest as _;
+        Digest::new(sha2::Sha512::digest(data).to_vec())
+    }
+}
+
+/// `Digester` backed by the `ring` crate.
+#[cfg(feature = "ring")]
+pub struct RingDigester;
+
+#[cfg(feature = "ring")]
+impl Digester for RingDigester {
+    const NAME: &'static str = "ring";
+
+    fn sha256(data: &[u8]) -> Digest {
+        Digest::new(ring::digest::digest(&ring::digest::SHA256, data).as_ref().to_vec())
+    }
+
+    fn sha512(data: &[u8]) -> Digest {
+        Digest::new(ring::digest::digest(&ring::digest::SHA512, data).as_ref().to_vec())
+    }
+}
+
+/// `Digester` backed by OpenSSL.
+#[cfg(feature = "openssl")]
+pub struct OpensslDigester;
+
+#[cfg(feature = "openssl")]
+impl Digester for OpensslDigester {
+    const NAME: &'static str = "openssl";
+
+    fn sha256(data: &[u8]) -> Digest {
+        Digest::new(openssl::sha::sha256(data).to_vec())
+    }
+
+    fn sha512(data: &[u8]) -> Digest {
+        Digest::new(openssl::sha::sha512(data).to_vec())
+    }
+}
+
+/// Compute the SHA-256 digest of `data` with the `DefaultDigester`.
+pub fn sha256(data: &[u8]) -> Digest {
+    DefaultDigester::sha256(data)
+}
+
+/// Compute the SHA-512 digest of `data` with the `DefaultDigester`.
+pub fn sha512(data: &[u8]) -> Digest {
+    DefaultDigester::sha512(data)
+}
+
+/// Compute the digest of `data` with `algorithm` and the `DefaultDigester`.
+pub fn digest(algorithm: Algorithm, data: &[u8]) -> Digest {
+    match algorithm {
+        Algorithm::Sha256 => sha256(data),
+        Algorithm::Sha512 => sha512(data),
+    }
+}
+
+/// Computes a SHA-512 digest incrementally with the `DefaultDigester` backend.
+///
+/// Unlike `sha512` the data does not have to be in memory at once. This allows hashing an archive while it is
+/// downloaded. The hasher implements `Write`, so it can be the target of `std::io::copy`.
+///
+/// # Examples
+///
+/// ```
+/// use ge_man_lib::digest::{self, Sha512Hasher};
+///
+/// let mut hasher = Sha512Hasher::new();
+/// hasher.update(b"GE-");
+/// hasher.update(b"Proton");
+/// assert_eq!(hasher.finish(), digest::sha512(b"GE-Proton"));
+/// ```
+pub struct Sha512Hasher {
+    #[cfg(not(any(feature = "ring", feature = "openssl")))]
+    state: sha2::Sha512,
+    #[cfg(feature = "ring")]
+    state: ring::digest::Context,
+    #[cfg(all(not(feature = "ring"), feature = "openssl"))]
+    state: openssl::sha::Sha512,
+}
+
+impl Sha512Hasher {
+    pub fn new() -> Self {
+        Sha512Hasher {
+            #[cfg(not(any(feature = "ring", feature = "openssl")))]
+            state: <sha2::Sha512 as sha2::Digest>::new(),
+            #[cfg(feature = "ring")]
+            state: ring::digest::Context::new(&ring::digest::SHA512),
+            #[cfg(all(not(feature = "ring"), feature = "openssl"))]
+            state: openssl::sha::Sha512::new(),
+        }
+    }
+
+    /// Add `data` to the digest.
+    pub fn update(&mut self, data: &[u8]) {
+        #[cfg(not(any(feature = "ring", feature = "openssl")))]
+        sha2::Digest::update(&mut self.state, data);
+        #[cfg(any(feature = "ring", feature = "openssl"))]
+        self.state.update(data);
+    }
+
+    /// Get the digest of all data added with `update`.
+    pub fn finish(self) -> Digest {
+        #[cfg(not(any(feature = "ring", feature = "openssl")))]
+        let bytes = sha2::Digest::finalize(self.state).to_vec();
+        #[cfg(feature = "ring")]
+        let bytes = self.state.finish().as_ref().to_vec();
+        #[cfg(all(not(feature = "ring"), feature = "openssl"))]
+        let bytes = self.state.finish().to_vec();
+        Digest::new(bytes)
+    }
+}
+
+impl Default for Sha512Hasher {
+    fn default() -> Self {
+        Sha512Hasher::new()
+    }
+}
+
+impl Write for Sha512Hasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Incremental SHA-256 state of the `DefaultDigester` backend.
+struct Sha256State {
+    #[cfg(not(any(feature = "ring", feature = "openssl")))]
+    state: sha2::Sha256,
+    #[cfg(feature = "ring")]
+    state: ring::digest::Context,
+    #[cfg(all(not(feature = "ring"), feature = "openssl"))]
+    state: openssl::sha::Sha256,
+}
+
+impl Sha256State {
+    fn new() -> Self {
+        Sha256State {
+            #[cfg(not(any(feature = "ring", feature = "openssl")))]
+            state: <sha2::Sha256 as sha2::Digest>::new(),
+            #[cfg(feature = "ring")]
+            state: ring::digest::Context::new(&ring::digest::SHA256),
+            #[cfg(all(not(feature = "ring"), feature = "openssl"))]
+            state: openssl::sha::Sha256::new(),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        #[cfg(not(any(feature = "ring", feature = "openssl")))]
+        sha2::Digest::update(&mut self.state, data);
+        #[cfg(any(feature = "ring", feature = "openssl"))]
+        self.state.update(data);
+    }
+
+    fn finish(self) -> Digest {
+        #[cfg(not(any(feature = "ring", feature = "openssl")))]
+        let bytes = sha2::Digest::finalize(self.state).to_vec();
+        #[cfg(feature = "ring")]
+        let bytes = self.state.finish().as_ref().to_vec();
+        #[cfg(all(not(feature = "ring"), feature = "openssl"))]
+        let bytes = self.state.finish().to_vec();
+        Digest::new(bytes)
+    }
+}
+
+/// Computes a digest with one of the supported `Algorithm`s incrementally, like `Sha512Hasher` does for SHA-512.
+///
+/// # Examples
+///
+/// ```
+/// use ge_man_lib::digest::{self, Algorithm, Hasher};
+///
+/// let mut hasher = Hasher::new(Algorithm::Sha256);
+/// hasher.update(b"GE-");
+/// hasher.update(b"Proton");
+/// assert_eq!(hasher.finish(), digest::sha256(b"GE-Proton"));
+/// ```
+pub struct Hasher {
+    state: HasherState,
+}
+
+enum HasherState {
+    Sha256(Sha256State),
+    Sha512(Sha512Hasher),
+}
+
+impl Hasher {
+    pub fn new(algorithm: Algorithm) -> Self {
+        let state = match algorithm {
+            Algorithm::Sha256 => HasherState::Sha256(Sha256State::new()),
+            Algorithm::Sha512 => HasherState::Sha512(Sha512Hasher::new()),
+        };
+        Hasher { state }
+    }
+
+    /// Get the algorithm of this hasher.
+    pub fn algorithm(&self) -> Algorithm {
+        match self.state {
+            HasherState::Sha256(_) => Algorithm::Sha256,
+            HasherState::Sha512(_) => Algorithm::Sha512,
+        }
+    }
+
+    /// Add `data` to the digest.
+    pub fn update(&mut self, data: &[u8]) {
+        match &mut self.state {
+            HasherState::Sha256(state) => state.update(data),
+            HasherState::Sha512(state) => state.update(data),
+        }
+    }
+
+    /// Get the digest of all data added with `update`.
+    pub fn finish(self) -> Digest {
+        match self.state {
+            HasherState::Sha256(state) => state.finish(),
+            HasherState::Sha512(state) => state.finish(),
+        }
+    }
+}
+
+impl Write for Hasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A digest together with its algorithm, in the format `<algorithm>:<hex>` the GitHub API uses for the `digest` of
+/// release assets, e.g. `sha256:ba7816bf...`.
+///
+/// # Examples
+///
+/// ```
+/// use ge_man_lib::digest::{self, Algorithm, AssetDigest};
+///
+/// let asset_digest = AssetDigest::parse(&format!("sha256:{}", digest::sha256(b"abc"))).unwrap();
+/// assert_eq!(asset_digest.algorithm(), Algorithm::Sha256);
+/// assert_eq!(asset_digest.verify(&digest::sha256(b"abc")), Ok(()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssetDigest {
+    algorithm: Algorithm,
+    digest: Digest,
+}
+
+impl AssetDigest {
+    pub fn new(algorithm: Algorithm, digest: Digest) -> Self {
+        AssetDigest { algorithm, digest }
+    }
+
+    /// Parse a digest in the format `<algorithm>:<hex>`.
+    ///
+    /// The algorithm is matched case-insensitively and the hex digest may be upper- or lowercase. Returns `None` for
+    /// unknown algorithms and for digests that are not hex encoded or have the wrong length for their algorithm.
+    pub fn parse(value: &str) -> Option<Self> {
+        let (name, hex) = value.trim().split_once(':')?;
+        let algorithm = [Algorithm::Sha256, Algorithm::Sha512]
+            .into_iter()
+            .find(|algorithm| algorithm.name().eq_ignore_ascii_case(name))?;
+        let bytes = HEXLOWER_PERMISSIVE.decode(hex.as_bytes()).ok()?;
+        (bytes.len() == algorithm.digest_len()).then(|| AssetDigest::new(algorithm, Digest::new(bytes)))
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    pub fn digest(&self) -> &Digest {
+        &self.digest
+    }
+
+    /// Verify the `actual` digest of an asset, which was computed with `algorithm`, against this digest.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ChecksumError::Mismatch` if the digests differ.
+    pub fn verify(&self, actual: &Digest) -> Result<(), ChecksumError> {
+        if actual == &self.digest {
+            Ok(())
+        } else {
+            Err(ChecksumError::Mismatch {
+                expected: self.digest.to_hex(),
+                actual: actual.to_hex(),
+            })
+        }
+    }
+}
+
+impl Display for AssetDigest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.algorithm, self.digest)
+    }
+}
+
+/// A parsed checksum file in the format of coreutils' `sha512sum` or `sha256sum`.
+///
+/// A checksum file contains a line with the hex encoded digest, a separator and the name of the checksummed
+/// file. The separator is two spaces for files that were read in text mode and a space followed by `*` for files that
+/// were read in binary mode, e.g. `<digest>  GE-Proton7-8.tar.gz` or `<digest> *GE-Proton7-8.tar.gz`. A line that
+/// only contains the digest is accepted as well.
+///
+/// # Examples
+///
+/// ```
+/// use ge_man_lib::digest::ChecksumFile;
+///
+/// let content = format!("{} *GE-Proton7-8.tar.gz\n", "AB".repeat(64));
+/// let checksum_file = ChecksumFile::parse(&content).unwrap();
+/// assert_eq!(checksum_file.digest_hex(), "ab".repeat(64));
+/// assert_eq!(checksum_file.file_name(), Some("GE-Proton7-8.tar.gz"));
+/// assert!(checksum_file.is_binary());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChecksumFile {
+    algorithm: Algorithm,
+    digest: Digest,
+    file_name: Option<String>,
+    binary: bool,
+}
+
+impl ChecksumFile {
+    /// Parse the content of a `sha512sum` checksum file.
+    ///
+    /// Empty lines and trailing whitespace, like the final newline, are ignored. The digest may be upper- or
+    /// lowercase.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChecksumParseError` if the content is empty, contains more than one checksum line, the digest is not
+    /// a hex encoded SHA-512 digest or the separator after the digest is neither `  ` nor ` *`.
+    pub fn parse(content: &str) -> Result<Self, ChecksumParseError> {
+        ChecksumFile::parse_with(content, Algorithm::Sha512)
+    }
+
+    /// Parse the content of a checksum file with a digest of `algorithm`, like the output of `sha256sum` for
+    /// `Algorithm::Sha256`.
+    ///
+    /// # Errors
+    ///
+    /// The errors of `ChecksumFile::parse`, except that the digest must have the length of a digest of `algorithm`.
+    pub fn parse_with(content: &str, algorithm: Algorithm) -> Result<Self, ChecksumParseError> {
+        let mut lines = content.lines().map(str::trim_end).filter(|line| !line.is_empty());
+        let line = lines.next().ok_or(ChecksumParseError::Empty)?;
+        let additional_lines = lines.count();
+        if additional_lines > 0 {
+            return Err(ChecksumParseError::MultipleEntries {
+                count: additional_lines + 1,
+            });
+        }
+
+        let (digest, rest) = line.split_at(line.find(char::is_whitespace).unwrap_or(line.len()));
+        let expected = algorithm.digest_len() * 2;
+        if digest.len() != expected {
+            return Err(ChecksumParseError::InvalidDigestLength {
+                length: digest.len(),
+                expected,
+            });
+        }
+        let digest = HEXLOWER_PERMISSIVE
+            .decode(digest.as_bytes())
+            .map_err(|_| ChecksumParseError::InvalidDigest {
+                digest: String::from(digest),
+            })?;
+
+        let (file_name, binary) = if rest.is_empty() {
+            (None, false)
+        } else if let Some(file_name) = rest.strip_prefix("  ") {
+            (Some(file_name), false)
+        } else if let Some(file_name) = rest.strip_prefix(" *") {
+            (Some(file_name), true)
+        } else {
+            return Err(ChecksumParseError::InvalidSeparator);
+        };
+        if file_name.is_some_and(str::is_empty) {
+            return Err(ChecksumParseError::EmptyFileName);
+        }
+
+        Ok(ChecksumFile {
+            algorithm,
+            digest: Digest::new(digest),
+            file_name: file_name.map(String::from),
+            binary,
+        })
+    }
+
+    /// Get the algorithm of the expected digest.
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Get the expected digest.
+    pub fn digest(&self) -> &Digest {
+        &self.digest
+    }
+
+    /// Get the lowercase hex representation of the expected digest.
+    pub fn digest_hex(&self) -> String {
+        self.digest.to_hex()
+    }
+
+    /// Get the bytes of the expected digest.
+    pub fn digest_bytes(&self) -> &[u8] {
+        self.digest.bytes()
+    }
+
+    /// Get the name of the checksummed file, if the checksum file contains one.
+    ///
+    /// The name is returned as written in the checksum file and may contain directories.
+    pub fn file_name(&self) -> Option<&str> {
+        self.file_name.as_deref()
+    }
+
+    /// Was the checksummed file read in binary mode, i.e. is the separator ` *`.
+    pub fn is_binary(&self) -> bool {
+        self.binary
+    }
+}
+
+impl FromStr for ChecksumFile {
+    type Err = ChecksumParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ChecksumFile::parse(s)
+    }
+}
+
+impl Display for ChecksumFile {
+    /// Format the checksum file as a line in the format of `sha512sum` or `sha256sum`, without a trailing newline.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.file_name {
+            Some(file_name) if self.binary => write!(f, "{} *{}", self.digest, file_name),
+            Some(file_name) => write!(f, "{}  {}", self.digest, file_name),
+            None => write!(f, "{}", self.digest),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    const EMPTY: &str = "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e";
+    const ABC: &str = "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f";
+    const TWO_BLOCKS: &str = "8e959b75dae313da8cf4f72814fc143f8f7779c6eb9f7fa17299aeadb6889018501d289e4900f7e4331b99dec4b5433ac7d329eeb6dd26545e96e55b874be909";
+
+    const SHA256_EMPTY: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+    const SHA256_ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+    const SHA256_TWO_BLOCKS: &str = "cf5b16a778af8380036ce59e7b0492370b249b11e8f07a51afac45037afee9d1";
+
+    fn vectors() -> [(&'static [u8], &'static str); 3] {
+        [
+            (b"", EMPTY),
+            (b"abc", ABC),
+            (
+                b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu",
+                TWO_BLOCKS,
+            ),
+        ]
+    }
+
+    fn sha256_vectors() -> [(&'static [u8], &'static str); 3] {
+        [
+            (b"", SHA256_EMPTY),
+            (b"abc", SHA256_ABC),
+            (
+                b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu",
+                SHA256_TWO_BLOCKS,
+            ),
+        ]
+    }
+
+    fn assert_vectors<D: Digester>() {
+        for (data, expected) in vectors() {
+            assert_eq!(D::sha512(data).to_hex(), expected, "backend {}", D::NAME);
+        }
+        for (data, expected) in sha256_vectors() {
+            assert_eq!(D::sha256(data).to_hex(), expected, "backend {}", D::NAME);
+        }
+    }
+
+    #[test]
+    fn sha2_backend() {
+        assert_vectors::<Sha2Digester>();
+    }
+
+    #[cfg(feature = "ring")]
+    #[test]
+    fn ring_backend() {
+        assert_vectors::<RingDigester>();
+    }
+
+    #[cfg(feature = "openssl")]
+    #[test]
+    fn openssl_backend() {
+        assert_vectors::<OpensslDigester>();
+    }
+
+    #[test]
+    fn default_backend() {
+        assert_vectors::<DefaultDigester>();
+    }
+
+    #[test]
+    fn hash_incrementally() {
+        for (data, expected) in vectors() {
+            let mut hasher = Sha512Hasher::new();
+            for chunk in data.chunks(7) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.finish().to_hex(), expected);
+        }
+    }
+
+    #[test_case(Algorithm::Sha256; "sha256")]
+    #[test_case(Algorithm::Sha512; "sha512")]
+    fn hash_incrementally_with_algorithm(algorithm: Algorithm) {
+        let data = vectors()[2].0;
+        let mut hasher = Hasher::new(algorithm);
+        for chunk in data.chunks(7) {
+            hasher.write_all(chunk).unwrap();
+        }
+        assert_eq!(hasher.algorithm(), algorithm);
+        let digest = hasher.finish();
+        assert_eq!(digest.bytes().len(), algorithm.digest_len());
+        assert_eq!(digest, super::digest(algorithm, data));
+    }
+
+    #[test_case(&format!("sha256:{}", SHA256_ABC) => Some((Algorithm::Sha256, String::from(SHA256_ABC))); "sha256")]
+    #[test_case(&format!("SHA256:{}", SHA256_ABC.to_uppercase()) => Some((Algorithm::Sha256, String::from(SHA256_ABC))); "uppercase")]
+    #[test_case(&format!("sha512:{}", ABC) => Some((Algorithm::Sha512, String::from(ABC))); "sha512")]
+    #[test_case(&format!("sha256:{}", ABC) => None; "wrong length")]
+    #[test_case(&format!("md5:{}", SHA256_ABC) => None; "unknown algorithm")]
+    #[test_case(SHA256_ABC => None; "missing algorithm")]
+    #[test_case("sha256:xyz" => None; "non-hex digest")]
+    fn parse_asset_digest(value: &str) -> Option<(Algorithm, String)> {
+        AssetDigest::parse(value).map(|digest| (digest.algorithm(), digest.digest().to_hex()))
+    }
+
+    #[test]
+    fn verify_asset_digest() {
+        let asset_digest = AssetDigest::parse(&format!("sha256:{}", SHA256_ABC)).unwrap();
+        assert_eq!(asset_digest.to_string(), format!("sha256:{}", SHA256_ABC));
+        assert_eq!(asset_digest.verify(&sha256(b"abc")), Ok(()));
+        assert_eq!(
+            asset_digest.verify(&sha256(b"abd")),
+            Err(ChecksumError::Mismatch {
+                expected: String::from(SHA256_ABC),
+                actual: sha256(b"abd").to_hex(),
+            })
+        );
+    }
+
+    #[test_case(&[0x0f, 0xa0] => "0fa0")]
+    #[test_case(&[] => "")]
+    fn digest_as_hex(bytes: &[u8]) -> String {
+        Digest::new(bytes.to_vec()).to_string()
+    }
+
+    #[test_case(&format!("{}  GE-Proton7-8.tar.gz\n", ABC) => (Some(String::from("GE-Proton7-8.tar.gz")), false); "text mode")]
+    #[test_case(&format!("{} *GE-Proton7-8.tar.gz", ABC) => (Some(String::from("GE-Proton7-8.tar.gz")), true); "binary mode")]
+    #[test_case(&format!("\r\n{}  dir/my file.tar.gz  \r\n\n", ABC) => (Some(String::from("dir/my file.tar.gz")), false); "whitespace around line")]
+    #[test_case(&format!("{}\n", ABC.to_uppercase()) => (None, false); "uppercase digest only")]
+    fn parse_checksum_file(content: &str) -> (Option<String>, bool) {
+        let checksum_file = ChecksumFile::parse(content).unwrap();
+        assert_eq!(checksum_file.digest_hex(), ABC);
+        assert_eq!(checksum_file.digest_bytes(), sha512(b"abc").bytes());
+        (checksum_file.file_name().map(String::from), checksum_file.is_binary())
+    }
+
+    #[test]
+    fn parse_sha256_checksum_file() {
+        let content = format!("{}  GE-Proton7-8.tar.gz\n", SHA256_ABC);
+
+        let checksum_file = ChecksumFile::parse_with(&content, Algorithm::Sha256).unwrap();
+
+        assert_eq!(checksum_file.algorithm(), Algorithm::Sha256);
+        assert_eq!(checksum_file.digest(), &sha256(b"abc"));
+        assert_eq!(checksum_file.file_name(), Some("GE-Proton7-8.tar.gz"));
+        assert_eq!(
+            ChecksumFile::parse(&content),
+            Err(ChecksumParseError::InvalidDigestLength {
+                length: 64,
+                expected: 128
+            })
+        );
+    }
+
+    #[test_case("GE-Proton7-8.sha512sum" => Some(Algorithm::Sha512); "sha512sum")]
+    #[test_case("GE-Proton7-8.SHA256SUM" => Some(Algorithm::Sha256); "uppercase sha256sum")]
+    #[test_case("sha256sums.txt" => Some(Algorithm::Sha256); "sha256sums")]
+    #[test_case("proton-sha512-fix.sha256sum" => Some(Algorithm::Sha256); "extension decides")]
+    #[test_case("GE-Proton7-8.md5sum" => None; "unknown algorithm")]
+    fn algorithm_from_checksum_file_name(file_name: &str) -> Option<Algorithm> {
+        Algorithm::from_checksum_file_name(file_name)
+    }
+
+    #[test_case("" => ChecksumParseError::Empty; "empty")]
+    #[test_case(" \n\n" => ChecksumParseError::Empty; "blank lines")]
+    #[test_case("abcdef  test.tar.gz" => ChecksumParseError::InvalidDigestLength { length: 6, expected: 128 }; "short digest")]
+    #[test_case(&format!("{}0  test.tar.gz", ABC) => ChecksumParseError::InvalidDigestLength { length: 129, expected: 128 }; "long digest")]
+    #[test_case(&format!("{}  test.tar.gz", "g".repeat(128)) => ChecksumParseError::InvalidDigest { digest: "g".repeat(128) }; "non-hex digest")]
+    #[test_case(&format!("{} test.tar.gz", ABC) => ChecksumParseError::InvalidSeparator; "single space")]
+    #[test_case(&format!("{}\ttest.tar.gz", ABC) => ChecksumParseError::InvalidSeparator; "tab")]
+    #[test_case(&format!("{} *", ABC) => ChecksumParseError::EmptyFileName; "empty file name")]
+    #[test_case(&format!("{0}  a.tar.gz\n{0}  b.tar.gz\n", ABC) => ChecksumParseError::MultipleEntries { count: 2 }; "multiple entries")]
+    fn reject_malformed_checksum_file(content: &str) -> ChecksumParseError {
+        ChecksumFile::parse(content).unwrap_err()
+    }
+
+    #[test_case(&format!("{}  test.tar.gz", ABC); "text mode")]
+    #[test_case(&format!("{} *test.tar.gz", ABC); "binary mode")]
+    #[test_case(ABC; "digest only")]
+    fn display_round_trip(content: &str) {
+        let checksum_file: ChecksumFile = content.parse().unwrap();
+        assert_eq!(checksum_file.to_string(), content);
+    }
+}
@@ -0,0 +1,349 @@
+//! Download of the assets of several releases at once.
+//!
+//! A `BatchDownload` runs a list of `DownloadRequest`s with `GeDownload::download_release_assets` on a limited number
+//! of threads. Every request reports its progress through its own `ReadProgressWrapper`, so the progress of each item
+//! can be displayed separately. The result of every request is returned, failed items do not abort the others unless
+//! the batch is set to fail fast. The whole batch can be cancelled with a `BatchCancelHandle`.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use ge_man_lib::download::batch::BatchDownload;
+//! use ge_man_lib::download::{DownloadRequest, GeDownloader};
+//! use ge_man_lib::tag::TagKind;
+//!
+//! let requests = ["GE-Proton8-1", "GE-Proton8-2"]
+//!     .into_iter()
+//!     .map(|tag| DownloadRequest::builder().with_tag(tag).with_kind(TagKind::Proton).build().unwrap())
+//!     .collect();
+//! let batch = BatchDownload::new(requests).with_concurrency(2);
+//! let cancel = batch.cancel_handle();
+//!
+//! for (request, result) in batch.run(&GeDownloader::default()) {
+//!     match result {
+//!         Ok(assets) => println!("Downloaded {}", assets.tag),
+//!         Err(err) => println!("Failed to download {:?}: {}", request.tag, err),
+//!     }
+//! }
+//! # cancel.cancel();
+//! ```
+use std::io::Read;
+use std::mem;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+use crate::download::progress::{DownloadProgress, NoProgress};
+use crate::download::response::{DownloadedAssets, GeAsset};
+use crate::download::{CancellableReader, DownloadRequest, GeDownload, ReadProgressWrapper};
+use crate::error::GithubError;
+
+/// Number of requests that are downloaded at the same time by default.
+const DEFAULT_CONCURRENCY: usize = 3;
+
+/// The result of a single request of a `BatchDownload`.
+pub type BatchItem = (DownloadRequest, Result<DownloadedAssets, GithubError>);
+
+/// Downloads the assets of several `DownloadRequest`s concurrently.
+///
+/// By default three requests are downloaded at the same time. A failed request does not stop the other requests,
+/// unless `BatchDownload::with_fail_fast` is set.
+pub struct BatchDownload {
+    requests: Vec<DownloadRequest>,
+    concurrency: usize,
+    fail_fast: bool,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl BatchDownload {
+    pub fn new(requests: Vec<DownloadRequest>) -> Self {
+        BatchDownload {
+            requests,
+            concurrency: DEFAULT_CONCURRENCY,
+            fail_fast: false,
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Set the maximum number of requests that are downloaded at the same time. A `concurrency` of `0` is treated as
+    /// `1`.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Set if the first failed request should cancel the remaining requests of the batch. Defaults to `false`.
+    pub fn with_fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = fail_fast;
+        self
+    }
+
+    /// Get a handle to cancel the batch, for example from another thread while `BatchDownload::run` is running.
+    pub fn cancel_handle(&self) -> BatchCancelHandle {
+        BatchCancelHandle {
+            cancelled: Arc::clone(&self.cancelled),
+        }
+    }
+
+    /// Download the assets of all requests with `downloader` and return the result of every request.
+    ///
+    /// The results are returned in the order of the requests. Every request is returned with its result, but its
+    /// `progress_wrapper` was used up by the download and is replaced by `NoProgress`.
+    ///
+    /// Once the batch is cancelled, requests that were not started yet fail with `GithubError::Cancelled` without a
+    /// call to the `downloader`. Running downloads are stopped with the next read of their archive and fail with
+    /// `GithubError::Cancelled` as well.
+    pub fn run<D>(self, downloader: &D) -> Vec<BatchItem>
+    where
+        D: GeDownload + Sync + ?Sized,
+    {
+        let BatchDownload {
+            requests,
+            concurrency,
+            fail_fast,
+            cancelled,
+        } = self;
+        let len = requests.len();
+        let queue = Mutex::new(requests.into_iter().enumerate());
+        let items = Mutex::new(Vec::with_capacity(len));
+
+        thread::scope(|scope| {
+            for _ in 0..concurrency.min(len) {
+                scope.spawn(|| loop {
+                    let next = queue.lock().unwrap_or_else(PoisonError::into_inner).next();
+                    let Some((index, request)) = next else {
+                        break;
+                    };
+                    let item = download(downloader, request, &cancelled, fail_fast);
+                    items.lock().unwrap_or_else(PoisonError::into_inner).push((index, item));
+                });
+            }
+        });
+
+        let mut items = items.into_inner().unwrap_or_else(PoisonError::into_inner);
+        items.sort_by_key(|(index, _)| *index);
+        items.into_iter().map(|(_, item)| item).collect()
+    }
+}
+
+/// Cancels all requests of a `BatchDownload`. The handle can be cloned and sent to other threads.
+#[derive(Clone, Debug)]
+pub struct BatchCancelHandle {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl BatchCancelHandle {
+    /// Cancel the batch. Cancelling a batch more than once has no further effect.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Download the assets of a single `request` of a batch.
+fn download<D>(downloader: &D, mut request: DownloadRequest, cancelled: &Arc<AtomicBool>, fail_fast: bool) -> BatchItem
+where
+    D: GeDownload + ?Sized,
+{
+    if cancelled.load(Ordering::Relaxed) {
+        return (request, Err(GithubError::Cancelled));
+    }
+
+    let progress_wrapper = mem::replace(&mut request.progress_wrapper, Box::new(NoProgress));
+    let batch_request = DownloadRequest {
+        tag: request.tag.clone(),
+        kind: request.kind,
+        progress_wrapper: Box::new(CancellingProgressWrapper {
+            inner: progress_wrapper,
+            cancelled: Arc::clone(cancelled),
+        }),
+        download_checksum: request.download_checksum,
+        overwrite: request.overwrite,
+        verify: request.verify,
+        missing_checksum: request.missing_checksum,
+        max_bytes_per_sec: request.max_bytes_per_sec,
+    };
+
+    let result = downloader
+        .download_release_assets(batch_request)
+        .map_err(|err| match err {
+            GithubError::TransferError { .. } if cancelled.load(Ordering::Relaxed) => GithubError::Cancelled,
+            err => err,
+        });
+    if fail_fast && matches!(&result, Err(err) if !matches!(err, GithubError::Cancelled)) {
+        cancelled.store(true, Ordering::Relaxed);
+    }
+    (request, result)
+}
+
+/// Progress wrapper that stops the archive download of a request once its batch is cancelled.
+struct CancellingProgressWrapper {
+    inner: Box<dyn ReadProgressWrapper>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl ReadProgressWrapper for CancellingProgressWrapper {
+    fn init(self: Box<Self>, len: u64, asset: &GeAsset) -> Box<dyn ReadProgressWrapper> {
+        Box::new(CancellingProgressWrapper {
+            inner: self.inner.init(len, asset),
+            cancelled: self.cancelled,
+        })
+    }
+
+    fn wrap(&self, reader: Box<dyn Read>) -> Box<dyn Read> {
+        Box::new(CancellableReader::new(
+            self.inner.wrap(reader),
+            Arc::clone(&self.cancelled),
+        ))
+    }
+
+    fn finish(&self, asset: &GeAsset) {
+        self.inner.finish(asset);
+    }
+
+    fn update(&mut self, progress: DownloadProgress) {
+        self.inner.update(progress);
+    }
+
+    fn progress_interval(&self) -> Duration {
+        self.inner.progress_interval()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use crate::download::fake::{FakeCall, FakeGeDownloader};
+    use crate::tag::{Tag, TagKind};
+
+    use super::*;
+
+    const TAGS: [&str; 3] = ["GE-Proton8-1", "GE-Proton8-2", "GE-Proton8-3"];
+
+    fn downloader() -> FakeGeDownloader {
+        TAGS.iter().fold(FakeGeDownloader::new(), |downloader, tag| {
+            downloader.with_archive(&Tag::new(*tag), TagKind::Proton, tag.as_bytes().to_vec())
+        })
+    }
+
+    fn requests(tags: &[&str]) -> Vec<DownloadRequest> {
+        tags.iter()
+            .map(|tag| DownloadRequest::new(Some(String::from(*tag)), TagKind::Proton, Box::new(NoProgress), true))
+            .collect()
+    }
+
+    fn download_calls(downloader: &FakeGeDownloader) -> usize {
+        downloader
+            .calls()
+            .iter()
+            .filter(|call| matches!(call, FakeCall::DownloadReleaseAssets(_)))
+            .count()
+    }
+
+    /// Progress wrapper that cancels its batch when the download of its archive starts.
+    struct CancelOnInit(BatchCancelHandle);
+
+    impl ReadProgressWrapper for CancelOnInit {
+        fn init(self: Box<Self>, _len: u64, _asset: &GeAsset) -> Box<dyn ReadProgressWrapper> {
+            self.0.cancel();
+            self
+        }
+
+        fn wrap(&self, reader: Box<dyn Read>) -> Box<dyn Read> {
+            reader
+        }
+
+        fn finish(&self, _asset: &GeAsset) {}
+    }
+
+    #[test_case(1; "one thread")]
+    #[test_case(2; "fewer threads than requests")]
+    #[test_case(5; "more threads than requests")]
+    fn download_all_requests_in_order(concurrency: usize) {
+        let downloader = downloader();
+
+        let items = BatchDownload::new(requests(&TAGS))
+            .with_concurrency(concurrency)
+            .run(&downloader);
+
+        assert_eq!(items.len(), 3);
+        for ((request, result), tag) in items.into_iter().zip(TAGS) {
+            let assets = result.unwrap();
+            assert_eq!(request.tag.as_deref(), Some(tag));
+            assert_eq!(assets.tag, tag);
+            assert_eq!(assets.compressed_archive.compressed_content, tag.as_bytes());
+        }
+        assert_eq!(download_calls(&downloader), 3);
+    }
+
+    #[test]
+    fn download_without_requests() {
+        assert!(BatchDownload::new(Vec::new()).run(&downloader()).is_empty());
+    }
+
+    #[test]
+    fn failed_request_does_not_abort_others() {
+        let downloader = downloader();
+
+        let items = BatchDownload::new(requests(&["GE-Proton8-1", "GE-Proton9-1", "GE-Proton8-3"]))
+            .with_concurrency(1)
+            .run(&downloader);
+
+        assert!(items[0].1.is_ok());
+        assert!(matches!(items[1].1, Err(GithubError::NoTags)));
+        assert!(items[2].1.is_ok());
+        assert_eq!(download_calls(&downloader), 3);
+    }
+
+    #[test]
+    fn fail_fast_cancels_remaining_requests() {
+        let downloader = downloader();
+
+        let items = BatchDownload::new(requests(&["GE-Proton9-1", "GE-Proton8-1", "GE-Proton8-2"]))
+            .with_concurrency(1)
+            .with_fail_fast(true)
+            .run(&downloader);
+
+        assert!(matches!(items[0].1, Err(GithubError::NoTags)));
+        assert!(matches!(items[1].1, Err(GithubError::Cancelled)));
+        assert!(matches!(items[2].1, Err(GithubError::Cancelled)));
+        assert_eq!(download_calls(&downloader), 1);
+    }
+
+    #[test]
+    fn cancel_before_run() {
+        let downloader = downloader();
+        let batch = BatchDownload::new(requests(&TAGS));
+        let handle = batch.cancel_handle();
+
+        handle.cancel();
+        let items = batch.run(&downloader);
+
+        assert!(handle.is_cancelled());
+        assert!(items
+            .iter()
+            .all(|(_, result)| matches!(result, Err(GithubError::Cancelled))));
+        assert!(downloader.calls().is_empty());
+    }
+
+    #[test]
+    fn cancel_running_download() {
+        let downloader = downloader();
+        let mut requests = requests(&TAGS);
+        let batch = BatchDownload::new(Vec::new());
+        requests[1].progress_wrapper = Box::new(CancelOnInit(batch.cancel_handle()));
+        let batch = BatchDownload { requests, ..batch }.with_concurrency(1);
+
+        let items = batch.run(&downloader);
+
+        assert!(items[0].1.is_ok());
+        assert!(matches!(items[1].1, Err(GithubError::Cancelled)));
+        assert!(matches!(items[2].1, Err(GithubError::Cancelled)));
+        assert_eq!(download_calls(&downloader), 2);
+    }
+}
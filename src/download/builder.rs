@@ -0,0 +1,530 @@
+//! Configuration of a `GeDownloader` and of its `DownloadRequest`s.
+//!
+//! `GeDownloaderBuilder` creates a `GeDownloader` with a custom configuration of its HTTP client, like `Timeouts`, a
+//! `ProxyConfig` or a personal access token, and the `RepositorySource`s of the releases in a single place.
+//! `DownloadRequestBuilder` creates a `DownloadRequest` with named options instead of positional arguments.
+//!
+//! # Proxies
+//!
+//! The proxy of the HTTP client is chosen by the following precedence:
+//! 1. A client passed to `GeDownloaderBuilder::with_client` uses its own configuration. Proxy settings of the builder
+//!    are ignored.
+//! 2. A proxy set with `GeDownloaderBuilder::with_proxy` is used for all requests. Proxy environment variables are
+//!    ignored.
+//! 3. `GeDownloaderBuilder::without_proxy` connects directly and ignores the proxy environment variables.
+//! 4. Otherwise the proxy is taken from the environment variables `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY` and
+//!    `NO_PROXY`.
+use std::fmt::{Debug, Formatter};
+use std::time::Duration;
+
+use reqwest::blocking::Client;
+use reqwest::Proxy;
+
+use crate::download::github::{GithubDownloader, ProxyMode};
+use crate::download::progress::NoProgress;
+use crate::download::retry::RetryPolicy;
+use crate::download::source::RepositorySource;
+use crate::download::{DownloadRequest, GeDownloader, MissingChecksumPolicy, ReadProgressWrapper};
+use crate::error::{DownloadRequestError, GithubError};
+use crate::tag::TagKind;
+
+/// Timeouts of the requests of a `GeDownloader`.
+///
+/// A timeout of `None` disables the timeout. A request that times out fails with `GithubError::Timeout`, which tells
+/// in which `TimeoutPhase` the timeout occurred.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use ge_man_lib::download::builder::Timeouts;
+/// use ge_man_lib::download::GeDownloader;
+///
+/// let downloader = GeDownloader::builder()
+///     .with_timeouts(Timeouts {
+///         connect: Some(Duration::from_secs(5)),
+///         ..Timeouts::default()
+///     })
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Timeouts {
+    /// Maximum time to establish a connection to a server.
+    pub connect: Option<Duration>,
+    /// Maximum total time of a request to the GitHub API, including the time to receive the response body.
+    ///
+    /// Asset downloads are not limited by this timeout, as archives can take a long time to download.
+    pub request: Option<Duration>,
+    /// Maximum time to wait for the response of a server and for every further part of the response body.
+    ///
+    /// This timeout detects stalled asset downloads without limiting their total duration.
+    pub read: Option<Duration>,
+}
+
+impl Default for Timeouts {
+    /// Connect within 10 seconds, complete requests to the GitHub API within 30 seconds and receive data at least
+    /// every 30 seconds.
+    fn default() -> Self {
+        Timeouts {
+            connect: Some(Duration::from_secs(10)),
+            request: Some(Duration::from_secs(30)),
+            read: Some(Duration::from_secs(30)),
+        }
+    }
+}
+
+/// An HTTP or HTTPS proxy for all requests of a `GeDownloader`.
+///
+/// # Examples
+///
+/// ```
+/// use ge_man_lib::download::builder::ProxyConfig;
+/// use ge_man_lib::download::GeDownloader;
+///
+/// let proxy = ProxyConfig::new("http://proxy.example.com:3128").with_basic_auth("user", "password");
+/// let downloader = GeDownloader::builder().with_proxy(proxy).build().unwrap();
+/// ```
+#[derive(Clone)]
+pub struct ProxyConfig {
+    url: String,
+    basic_auth: Option<(String, String)>,
+}
+
+impl ProxyConfig {
+    /// Create a proxy configuration for the proxy at `url`, e.g. `http://proxy.example.com:3128`.
+    pub fn new<S: Into<String>>(url: S) -> Self {
+        ProxyConfig {
+            url: url.into(),
+            basic_auth: None,
+        }
+    }
+
+    /// Authenticate with the proxy with HTTP basic authentication.
+    pub fn with_basic_auth<U: Into<String>, P: Into<String>>(mut self, username: U, password: P) -> Self {
+        self.basic_auth = Some((username.into(), password.into()));
+        self
+    }
+
+    /// Get the URL of the proxy.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn to_proxy(&self) -> Result<Proxy, GithubError> {
+        let proxy = Proxy::all(&self.url).map_err(|source| GithubError::InvalidProxy {
+            url: self.url.clone(),
+            source,
+        })?;
+        Ok(match &self.basic_auth {
+            Some((username, password)) => proxy.basic_auth(username, password),
+            None => proxy,
+        })
+    }
+}
+
+impl Debug for ProxyConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProxyConfig")
+            .field("url", &self.url)
+            .field(
+                "basic_auth",
+                &self.basic_auth.as_ref().map(|(username, _)| (username, "<redacted>")),
+            )
+            .finish()
+    }
+}
+
+/// Builder for a `GeDownloader`.
+///
+/// Every setting that is not configured keeps the default of `GeDownloader::default()`. See the module documentation
+/// for how the proxy is chosen.
+#[derive(Clone)]
+pub struct GeDownloaderBuilder {
+    token: Option<String>,
+    user_agent: Option<String>,
+    timeouts: Timeouts,
+    proxy: Option<ProxyConfig>,
+    use_system_proxy: bool,
+    client: Option<Client>,
+    retry_policy: RetryPolicy,
+    page_size: u8,
+    sources: Vec<(TagKind, RepositorySource)>,
+    api_url: Option<String>,
+}
+
+impl GeDownloaderBuilder {
+    pub fn new() -> Self {
+        GeDownloaderBuilder {
+            token: None,
+            user_agent: None,
+            timeouts: Timeouts::default(),
+            proxy: None,
+            use_system_proxy: true,
+            client: None,
+            retry_policy: RetryPolicy::default(),
+            page_size: 30,
+            sources: Vec::new(),
+            api_url: None,
+        }
+    }
+
+    /// Authenticate requests to the GitHub API with a personal access `token`. See `GeDownloader::with_token`.
+    pub fn with_token<S: Into<String>>(mut self, token: S) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Identify the requests with `user_agent`, e.g. `my-app/1.2.3`.
+    ///
+    /// GitHub asks API consumers to send a `User-Agent` that identifies their application. Defaults to `ge_man-lib`.
+    /// The user agent is also sent if a client is set with `GeDownloaderBuilder::with_client`.
+    pub fn with_user_agent<S: Into<String>>(mut self, user_agent: S) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Set the `Timeouts` of the requests.
+    pub fn with_timeouts(mut self, timeouts: Timeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Send all requests through `proxy`.
+    pub fn with_proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Connect directly to GitHub, even if a proxy is configured by the environment.
+    pub fn without_proxy(mut self) -> Self {
+        self.proxy = None;
+        self.use_system_proxy = false;
+        self
+    }
+
+    /// Send all requests with a pre-configured `client`, e.g. one that trusts the root certificate of a
+    /// TLS-intercepting proxy.
+    ///
+    /// Sharing a client with the rest of an application lets the downloader reuse its connection pool. The connect and
+    /// read `Timeouts` and the proxy settings of this builder are not applied to the `client`. The total timeout of
+    /// GitHub API requests is still applied to every request. Requests are sent with the user agent of the `client`,
+    /// unless one is set with `GeDownloaderBuilder::with_user_agent`. GitHub rejects requests without a user agent.
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Set the `RetryPolicy` for failed requests. See `GeDownloader::with_retry_policy`.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Set the number of items that are requested per page. See `GeDownloader::with_page_size`.
+    pub fn with_page_size(mut self, page_size: u8) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Download the releases of the given `TagKind`s from other repositories. See `GeDownloader::with_sources`.
+    pub fn with_sources<I: IntoIterator<Item = (TagKind, RepositorySource)>>(mut self, sources: I) -> Self {
+        self.sources.extend(sources);
+        self
+    }
+
+    /// Send the requests to the GitHub API to `api_url` instead of `https://api.github.com`, e.g. to a GitHub
+    /// Enterprise server or to a local mock server in tests. The token is sent to `api_url` only. See
+    /// `GeDownloader::with_api_url`.
+    pub fn with_api_url<S: Into<String>>(mut self, api_url: S) -> Self {
+        self.api_url = Some(api_url.into());
+        self
+    }
+
+    /// Create the `GeDownloader`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GithubError::InvalidProxy` if the URL of the `ProxyConfig` is not valid.
+    pub fn build(self) -> Result<GeDownloader, GithubError> {
+        let proxy = match (&self.proxy, self.use_system_proxy) {
+            (Some(proxy), _) => ProxyMode::Explicit(proxy.to_proxy()?),
+            (None, true) => ProxyMode::System,
+            (None, false) => ProxyMode::Disabled,
+        };
+        let github_downloader = match self.token {
+            Some(token) => GithubDownloader::with_token(token),
+            None => GithubDownloader::new(),
+        }
+        .with_timeouts(self.timeouts)
+        .with_proxy(proxy);
+        let github_downloader = match self.user_agent {
+            Some(user_agent) => github_downloader.with_user_agent(user_agent),
+            None => github_downloader,
+        };
+        let github_downloader = match self.client {
+            Some(client) => github_downloader.with_client(client),
+            None => github_downloader,
+        };
+        let github_downloader = match &self.api_url {
+            Some(api_url) => github_downloader.with_api_url(api_url.clone()),
+            None => github_downloader,
+        };
+
+        let downloader = GeDownloader::new(Box::new(github_downloader))
+            .with_retry_policy(self.retry_policy)
+            .with_page_size(self.page_size)
+            .with_sources(self.sources);
+        Ok(match self.api_url {
+            Some(api_url) => downloader.with_api_url(api_url),
+            None => downloader,
+        })
+    }
+}
+
+impl Debug for GeDownloaderBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GeDownloaderBuilder")
+            .field("token", &self.token.as_ref().map(|_| "<redacted>"))
+            .field("user_agent", &self.user_agent)
+            .field("timeouts", &self.timeouts)
+            .field("proxy", &self.proxy)
+            .field("use_system_proxy", &self.use_system_proxy)
+            .field("client", &self.client)
+            .field("retry_policy", &self.retry_policy)
+            .field("page_size", &self.page_size)
+            .field("sources", &self.sources)
+            .field("api_url", &self.api_url)
+            .finish()
+    }
+}
+
+impl Default for GeDownloaderBuilder {
+    fn default() -> Self {
+        GeDownloaderBuilder::new()
+    }
+}
+
+/// Builder for a `DownloadRequest`.
+///
+/// Only the `TagKind` must be set. By default the latest release is requested, the checksum is downloaded, the
+/// progress is not tracked and existing files are not replaced.
+///
+/// # Examples
+///
+/// ```
+/// use ge_man_lib::download::DownloadRequest;
+/// use ge_man_lib::tag::TagKind;
+///
+/// let request = DownloadRequest::builder()
+///     .with_tag("GE-Proton7-8")
+///     .with_kind(TagKind::Proton)
+///     .without_checksum()
+///     .build()
+///     .unwrap();
+/// assert!(!request.download_checksum);
+/// ```
+pub struct DownloadRequestBuilder {
+    tag: Option<String>,
+    kind: Option<TagKind>,
+    progress_wrapper: Box<dyn ReadProgressWrapper>,
+    download_checksum: bool,
+    overwrite: bool,
+    verify: bool,
+    missing_checksum: MissingChecksumPolicy,
+    max_bytes_per_sec: Option<u64>,
+}
+
+impl DownloadRequestBuilder {
+    pub fn new() -> Self {
+        DownloadRequestBuilder {
+            tag: None,
+            kind: None,
+            progress_wrapper: Box::new(NoProgress),
+            download_checksum: true,
+            overwrite: false,
+            verify: true,
+            missing_checksum: MissingChecksumPolicy::default(),
+            max_bytes_per_sec: None,
+        }
+    }
+
+    /// Request the release with the given `tag` instead of the latest release.
+    pub fn with_tag<S: Into<String>>(mut self, tag: S) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// Request the release of the given `kind`.
+    pub fn with_kind(mut self, kind: TagKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Track the download progress with `progress_wrapper`.
+    pub fn with_progress_wrapper(mut self, progress_wrapper: Box<dyn ReadProgressWrapper>) -> Self {
+        self.progress_wrapper = progress_wrapper;
+        self
+    }
+
+    /// Do not download the checksum of the release.
+    pub fn without_checksum(mut self) -> Self {
+        self.download_checksum = false;
+        self
+    }
+
+    /// Set if existing files should be replaced by `GeDownload::download_release_assets_to_dir`.
+    pub fn with_overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    /// Do not verify the archive with the downloaded checksum.
+    pub fn without_verification(mut self) -> Self {
+        self.verify = false;
+        self
+    }
+
+    /// Set what to do if the release has no checksum file.
+    pub fn with_missing_checksum(mut self, missing_checksum: MissingChecksumPolicy) -> Self {
+        self.missing_checksum = missing_checksum;
+        self
+    }
+
+    /// Download the archive with at most `max_bytes_per_sec` bytes per second.
+    pub fn with_max_bytes_per_sec(mut self, max_bytes_per_sec: u64) -> Self {
+        self.max_bytes_per_sec = Some(max_bytes_per_sec);
+        self
+    }
+
+    /// Create the `DownloadRequest`.
+    ///
+    /// # Errors
+    ///
+    /// * `DownloadRequestError::MissingKind` if no `TagKind` was set
+    /// * `DownloadRequestError::EmptyTag` if the tag is empty or consists only of whitespace
+    pub fn build(self) -> Result<DownloadRequest, DownloadRequestError> {
+        let kind = self.kind.ok_or(DownloadRequestError::MissingKind)?;
+        if self.tag.as_ref().is_some_and(|tag| tag.trim().is_empty()) {
+            return Err(DownloadRequestError::EmptyTag);
+        }
+
+        Ok(
+            DownloadRequest::new(self.tag, kind, self.progress_wrapper, self.download_checksum)
+                .with_overwrite(self.overwrite)
+                .with_verify(self.verify)
+                .with_missing_checksum(self.missing_checksum)
+                .with_max_bytes_per_sec(self.max_bytes_per_sec),
+        )
+    }
+}
+
+impl Debug for DownloadRequestBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DownloadRequestBuilder")
+            .field("tag", &self.tag)
+            .field("kind", &self.kind)
+            .field("download_checksum", &self.download_checksum)
+            .field("overwrite", &self.overwrite)
+            .field("verify", &self.verify)
+            .field("missing_checksum", &self.missing_checksum)
+            .field("max_bytes_per_sec", &self.max_bytes_per_sec)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for DownloadRequestBuilder {
+    fn default() -> Self {
+        DownloadRequestBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test]
+    fn build_with_settings() {
+        let downloader = GeDownloader::builder()
+            .with_token("secret-token")
+            .with_retry_policy(RetryPolicy::none())
+            .with_page_size(0)
+            .build()
+            .unwrap();
+
+        assert_eq!(downloader.retry_policy.max_attempts, 1);
+        assert_eq!(downloader.page_size, 1);
+    }
+
+    #[test]
+    fn default_timeouts() {
+        let timeouts = Timeouts::default();
+        assert_eq!(timeouts.connect, Some(Duration::from_secs(10)));
+        assert!(timeouts.request.is_some());
+        assert!(timeouts.read.is_some());
+    }
+
+    #[test]
+    fn reject_invalid_proxy_url() {
+        let result = GeDownloader::builder()
+            .with_proxy(ProxyConfig::new("not a url"))
+            .build();
+        assert!(matches!(result, Err(GithubError::InvalidProxy { url, .. }) if url == "not a url"));
+    }
+
+    #[test]
+    fn secrets_are_not_part_of_debug_output() {
+        let proxy = ProxyConfig::new("http://proxy:3128").with_basic_auth("user", "secret-password");
+        let builder = GeDownloader::builder().with_token("secret-token").with_proxy(proxy);
+        let debug = format!("{:?}", builder);
+        assert!(debug.contains("user"));
+        assert!(!debug.contains("secret-password"));
+        assert!(!debug.contains("secret-token"));
+    }
+
+    #[test]
+    fn build_request_with_defaults() {
+        let request = DownloadRequest::builder().with_kind(TagKind::lol()).build().unwrap();
+
+        assert_eq!(request.tag, None);
+        assert_eq!(request.kind, TagKind::lol());
+        assert!(request.download_checksum);
+        assert!(!request.overwrite);
+        assert!(request.verify);
+        assert_eq!(request.missing_checksum, MissingChecksumPolicy::Continue);
+        assert_eq!(request.max_bytes_per_sec, None);
+    }
+
+    #[test]
+    fn build_request_with_options() {
+        let request = DownloadRequest::builder()
+            .with_tag("GE-Proton7-8")
+            .with_kind(TagKind::Proton)
+            .with_progress_wrapper(Box::new(NoProgress))
+            .without_checksum()
+            .with_overwrite(true)
+            .without_verification()
+            .with_missing_checksum(MissingChecksumPolicy::Fail)
+            .with_max_bytes_per_sec(1_000_000)
+            .build()
+            .unwrap();
+
+        assert_eq!(request.tag.as_deref(), Some("GE-Proton7-8"));
+        assert_eq!(request.kind, TagKind::Proton);
+        assert!(!request.download_checksum);
+        assert!(request.overwrite);
+        assert!(!request.verify);
+        assert_eq!(request.missing_checksum, MissingChecksumPolicy::Fail);
+        assert_eq!(request.max_bytes_per_sec, Some(1_000_000));
+    }
+
+    #[test_case(DownloadRequest::builder().with_tag("GE-Proton7-8") => DownloadRequestError::MissingKind; "missing kind")]
+    #[test_case(DownloadRequest::builder().with_kind(TagKind::Proton).with_tag("") => DownloadRequestError::EmptyTag; "empty tag")]
+    #[test_case(DownloadRequest::builder().with_kind(TagKind::Proton).with_tag(" \t") => DownloadRequestError::EmptyTag; "whitespace tag")]
+    fn reject_invalid_request(builder: DownloadRequestBuilder) -> DownloadRequestError {
+        builder.build().err().unwrap()
+    }
+}
@@ -0,0 +1,836 @@
+//! In-memory implementation of `GeDownload` for tests.
+//!
+//! With the `test-util` feature a `FakeGeDownloader` can replace a `GeDownloader` in the tests of consuming crates.
+//! It serves canned releases and asset contents without any network access, records every call it receives and can
+//! fail a call with a configured `GithubError`.
+//!
+//! # Examples
+//!
+//! ```
+//! use ge_man_lib::download::fake::{FakeCall, FakeGeDownloader};
+//! use ge_man_lib::download::{DownloadRequest, GeDownload};
+//! use ge_man_lib::tag::{Tag, TagKind};
+//!
+//! let downloader = FakeGeDownloader::new().with_archive(&Tag::new("GE-Proton8-1"), TagKind::Proton, b"archive".to_vec());
+//!
+//! let request = DownloadRequest::builder().with_kind(TagKind::Proton).build().unwrap();
+//! let assets = downloader.download_release_assets(request).unwrap();
+//!
+//! assert_eq!(assets.tag, "GE-Proton8-1");
+//! assert_eq!(assets.compressed_archive.compressed_content, b"archive");
+//! assert!(assets.checksum.is_some());
+//! assert!(matches!(&downloader.calls()[..], [FakeCall::DownloadReleaseAssets(_)]));
+//! ```
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Cursor, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+use crate::digest;
+use crate::download::plan::DownloadPlan;
+use crate::download::response::{
+    ArchiveMetadata, DownloadedArchive, DownloadedAssetPaths, DownloadedAssets, DownloadedChecksum, GeAsset, GeRelease,
+    Verification,
+};
+use crate::download::source::RepositorySource;
+use crate::download::{verify_download, write_asset_file, DownloadRequest, GeDownload, ReadProgressWrapper};
+use crate::error::GithubError;
+use crate::tag::{Tag, TagKind};
+
+const PAGE_SIZE: usize = 30;
+const APPLICATION_OCTET_STREAM: &str = "application/octet-stream";
+
+/// The parts of a `DownloadRequest` that a `FakeGeDownloader` records.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordedRequest {
+    pub tag: Option<String>,
+    pub kind: TagKind,
+    pub download_checksum: bool,
+    pub overwrite: bool,
+}
+
+impl From<&DownloadRequest> for RecordedRequest {
+    fn from(request: &DownloadRequest) -> Self {
+        RecordedRequest {
+            tag: request.tag.clone(),
+            kind: request.kind,
+            download_checksum: request.download_checksum,
+            overwrite: request.overwrite,
+        }
+    }
+}
+
+/// A call of a `GeDownload` method received by a `FakeGeDownloader`.
+///
+/// Provided methods of `GeDownload`, like `GeDownload::fetch_release_if_modified`, are recorded as the calls they are
+/// implemented with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FakeCall {
+    FetchRelease { tag: Option<String>, kind: TagKind },
+    FetchReleases { kind: TagKind, page: u8 },
+    Resolve(RecordedRequest),
+    Execute(DownloadPlan),
+    DownloadReleaseAssets(RecordedRequest),
+    DownloadArchiveTo(RecordedRequest),
+    DownloadReleaseAssetsToDir { request: RecordedRequest, dir: PathBuf },
+    ResumeArchiveDownload { request: RecordedRequest, path: PathBuf },
+    FetchTags { kind: TagKind, limit: Option<usize> },
+}
+
+/// A `GeDownload` implementation that serves canned releases from memory.
+///
+/// Releases are added with `FakeGeDownloader::with_release`, and the contents of their assets with
+/// `FakeGeDownloader::with_asset_content`. `FakeGeDownloader::with_archive` does both for a release with an archive
+/// and a matching `sha512sum` checksum file. The latest release of a kind is the release with the greatest `Tag`.
+///
+/// A release that was not added is reported as `GithubError::NoTags`. Downloading an asset without content fails
+/// with a `GithubError::TransferError`.
+#[derive(Debug, Default)]
+pub struct FakeGeDownloader {
+    releases: Vec<(TagKind, GeRelease)>,
+    contents: HashMap<String, Vec<u8>>,
+    failures: Mutex<BTreeMap<usize, GithubError>>,
+    calls: Mutex<Vec<FakeCall>>,
+}
+
+impl FakeGeDownloader {
+    pub fn new() -> Self {
+        FakeGeDownloader::default()
+    }
+
+    /// Add a canned `release` of `kind`.
+    pub fn with_release(mut self, kind: TagKind, release: GeRelease) -> Self {
+        self.releases.push((kind, release));
+        self
+    }
+
+    /// Set the `content` served for the asset with the download URL `url`.
+    pub fn with_asset_content<S: Into<String>>(mut self, url: S, content: Vec<u8>) -> Self {
+        self.contents.insert(url.into(), content);
+        self
+    }
+
+    /// Add a release of `kind` for `tag` with the archive `content` and a checksum file with its SHA-512 checksum.
+    ///
+    /// The assets have the official file names of the release, see `Tag::archive_file_name` and
+    /// `Tag::checksum_file_name`.
+    pub fn with_archive(self, tag: &Tag, kind: TagKind, content: Vec<u8>) -> Self {
+        let archive_name = tag.archive_file_name(kind);
+        let checksum_name = tag.checksum_file_name(kind);
+        let checksum = format!("{}  {}\n", digest::sha512(&content).to_hex(), archive_name);
+
+        let url = |name: &str| {
+            format!(
+                "https://github.com/{}/releases/download/{}/{}",
+                RepositorySource::official(&kind),
+                tag,
+                name
+            )
+        };
+        let archive = GeAsset::new(
+            archive_name.as_str(),
+            APPLICATION_OCTET_STREAM,
+            url(&archive_name).as_str(),
+        )
+        .with_size(content.len() as u64);
+        let checksum_asset = GeAsset::new(
+            checksum_name.as_str(),
+            APPLICATION_OCTET_STREAM,
+            url(&checksum_name).as_str(),
+        )
+        .with_size(checksum.len() as u64);
+        let release = GeRelease::new(tag.str().clone(), vec![archive.clone(), checksum_asset.clone()]);
+
+        self.with_release(kind, release)
+            .with_asset_content(archive.browser_download_url, content)
+            .with_asset_content(checksum_asset.browser_download_url, checksum.into_bytes())
+    }
+
+    /// Fail the `call`th call of a `GeDownload` method with `error`.
+    ///
+    /// Calls are counted from `1` over all methods, in the order in which they are recorded in
+    /// `FakeGeDownloader::calls`.
+    pub fn with_failure(self, call: usize, error: GithubError) -> Self {
+        self.failures
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(call, error);
+        self
+    }
+
+    /// Get the calls received so far, from first to last.
+    pub fn calls(&self) -> Vec<FakeCall> {
+        self.calls.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    fn record(&self, call: FakeCall) -> Result<(), GithubError> {
+        let number = {
+            let mut calls = self.calls.lock().unwrap_or_else(PoisonError::into_inner);
+            calls.push(call);
+            calls.len()
+        };
+        match self
+            .failures
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&number)
+        {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Get the releases of `kind` from newest to oldest.
+    fn releases_of(&self, kind: TagKind) -> Vec<&GeRelease> {
+        let mut releases: Vec<&GeRelease> = self
+            .releases
+            .iter()
+            .filter(|(release_kind, _)| *release_kind == kind)
+            .map(|(_, release)| release)
+            .collect();
+        releases.sort_by_cached_key(|release| std::cmp::Reverse(Tag::from(release.tag_name.as_str())));
+        releases
+    }
+
+    fn find_release(&self, tag: Option<&str>, kind: TagKind) -> Result<&GeRelease, GithubError> {
+        let releases = self.releases_of(kind);
+        let release = match tag {
+            Some(tag) => releases.into_iter().find(|release| release.tag_name == tag),
+            None => releases.into_iter().next(),
+        };
+        release.ok_or(GithubError::NoTags)
+    }
+
+    fn resolve_release(&self, request: &DownloadRequest) -> Result<(&GeRelease, DownloadPlan), GithubError> {
+        let release = self.find_release(request.tag.as_deref(), request.kind)?;
+        if release.assets.is_empty() {
+            return Err(GithubError::ReleaseHasNoAssets {
+                tag: release.tag_name.clone(),
+                kind: request.kind,
+            });
+        }
+
+        let plan = request.plan(release)?;
+        Ok((release, plan))
+    }
+
+    fn content(&self, asset: &GeAsset) -> Result<&[u8], GithubError> {
+        self.contents
+            .get(&asset.browser_download_url)
+            .map(Vec::as_slice)
+            .ok_or_else(|| GithubError::TransferError {
+                file_name: asset.file_name(),
+                source: io::Error::new(ErrorKind::NotFound, "the fake has no content for this asset"),
+            })
+    }
+
+    /// Read the content of `asset` through the `progress_wrapper`.
+    fn read_archive(
+        &self,
+        progress_wrapper: Box<dyn ReadProgressWrapper>,
+        asset: &GeAsset,
+    ) -> Result<Vec<u8>, GithubError> {
+        let content = self.content(asset)?;
+        let progress_wrapper = progress_wrapper.init(content.len() as u64, asset);
+
+        let mut archive = Vec::with_capacity(content.len());
+        progress_wrapper
+            .wrap(Box::new(Cursor::new(content.to_vec())))
+            .read_to_end(&mut archive)
+            .map_err(|source| GithubError::TransferError {
+                file_name: asset.file_name(),
+                source,
+            })?;
+        progress_wrapper.finish(asset);
+        Ok(archive)
+    }
+
+    /// Verify the downloaded `content` of the archive `file_name` with the `checksum` or the asset digest, if the
+    /// `plan` verifies.
+    fn verify(
+        plan: &DownloadPlan,
+        file_name: &str,
+        content: &[u8],
+        checksum: Option<&DownloadedChecksum>,
+    ) -> Result<Verification, GithubError> {
+        let digest = plan
+            .hash_algorithm()
+            .map(|algorithm| digest::digest(algorithm, content));
+        verify_download(file_name, digest, checksum, plan)
+    }
+
+    fn checksum(&self, asset: Option<&GeAsset>) -> Result<Option<DownloadedChecksum>, GithubError> {
+        asset
+            .map(|asset| {
+                let content = self.content(asset)?;
+                Ok(DownloadedChecksum::new(
+                    String::from_utf8_lossy(content).into_owned(),
+                    asset.file_name(),
+                ))
+            })
+            .transpose()
+    }
+
+    fn execute_for_release(
+        &self,
+        plan: &DownloadPlan,
+        release: &GeRelease,
+        progress_wrapper: Box<dyn ReadProgressWrapper>,
+    ) -> Result<DownloadedAssets, GithubError> {
+        let (archive_asset, checksum_asset) = plan.validate(release)?;
+        let checksum = self.checksum(checksum_asset)?;
+        let content = self.read_archive(progress_wrapper, archive_asset)?;
+        let verification = FakeGeDownloader::verify(plan, &archive_asset.file_name(), &content, checksum.as_ref())?;
+
+        let archive = DownloadedArchive::new(content, archive_asset.file_name());
+        let archive = match archive_asset.compression() {
+            Some(compression) => archive.with_compression(compression),
+            None => archive,
+        };
+        Ok(DownloadedAssets::new(plan.tag.clone(), archive, checksum).with_verification(verification))
+    }
+}
+
+impl GeDownload for FakeGeDownloader {
+    fn fetch_release(&self, tag: Option<String>, kind: TagKind) -> Result<GeRelease, GithubError> {
+        self.record(FakeCall::FetchRelease { tag: tag.clone(), kind })?;
+        self.find_release(tag.as_deref(), kind).cloned()
+    }
+
+    /// Get a page of the releases of `kind` from newest to oldest, with 30 releases per page.
+    fn fetch_releases(&self, kind: TagKind, page: u8) -> Result<Vec<GeRelease>, GithubError> {
+        self.record(FakeCall::FetchReleases { kind, page })?;
+        let skip = usize::from(page.max(1) - 1) * PAGE_SIZE;
+        Ok(self
+            .releases_of(kind)
+            .into_iter()
+            .skip(skip)
+            .take(PAGE_SIZE)
+            .cloned()
+            .collect())
+    }
+
+    fn resolve(&self, request: &DownloadRequest) -> Result<DownloadPlan, GithubError> {
+        self.record(FakeCall::Resolve(RecordedRequest::from(request)))?;
+        self.resolve_release(request).map(|(_, plan)| plan)
+    }
+
+    fn execute(
+        &self,
+        plan: &DownloadPlan,
+        progress_wrapper: Box<dyn ReadProgressWrapper>,
+    ) -> Result<DownloadedAssets, GithubError> {
+        self.record(FakeCall::Execute(plan.clone()))?;
+        let release = self.find_release(Some(&plan.tag), plan.kind)?;
+        self.execute_for_release(plan, release, progress_wrapper)
+    }
+
+    fn download_release_assets(&self, request: DownloadRequest) -> Result<DownloadedAssets, GithubError> {
+        self.record(FakeCall::DownloadReleaseAssets(RecordedRequest::from(&request)))?;
+        let (release, plan) = self.resolve_release(&request)?;
+        self.execute_for_release(&plan, release, request.progress_wrapper)
+    }
+
+    fn download_archive_to(
+        &self,
+        request: DownloadRequest,
+        sink: &mut dyn Write,
+    ) -> Result<ArchiveMetadata, GithubError> {
+        self.record(FakeCall::DownloadArchiveTo(RecordedRequest::from(&request)))?;
+        let (release, plan) = self.resolve_release(&request)?;
+        let (archive_asset, checksum_asset) = plan.validate(release)?;
+        let checksum = self.checksum(checksum_asset)?;
+
+        let file_name = archive_asset.file_name();
+        let content = self.read_archive(request.progress_wrapper, archive_asset)?;
+        sink.write_all(&content).map_err(|source| GithubError::WriteError {
+            file_name: file_name.clone(),
+            source,
+        })?;
+        let verification = FakeGeDownloader::verify(&plan, &file_name, &content, checksum.as_ref())?;
+
+        Ok(ArchiveMetadata {
+            tag: plan.tag,
+            file_name,
+            size: content.len() as u64,
+            content_type: Some(archive_asset.content_type.clone()),
+            checksum,
+            resumed_from: None,
+            verification,
+        })
+    }
+
+    fn download_release_assets_to_dir(
+        &self,
+        request: DownloadRequest,
+        dir: &Path,
+    ) -> Result<DownloadedAssetPaths, GithubError> {
+        self.record(FakeCall::DownloadReleaseAssetsToDir {
+            request: RecordedRequest::from(&request),
+            dir: dir.to_path_buf(),
+        })?;
+        let (release, plan) = self.resolve_release(&request)?;
+        let (archive_asset, checksum_asset) = plan.validate(release)?;
+
+        let archive_path = dir.join(archive_asset.file_name());
+        let checksum_path = checksum_asset.map(|asset| dir.join(asset.file_name()));
+        if !request.overwrite {
+            if let Some(path) = std::iter::once(&archive_path)
+                .chain(&checksum_path)
+                .find(|path| path.exists())
+            {
+                return Err(GithubError::FileExists { path: path.clone() });
+            }
+        }
+
+        let checksum = self.checksum(checksum_asset)?;
+        let content = self.read_archive(request.progress_wrapper, archive_asset)?;
+        if let (Some(checksum), Some(path)) = (&checksum, &checksum_path) {
+            write_asset_file(path, request.overwrite, |file| {
+                file.write_all(checksum.checksum.as_bytes())
+                    .map_err(|source| GithubError::WriteError {
+                        file_name: checksum.file_name.clone(),
+                        source,
+                    })
+            })?;
+        }
+
+        let archive_result = write_asset_file(&archive_path, request.overwrite, |file| {
+            file.write_all(&content).map_err(|source| GithubError::WriteError {
+                file_name: archive_asset.file_name(),
+                source,
+            })?;
+            FakeGeDownloader::verify(&plan, &archive_asset.file_name(), &content, checksum.as_ref())
+        });
+        let verification = match archive_result {
+            Ok(verification) => verification,
+            Err(err) => {
+                if let Some(path) = &checksum_path {
+                    let _ = fs::remove_file(path);
+                }
+                return Err(err);
+            }
+        };
+
+        Ok(DownloadedAssetPaths {
+            tag: plan.tag,
+            archive: archive_path,
+            checksum: checksum_path,
+            verification,
+        })
+    }
+
+    /// Append the rest of the archive to the partial file at `path`.
+    ///
+    /// The download is resumed if the file is shorter than the archive. Otherwise, the whole archive is written into
+    /// the file. Like `GeDownloader::resume_archive_download`, a file that does not match the checksum is removed.
+    fn resume_archive_download(&self, request: DownloadRequest, path: &Path) -> Result<ArchiveMetadata, GithubError> {
+        self.record(FakeCall::ResumeArchiveDownload {
+            request: RecordedRequest::from(&request),
+            path: path.to_path_buf(),
+        })?;
+        let (release, plan) = self.resolve_release(&request)?;
+        let (archive_asset, checksum_asset) = plan.validate(release)?;
+        let checksum = self.checksum(checksum_asset)?;
+
+        let file_name = archive_asset.file_name();
+        let write_error = |source| GithubError::WriteError {
+            file_name: file_name.clone(),
+            source,
+        };
+        let content = self.read_archive(request.progress_wrapper, archive_asset)?;
+        let offset = match fs::metadata(path) {
+            Ok(metadata) => metadata.len(),
+            Err(err) if err.kind() == ErrorKind::NotFound => 0,
+            Err(err) => return Err(write_error(err)),
+        };
+
+        let resumed_from = (offset > 0 && offset < content.len() as u64).then_some(offset);
+        match resumed_from {
+            Some(offset) => OpenOptions::new()
+                .append(true)
+                .open(path)
+                .and_then(|mut file| file.write_all(&content[offset as usize..])),
+            None => fs::write(path, &content),
+        }
+        .map_err(write_error)?;
+
+        let written = fs::read(path).map_err(write_error)?;
+        let verification = match FakeGeDownloader::verify(&plan, &file_name, &written, checksum.as_ref()) {
+            Ok(verification) => verification,
+            Err(err) => {
+                let _ = fs::remove_file(path);
+                return Err(err);
+            }
+        };
+
+        Ok(ArchiveMetadata {
+            tag: plan.tag,
+            file_name,
+            size: content.len() as u64,
+            content_type: Some(archive_asset.content_type.clone()),
+            checksum,
+            resumed_from,
+            verification,
+        })
+    }
+
+    fn fetch_tags(&self, kind: TagKind, limit: Option<usize>) -> Result<Vec<Tag>, GithubError> {
+        self.record(FakeCall::FetchTags { kind, limit })?;
+        Ok(self
+            .releases_of(kind)
+            .into_iter()
+            .map(|release| Tag::from(release.tag_name.as_str()))
+            .take(limit.unwrap_or(usize::MAX))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+    use test_case::test_case;
+
+    use crate::archive;
+    use crate::download::progress::NoProgress;
+
+    use super::*;
+
+    /// Implements only the required methods of `GeDownload` to test the provided methods.
+    struct RequiredMethods(FakeGeDownloader);
+
+    impl GeDownload for RequiredMethods {
+        fn fetch_release(&self, tag: Option<String>, kind: TagKind) -> Result<GeRelease, GithubError> {
+            self.0.fetch_release(tag, kind)
+        }
+
+        fn fetch_releases(&self, kind: TagKind, page: u8) -> Result<Vec<GeRelease>, GithubError> {
+            self.0.fetch_releases(kind, page)
+        }
+
+        fn download_release_assets(&self, request: DownloadRequest) -> Result<DownloadedAssets, GithubError> {
+            self.0.download_release_assets(request)
+        }
+    }
+
+    fn proton_downloader() -> FakeGeDownloader {
+        FakeGeDownloader::new()
+            .with_archive(&Tag::new("GE-Proton8-1"), TagKind::Proton, b"old archive".to_vec())
+            .with_archive(&Tag::new("GE-Proton8-2"), TagKind::Proton, b"new archive".to_vec())
+            .with_archive(&Tag::new("GE-8-1"), TagKind::wine(), b"wine archive".to_vec())
+    }
+
+    fn request(tag: Option<&str>, download_checksum: bool) -> DownloadRequest {
+        DownloadRequest::new(
+            tag.map(String::from),
+            TagKind::Proton,
+            Box::new(NoProgress),
+            download_checksum,
+        )
+    }
+
+    #[test_case(None => "GE-Proton8-2"; "latest release")]
+    #[test_case(Some("GE-Proton8-1") => "GE-Proton8-1"; "release of tag")]
+    fn fetch_release(tag: Option<&str>) -> String {
+        let downloader = proton_downloader();
+        downloader
+            .fetch_release(tag.map(String::from), TagKind::Proton)
+            .unwrap()
+            .tag_name
+    }
+
+    #[test]
+    fn fetch_unknown_release() {
+        let result = proton_downloader().fetch_release(Some(String::from("GE-Proton9-1")), TagKind::Proton);
+        assert!(matches!(result, Err(GithubError::NoTags)));
+    }
+
+    #[test]
+    fn download_release_assets_with_valid_checksum() {
+        let downloader = proton_downloader();
+
+        let assets = downloader.download_release_assets(request(None, true)).unwrap();
+
+        assert_eq!(assets.tag, "GE-Proton8-2");
+        assert_eq!(assets.compressed_archive.file_name, "GE-Proton8-2.tar.gz");
+        assert_eq!(assets.compressed_archive.compressed_content, b"new archive");
+        let checksum = assets.checksum.unwrap();
+        assert_eq!(checksum.file_name, "GE-Proton8-2.sha512sum");
+        archive::verify_named("GE-Proton8-2.tar.gz", b"new archive", checksum.checksum.as_bytes()).unwrap();
+    }
+
+    #[test_case(true; "verify")]
+    #[test_case(false; "without verification")]
+    fn download_changed_archive(verify: bool) {
+        let downloader = proton_downloader().with_asset_content(
+            "https://github.com/GloriousEggroll/proton-ge-custom/releases/download/GE-Proton8-2/GE-Proton8-2.tar.gz",
+            b"changed archive".to_vec(),
+        );
+
+        let result = downloader.download_release_assets(request(None, true).with_verify(verify));
+        assert_eq!(
+            matches!(result, Err(GithubError::VerificationFailed { file_name, .. }) if file_name == "GE-Proton8-2.tar.gz"),
+            verify
+        );
+    }
+
+    #[test]
+    fn record_calls() {
+        let downloader = proton_downloader();
+
+        let plan = downloader.resolve(&request(Some("GE-Proton8-1"), false)).unwrap();
+        downloader.execute(&plan, Box::new(NoProgress)).unwrap();
+        downloader.fetch_tags(TagKind::Proton, Some(1)).unwrap();
+
+        assert_eq!(
+            downloader.calls(),
+            vec![
+                FakeCall::Resolve(RecordedRequest {
+                    tag: Some(String::from("GE-Proton8-1")),
+                    kind: TagKind::Proton,
+                    download_checksum: false,
+                    overwrite: false,
+                }),
+                FakeCall::Execute(plan),
+                FakeCall::FetchTags {
+                    kind: TagKind::Proton,
+                    limit: Some(1),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn fail_nth_call() {
+        let downloader = proton_downloader().with_failure(2, GithubError::NoTags);
+
+        assert!(downloader.fetch_tags(TagKind::Proton, None).is_ok());
+        assert!(matches!(
+            downloader.fetch_tags(TagKind::Proton, None),
+            Err(GithubError::NoTags)
+        ));
+        assert_eq!(downloader.fetch_tags(TagKind::Proton, None).unwrap().len(), 2);
+        assert_eq!(downloader.calls().len(), 3);
+    }
+
+    #[test]
+    fn fetch_latest_for_all_kinds() {
+        let releases = proton_downloader().fetch_latest_for_all_kinds();
+
+        let kinds: Vec<TagKind> = releases.iter().map(|(kind, _)| *kind).collect();
+        assert_eq!(kinds, TagKind::values());
+        assert_eq!(releases[0].1.as_ref().unwrap().tag_name, "GE-Proton8-2");
+        assert_eq!(releases[1].1.as_ref().unwrap().tag_name, "GE-8-1");
+        assert!(matches!(releases[2].1, Err(GithubError::NoTags)));
+    }
+
+    #[test]
+    fn fetch_releases_of_kind() {
+        let downloader = proton_downloader();
+
+        let releases = downloader.fetch_releases(TagKind::Proton, 1).unwrap();
+        let tags: Vec<&str> = releases.iter().map(|release| release.tag_name.as_str()).collect();
+        assert_eq!(tags, ["GE-Proton8-2", "GE-Proton8-1"]);
+        assert!(downloader.fetch_releases(TagKind::Proton, 2).unwrap().is_empty());
+    }
+
+    #[test]
+    fn asset_without_content() {
+        let release = GeRelease::new(
+            String::from("GE-Proton8-1"),
+            vec![GeAsset::new("GE-Proton8-1.tar.gz", APPLICATION_OCTET_STREAM, "missing")],
+        );
+        let downloader = FakeGeDownloader::new().with_release(TagKind::Proton, release);
+
+        let result = downloader.download_release_assets(request(None, false));
+        assert!(
+            matches!(result, Err(GithubError::TransferError { file_name, .. }) if file_name == "GE-Proton8-1.tar.gz")
+        );
+    }
+
+    #[test]
+    fn download_release_assets_to_dir() {
+        let dir = TempDir::new().unwrap();
+
+        let paths = proton_downloader()
+            .download_release_assets_to_dir(request(None, true), dir.path())
+            .unwrap();
+
+        assert_eq!(paths.archive, dir.path().join("GE-Proton8-2.tar.gz"));
+        dir.child("GE-Proton8-2.tar.gz").assert("new archive");
+        dir.child("GE-Proton8-2.sha512sum")
+            .assert(predicates::str::ends_with("  GE-Proton8-2.tar.gz\n"));
+    }
+
+    #[test]
+    fn download_and_extract() {
+        let dir = TempDir::new().unwrap();
+        let content = std::fs::read("test_resources/assets/test.tar.gz").unwrap();
+        let downloader = FakeGeDownloader::new().with_archive(&Tag::new("GE-Proton9-1"), TagKind::Proton, content);
+
+        let tool = downloader
+            .download_and_extract(request(None, true), dir.path())
+            .unwrap();
+
+        assert_eq!(tool.path, dir.path().join("test"));
+        dir.child("test/hello-world.txt").assert(predicates::path::exists());
+        assert!(matches!(downloader.calls()[..], [FakeCall::DownloadArchiveTo(_)]));
+    }
+
+    #[test]
+    fn resume_archive_download() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.child("GE-Proton8-2.tar.gz");
+        file.write_binary(b"new ").unwrap();
+
+        let metadata = proton_downloader()
+            .resume_archive_download(request(None, true), file.path())
+            .unwrap();
+
+        assert_eq!(metadata.resumed_from, Some(4));
+        assert_eq!(metadata.size, 11);
+        file.assert("new archive");
+    }
+
+    #[test]
+    fn default_resolve_and_execute() {
+        let downloader = RequiredMethods(proton_downloader());
+
+        let plan = downloader.resolve(&request(Some("GE-Proton8-1"), true)).unwrap();
+        let assets = downloader.execute(&plan, Box::new(NoProgress)).unwrap();
+
+        assert_eq!(plan.archive.name, "GE-Proton8-1.tar.gz");
+        assert_eq!(assets.compressed_archive.compressed_content, b"old archive");
+        assert!(assets.checksum.is_some());
+        assert_eq!(
+            downloader.0.calls(),
+            vec![
+                FakeCall::FetchRelease {
+                    tag: Some(String::from("GE-Proton8-1")),
+                    kind: TagKind::Proton,
+                },
+                FakeCall::FetchRelease {
+                    tag: Some(String::from("GE-Proton8-1")),
+                    kind: TagKind::Proton,
+                },
+                FakeCall::DownloadReleaseAssets(RecordedRequest {
+                    tag: Some(String::from("GE-Proton8-1")),
+                    kind: TagKind::Proton,
+                    download_checksum: true,
+                    overwrite: false,
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn default_execute_with_outdated_plan() {
+        let downloader = RequiredMethods(proton_downloader());
+        let mut plan = downloader.resolve(&request(None, false)).unwrap();
+        plan.archive.url = String::from("https://host/moved.tar.gz");
+
+        let result = downloader.execute(&plan, Box::new(NoProgress));
+        assert!(
+            matches!(result, Err(GithubError::OutdatedDownloadPlan { asset, .. }) if asset == "GE-Proton8-2.tar.gz")
+        );
+    }
+
+    #[test]
+    fn default_download_archive_to() {
+        let downloader = RequiredMethods(proton_downloader());
+
+        let mut sink = Vec::new();
+        let metadata = downloader.download_archive_to(request(None, true), &mut sink).unwrap();
+
+        assert_eq!(sink, b"new archive");
+        assert_eq!(metadata.file_name, "GE-Proton8-2.tar.gz");
+        assert_eq!(metadata.size, 11);
+        assert!(metadata.checksum.is_some());
+        assert!(matches!(downloader.0.calls()[..], [FakeCall::DownloadReleaseAssets(_)]));
+    }
+
+    #[test]
+    fn default_download_release_assets_to_dir() {
+        let dir = TempDir::new().unwrap();
+        let downloader = RequiredMethods(proton_downloader());
+
+        let paths = downloader
+            .download_release_assets_to_dir(request(None, true), dir.path())
+            .unwrap();
+
+        assert_eq!(paths.tag, "GE-Proton8-2");
+        assert_eq!(paths.checksum, Some(dir.path().join("GE-Proton8-2.sha512sum")));
+        dir.child("GE-Proton8-2.tar.gz").assert("new archive");
+        dir.child("GE-Proton8-2.sha512sum")
+            .assert(predicates::str::ends_with("  GE-Proton8-2.tar.gz\n"));
+    }
+
+    #[test]
+    fn default_download_release_assets_to_dir_keeps_existing_files() {
+        let dir = TempDir::new().unwrap();
+        dir.child("GE-Proton8-2.sha512sum").write_str("existing").unwrap();
+        let downloader = RequiredMethods(proton_downloader());
+
+        let result = downloader.download_release_assets_to_dir(request(None, true), dir.path());
+
+        assert!(
+            matches!(result, Err(GithubError::FileExists { path }) if path == dir.path().join("GE-Proton8-2.sha512sum"))
+        );
+        dir.child("GE-Proton8-2.tar.gz").assert(predicates::path::missing());
+        dir.child("GE-Proton8-2.sha512sum").assert("existing");
+    }
+
+    #[test_case(None => vec!["GE-Proton8-33", "GE-Proton8-32", "GE-Proton8-31", "GE-Proton8-30"]; "all tags")]
+    #[test_case(Some(2) => vec!["GE-Proton8-33", "GE-Proton8-32"]; "limit")]
+    fn default_fetch_tags(limit: Option<usize>) -> Vec<String> {
+        let downloader = RequiredMethods((1..=33).fold(FakeGeDownloader::new(), |downloader, patch| {
+            let tag = Tag::new(format!("GE-Proton8-{}", patch));
+            downloader.with_archive(&tag, TagKind::Proton, Vec::new())
+        }));
+
+        let tags = downloader.fetch_tags(TagKind::Proton, limit).unwrap();
+
+        let pages = downloader
+            .0
+            .calls()
+            .iter()
+            .filter(|call| matches!(call, FakeCall::FetchReleases { .. }))
+            .count();
+        assert_eq!(pages, if limit.is_some() { 1 } else { 3 });
+        tags.into_iter().take(4).map(String::from).collect()
+    }
+
+    #[test]
+    fn default_resume_archive_download() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.child("GE-Proton8-2.tar.gz");
+        file.write_binary(b"new archive with trailing bytes").unwrap();
+        let downloader = RequiredMethods(proton_downloader());
+
+        let metadata = downloader
+            .resume_archive_download(request(None, true), file.path())
+            .unwrap();
+
+        assert_eq!(metadata.resumed_from, None);
+        assert_eq!(metadata.size, 11);
+        file.assert("new archive");
+    }
+
+    #[test]
+    fn default_resume_archive_download_removes_unverified_file() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.child("GE-Proton8-2.tar.gz");
+        file.write_binary(b"new ").unwrap();
+        let downloader = RequiredMethods(proton_downloader().with_asset_content(
+            "https://github.com/GloriousEggroll/proton-ge-custom/releases/download/GE-Proton8-2/GE-Proton8-2.tar.gz",
+            b"changed archive".to_vec(),
+        ));
+
+        let result = downloader.resume_archive_download(request(None, true), file.path());
+
+        assert!(matches!(result, Err(GithubError::VerificationFailed { .. })));
+        file.assert(predicates::path::missing());
+    }
+}
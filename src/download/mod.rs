@@ -1,24 +1,55 @@
 //! Functionality relating to downloading GE release assets.
 //!
-//! This module interfaces with the GitHub API of the `proton-ge-custom` and `wine-ge-custom` repository. It provides:
+//! This module interfaces with the GitHub API of the `proton-ge-custom` and `wine-ge-custom` repository, or of the
+//! forks configured with `GeDownloader::with_sources`. It provides:
 //! * A struct for downloading release assets from the above repositories
 //! * Structs containing the downloaded data
 use std::fmt::Display;
-use std::io::Read;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use std::{panic, thread};
 
-use lazy_static::lazy_static;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use reqwest::blocking::Response;
+use reqwest::header::{ETAG, LINK};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 
+use crate::archive::{self, Compression, ExtractOptions, ExtractedTool};
+use crate::digest::{Algorithm, Digest, Hasher};
+use crate::download::builder::{DownloadRequestBuilder, GeDownloaderBuilder};
 use crate::download::github::{GithubDownload, GithubDownloader};
+use crate::download::plan::DownloadPlan;
+use crate::download::progress::{DownloadProgress, ProgressReader};
 use crate::download::response::{
-    CompatibilityToolTag, DownloadedArchive, DownloadedAssets, DownloadedChecksum, GeAsset, GeRelease,
+    ArchiveMetadata, CompatibilityToolTag, ConditionalRelease, DownloadedArchive, DownloadedAssetPaths,
+    DownloadedAssets, DownloadedChecksum, FetchedTags, GeAsset, GeRelease, ResolvedAssets, TaggedRelease, Verification,
 };
-use crate::error::GithubError;
-use crate::tag::{Tag, TagKind, WineTagKind};
+use crate::download::retry::RetryPolicy;
+use crate::download::source::{RepositorySource, RepositorySources};
+use crate::download::throttle::ThrottledReader;
+use crate::error::{ExtractError, GithubError};
+use crate::tag::{SemVer, Tag, TagKind, WineTagKind};
 
+pub mod batch;
+pub mod builder;
+#[cfg(any(test, feature = "test-util"))]
+pub mod fake;
 mod github;
 
+#[cfg(feature = "async")]
+pub mod nonblocking;
+pub mod plan;
+pub mod progress;
 pub mod response;
+pub mod retry;
+pub mod source;
+mod throttle;
 
 #[cfg(test)]
 mod mime {
@@ -28,16 +59,362 @@ mod mime {
 }
 
 const GITHUB_API_URL: &str = "https://api.github.com";
-const PROTON_GE_RELEASE_LATEST_URL: &str = "repos/GloriousEggroll/proton-ge-custom/releases/latest";
-const PROTON_GE_RELEASE_TAGS_URL: &str = "repos/GloriousEggroll/proton-ge-custom/releases/tags";
-const WINE_GE_RELEASE_TAGS_URL: &str = "repos/GloriousEggroll/wine-ge-custom/releases/tags";
-const WINE_GE_TAGS_URL: &str = "repos/GloriousEggroll/wine-ge-custom/tags";
 
-lazy_static! {
-    static ref GITHUB_PROTON_GE_LATEST_URL: String = format!("{}/{}", GITHUB_API_URL, PROTON_GE_RELEASE_LATEST_URL);
-    static ref GITHUB_PROTON_GE_TAG_URL: String = format!("{}/{}", GITHUB_API_URL, PROTON_GE_RELEASE_TAGS_URL);
-    static ref GITHUB_WINE_GE_RELEASE_TAG_URL: String = format!("{}/{}", GITHUB_API_URL, WINE_GE_RELEASE_TAGS_URL);
-    static ref GITHUB_WINE_GE_TAGS_URL: String = format!("{}/{}", GITHUB_API_URL, WINE_GE_TAGS_URL);
+// Everything except the unreserved characters of RFC 3986 is encoded in a URL path segment.
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'.').remove(b'_').remove(b'~');
+
+/// Remove the trailing slashes of `api_url`, so paths can be appended with a single slash.
+fn normalize_api_url(api_url: &str) -> String {
+    api_url.trim_end_matches('/').to_owned()
+}
+
+/// Create the URL of a release by appending the percent-encoded `tag` to `release_tags_url`.
+fn release_tag_url(release_tags_url: &str, tag: &str) -> String {
+    format!("{}/{}", release_tags_url, utf8_percent_encode(tag, PATH_SEGMENT))
+}
+
+/// Pick the latest tag of `kind` from a page of Wine GE repository tags.
+fn latest_wine_ge_tag(tag_names: Vec<String>, kind: &WineTagKind) -> Option<Tag> {
+    tag_names
+        .into_iter()
+        .filter(|t| is_wine_tag_of_kind(t, kind))
+        .map(Tag::from)
+        .max_by(Tag::cmp)
+}
+
+/// Does a tag of the Wine GE repository belong to `kind`. Both Wine kinds share the repository.
+fn is_wine_tag_of_kind(tag_name: &str, kind: &WineTagKind) -> bool {
+    match kind {
+        WineTagKind::LolWineGe => tag_name.contains("LoL"),
+        WineTagKind::WineGe => !tag_name.contains("LoL"),
+    }
+}
+
+/// Find the tag with the version `semver`.
+///
+/// A tag matches if its major, minor and patch version are equal to `semver`. Tags with the same version and identifier
+/// are preferred over tags with a different identifier. Among equally good tags the highest tag is returned.
+fn find_tag_by_semver(tags: Vec<Tag>, semver: &SemVer) -> Option<Tag> {
+    let rank = |tag: &Tag| {
+        let tag_semver = tag.semver();
+        if tag_semver == semver {
+            0
+        } else if tag_semver.parsed_identifier() == semver.parsed_identifier() {
+            1
+        } else {
+            2
+        }
+    };
+
+    tags.into_iter()
+        .filter(|tag| {
+            let tag_semver = tag.semver();
+            tag_semver.major() == semver.major()
+                && tag_semver.minor() == semver.minor()
+                && tag_semver.patch() == semver.patch()
+        })
+        .min_by(|a, b| rank(a).cmp(&rank(b)).then_with(|| b.cmp(a)))
+}
+
+/// Get the URL with `rel="next"` from the value of a `Link` header.
+fn next_page_url(link: &str) -> Option<String> {
+    link.split(',').find_map(|link| {
+        let (url, params) = link.split_once(';')?;
+        params
+            .split(';')
+            .any(|param| param.trim() == "rel=\"next\"")
+            .then(|| String::from(url.trim().trim_start_matches('<').trim_end_matches('>')))
+    })
+}
+
+/// Copy the content of the asset `file_name` from `reader` into `sink` and return the number of copied bytes.
+///
+/// Errors of the `reader` are returned as `GithubError::TransferError` or `GithubError::Timeout` and errors of the
+/// `sink` as `GithubError::WriteError`.
+fn copy_asset(reader: &mut dyn Read, sink: &mut dyn Write, file_name: &str) -> Result<u64, GithubError> {
+    let mut buf = vec![0; 64 * 1024];
+    let mut copied = 0;
+    loop {
+        let read = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(source) => return Err(GithubError::transfer(file_name, source)),
+        };
+        sink.write_all(&buf[..read]).map_err(|source| GithubError::WriteError {
+            file_name: String::from(file_name),
+            source,
+        })?;
+        copied += read as u64;
+    }
+
+    sink.flush().map_err(|source| GithubError::WriteError {
+        file_name: String::from(file_name),
+        source,
+    })?;
+    Ok(copied)
+}
+
+/// Tell if the response to a request for a resource found the resource. A `404` response is `false`, every other not
+/// successful response is an error.
+fn is_found(result: Result<Response, GithubError>) -> Result<bool, GithubError> {
+    let response = match result {
+        Ok(response) => response,
+        Err(GithubError::StatusNotOk {
+            status: StatusCode::NOT_FOUND,
+            ..
+        }) => return Ok(false),
+        Err(err) => return Err(err),
+    };
+    match response.status() {
+        status if status.is_success() || status.is_redirection() => Ok(true),
+        StatusCode::NOT_FOUND => Ok(false),
+        _ => Err(GithubError::status_not_ok(response)),
+    }
+}
+
+/// Check that the `received` bytes of the asset `file_name` match its `expected` size, if the size is known.
+fn check_size(file_name: &str, expected: Option<u64>, received: u64) -> Result<(), GithubError> {
+    match expected {
+        Some(expected) if expected != received => Err(GithubError::TruncatedDownload {
+            file_name: String::from(file_name),
+            expected,
+            received,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Reader that fails once `cancelled` is set, to stop a download whose result is no longer needed.
+pub(crate) struct CancellableReader<R> {
+    reader: R,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl<R> CancellableReader<R> {
+    pub(crate) fn new(reader: R, cancelled: Arc<AtomicBool>) -> Self {
+        CancellableReader { reader, cancelled }
+    }
+}
+
+impl<R: Read> Read for CancellableReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.cancelled.load(Ordering::Relaxed) {
+            return Err(std::io::Error::other("The download was cancelled"));
+        }
+        self.reader.read(buf)
+    }
+}
+
+/// Writer that computes the digest of everything written into `sink` with `algorithm`, if it is set.
+struct HashingWriter<W> {
+    sink: W,
+    hasher: Option<Hasher>,
+}
+
+impl<W: Write> HashingWriter<W> {
+    fn new(sink: W, algorithm: Option<Algorithm>) -> Self {
+        HashingWriter {
+            sink,
+            hasher: algorithm.map(Hasher::new),
+        }
+    }
+
+    fn finish(self) -> (W, Option<Digest>) {
+        (self.sink, self.hasher.map(Hasher::finish))
+    }
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.sink.write(buf)?;
+        if let Some(hasher) = &mut self.hasher {
+            hasher.update(&buf[..written]);
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.sink.flush()
+    }
+}
+
+/// Reader that computes the digest of everything read from `reader` with `algorithm`, if it is set, and counts the
+/// read bytes.
+///
+/// The first error of `reader` is kept, so that a failed download can be told apart from an error of the consumer of
+/// the content, e.g. a decompression error.
+struct HashingReader<R> {
+    reader: R,
+    hasher: Option<Hasher>,
+    received: u64,
+    error: Option<io::Error>,
+}
+
+impl<R: Read> HashingReader<R> {
+    fn new(reader: R, algorithm: Option<Algorithm>) -> Self {
+        HashingReader {
+            reader,
+            hasher: algorithm.map(Hasher::new),
+            received: 0,
+            error: None,
+        }
+    }
+
+    fn finish(self) -> (u64, Option<Digest>) {
+        (self.received, self.hasher.map(Hasher::finish))
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.reader.read(buf) {
+            Ok(read) => {
+                if let Some(hasher) = &mut self.hasher {
+                    hasher.update(&buf[..read]);
+                }
+                self.received += read as u64;
+                Ok(read)
+            }
+            Err(err) if err.kind() == ErrorKind::Interrupted => Err(err),
+            Err(err) => {
+                let kind = err.kind();
+                self.error.get_or_insert(err);
+                Err(io::Error::new(kind, "The archive could not be received"))
+            }
+        }
+    }
+}
+
+/// Verify the `digest` of the archive `file_name`, which was computed with `DownloadPlan::hash_algorithm` while the
+/// archive was downloaded.
+///
+/// The digest is verified with the downloaded `checksum` or, if the release has no checksum file, with the expected
+/// digest of the `plan`. Nothing is verified without a `digest`. Returns how the archive was verified.
+fn verify_download(
+    file_name: &str,
+    digest: Option<Digest>,
+    checksum: Option<&DownloadedChecksum>,
+    plan: &DownloadPlan,
+) -> Result<Verification, GithubError> {
+    let failed = |source| GithubError::VerificationFailed {
+        file_name: String::from(file_name),
+        source,
+    };
+    match (digest, checksum, plan.expected_digest()) {
+        (Some(digest), Some(checksum), _) => {
+            checksum.verify_digest(file_name, &digest).map_err(failed)?;
+            Ok(Verification::ChecksumFile)
+        }
+        (Some(digest), None, Some(expected)) => {
+            expected.verify(&digest).map_err(failed)?;
+            Ok(Verification::AssetDigest(expected.algorithm()))
+        }
+        _ => Ok(Verification::Unverified),
+    }
+}
+
+/// Get the value of the `ETag` header of `response`.
+fn response_etag(response: &Response) -> Option<String> {
+    response
+        .headers()
+        .get(ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from)
+}
+
+/// Write the body of `response` with the content of `asset` into `sink`.
+///
+/// `len` is the expected length of the body. A body of another length fails with `GithubError::TruncatedDownload`.
+/// The body is read with at most `max_bytes_per_sec`, if set. Returns the number of written bytes and the content type
+/// of the response.
+fn stream_response(
+    response: Response,
+    progress_wrapper: Box<dyn ReadProgressWrapper>,
+    asset: &GeAsset,
+    len: Option<u64>,
+    sink: &mut dyn Write,
+    max_bytes_per_sec: Option<u64>,
+    cancelled: Arc<AtomicBool>,
+) -> Result<(u64, Option<String>), GithubError> {
+    let content_length = response.content_length();
+    let tar_size = content_length.or(len).unwrap_or_default();
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+    let response = CancellableReader::new(ThrottledReader::new(response, max_bytes_per_sec), cancelled);
+
+    let mut progress_wrapper = progress_wrapper.init(tar_size, asset);
+    let reader = progress_wrapper.wrap(Box::new(response));
+    let mut reader = ProgressReader::new(reader, content_length, progress_wrapper.as_mut());
+    let size = copy_asset(&mut reader, sink, &asset.file_name())?;
+    check_size(&asset.file_name(), len, size)?;
+    progress_wrapper.finish(asset);
+
+    Ok((size, content_type))
+}
+
+/// Parse a `Content-Range` header value like `bytes 100-199/200` into the first byte, the last byte and the total
+/// size. The total size is `None` if it is unknown (`*`).
+fn parse_content_range(value: &str) -> Option<(u64, u64, Option<u64>)> {
+    let (range, total) = value.strip_prefix("bytes ")?.split_once('/')?;
+    let (first, last) = range.split_once('-')?;
+    let total = match total {
+        "*" => None,
+        total => Some(total.parse().ok()?),
+    };
+    Some((first.parse().ok()?, last.parse().ok()?, total))
+}
+
+/// Check if `content_range` covers everything from the byte `start` up to the end of an asset with the given `size`.
+fn is_remainder(content_range: Option<&str>, start: u64, size: Option<u64>) -> bool {
+    match content_range.and_then(parse_content_range) {
+        Some((first, last, total)) => {
+            let total = total.or(size);
+            first == start
+                && first <= last
+                && total.is_none_or(|total| last + 1 == total)
+                && size.is_none_or(|size| total == Some(size))
+        }
+        None => false,
+    }
+}
+
+/// Create the file at `path`, write its content with `write` and sync it to disk.
+///
+/// An existing file is only replaced if `overwrite` is set. The file is removed again if its content could not be
+/// written.
+fn write_asset_file<T, F>(path: &Path, overwrite: bool, write: F) -> Result<T, GithubError>
+where
+    F: FnOnce(&mut File) -> Result<T, GithubError>,
+{
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+    let write_error = |source| GithubError::WriteError {
+        file_name: file_name.clone(),
+        source,
+    };
+
+    let mut options = OpenOptions::new();
+    if overwrite {
+        options.write(true).create(true).truncate(true);
+    } else {
+        options.write(true).create_new(true);
+    }
+    let mut file = options.open(path).map_err(|source| match source.kind() {
+        ErrorKind::AlreadyExists => GithubError::FileExists {
+            path: path.to_path_buf(),
+        },
+        _ => write_error(source),
+    })?;
+
+    let result = write(&mut file).and_then(|value| file.sync_all().map(|_| value).map_err(write_error));
+    if result.is_err() {
+        drop(file);
+        let _ = fs::remove_file(path);
+    }
+    result
 }
 
 /// Trait defining how to determine the progress for a `Read` type.
@@ -45,7 +422,7 @@ lazy_static! {
 /// This trait helps with providing progress information when performing a download. Currently, this trait is
 /// mostly focused on providing a working implementation with the `indicatif` crate due to that crate being used in
 /// `ge_man` itself. Therefore, this trait might not work too well with other progress tracking crates.
-pub trait ReadProgressWrapper {
+pub trait ReadProgressWrapper: Send {
     /// Tells the implementing struct how to construct itself. For example, `indicatif` requires a progress bar
     /// struct to be created that performs the tracking of a `Read` type.
     ///
@@ -67,6 +444,15 @@ pub trait ReadProgressWrapper {
     fn wrap(&self, reader: Box<dyn Read>) -> Box<dyn Read>;
     /// Define how the implementing struct should finish tracking progress.
     fn finish(&self, release: &GeAsset);
+    /// Receive the progress of the archive download.
+    ///
+    /// This method is called on the initialized wrapper while the archive is read, at most once per
+    /// `progress_interval`, and always once the archive was read completely. The default implementation does nothing.
+    fn update(&mut self, _progress: DownloadProgress) {}
+    /// Minimum time between two calls of `update`. Defaults to 100 milliseconds.
+    fn progress_interval(&self) -> Duration {
+        Duration::from_millis(100)
+    }
 }
 
 /// Data required to perform a download requests against the GitHub API for a GE version.
@@ -82,9 +468,29 @@ pub struct DownloadRequest {
     pub progress_wrapper: Box<dyn ReadProgressWrapper>,
     /// Should the checksum file be downloaded.
     pub download_checksum: bool,
+    /// Should existing files be replaced by `GeDownload::download_release_assets_to_dir`. Defaults to `false`.
+    pub overwrite: bool,
+    /// Should the archive be verified with the downloaded checksum. Defaults to `true`.
+    ///
+    /// The digest of the archive is computed with the algorithm of the checksum file (SHA-512 or SHA-256) while it is
+    /// downloaded, so the archive is not read twice. If the release has no checksum file, the archive is verified with
+    /// the digest of its asset instead, see `GeAsset::digest`. This has no effect if `download_checksum` is not set.
+    pub verify: bool,
+    /// What to do if `download_checksum` is set, but the release has no checksum file and the archive asset has no
+    /// digest. Defaults to `MissingChecksumPolicy::Continue`.
+    pub missing_checksum: MissingChecksumPolicy,
+    /// Maximum download rate of the archive in bytes per second. Defaults to `None`, which does not limit the rate.
+    ///
+    /// The limit is applied by `GeDownloader` and `AsyncGeDownloader`.
+    pub max_bytes_per_sec: Option<u64>,
 }
 
 impl DownloadRequest {
+    /// Create a `DownloadRequestBuilder` to build a request with named options.
+    pub fn builder() -> DownloadRequestBuilder {
+        DownloadRequestBuilder::new()
+    }
+
     pub fn new(
         tag: Option<String>,
         kind: TagKind,
@@ -96,753 +502,4045 @@ impl DownloadRequest {
             kind,
             progress_wrapper,
             download_checksum,
+            overwrite: false,
+            verify: true,
+            missing_checksum: MissingChecksumPolicy::default(),
+            max_bytes_per_sec: None,
         }
     }
-}
 
-/// Trait defining methods for fetching release data.
-///
-/// This trait mostly exists for testing purposes so consuming crates can crate a mock from this trait.
-pub trait GeDownload {
-    fn fetch_release(&self, tag: Option<String>, kind: TagKind) -> Result<GeRelease, GithubError>;
-    fn download_release_assets(&self, request: DownloadRequest) -> Result<DownloadedAssets, GithubError>;
-}
+    /// Set if existing files should be replaced by `GeDownload::download_release_assets_to_dir`.
+    pub fn with_overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
 
-/// Default implementation for the `GeDownload` trait.
-pub struct GeDownloader {
-    github_downloader: Box<dyn GithubDownload>,
-}
+    /// Set if the archive should be verified with the downloaded checksum.
+    pub fn with_verify(mut self, verify: bool) -> Self {
+        self.verify = verify;
+        self
+    }
 
-impl GeDownloader {
-    pub fn new(github_downloader: Box<dyn GithubDownload>) -> Self {
-        GeDownloader { github_downloader }
+    /// Set what to do if the release has no checksum file.
+    pub fn with_missing_checksum(mut self, missing_checksum: MissingChecksumPolicy) -> Self {
+        self.missing_checksum = missing_checksum;
+        self
     }
 
-    fn create_url<S: AsRef<str>>(&self, tag: Option<S>, kind: &TagKind) -> Result<String, GithubError>
-    where
-        S: AsRef<str> + Display,
-    {
-        let tag = tag.as_ref();
-        match kind {
-            TagKind::Proton => {
-                if let Some(t) = tag {
-                    Ok(format!("{}/{}", *GITHUB_PROTON_GE_TAG_URL, t))
-                } else {
-                    Ok(String::from(&*GITHUB_PROTON_GE_LATEST_URL))
-                }
-            }
-            TagKind::Wine { kind: wine_kind } => {
-                if let Some(t) = tag {
-                    Ok(format!("{}/{}", *GITHUB_WINE_GE_RELEASE_TAG_URL, t))
-                } else {
-                    self.find_latest_wine_ge_release_tag(wine_kind)
-                        .map(|t| format!("{}/{}", *GITHUB_WINE_GE_RELEASE_TAG_URL, t))
-                }
-            }
-        }
+    /// Set the maximum download rate of the archive in bytes per second. `None` does not limit the rate.
+    pub fn with_max_bytes_per_sec(mut self, max_bytes_per_sec: Option<u64>) -> Self {
+        self.max_bytes_per_sec = max_bytes_per_sec;
+        self
     }
 
-    fn create_wine_ge_tags_url(&self, page: u8) -> String {
-        format!("{}?page={}", *GITHUB_WINE_GE_TAGS_URL, page)
+    /// Create the `DownloadPlan` of this request for `release`.
+    fn plan(&self, release: &GeRelease) -> Result<DownloadPlan, GithubError> {
+        let plan = DownloadPlan::from_release(release, self.kind, self.download_checksum)?;
+        if self.download_checksum
+            && !plan.checksum_available
+            && plan.expected_digest().is_none()
+            && self.missing_checksum == MissingChecksumPolicy::Fail
+        {
+            return Err(GithubError::MissingChecksumAsset { tag: plan.tag });
+        }
+        Ok(plan
+            .with_verify(self.verify)
+            .with_max_bytes_per_sec(self.max_bytes_per_sec))
     }
+}
 
-    fn find_latest_wine_ge_release_tag(&self, kind: &WineTagKind) -> Result<Tag, GithubError> {
-        let mut page = 1;
-        loop {
-            let mut tag_names: Vec<String> = self
-                .fetch_wine_ge_tags(page)?
-                .json::<Vec<CompatibilityToolTag>>()?
-                .into_iter()
-                .map(Into::into)
-                .collect();
+/// Fetch the release of a `request` and create its `DownloadPlan`.
+fn resolve_release<D: GeDownload + ?Sized>(
+    downloader: &D,
+    request: &DownloadRequest,
+) -> Result<(GeRelease, DownloadPlan), GithubError> {
+    let release = downloader.fetch_release(request.tag.clone(), request.kind)?;
+    if release.assets.is_empty() {
+        return Err(GithubError::ReleaseHasNoAssets {
+            tag: release.tag_name,
+            kind: request.kind,
+        });
+    }
 
-            if tag_names.is_empty() {
-                return Err(GithubError::NoTags);
-            }
+    let plan = request.plan(&release)?;
+    Ok((release, plan))
+}
 
-            if let WineTagKind::LolWineGe = kind {
-                tag_names.retain(|t| t.contains("LoL"));
-            } else {
-                tag_names.retain(|t| !t.contains("LoL"));
-            }
+/// What to do if a checksum is requested for a release that has no checksum file.
+///
+/// The policy only applies if the archive can not be verified with the digest of its asset either.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MissingChecksumPolicy {
+    /// Download the archive without a checksum. The downloaded assets contain no checksum and their verification is
+    /// `Verification::Unverified`, which the caller can report as a warning.
+    #[default]
+    Continue,
+    /// Fail the download with `GithubError::MissingChecksumAsset`.
+    Fail,
+}
 
-            let latest_tag = tag_names.into_iter().map(|t| Tag::from(t)).max_by(Tag::cmp);
-            if let Some(t) = latest_tag {
-                return Ok(t);
-            }
-            page += 1
-        }
-    }
+/// Trait defining methods for fetching release data.
+///
+/// This trait mostly exists for testing purposes so consuming crates can crate a mock from this trait.
+pub trait GeDownload {
+    fn fetch_release(&self, tag: Option<String>, kind: TagKind) -> Result<GeRelease, GithubError>;
+    fn fetch_releases(&self, kind: TagKind, page: u8) -> Result<Vec<GeRelease>, GithubError>;
+    fn download_release_assets(&self, request: DownloadRequest) -> Result<DownloadedAssets, GithubError>;
 
-    fn fetch_wine_ge_tags(&self, page: u8) -> Result<Response, GithubError> {
-        let url = self.create_wine_ge_tags_url(page);
-        self.github_downloader.download_from_url(&url)
+    /// Resolve the assets of a GE version release without downloading them.
+    ///
+    /// The returned `DownloadPlan` contains the file names, sizes and URLs of the assets that would be downloaded for
+    /// the `request`. No asset bytes are transferred by this method. To perform the download pass the plan to
+    /// `GeDownload::execute`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the errors of `fetch_release` and an error in the following situations:
+    /// * A release was found but that release has no assets or no archive asset
+    /// * The release has no checksum asset, the archive asset has no digest and `missing_checksum` of the `request` is
+    ///   `MissingChecksumPolicy::Fail`
+    fn resolve(&self, request: &DownloadRequest) -> Result<DownloadPlan, GithubError> {
+        resolve_release(self, request).map(|(_, plan)| plan)
     }
 
-    fn download_archive(
+    /// Download the assets of a `DownloadPlan`.
+    ///
+    /// Before any asset is downloaded, the release of the plan is fetched again to ensure that all assets of the plan
+    /// are still available under the same URL.
+    ///
+    /// The default implementation downloads the assets of the plan with `download_release_assets`, which fetches the
+    /// release once more.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the errors of `download_release_assets` and `GithubError::OutdatedDownloadPlan` if an asset
+    /// of the plan is no longer available.
+    fn execute(
         &self,
+        plan: &DownloadPlan,
         progress_wrapper: Box<dyn ReadProgressWrapper>,
-        asset: &GeAsset,
-    ) -> Result<DownloadedArchive, GithubError> {
-        let response = self.github_downloader.download_from_url(&asset.browser_download_url)?;
-
-        let tar_size: u64 = response.content_length().unwrap();
-        let mut compressed_archive: Vec<u8> = Vec::with_capacity(tar_size as usize);
-
-        let progress_wrapper = progress_wrapper.init(tar_size, asset);
-        progress_wrapper
-            .wrap(Box::new(response))
-            .read_to_end(&mut compressed_archive)
-            .unwrap();
-        progress_wrapper.finish(asset);
+    ) -> Result<DownloadedAssets, GithubError> {
+        let release = self.fetch_release(Some(plan.tag.clone()), plan.kind)?;
+        plan.validate(&release)?;
 
-        Ok(DownloadedArchive::new(compressed_archive, String::from(&asset.name)))
+        let download_checksum = plan.checksum.is_some() || plan.fallback_digest.is_some();
+        let request = DownloadRequest::new(Some(plan.tag.clone()), plan.kind, progress_wrapper, download_checksum)
+            .with_verify(plan.verify)
+            .with_max_bytes_per_sec(plan.max_bytes_per_sec);
+        self.download_release_assets(request)
     }
 
-    fn download_checksum(&self, asset: &GeAsset) -> Result<DownloadedChecksum, GithubError> {
-        let mut response = self.github_downloader.download_from_url(&asset.browser_download_url)?;
-
-        let file_size = response.content_length().unwrap();
-        let mut checksum_str = String::with_capacity(file_size as usize);
-        response.read_to_string(&mut checksum_str).unwrap();
+    /// Download the archive of a GE version release into `sink`.
+    ///
+    /// The archive is verified like with `download_release_assets`. The checksum is downloaded into memory if
+    /// `download_checksum` of the `request` is set.
+    ///
+    /// The default implementation downloads the assets into memory with `download_release_assets` and writes the
+    /// archive into the `sink` afterwards, so nothing is written if the verification fails. `GeDownloader` streams
+    /// the archive into the `sink` instead.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the errors of `download_release_assets` and `GithubError::WriteError` if the archive could
+    /// not be written into the `sink`.
+    fn download_archive_to(
+        &self,
+        request: DownloadRequest,
+        sink: &mut dyn Write,
+    ) -> Result<ArchiveMetadata, GithubError> {
+        let assets = self.download_release_assets(request)?;
+        let archive = assets.compressed_archive;
+        sink.write_all(&archive.compressed_content)
+            .map_err(|source| GithubError::WriteError {
+                file_name: archive.file_name.clone(),
+                source,
+            })?;
 
-        Ok(DownloadedChecksum::new(checksum_str, String::from(&asset.name)))
+        Ok(ArchiveMetadata {
+            tag: assets.tag,
+            file_name: archive.file_name,
+            size: archive.compressed_content.len() as u64,
+            content_type: None,
+            checksum: assets.checksum,
+            resumed_from: None,
+            verification: assets.verification,
+        })
     }
-}
 
-impl GeDownload for GeDownloader {
-    /// Get release information for a GitHub release.
+    /// Continue the download of the archive of a GE version release into the partial file at `path`.
     ///
-    /// This method fetches information from a GE version GitHub release via the GitHub API.
-    ///
-    /// If the `tag` is a `None` this method will fetch the latest release.
+    /// `resumed_from` of the returned `ArchiveMetadata` tells if and where the download was resumed. If the archive is
+    /// verified, the whole file is verified with the checksum of the release. A file that does not match the checksum
+    /// is removed.
     ///
-    /// The relevant APIs for this method are:
-    /// * <https://api.github.com/repos/GloriousEggroll/proton-ge-custom/releases/tags>
-    /// * <https://api.github.com/repos/GloriousEggroll/proton-ge-custom/releases/latest>
-    /// * <https://api.github.com/repos/GloriousEggroll/wine-ge-custom/releases/tags>
-    /// * <https://api.github.com/repos/GloriousEggroll/wine-ge-custom/tags>
+    /// The default implementation can not resume a download. It replaces the file at `path` with the whole archive
+    /// downloaded with `download_archive_to`, so `resumed_from` is always `None`. `GeDownloader` requests the rest of
+    /// the archive with an HTTP `Range` header instead.
     ///
     /// # Errors
     ///
-    /// This method returns an error in the following situations:
-    /// * The GitHub API returned no tags
-    /// * The GitHub API returned a not OK HTTP response
-    /// * A release was found but that release has no assets
-    /// * Reqwest could not fetch the resource from GitHub
-    /// * The API response could not be converted into a struct with serde
-    fn fetch_release(&self, tag: Option<String>, kind: TagKind) -> Result<GeRelease, GithubError> {
-        let tag = tag.as_ref();
-        let url = self.create_url(tag, &kind)?;
-        self.github_downloader.download_from_url(&url).and_then(|response| {
-            response
-                .json::<GeRelease>()
-                .map_err(|err| GithubError::ReqwestError { source: err })
-        })
+    /// This method returns the errors of `download_archive_to` and `GithubError::WriteError` if the file could not be
+    /// written.
+    fn resume_archive_download(&self, request: DownloadRequest, path: &Path) -> Result<ArchiveMetadata, GithubError> {
+        write_asset_file(path, true, |file| self.download_archive_to(request, file))
     }
 
-    /// Download the assets of a GE version release.
+    /// Download the assets of a GE version release into the directory `dir`.
+    ///
+    /// The assets are written into files named after the assets and are synced to disk before this method returns.
+    /// Existing files are only replaced if `overwrite` of the `request` is set. If a download fails, the files that
+    /// were created for the release are removed again. This includes an archive that does not match the checksum.
+    ///
+    /// The default implementation resolves the assets with `resolve` and writes the archive into its file with
+    /// `download_archive_to`. The checksum file is written once the archive was verified.
     ///
     /// # Errors
     ///
-    /// This method will return an error in the following situations:
-    /// * The GitHub API returned no tags
-    /// * The GitHub API returned a not OK HTTP response
-    /// * A release was found but that release has no assets
-    /// * Reqwest could not fetch the resource from GitHub
-    /// * The API response could not be converted into a struct with serde
-    fn download_release_assets(&self, request: DownloadRequest) -> Result<DownloadedAssets, GithubError> {
-        let DownloadRequest {
-            tag,
-            kind,
-            progress_wrapper,
-            download_checksum: skip_checksum,
-        } = request;
+    /// This method returns the errors of `resolve` and `download_archive_to`, `GithubError::FileExists` if a file
+    /// already exists and `overwrite` is not set and `GithubError::WriteError` if a file could not be written.
+    fn download_release_assets_to_dir(
+        &self,
+        request: DownloadRequest,
+        dir: &Path,
+    ) -> Result<DownloadedAssetPaths, GithubError> {
+        let plan = self.resolve(&request)?;
+        let archive_path = dir.join(&plan.archive.name);
+        let checksum_path = plan.checksum.as_ref().map(|checksum| dir.join(&checksum.name));
+        if !request.overwrite {
+            if let Some(path) = std::iter::once(&archive_path)
+                .chain(&checksum_path)
+                .find(|path| path.exists())
+            {
+                return Err(GithubError::FileExists { path: path.clone() });
+            }
+        }
 
-        let release = self.fetch_release(tag, kind)?;
-        if release.assets.is_empty() {
-            return Err(GithubError::ReleaseHasNoAssets {
-                tag: release.tag_name,
-                kind,
+        let overwrite = request.overwrite;
+        let request = DownloadRequest {
+            tag: Some(plan.tag),
+            ..request
+        };
+        let metadata = write_asset_file(&archive_path, overwrite, |file| self.download_archive_to(request, file))?;
+        if let (Some(checksum), Some(path)) = (&metadata.checksum, &checksum_path) {
+            let checksum_result = write_asset_file(path, overwrite, |file| {
+                file.write_all(checksum.checksum.as_bytes())
+                    .map_err(|source| GithubError::WriteError {
+                        file_name: checksum.file_name.clone(),
+                        source,
+                    })
             });
+            if let Err(err) = checksum_result {
+                let _ = fs::remove_file(&archive_path);
+                return Err(err);
+            }
         }
 
-        let downloaded_checksum = match skip_checksum {
-            false => Some(self.download_checksum(release.checksum_asset())?),
-            true => None,
+        Ok(DownloadedAssetPaths {
+            tag: metadata.tag,
+            archive: archive_path,
+            checksum: checksum_path,
+            verification: metadata.verification,
+        })
+    }
+
+    /// Download the archive of a GE version release and extract it into the directory `dest`.
+    ///
+    /// The archive is verified with the checksum of the release like with `download_archive_to`. If the download, the
+    /// extraction or the verification fails, the extracted files are removed again.
+    ///
+    /// The default implementation downloads the archive into memory with `download_archive_to` and extracts it
+    /// afterwards.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the errors of `download_archive_to` and `GithubError::ExtractionFailed` if the archive
+    /// could not be extracted.
+    fn download_and_extract(&self, request: DownloadRequest, dest: &Path) -> Result<ExtractedTool, GithubError> {
+        let mut content = Vec::new();
+        let metadata = self.download_archive_to(request, &mut content)?;
+        let archive = DownloadedArchive::new(content, metadata.file_name.clone());
+        let archive = match Compression::from_file_name(&metadata.file_name) {
+            Some(compression) => archive.with_compression(compression),
+            None => archive,
         };
+        archive
+            .extract_to(dest)
+            .map_err(|source| GithubError::ExtractionFailed {
+                file_name: metadata.file_name,
+                source,
+            })
+    }
 
-        let downloaded_archive = self.download_archive(progress_wrapper, release.tar_asset())?;
+    /// Get release information for a GitHub release if it changed since `etag` was returned.
+    ///
+    /// With an `etag` of a previous response the release is requested with an `If-None-Match` header. If the release
+    /// did not change, `ConditionalRelease::NotModified` is returned instead of the release. The `ETag` of the
+    /// returned `ConditionalRelease` is meant to be persisted and passed to the next call.
+    ///
+    /// The default implementation ignores the `etag` and always returns the release fetched with `fetch_release`
+    /// without an `ETag`.
+    fn fetch_release_if_modified(
+        &self,
+        tag: Option<String>,
+        kind: TagKind,
+        _etag: Option<&str>,
+    ) -> Result<ConditionalRelease, GithubError> {
+        let release = self.fetch_release(tag, kind)?;
+        Ok(ConditionalRelease::Modified { release, etag: None })
+    }
 
-        Ok(DownloadedAssets::new(
-            release.tag_name,
-            downloaded_archive,
-            downloaded_checksum,
-        ))
+    /// Resolve the archive and checksum assets of the release `tag` of `kind` without downloading them.
+    ///
+    /// Only the release information is fetched with `fetch_release`. The returned assets contain the download URLs,
+    /// file names and sizes, e.g. to download the assets with an external download manager.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the errors of `fetch_release` and `GithubError::MissingArchiveAsset` if the release has no
+    /// archive asset.
+    fn resolve_assets(&self, tag: &Tag, kind: TagKind) -> Result<ResolvedAssets, GithubError> {
+        let release = self.fetch_release(Some(String::from(tag.str())), kind)?;
+        ResolvedAssets::from_release(&release)
     }
-}
 
-impl Default for GeDownloader {
-    fn default() -> Self {
-        let github_downloader = Box::new(GithubDownloader::new());
-        GeDownloader::new(github_downloader)
+    /// Get release information like `fetch_release`, but without the release notes in `GeRelease::body`.
+    ///
+    /// GitHub always sends the release notes. They are dropped as soon as the release is parsed, so they are not kept
+    /// in memory by callers that do not display them.
+    fn fetch_release_without_notes(&self, tag: Option<String>, kind: TagKind) -> Result<GeRelease, GithubError> {
+        self.fetch_release(tag, kind).map(GeRelease::without_notes)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::download::mime::{APPLICATION_GZIP, APPLICATION_OCTET_STREAM};
-    use httpmock::Method::GET;
-    use httpmock::MockServer;
-    use mockall::mock;
-    use reqwest::blocking::Response;
+    /// Get a page of the release history like `fetch_releases`, but without the release notes in `GeRelease::body`.
+    fn fetch_releases_without_notes(&self, kind: TagKind, page: u8) -> Result<Vec<GeRelease>, GithubError> {
+        let releases = self.fetch_releases(kind, page)?;
+        Ok(releases.into_iter().map(GeRelease::without_notes).collect())
+    }
 
-    use super::*;
+    /// Get release information for the release of `kind` with the version `semver`.
+    ///
+    /// The tags of `kind` are fetched with `fetch_tags` and the tag with the same major, minor and patch version is
+    /// looked up, so `7.8.0` finds `GE-Proton7-8` and `6.20.1` finds `6.20-GE-1`. A tag with the same identifier as
+    /// `semver` is preferred over tags with a different identifier.
+    ///
+    /// # Errors
+    ///
+    /// This method returns `GithubError::VersionNotFound` if no tag of `kind` has the version `semver`, and the errors
+    /// of `fetch_tags` and `fetch_release`.
+    fn fetch_release_by_semver(&self, semver: &SemVer, kind: TagKind) -> Result<GeRelease, GithubError> {
+        let tags = self.fetch_tags(kind, None)?;
+        let tag = find_tag_by_semver(tags, semver).ok_or_else(|| GithubError::VersionNotFound {
+            semver: semver.clone(),
+            kind,
+        })?;
+        self.fetch_release(Some(tag.into()), kind)
+    }
 
-    lazy_static! {
-        static ref RELEASES: &'static str = "test_resources/responses/releases";
-        static ref ASSETS: &'static str = "test_resources/assets";
-        static ref TAGS: &'static str = "test_resources/responses/tags";
-        pub static ref PROTON_GE: String = format!("{}/proton-ge-release.json", *RELEASES);
-        pub static ref WINE_GE: String = format!("{}/wine-ge-release.json", *RELEASES);
-        pub static ref WINE_GE_LOL: String = format!("{}/wine-ge-lol-release.json", *RELEASES);
-        pub static ref NO_TAGS: String = format!("{}/empty.json", *TAGS);
-        pub static ref WINE_GE_TAGS: String = format!("{}/wine_ge.json", *TAGS);
-        pub static ref WINE_GE_LOL_TAGS: String = format!("{}/wine_ge_lol.json", *TAGS);
-        pub static ref TEST_TAR_GZ: String = format!("{}/{}", *ASSETS, "test.tar.gz");
-        pub static ref TEST_SHA512SUM: String = format!("{}/{}", *ASSETS, "test-gz.sha512sum");
+    /// Get the `count` most recent releases of `kind`, sorted from newest to oldest by their `Tag`.
+    ///
+    /// The pages of the release history are fetched with `fetch_releases` until `count` releases are found. Drafts
+    /// and releases of the other Wine kind are skipped. Releases whose assets are not uploaded yet are included with an
+    /// empty or incomplete asset list, use `GeRelease::find_tar_asset` to check for an archive.
+    ///
+    /// Prereleases are included, use `list_latest_releases_filtered` to skip them.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the errors of `fetch_releases`.
+    fn list_latest_releases(&self, kind: TagKind, count: usize) -> Result<Vec<GeRelease>, GithubError> {
+        self.list_latest_releases_filtered(kind, count, true)
     }
 
-    mock! {
-        ProgressWrapper {}
-        impl ReadProgressWrapper for ProgressWrapper {
-            fn init(self: Box<Self>, len: u64, asset: &GeAsset) -> Box<dyn ReadProgressWrapper>;
-            fn wrap(&self, reader: Box<dyn Read>) -> Box<dyn Read>;
-            fn finish(&self, release: &GeAsset);
+    /// Get the `count` most recent releases of `kind` like `list_latest_releases`, but only include prereleases if
+    /// `include_prereleases` is true. Drafts are always skipped.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the errors of `fetch_releases`.
+    fn list_latest_releases_filtered(
+        &self,
+        kind: TagKind,
+        count: usize,
+        include_prereleases: bool,
+    ) -> Result<Vec<GeRelease>, GithubError> {
+        let mut releases = Vec::new();
+        for page in 1..=u8::MAX {
+            if releases.len() >= count {
+                break;
+            }
+
+            let page_releases = self.fetch_releases(kind, page)?;
+            if page_releases.is_empty() {
+                break;
+            }
+            releases.extend(
+                page_releases
+                    .into_iter()
+                    .filter(|release| !release.draft && release.is_kind(&kind))
+                    .filter(|release| include_prereleases || !release.prerelease),
+            );
         }
+
+        releases.sort_by_cached_key(|release| std::cmp::Reverse(Tag::new(release.tag_name.as_str())));
+        releases.truncate(count);
+        Ok(releases)
     }
 
-    pub fn download_url(server: Option<&str>, tag: &str, kind: &TagKind, file_name: &str) -> String {
-        let url = download_url_without_server(tag, kind, file_name);
+    /// Get the newest release of `kind` by its `Tag`, skipping drafts and, unless `include_prereleases` is true,
+    /// prereleases.
+    ///
+    /// Unlike `fetch_release` without a tag, the release history is searched with `list_latest_releases_filtered`, so
+    /// a release candidate that GitHub lists as the newest release is not returned to stable-only callers.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the errors of `fetch_releases` and `GithubError::NoTags` if `kind` has no matching release.
+    fn fetch_latest_release(&self, kind: TagKind, include_prereleases: bool) -> Result<GeRelease, GithubError> {
+        self.list_latest_releases_filtered(kind, 1, include_prereleases)?
+            .pop()
+            .ok_or(GithubError::NoTags)
+    }
 
-        if let Some(host) = server {
-            format!("{}{}", host, url)
-        } else {
-            format!("SERVER{}", url)
+    /// Get the tags of a GE version kind from newest to oldest.
+    ///
+    /// The pages are fetched until `limit` tags are collected or the last page is reached. A `limit` of `None`
+    /// fetches all tags. Only the tags of the requested kind count towards the `limit`.
+    ///
+    /// The default implementation takes the tag names of the releases returned by `fetch_releases`, so tags without a
+    /// release are not included. `GeDownloader` fetches the tag listing of the repository instead.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the errors of `fetch_releases`. The tags of the previous pages are discarded.
+    fn fetch_tags(&self, kind: TagKind, limit: Option<usize>) -> Result<Vec<Tag>, GithubError> {
+        let limit = limit.unwrap_or(usize::MAX);
+        let mut tags = Vec::new();
+        for page in 1..=u8::MAX {
+            if tags.len() >= limit {
+                break;
+            }
+
+            let releases = self.fetch_releases(kind, page)?;
+            if releases.is_empty() {
+                break;
+            }
+            tags.extend(
+                releases
+                    .iter()
+                    .filter(|release| release.is_kind(&kind))
+                    .map(|release| Tag::from(release.tag_name.as_str())),
+            );
         }
+
+        tags.truncate(limit);
+        Ok(tags)
     }
 
-    pub fn download_url_without_server(tag: &str, kind: &TagKind, file_name: &str) -> String {
-        let repo = match kind {
-            TagKind::Proton => "proton",
-            TagKind::Wine { .. } => "wine",
+    /// Get all tags of `kind`, parsed with `Tag::try_new` and sorted from newest to oldest.
+    ///
+    /// The tags are fetched with `fetch_tags` without a limit. Tag names that can not be parsed, like the stray
+    /// upstream tag `lol`, do not fail the whole listing. They are skipped and reported in `FetchedTags::skipped`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the errors of `fetch_tags`.
+    fn fetch_all_tags(&self, kind: TagKind) -> Result<FetchedTags, GithubError> {
+        let mut fetched = FetchedTags::default();
+        for tag in self.fetch_tags(kind, None)? {
+            match Tag::try_new(String::from(tag)) {
+                Ok(tag) => fetched.tags.push(tag),
+                Err(err) => fetched.skipped.push(err),
+            }
+        }
+        fetched.tags.sort_by(|a, b| b.cmp(a));
+        Ok(fetched)
+    }
+
+    /// Get a single page of the release history for `kind` as `TaggedRelease`s.
+    ///
+    /// Unlike `fetch_releases` only the releases of `kind` are returned, so a page for Wine GE contains no Wine GE
+    /// (LoL) releases.
+    fn fetch_tagged_releases(&self, kind: TagKind, page: u8) -> Result<Vec<TaggedRelease>, GithubError> {
+        let releases = self.fetch_releases(kind, page)?;
+        Ok(releases
+            .iter()
+            .filter(|release| release.is_kind(&kind))
+            .map(|release| release.tagged(kind))
+            .collect())
+    }
+
+    /// Get the latest release of every `TagKind`.
+    ///
+    /// The result contains one entry per kind in the order of `TagKind::values()`. Every kind has its own `Result`,
+    /// so a kind whose release could not be fetched does not hide the releases of the other kinds.
+    ///
+    /// The default implementation fetches the releases one after another with `fetch_release`.
+    fn fetch_latest_for_all_kinds(&self) -> Vec<(TagKind, Result<GeRelease, GithubError>)> {
+        TagKind::values()
+            .into_iter()
+            .map(|kind| (kind, self.fetch_release(None, kind)))
+            .collect()
+    }
+}
+
+/// Default implementation for the `GeDownload` trait.
+pub struct GeDownloader {
+    github_downloader: Box<dyn GithubDownload>,
+    retry_policy: RetryPolicy,
+    page_size: u8,
+    sources: RepositorySources,
+    api_url: String,
+}
+
+impl GeDownloader {
+    pub fn new(github_downloader: Box<dyn GithubDownload>) -> Self {
+        GeDownloader {
+            github_downloader,
+            retry_policy: RetryPolicy::default(),
+            page_size: 30,
+            sources: RepositorySources::default(),
+            api_url: String::from(GITHUB_API_URL),
+        }
+    }
+
+    /// Create a `GeDownloaderBuilder` to configure the HTTP client of a downloader, e.g. its timeouts.
+    pub fn builder() -> GeDownloaderBuilder {
+        GeDownloaderBuilder::new()
+    }
+
+    /// Set the number of items that are requested per page when `GeDownloader::fetch_tags` walks through the pages
+    /// of a listing. The size is clamped to the range `1..=100` supported by GitHub. Defaults to `30`.
+    pub fn with_page_size(mut self, page_size: u8) -> Self {
+        self.page_size = page_size.clamp(1, 100);
+        self
+    }
+
+    /// Create a downloader that authenticates its requests to the GitHub API with a personal access `token`.
+    ///
+    /// Authenticated requests have a higher rate limit than anonymous requests. The token is sent as a bearer token to
+    /// `https://api.github.com` only. Release assets are downloaded without it.
+    pub fn with_token<S: Into<String>>(token: S) -> Self {
+        GeDownloader::new(Box::new(GithubDownloader::with_token(token.into())))
+    }
+
+    /// Set the `RetryPolicy` for failed requests. By default `RetryPolicy::default()` is used.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Download the releases of the given `TagKind`s from other repositories, e.g. forks or mirrors.
+    ///
+    /// Kinds without a `RepositorySource` are downloaded from their official repository. Wine GE and Wine GE (LoL)
+    /// share a repository by default, so a fork of `wine-ge-custom` usually has to be set for both kinds.
+    pub fn with_sources<I: IntoIterator<Item = (TagKind, RepositorySource)>>(mut self, sources: I) -> Self {
+        self.sources.extend(sources);
+        self
+    }
+
+    /// Send the requests to the GitHub API to `api_url` instead of `https://api.github.com`, e.g. to a GitHub
+    /// Enterprise server at `https://github.example.com/api/v3`.
+    ///
+    /// All API URLs are built relative to `api_url`. Release assets are downloaded from the `browser_download_url` of
+    /// the API response as is. This method only changes the URLs. To authenticate requests to another API, use
+    /// `GeDownloaderBuilder::with_api_url`, which also sends the token to `api_url`.
+    pub fn with_api_url<S: Into<String>>(mut self, api_url: S) -> Self {
+        self.api_url = normalize_api_url(&api_url.into());
+        self
+    }
+
+    /// Check if a release with the given `tag` exists for `kind`, without downloading its assets.
+    ///
+    /// The release is requested from the GitHub API, but the response is not deserialized. A release that does not
+    /// exist, e.g. because the tag is misspelled, returns `Ok(false)`. Use `GeDownloader::asset_available` to check
+    /// if the assets of an existing release are uploaded.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error in the following situations:
+    /// * The GitHub API returned a not OK HTTP response other than `404`
+    /// * Reqwest could not fetch the resource from GitHub
+    pub fn release_exists(&self, tag: &Tag, kind: TagKind) -> Result<bool, GithubError> {
+        let url = self.create_url(Some(tag.str()), &kind)?;
+        is_found(self.download_from_url(&url))
+    }
+
+    /// Check if the archive of `release` can be downloaded, without downloading any of its content.
+    ///
+    /// The archive is requested with a `HEAD` request. A release whose archive is not uploaded yet, either because the
+    /// release has no archive asset or because the download URL returns `404`, returns `Ok(false)`.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error in the following situations:
+    /// * The download URL returned a not OK HTTP response other than `404`
+    /// * Reqwest could not send the request
+    pub fn asset_available(&self, release: &GeRelease) -> Result<bool, GithubError> {
+        let Some(asset) = release.find_tar_asset() else {
+            return Ok(false);
         };
-        format!(
-            "/GloriousEggroll/{}-ge-custom/releases/download/{}/{}",
-            repo, tag, file_name
+        is_found(
+            self.retry_policy
+                .retry(|| self.github_downloader.head(&asset.browser_download_url)),
         )
     }
 
-    pub fn mock_url(kind: &TagKind, server: &str) -> String {
-        let file_path = match kind {
-            TagKind::Proton => &*PROTON_GE,
-            TagKind::Wine { kind: wine_kind } => match wine_kind {
-                WineTagKind::WineGe => &*WINE_GE,
-                WineTagKind::LolWineGe => &*WINE_GE_LOL,
-            },
-        };
-        std::fs::read_to_string(file_path).unwrap().replace("SERVER", server)
+    fn download_from_url(&self, url: &str) -> Result<Response, GithubError> {
+        self.retry_policy
+            .retry(|| self.github_downloader.download_from_url(url))
     }
 
-    struct FetchSpecifiedReleaseTestData {
-        given_tag: Option<String>,
-        expected_tag: String,
-        kind: TagKind,
-        github_resource: String,
-        body_content_file: String,
-        gzip_name: String,
-        gzip_download_url: String,
-        checksum_name: String,
-        checksum_download_url: String,
+    /// Fetch `url` and deserialize the JSON response. Failures while receiving the response body are retried as well.
+    fn fetch_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, GithubError> {
+        self.retry_policy.retry(|| {
+            let value = self.github_downloader.download_from_url(url)?.json::<T>()?;
+            Ok(value)
+        })
     }
 
-    impl FetchSpecifiedReleaseTestData {
-        pub fn new(
-            tag: Option<&str>,
-            expected_tag: &str,
-            kind: TagKind,
-            gzip_name: &str,
-            checksum_name: &str,
-            github_resource: &str,
-            body_content_file: &str,
-        ) -> Self {
-            let github_resource = if tag.is_some() {
-                format!("{}/{}", github_resource, tag.unwrap())
-            } else {
-                String::from(github_resource)
-            };
-            let tag = tag.map(String::from);
+    fn create_url<S>(&self, tag: Option<S>, kind: &TagKind) -> Result<String, GithubError>
+    where
+        S: AsRef<str> + Display,
+    {
+        let tag = tag.as_ref();
+        let repository_url = self.repository_url(kind);
+        let release_tags_url = format!("{}/releases/tags", repository_url);
+        match kind {
+            TagKind::Proton => {
+                if let Some(t) = tag {
+                    Ok(release_tag_url(&release_tags_url, t.as_ref()))
+                } else {
+                    Ok(format!("{}/releases/latest", repository_url))
+                }
+            }
+            TagKind::Wine { kind: wine_kind } => {
+                if let Some(t) = tag {
+                    Ok(release_tag_url(&release_tags_url, t.as_ref()))
+                } else {
+                    self.find_latest_wine_ge_release_tag(&repository_url, wine_kind)
+                        .map(|t| release_tag_url(&release_tags_url, t.str()))
+                }
+            }
+        }
+    }
 
-            let gzip_download_url = download_url(None, expected_tag, &kind, gzip_name);
-            let checksum_download_url = download_url(None, expected_tag, &kind, checksum_name);
+    fn repository_url(&self, kind: &TagKind) -> String {
+        self.sources.api_url(kind, &self.api_url)
+    }
 
-            let expected_tag = String::from(expected_tag);
-            let body_content_file = String::from(body_content_file);
-            let gzip_name = String::from(gzip_name);
-            let checksum_name = String::from(checksum_name);
-            FetchSpecifiedReleaseTestData {
-                given_tag: tag,
-                expected_tag,
-                kind,
-                github_resource,
-                body_content_file,
-                gzip_name,
-                gzip_download_url,
-                checksum_name,
-                checksum_download_url,
+    fn create_releases_url(&self, kind: &TagKind, page: u8) -> String {
+        format!("{}/releases?page={}", self.repository_url(kind), page)
+    }
+
+    fn find_latest_wine_ge_release_tag(&self, repository_url: &str, kind: &WineTagKind) -> Result<Tag, GithubError> {
+        let mut page = 1;
+        loop {
+            let tags_url = format!("{}/tags?page={}", repository_url, page);
+            let tag_names: Vec<String> = self
+                .fetch_json::<Vec<CompatibilityToolTag>>(&tags_url)?
+                .into_iter()
+                .map(Into::into)
+                .collect();
+
+            if tag_names.is_empty() {
+                return Err(GithubError::NoTags);
+            }
+
+            if let Some(t) = latest_wine_ge_tag(tag_names, kind) {
+                return Ok(t);
             }
+            page += 1
         }
     }
 
-    struct FetchLatestReleaseTestData {
-        tags_body_from_file: String,
-        github_tags_resource: String,
-        expected_specific_release: FetchSpecifiedReleaseTestData,
+    fn download_archive(
+        &self,
+        progress_wrapper: Box<dyn ReadProgressWrapper>,
+        asset: &GeAsset,
+        hash: Option<Algorithm>,
+        max_bytes_per_sec: Option<u64>,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<(DownloadedArchive, Option<Digest>), GithubError> {
+        let response = self.download_from_url(&asset.browser_download_url)?;
+        let content_length = response.content_length();
+        let response = CancellableReader::new(ThrottledReader::new(response, max_bytes_per_sec), cancelled);
+
+        let tar_size = content_length.or(asset.size).unwrap_or_default();
+        let mut compressed_archive = HashingWriter::new(Vec::with_capacity(tar_size as usize), hash);
+
+        let file_name = asset.file_name();
+        let mut progress_wrapper = progress_wrapper.init(tar_size, asset);
+        let reader = progress_wrapper.wrap(Box::new(response));
+        let mut reader = ProgressReader::new(reader, content_length, progress_wrapper.as_mut());
+        let received = copy_asset(&mut reader, &mut compressed_archive, &file_name)?;
+        check_size(&file_name, asset.size, received)?;
+        progress_wrapper.finish(asset);
+
+        let (compressed_archive, digest) = compressed_archive.finish();
+        let archive = DownloadedArchive::new(compressed_archive, file_name);
+        let archive = match asset.compression() {
+            Some(compression) => archive.with_compression(compression),
+            None => archive,
+        };
+        Ok((archive, digest))
     }
 
-    impl FetchLatestReleaseTestData {
-        pub fn new<S: Into<String>>(
-            tags_body_from_file: S,
-            github_tags_resource: S,
-            mut expected_specific_release: FetchSpecifiedReleaseTestData,
-        ) -> Self {
-            let tags_body_from_file = tags_body_from_file.into();
-            let github_tags_resource = github_tags_resource.into();
-            expected_specific_release.github_resource = format!(
-                "{}/{}",
-                expected_specific_release.github_resource, expected_specific_release.expected_tag
-            );
+    /// Stream the archive of `asset` into `sink`. Returns the number of written bytes and the content type.
+    fn stream_archive(
+        &self,
+        progress_wrapper: Box<dyn ReadProgressWrapper>,
+        asset: &GeAsset,
+        sink: &mut dyn Write,
+        max_bytes_per_sec: Option<u64>,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<(u64, Option<String>), GithubError> {
+        let response = self.download_from_url(&asset.browser_download_url)?;
+        stream_response(
+            response,
+            progress_wrapper,
+            asset,
+            asset.size,
+            sink,
+            max_bytes_per_sec,
+            cancelled,
+        )
+    }
 
-            FetchLatestReleaseTestData {
-                tags_body_from_file,
-                github_tags_resource,
-                expected_specific_release,
+    /// Download the rest of the archive `asset` into the partial file at `path`.
+    ///
+    /// If the file exists, only the bytes after its end are requested. The whole archive is downloaded into the file
+    /// if the file does not exist, the server ignores the range or answers with an unexpected range. Returns the size
+    /// of the file, the content type of the response and the size of the partial file if the download was resumed.
+    fn resume_archive(
+        &self,
+        progress_wrapper: Box<dyn ReadProgressWrapper>,
+        asset: &GeAsset,
+        path: &Path,
+        max_bytes_per_sec: Option<u64>,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<(u64, Option<String>, Option<u64>), GithubError> {
+        let file_name = asset.file_name();
+        let write_error = |source| GithubError::WriteError {
+            file_name: file_name.clone(),
+            source,
+        };
+        let offset = match fs::metadata(path) {
+            Ok(metadata) => metadata.len(),
+            Err(err) if err.kind() == ErrorKind::NotFound => 0,
+            Err(err) => return Err(write_error(err)),
+        };
+
+        let url = &asset.browser_download_url;
+        let response = if offset > 0 {
+            let response = self
+                .retry_policy
+                .retry(|| self.github_downloader.download_range(url, offset))?;
+            let content_range = response
+                .headers()
+                .get(reqwest::header::CONTENT_RANGE)
+                .and_then(|value| value.to_str().ok());
+
+            if response.status() == StatusCode::PARTIAL_CONTENT && is_remainder(content_range, offset, asset.size) {
+                let mut file = OpenOptions::new().append(true).open(path).map_err(write_error)?;
+                let remaining = asset.size.map(|size| size - offset);
+                let (written, content_type) = stream_response(
+                    response,
+                    progress_wrapper,
+                    asset,
+                    remaining,
+                    &mut file,
+                    max_bytes_per_sec,
+                    cancelled,
+                )?;
+                file.sync_all().map_err(write_error)?;
+                return Ok((offset + written, content_type, Some(offset)));
             }
-        }
+            (response.status() == StatusCode::OK).then_some(response)
+        } else {
+            None
+        };
+
+        let response = match response {
+            Some(response) => response,
+            None => self.download_from_url(url)?,
+        };
+        let mut file = File::create(path).map_err(write_error)?;
+        let (size, content_type) = stream_response(
+            response,
+            progress_wrapper,
+            asset,
+            asset.size,
+            &mut file,
+            max_bytes_per_sec,
+            cancelled,
+        )?;
+        file.sync_all().map_err(write_error)?;
+        Ok((size, content_type, None))
+    }
+
+    /// Extract the archive `asset` into `dest` while it is downloaded.
+    ///
+    /// The compressed bytes are hashed for the verification of the `plan` while they are decompressed. The compression
+    /// is detected from the asset or, if the asset does not tell, from the first bytes of the archive. Every
+    /// top-level path created by the extraction is added to `created`, so the caller can remove it if a later step
+    /// fails. Returns the extracted tool and the digest of the archive.
+    fn extract_archive(
+        &self,
+        progress_wrapper: Box<dyn ReadProgressWrapper>,
+        asset: &GeAsset,
+        plan: &DownloadPlan,
+        dest: &Path,
+        cancelled: Arc<AtomicBool>,
+        created: &mut Vec<PathBuf>,
+    ) -> Result<(ExtractedTool, Option<Digest>), GithubError> {
+        let response = self.download_from_url(&asset.browser_download_url)?;
+        let content_length = response.content_length();
+        let tar_size = content_length.or(asset.size).unwrap_or_default();
+        let response = CancellableReader::new(ThrottledReader::new(response, plan.max_bytes_per_sec), cancelled);
+
+        let file_name = asset.file_name();
+        let mut progress_wrapper = progress_wrapper.init(tar_size, asset);
+        let reader = progress_wrapper.wrap(Box::new(response));
+        let reader = ProgressReader::new(reader, content_length, progress_wrapper.as_mut());
+        let mut reader = BufReader::new(HashingReader::new(reader, plan.hash_algorithm()));
+
+        let compression = match asset.compression() {
+            Some(compression) => compression,
+            None => {
+                let start = reader
+                    .fill_buf()
+                    .map_err(|source| GithubError::transfer(&file_name, source))?;
+                Compression::from_magic_bytes(start).ok_or_else(|| GithubError::ExtractionFailed {
+                    file_name: file_name.clone(),
+                    source: ExtractError::UnknownCompression {
+                        file_name: file_name.clone(),
+                    },
+                })?
+            }
+        };
+
+        let extracted = archive::extract_tracked(compression, &mut reader, dest, &ExtractOptions::default(), created);
+        if let Some(source) = reader.get_mut().error.take() {
+            return Err(GithubError::transfer(&file_name, source));
+        }
+        let path = extracted.map_err(|source| GithubError::ExtractionFailed {
+            file_name: file_name.clone(),
+            source,
+        })?;
+
+        // The end of the tar archive is reached before the end of the compressed stream, but the digest and the size
+        // cover all bytes of the asset.
+        io::copy(&mut reader, &mut io::sink()).map_err(|source| GithubError::transfer(&file_name, source))?;
+        let (received, digest) = reader.into_inner().finish();
+        check_size(&file_name, asset.size, received)?;
+        progress_wrapper.finish(asset);
+
+        Ok((ExtractedTool { path, compression }, digest))
+    }
+
+    /// Download the checksum `asset` on another thread while `download_archive` downloads the archive on the current
+    /// thread.
+    ///
+    /// If the checksum can not be downloaded, the archive download is cancelled with its next read and the error of
+    /// the checksum download is returned.
+    fn download_with_checksum<T, F>(
+        &self,
+        checksum_asset: Option<&GeAsset>,
+        download_archive: F,
+    ) -> Result<(T, Option<DownloadedChecksum>), GithubError>
+    where
+        F: FnOnce(Arc<AtomicBool>) -> Result<T, GithubError>,
+    {
+        let checksum_failed = Arc::new(AtomicBool::new(false));
+        let (archive, checksum) = thread::scope(|scope| {
+            let checksum = checksum_asset.map(|asset| {
+                let checksum_failed = Arc::clone(&checksum_failed);
+                scope.spawn(move || {
+                    let checksum = self.download_checksum(asset);
+                    checksum_failed.store(checksum.is_err(), Ordering::Relaxed);
+                    checksum
+                })
+            });
+            let archive = download_archive(Arc::clone(&checksum_failed));
+            let checksum = checksum.map(|handle| handle.join().unwrap_or_else(|panic| panic::resume_unwind(panic)));
+            (archive, checksum)
+        });
+
+        let checksum = checksum.transpose()?;
+        Ok((archive?, checksum))
+    }
+
+    fn download_checksum(&self, asset: &GeAsset) -> Result<DownloadedChecksum, GithubError> {
+        let file_name = asset.file_name();
+        let checksum_str = self.retry_policy.retry(|| {
+            let mut response = self.github_downloader.download_from_url(&asset.browser_download_url)?;
+
+            let file_size = response.content_length().unwrap_or_default();
+            let mut checksum_str = String::with_capacity(file_size as usize);
+            response
+                .read_to_string(&mut checksum_str)
+                .map_err(|source| GithubError::transfer(&file_name, source))?;
+            Ok(checksum_str)
+        })?;
+
+        Ok(DownloadedChecksum::new(checksum_str, file_name))
+    }
+
+    fn execute_for_release(
+        &self,
+        plan: &DownloadPlan,
+        release: &GeRelease,
+        progress_wrapper: Box<dyn ReadProgressWrapper>,
+    ) -> Result<DownloadedAssets, GithubError> {
+        let (archive_asset, checksum_asset) = plan.validate(release)?;
+
+        let ((downloaded_archive, digest), downloaded_checksum) =
+            self.download_with_checksum(checksum_asset, |cancelled| {
+                self.download_archive(
+                    progress_wrapper,
+                    archive_asset,
+                    plan.hash_algorithm(),
+                    plan.max_bytes_per_sec,
+                    cancelled,
+                )
+            })?;
+        let verification = verify_download(
+            &downloaded_archive.file_name,
+            digest,
+            downloaded_checksum.as_ref(),
+            plan,
+        )?;
+
+        Ok(
+            DownloadedAssets::new(plan.tag.clone(), downloaded_archive, downloaded_checksum)
+                .with_verification(verification),
+        )
+    }
+}
+
+impl GeDownload for GeDownloader {
+    /// Get release information for a GitHub release.
+    ///
+    /// This method fetches information from a GE version GitHub release via the GitHub API.
+    ///
+    /// If the `tag` is a `None` this method will fetch the latest release.
+    ///
+    /// The relevant APIs for this method are:
+    /// * <https://api.github.com/repos/GloriousEggroll/proton-ge-custom/releases/tags>
+    /// * <https://api.github.com/repos/GloriousEggroll/proton-ge-custom/releases/latest>
+    /// * <https://api.github.com/repos/GloriousEggroll/wine-ge-custom/releases/tags>
+    /// * <https://api.github.com/repos/GloriousEggroll/wine-ge-custom/tags>
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error in the following situations:
+    /// * The GitHub API returned no tags
+    /// * The GitHub API returned a not OK HTTP response
+    /// * A release was found but that release has no assets
+    /// * Reqwest could not fetch the resource from GitHub
+    /// * The API response could not be converted into a struct with serde
+    fn fetch_release(&self, tag: Option<String>, kind: TagKind) -> Result<GeRelease, GithubError> {
+        let tag = tag.as_ref();
+        let url = self.create_url(tag, &kind)?;
+        self.fetch_json::<GeRelease>(&url)
+    }
+
+    /// Get a single page of the release history for a GE version kind.
+    ///
+    /// Pages start at `1` and are ordered from newest to oldest release. An empty `Vec` is returned once `page` is
+    /// past the last page of the release history. Since Wine GE and Wine GE (LoL) share a repository, a page for
+    /// either Wine kind contains the releases of both kinds. Use `GeRelease::is_kind` to filter them.
+    ///
+    /// The relevant APIs for this method are:
+    /// * <https://api.github.com/repos/GloriousEggroll/proton-ge-custom/releases>
+    /// * <https://api.github.com/repos/GloriousEggroll/wine-ge-custom/releases>
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error in the following situations:
+    /// * The GitHub API returned a not OK HTTP response
+    /// * Reqwest could not fetch the resource from GitHub
+    fn fetch_releases(&self, kind: TagKind, page: u8) -> Result<Vec<GeRelease>, GithubError> {
+        let url = self.create_releases_url(&kind, page);
+        self.fetch_json::<Vec<GeRelease>>(&url)
+    }
+
+    /// Get the latest release of every `TagKind`.
+    ///
+    /// The releases are fetched concurrently, each kind on its own thread. The result contains one entry per kind in
+    /// the order of `TagKind::values()`, see `GeDownload::fetch_latest_for_all_kinds`.
+    fn fetch_latest_for_all_kinds(&self) -> Vec<(TagKind, Result<GeRelease, GithubError>)> {
+        thread::scope(|scope| {
+            let handles: Vec<_> = TagKind::values()
+                .into_iter()
+                .map(|kind| (kind, scope.spawn(move || self.fetch_release(None, kind))))
+                .collect();
+            handles
+                .into_iter()
+                .map(|(kind, handle)| (kind, handle.join().unwrap_or_else(|panic| panic::resume_unwind(panic))))
+                .collect()
+        })
+    }
+
+    /// Download the assets of a `DownloadPlan`.
+    ///
+    /// Before any asset is downloaded, the release of the plan is fetched again to ensure that all assets of the plan
+    /// are still available under the same URL. The archive and the checksum are downloaded concurrently. If the
+    /// checksum can not be downloaded, the archive download is cancelled and the error of the checksum is returned.
+    ///
+    /// If the plan contains a checksum and `DownloadPlan::verify` is set, the digest of the archive is computed with
+    /// the algorithm of the checksum file while it is downloaded and compared with the checksum.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error in the following situations:
+    /// * An asset of the plan is no longer available
+    /// * The GitHub API returned a not OK HTTP response
+    /// * Reqwest could not fetch the resource from GitHub
+    /// * The API response could not be converted into a struct with serde
+    /// * The archive does not match the checksum (`GithubError::VerificationFailed`)
+    fn execute(
+        &self,
+        plan: &DownloadPlan,
+        progress_wrapper: Box<dyn ReadProgressWrapper>,
+    ) -> Result<DownloadedAssets, GithubError> {
+        let release = self.fetch_release(Some(plan.tag.clone()), plan.kind)?;
+        self.execute_for_release(plan, &release, progress_wrapper)
+    }
+
+    /// Download the assets of a GE version release.
+    ///
+    /// This is the same as calling `GeDownloader::resolve` followed by `GeDownloader::execute`, with the difference
+    /// that the release is only fetched once. The archive is verified with the checksum unless `download_checksum` or
+    /// `verify` of the `request` is not set.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error in the following situations:
+    /// * The GitHub API returned no tags
+    /// * The GitHub API returned a not OK HTTP response
+    /// * A release was found but that release has no assets or no archive asset
+    /// * The release has no checksum asset, the archive asset has no digest and `missing_checksum` of the `request` is
+    ///   `MissingChecksumPolicy::Fail`
+    /// * Reqwest could not fetch the resource from GitHub
+    /// * The API response could not be converted into a struct with serde
+    /// * The archive does not match the checksum (`GithubError::VerificationFailed`)
+    fn download_release_assets(&self, request: DownloadRequest) -> Result<DownloadedAssets, GithubError> {
+        let (release, plan) = resolve_release(self, &request)?;
+        self.execute_for_release(&plan, &release, request.progress_wrapper)
+    }
+
+    /// Get the tags of a GE version kind from newest to oldest.
+    ///
+    /// The pages of the tag listing are followed through the `Link` header of the responses until `limit` tags are
+    /// collected or the last page is reached. A `limit` of `None` fetches all tags. Since Wine GE and Wine GE (LoL)
+    /// share a repository, only the tags of the requested Wine kind are returned and count towards the `limit`. The
+    /// number of tags per request is set with `GeDownloader::with_page_size`.
+    ///
+    /// The relevant APIs for this method are:
+    /// * <https://api.github.com/repos/GloriousEggroll/proton-ge-custom/tags>
+    /// * <https://api.github.com/repos/GloriousEggroll/wine-ge-custom/tags>
+    ///
+    /// # Errors
+    ///
+    /// The tags are fetched atomically: if a page can not be fetched, the error is returned and the tags of the
+    /// previous pages are discarded. This method will return an error in the following situations:
+    /// * The GitHub API returned a not OK HTTP response
+    /// * Reqwest could not fetch the resource from GitHub
+    /// * The API response could not be converted into a struct with serde
+    fn fetch_tags(&self, kind: TagKind, limit: Option<usize>) -> Result<Vec<Tag>, GithubError> {
+        let mut next_url = Some(format!(
+            "{}/tags?per_page={}",
+            self.repository_url(&kind),
+            self.page_size
+        ));
+        let mut tags = Vec::new();
+
+        while let Some(url) = next_url {
+            if limit.is_some_and(|limit| tags.len() >= limit) {
+                break;
+            }
+
+            let (tag_names, next) = self.retry_policy.retry(|| {
+                let response = self.github_downloader.download_from_url(&url)?;
+                let next = response
+                    .headers()
+                    .get(LINK)
+                    .and_then(|link| link.to_str().ok())
+                    .and_then(next_page_url);
+                let tag_names = response.json::<Vec<CompatibilityToolTag>>()?;
+                Ok((tag_names, next))
+            })?;
+
+            tags.extend(
+                tag_names
+                    .into_iter()
+                    .map(String::from)
+                    .filter(|name| match &kind {
+                        TagKind::Proton => true,
+                        TagKind::Wine { kind } => is_wine_tag_of_kind(name, kind),
+                    })
+                    .map(Tag::from),
+            );
+            next_url = next;
+        }
+
+        if let Some(limit) = limit {
+            tags.truncate(limit);
+        }
+        Ok(tags)
+    }
+
+    /// Get release information for a GitHub release if it changed since `etag` was returned.
+    ///
+    /// See `GeDownload::fetch_release_if_modified`. Only the request for the release itself is conditional. To find the
+    /// latest Wine GE release, the tags of the repository are still fetched unconditionally.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as `GeDownloader::fetch_release`.
+    fn fetch_release_if_modified(
+        &self,
+        tag: Option<String>,
+        kind: TagKind,
+        etag: Option<&str>,
+    ) -> Result<ConditionalRelease, GithubError> {
+        let url = self.create_url(tag.as_ref(), &kind)?;
+        let etag = match etag {
+            Some(etag) => etag,
+            None => {
+                return self.retry_policy.retry(|| {
+                    let response = self.github_downloader.download_from_url(&url)?;
+                    let etag = response_etag(&response);
+                    let release = response.json::<GeRelease>()?;
+                    Ok(ConditionalRelease::Modified { release, etag })
+                })
+            }
+        };
+
+        self.retry_policy.retry(|| {
+            let response = self.github_downloader.download_if_none_match(&url, etag)?;
+            let new_etag = response_etag(&response);
+            if response.status() == StatusCode::NOT_MODIFIED {
+                return Ok(ConditionalRelease::NotModified {
+                    etag: new_etag.unwrap_or_else(|| String::from(etag)),
+                });
+            }
+
+            let release = response.json::<GeRelease>()?;
+            Ok(ConditionalRelease::Modified {
+                release,
+                etag: new_etag,
+            })
+        })
+    }
+
+    /// Download the archive of a GE version release into `sink`.
+    ///
+    /// Unlike `GeDownloader::download_release_assets` the archive is not kept in memory. The response body is written
+    /// into the `sink` in chunks as it is received, and every chunk is passed through the progress wrapper of the
+    /// `request`. The `sink` can for example be a `File`, a hasher or a pipe. Checksum files are small, so the
+    /// checksum is still downloaded into memory if `download_checksum` of the `request` is set. It is downloaded
+    /// concurrently to the archive, like in `GeDownloader::execute`.
+    ///
+    /// Unless `verify` of the `request` is unset, the archive is verified with the checksum once it was written into
+    /// the `sink`. If the verification fails, the `sink` already contains the archive and has to be discarded by the
+    /// caller.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error in the following situations:
+    /// * The GitHub API returned no tags
+    /// * The GitHub API returned a not OK HTTP response
+    /// * A release was found but that release has no assets or no archive asset
+    /// * The release has no checksum asset, the archive asset has no digest and `missing_checksum` of the `request` is
+    ///   `MissingChecksumPolicy::Fail`
+    /// * Reqwest could not fetch the resource from GitHub
+    /// * The API response could not be converted into a struct with serde
+    /// * The archive could not be read from the response or could not be written into the `sink`
+    /// * The archive does not match the checksum (`GithubError::VerificationFailed`)
+    fn download_archive_to(
+        &self,
+        request: DownloadRequest,
+        sink: &mut dyn Write,
+    ) -> Result<ArchiveMetadata, GithubError> {
+        let (release, plan) = resolve_release(self, &request)?;
+        let (archive_asset, checksum_asset) = plan.validate(&release)?;
+
+        let ((size, content_type, digest), checksum) = self.download_with_checksum(checksum_asset, |cancelled| {
+            let mut sink = HashingWriter::new(sink, plan.hash_algorithm());
+            let (size, content_type) = self.stream_archive(
+                request.progress_wrapper,
+                archive_asset,
+                &mut sink,
+                plan.max_bytes_per_sec,
+                cancelled,
+            )?;
+            Ok((size, content_type, sink.finish().1))
+        })?;
+        let file_name = archive_asset.file_name();
+        let verification = verify_download(&file_name, digest, checksum.as_ref(), &plan)?;
+
+        Ok(ArchiveMetadata {
+            tag: plan.tag,
+            file_name,
+            size,
+            content_type,
+            checksum,
+            resumed_from: None,
+            verification,
+        })
+    }
+
+    /// Continue the download of the archive of a GE version release into the partial file at `path`.
+    ///
+    /// If the file at `path` exists, the rest of the archive is requested with an HTTP `Range` header and appended to
+    /// the file. If the server ignores the range, answers with an unexpected `Content-Range` or the file does not
+    /// exist, the whole archive is downloaded into the file instead. `resumed_from` of the returned `ArchiveMetadata`
+    /// tells if and where the download was resumed.
+    ///
+    /// If `download_checksum` and `verify` of the `request` are set, the whole file is verified with the checksum of the
+    /// release afterwards. A file that does not match the checksum, e.g. because the partial file was corrupted, is removed.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error in the following situations:
+    /// * The GitHub API returned no tags
+    /// * The GitHub API returned a not OK HTTP response
+    /// * A release was found but that release has no assets or no archive asset
+    /// * Reqwest could not fetch the resource from GitHub
+    /// * The API response could not be converted into a struct with serde
+    /// * The archive could not be read from the response or could not be written into the file
+    /// * The file does not match the checksum of the release
+    fn resume_archive_download(&self, request: DownloadRequest, path: &Path) -> Result<ArchiveMetadata, GithubError> {
+        let (release, plan) = resolve_release(self, &request)?;
+        let (archive_asset, checksum_asset) = plan.validate(&release)?;
+
+        let ((size, content_type, resumed_from), checksum) =
+            self.download_with_checksum(checksum_asset, |cancelled| {
+                self.resume_archive(
+                    request.progress_wrapper,
+                    archive_asset,
+                    path,
+                    plan.max_bytes_per_sec,
+                    cancelled,
+                )
+            })?;
+
+        let file_name = archive_asset.file_name();
+        let digest = match plan.hash_algorithm() {
+            Some(algorithm) => {
+                let mut hasher = Hasher::new(algorithm);
+                File::open(path)
+                    .and_then(|mut file| io::copy(&mut file, &mut hasher))
+                    .map_err(|source| GithubError::WriteError {
+                        file_name: file_name.clone(),
+                        source,
+                    })?;
+                Some(hasher.finish())
+            }
+            None => None,
+        };
+        let verification = match verify_download(&file_name, digest, checksum.as_ref(), &plan) {
+            Ok(verification) => verification,
+            Err(err) => {
+                let _ = fs::remove_file(path);
+                return Err(err);
+            }
+        };
+
+        Ok(ArchiveMetadata {
+            tag: plan.tag,
+            file_name,
+            size,
+            content_type,
+            checksum,
+            resumed_from,
+            verification,
+        })
+    }
+
+    /// Download the assets of a GE version release into the directory `dir`.
+    ///
+    /// The assets are written into files named after the assets and are synced to disk before this method returns.
+    /// Like with `GeDownloader::download_archive_to` the archive is not kept in memory. Existing files are only
+    /// replaced if `overwrite` of the `request` is set. If a download fails, the files that were created for the
+    /// release are removed again. This includes an archive that does not match the checksum.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error in the following situations:
+    /// * The GitHub API returned no tags
+    /// * The GitHub API returned a not OK HTTP response
+    /// * A release was found but that release has no assets or no archive asset
+    /// * The release has no checksum asset, the archive asset has no digest and `missing_checksum` of the `request` is
+    ///   `MissingChecksumPolicy::Fail`
+    /// * Reqwest could not fetch the resource from GitHub
+    /// * The API response could not be converted into a struct with serde
+    /// * A file already exists and `overwrite` is not set
+    /// * An asset could not be read from the response or could not be written into its file
+    /// * The archive does not match the checksum (`GithubError::VerificationFailed`)
+    fn download_release_assets_to_dir(
+        &self,
+        request: DownloadRequest,
+        dir: &Path,
+    ) -> Result<DownloadedAssetPaths, GithubError> {
+        let (release, plan) = resolve_release(self, &request)?;
+        let (archive_asset, checksum_asset) = plan.validate(&release)?;
+
+        let archive_path = dir.join(archive_asset.file_name());
+        let checksum_path = checksum_asset.map(|asset| dir.join(asset.file_name()));
+        if !request.overwrite {
+            if let Some(path) = std::iter::once(&archive_path)
+                .chain(&checksum_path)
+                .find(|path| path.exists())
+            {
+                return Err(GithubError::FileExists { path: path.clone() });
+            }
+        }
+
+        let checksum = checksum_asset.map(|asset| self.download_checksum(asset)).transpose()?;
+        if let (Some(checksum), Some(path)) = (&checksum, &checksum_path) {
+            write_asset_file(path, request.overwrite, |file| {
+                file.write_all(checksum.checksum.as_bytes())
+                    .map_err(|source| GithubError::WriteError {
+                        file_name: checksum.file_name.clone(),
+                        source,
+                    })
+            })?;
+        }
+
+        let archive_result = write_asset_file(&archive_path, request.overwrite, |file| {
+            let mut file = HashingWriter::new(file, plan.hash_algorithm());
+            self.stream_archive(
+                request.progress_wrapper,
+                archive_asset,
+                &mut file,
+                plan.max_bytes_per_sec,
+                Arc::default(),
+            )?;
+            verify_download(&archive_asset.file_name(), file.finish().1, checksum.as_ref(), &plan)
+        });
+        let verification = match archive_result {
+            Ok(verification) => verification,
+            Err(err) => {
+                if let Some(path) = &checksum_path {
+                    let _ = fs::remove_file(path);
+                }
+                return Err(err);
+            }
+        };
+
+        Ok(DownloadedAssetPaths {
+            tag: plan.tag,
+            archive: archive_path,
+            checksum: checksum_path,
+            verification,
+        })
+    }
+
+    /// Download the archive of a GE version release and extract it into the directory `dest` while it is received.
+    ///
+    /// The archive is neither kept in memory nor written to disk before it is extracted. Its compressed bytes are
+    /// hashed while they are decompressed, so the archive is verified with the checksum of the release once the
+    /// download is complete. The progress of the `progress_wrapper` is reported for the compressed bytes. If the
+    /// download, the extraction or the verification fails, the extracted files are removed again.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error in the following situations:
+    /// * The GitHub API returned no tags
+    /// * The GitHub API returned a not OK HTTP response
+    /// * A release was found but that release has no assets or no archive asset
+    /// * The release has no checksum asset, the archive asset has no digest and `missing_checksum` of the `request` is
+    ///   `MissingChecksumPolicy::Fail`
+    /// * Reqwest could not fetch the resource from GitHub
+    /// * The API response could not be converted into a struct with serde
+    /// * The archive could not be read from the response
+    /// * The archive could not be extracted (`GithubError::ExtractionFailed`)
+    /// * The archive does not match the checksum (`GithubError::VerificationFailed`)
+    fn download_and_extract(&self, request: DownloadRequest, dest: &Path) -> Result<ExtractedTool, GithubError> {
+        let (release, plan) = resolve_release(self, &request)?;
+        let (archive_asset, checksum_asset) = plan.validate(&release)?;
+
+        let mut created = Vec::new();
+        let result = self
+            .download_with_checksum(checksum_asset, |cancelled| {
+                self.extract_archive(
+                    request.progress_wrapper,
+                    archive_asset,
+                    &plan,
+                    dest,
+                    cancelled,
+                    &mut created,
+                )
+            })
+            .and_then(|((tool, digest), checksum)| {
+                verify_download(&archive_asset.file_name(), digest, checksum.as_ref(), &plan)?;
+                Ok(tool)
+            });
+        if result.is_err() {
+            archive::remove_created(&created);
+        }
+        result
+    }
+}
+
+impl Default for GeDownloader {
+    fn default() -> Self {
+        let github_downloader = Box::new(GithubDownloader::new());
+        GeDownloader::new(github_downloader)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+    use predicates::prelude::*;
+
+    use crate::archive::Compression;
+    use crate::digest;
+    use crate::download::mime::{APPLICATION_GZIP, APPLICATION_OCTET_STREAM};
+    use crate::download::progress::NoProgress;
+    use crate::error::{ChecksumError, ErrorCategory, TagParseError};
+    use crate::timeutil;
+    use httpmock::prelude::HttpMockRequest;
+    use httpmock::Method::GET;
+    use httpmock::MockServer;
+    use lazy_static::lazy_static;
+    use mockall::mock;
+    use reqwest::blocking::Response;
+    use test_case::test_case;
+
+    use super::*;
+
+    pub const PROTON_GE_RELEASES_URL: &str = "repos/GloriousEggroll/proton-ge-custom/releases";
+    pub const PROTON_GE_RELEASE_LATEST_URL: &str = "repos/GloriousEggroll/proton-ge-custom/releases/latest";
+    pub const PROTON_GE_RELEASE_TAGS_URL: &str = "repos/GloriousEggroll/proton-ge-custom/releases/tags";
+    pub const PROTON_GE_TAGS_URL: &str = "repos/GloriousEggroll/proton-ge-custom/tags";
+    pub const WINE_GE_RELEASES_URL: &str = "repos/GloriousEggroll/wine-ge-custom/releases";
+    pub const WINE_GE_RELEASE_TAGS_URL: &str = "repos/GloriousEggroll/wine-ge-custom/releases/tags";
+    pub const WINE_GE_TAGS_URL: &str = "repos/GloriousEggroll/wine-ge-custom/tags";
+
+    lazy_static! {
+        static ref GITHUB_PROTON_GE_LATEST_URL: String = format!("{}/{}", GITHUB_API_URL, PROTON_GE_RELEASE_LATEST_URL);
+        static ref GITHUB_PROTON_GE_TAG_URL: String = format!("{}/{}", GITHUB_API_URL, PROTON_GE_RELEASE_TAGS_URL);
+        static ref GITHUB_WINE_GE_RELEASE_TAG_URL: String = format!("{}/{}", GITHUB_API_URL, WINE_GE_RELEASE_TAGS_URL);
+        static ref RELEASES: &'static str = "test_resources/responses/releases";
+        static ref ASSETS: &'static str = "test_resources/assets";
+        static ref TAGS: &'static str = "test_resources/responses/tags";
+        pub static ref PROTON_GE: String = format!("{}/proton-ge-release.json", *RELEASES);
+        pub static ref PROTON_GE_RELEASES: String = format!("{}/proton-ge-releases.json", *RELEASES);
+        pub static ref WINE_GE: String = format!("{}/wine-ge-release.json", *RELEASES);
+        pub static ref WINE_GE_LOL: String = format!("{}/wine-ge-lol-release.json", *RELEASES);
+        pub static ref NO_TAGS: String = format!("{}/empty.json", *TAGS);
+        pub static ref WINE_GE_TAGS: String = format!("{}/wine_ge.json", *TAGS);
+        pub static ref WINE_GE_LOL_TAGS: String = format!("{}/wine_ge_lol.json", *TAGS);
+        pub static ref TEST_TAR_GZ: String = format!("{}/{}", *ASSETS, "test.tar.gz");
+        pub static ref TEST_SHA512SUM: String = format!("{}/{}", *ASSETS, "test-gz.sha512sum");
+    }
+
+    /// Content of `TEST_SHA512SUM` for `TEST_TAR_GZ` served as the archive `file_name`.
+    pub fn test_checksum(file_name: &str) -> String {
+        std::fs::read_to_string(&*TEST_SHA512SUM)
+            .unwrap()
+            .replace("test.tar.gz", file_name)
+    }
+
+    mock! {
+        ProgressWrapper {}
+        impl ReadProgressWrapper for ProgressWrapper {
+            fn init(self: Box<Self>, len: u64, asset: &GeAsset) -> Box<dyn ReadProgressWrapper>;
+            fn wrap(&self, reader: Box<dyn Read>) -> Box<dyn Read>;
+            fn finish(&self, release: &GeAsset);
+        }
+    }
+
+    pub fn download_url(server: Option<&str>, tag: &str, kind: &TagKind, file_name: &str) -> String {
+        let url = download_url_without_server(tag, kind, file_name);
+
+        if let Some(host) = server {
+            format!("{}{}", host, url)
+        } else {
+            format!("SERVER{}", url)
+        }
+    }
+
+    pub fn download_url_without_server(tag: &str, kind: &TagKind, file_name: &str) -> String {
+        let repo = match kind {
+            TagKind::Proton => "proton",
+            TagKind::Wine { .. } => "wine",
+        };
+        format!(
+            "/GloriousEggroll/{}-ge-custom/releases/download/{}/{}",
+            repo, tag, file_name
+        )
+    }
+
+    pub fn mock_url(kind: &TagKind, server: &str) -> String {
+        let file_path = match kind {
+            TagKind::Proton => &*PROTON_GE,
+            TagKind::Wine { kind: wine_kind } => match wine_kind {
+                WineTagKind::WineGe => &*WINE_GE,
+                WineTagKind::LolWineGe => &*WINE_GE_LOL,
+            },
+        };
+        std::fs::read_to_string(file_path).unwrap().replace("SERVER", server)
+    }
+
+    struct FetchSpecifiedReleaseTestData {
+        given_tag: Option<String>,
+        expected_tag: String,
+        kind: TagKind,
+        github_resource: String,
+        body_content_file: String,
+        gzip_name: String,
+        gzip_download_url: String,
+        checksum_name: String,
+        checksum_download_url: String,
+    }
+
+    impl FetchSpecifiedReleaseTestData {
+        pub fn new(
+            tag: Option<&str>,
+            expected_tag: &str,
+            kind: TagKind,
+            gzip_name: &str,
+            checksum_name: &str,
+            github_resource: &str,
+            body_content_file: &str,
+        ) -> Self {
+            let github_resource = if let Some(tag) = tag {
+                format!("{}/{}", github_resource, tag)
+            } else {
+                String::from(github_resource)
+            };
+            let tag = tag.map(String::from);
+
+            let gzip_download_url = download_url(None, expected_tag, &kind, gzip_name);
+            let checksum_download_url = download_url(None, expected_tag, &kind, checksum_name);
+
+            let expected_tag = String::from(expected_tag);
+            let body_content_file = String::from(body_content_file);
+            let gzip_name = String::from(gzip_name);
+            let checksum_name = String::from(checksum_name);
+            FetchSpecifiedReleaseTestData {
+                given_tag: tag,
+                expected_tag,
+                kind,
+                github_resource,
+                body_content_file,
+                gzip_name,
+                gzip_download_url,
+                checksum_name,
+                checksum_download_url,
+            }
+        }
+    }
+
+    struct FetchLatestReleaseTestData {
+        tags_body_from_file: String,
+        github_tags_resource: String,
+        expected_specific_release: FetchSpecifiedReleaseTestData,
+    }
+
+    impl FetchLatestReleaseTestData {
+        pub fn new<S: Into<String>>(
+            tags_body_from_file: S,
+            github_tags_resource: S,
+            mut expected_specific_release: FetchSpecifiedReleaseTestData,
+        ) -> Self {
+            let tags_body_from_file = tags_body_from_file.into();
+            let github_tags_resource = github_tags_resource.into();
+            expected_specific_release.github_resource = format!(
+                "{}/{}",
+                expected_specific_release.github_resource, expected_specific_release.expected_tag
+            );
+
+            FetchLatestReleaseTestData {
+                tags_body_from_file,
+                github_tags_resource,
+                expected_specific_release,
+            }
+        }
+    }
+
+    struct FetchReleaseContentTestData {
+        expected_tag: String,
+        kind: TagKind,
+        compressed_tar_file_name: String,
+        checksum_file_name: String,
+        release_url: String,
+    }
+
+    impl FetchReleaseContentTestData {
+        pub fn new<S: Into<String>>(
+            tag: S,
+            kind: &TagKind,
+            compressed_tar_file_name: S,
+            checksum_file_name: S,
+            release_url: S,
+        ) -> Self {
+            let expected_tag = tag.into();
+            let kind = *kind;
+            let compressed_tar_file_name = compressed_tar_file_name.into();
+            let checksum_file_name = checksum_file_name.into();
+            let release_url = release_url.into();
+
+            FetchReleaseContentTestData {
+                expected_tag,
+                kind,
+                compressed_tar_file_name,
+                checksum_file_name,
+                release_url,
+            }
+        }
+    }
+
+    struct MockGithubDownloader {
+        host: String,
+    }
+
+    impl MockGithubDownloader {
+        pub fn new<S: Into<String>>(host: S) -> Self {
+            MockGithubDownloader { host: host.into() }
+        }
+    }
+
+    impl MockGithubDownloader {
+        fn mocked_url(&self, url: &str) -> String {
+            let find_index = match url.find("repos") {
+                Some(i) => i,
+                None => url.find("G").unwrap(),
+            };
+
+            let target = url.split_at(find_index).1;
+            format!("{}/{}", self.host, target)
+        }
+    }
+
+    impl GithubDownload for MockGithubDownloader {
+        fn download_from_url(&self, url: &str) -> Result<Response, GithubError> {
+            match reqwest::blocking::get(self.mocked_url(url)) {
+                Ok(resp) => Ok(resp),
+                Err(err) => panic!("Get request failed during integration test: {:?}", err),
+            }
+        }
+
+        fn download_if_none_match(&self, url: &str, etag: &str) -> Result<Response, GithubError> {
+            let request = reqwest::blocking::Client::new()
+                .get(self.mocked_url(url))
+                .header(reqwest::header::IF_NONE_MATCH, etag);
+            match request.send() {
+                Ok(resp) => Ok(resp),
+                Err(err) => panic!("Get request failed during integration test: {:?}", err),
+            }
+        }
+
+        fn head(&self, url: &str) -> Result<Response, GithubError> {
+            match reqwest::blocking::Client::new().head(self.mocked_url(url)).send() {
+                Ok(resp) => Ok(resp),
+                Err(err) => panic!("Head request failed during integration test: {:?}", err),
+            }
+        }
+
+        fn download_range(&self, url: &str, start: u64) -> Result<Response, GithubError> {
+            let request = reqwest::blocking::Client::new()
+                .get(self.mocked_url(url))
+                .header(reqwest::header::RANGE, format!("bytes={}-", start));
+            match request.send() {
+                Ok(resp) => Ok(resp),
+                Err(err) => panic!("Get request failed during integration test: {:?}", err),
+            }
+        }
+    }
+
+    /// Github downloader that rejects responses with a status other than OK, like `GithubDownloader`.
+    struct StatusCheckingGithubDownloader(MockGithubDownloader);
+
+    impl GithubDownload for StatusCheckingGithubDownloader {
+        fn download_from_url(&self, url: &str) -> Result<Response, GithubError> {
+            let response = self.0.download_from_url(url)?;
+            if !response.status().is_success() {
+                return Err(GithubError::status_not_ok(response));
+            }
+            Ok(response)
+        }
+    }
+
+    fn fetch_release_test(test_data: FetchSpecifiedReleaseTestData) {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET).path(format!("/{}", &test_data.github_resource));
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body_from_file(&test_data.body_content_file);
+        });
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+
+        let release = tool_downloader
+            .fetch_release(test_data.given_tag, test_data.kind)
+            .unwrap();
+        let gzip = release.tar_asset().unwrap();
+        let checksum = release.checksum_asset().unwrap();
+        assert_eq!(release.tag_name, test_data.expected_tag);
+        assert_eq!(gzip.name, test_data.gzip_name);
+        assert_eq!(gzip.browser_download_url, test_data.gzip_download_url);
+        assert_eq!(gzip.content_type, APPLICATION_GZIP);
+        assert_eq!(checksum.name, test_data.checksum_name);
+        assert_eq!(checksum.browser_download_url, test_data.checksum_download_url);
+        assert_eq!(checksum.content_type, APPLICATION_OCTET_STREAM);
+
+        mock.assert();
+    }
+
+    fn fetch_latest_wine_or_lol_release_test(test_data: FetchLatestReleaseTestData) {
+        let server = MockServer::start();
+        let FetchLatestReleaseTestData {
+            expected_specific_release: test_data,
+            tags_body_from_file,
+            github_tags_resource,
+        } = test_data;
+
+        let tags_mock = server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}", github_tags_resource))
+                .query_param("page", "1");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body_from_file(tags_body_from_file);
+        });
+
+        let release_mock = server.mock(|when, then| {
+            when.method(GET).path(format!("/{}", &test_data.github_resource));
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body_from_file(&test_data.body_content_file);
+        });
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+
+        let release = tool_downloader
+            .fetch_release(test_data.given_tag, test_data.kind)
+            .unwrap();
+        let gzip = release.tar_asset().unwrap();
+        let checksum = release.checksum_asset().unwrap();
+
+        release_mock.assert();
+        tags_mock.assert();
+
+        assert_eq!(release.tag_name, test_data.expected_tag);
+        assert_eq!(gzip.name, test_data.gzip_name);
+        assert_eq!(gzip.browser_download_url, test_data.gzip_download_url);
+        assert_eq!(gzip.content_type, APPLICATION_GZIP);
+        assert_eq!(checksum.name, test_data.checksum_name);
+        assert_eq!(checksum.browser_download_url, test_data.checksum_download_url);
+        assert_eq!(checksum.content_type, APPLICATION_OCTET_STREAM);
+    }
+
+    #[test]
+    fn fetch_proton_ge_release() {
+        let expected_tag = "6.20-GE-1";
+        let given_tag = Some(expected_tag);
+        let gzip = "Proton-6.20-GE-1.tar.gz";
+        let checksum = "Proton-6.20-GE-1.sha512sum";
+        fetch_release_test(FetchSpecifiedReleaseTestData::new(
+            given_tag,
+            expected_tag,
+            TagKind::Proton,
+            gzip,
+            checksum,
+            PROTON_GE_RELEASE_TAGS_URL,
+            &PROTON_GE,
+        ));
+    }
+
+    #[test]
+    fn fetch_wine_ge_release() {
+        let expected_tag = "6.20-GE-1";
+        let given_tag = Some(expected_tag);
+        let gzip = "wine-lutris-ge-6.20-1-x86_64.tar.gz";
+        let checksum = "wine-lutris-ge-6.20-1-x86_64.sha512sum";
+        fetch_release_test(FetchSpecifiedReleaseTestData::new(
+            given_tag,
+            expected_tag,
+            TagKind::wine(),
+            gzip,
+            checksum,
+            WINE_GE_RELEASE_TAGS_URL,
+            &WINE_GE,
+        ));
+    }
+
+    #[test]
+    fn fetch_wine_lol_ge_release() {
+        let expected_tag = "6.16-GE-3-LoL";
+        let given_tag = Some(expected_tag);
+        let gzip = "wine-lutris-ge-6.16-3-lol-x86_64.tar.gz";
+        let checksum = "wine-lutris-ge-6.16-3-lol-x86_64.sha512sum";
+        fetch_release_test(FetchSpecifiedReleaseTestData::new(
+            given_tag,
+            expected_tag,
+            TagKind::lol(),
+            gzip,
+            checksum,
+            WINE_GE_RELEASE_TAGS_URL,
+            &WINE_GE_LOL,
+        ));
+    }
+
+    #[test]
+    fn fetch_latest_proton_release() {
+        let expected_tag = "6.20-GE-1";
+        let given_tag = None;
+        let gzip = "Proton-6.20-GE-1.tar.gz";
+        let checksum = "Proton-6.20-GE-1.sha512sum";
+        fetch_release_test(FetchSpecifiedReleaseTestData::new(
+            given_tag,
+            expected_tag,
+            TagKind::Proton,
+            gzip,
+            checksum,
+            PROTON_GE_RELEASE_LATEST_URL,
+            &PROTON_GE,
+        ));
+    }
+
+    #[test]
+    fn fetch_latest_wine_ge_release() {
+        let expected_tag = "6.20-GE-1";
+        let given_tag = None;
+        let gzip = "wine-lutris-ge-6.20-1-x86_64.tar.gz";
+        let checksum = "wine-lutris-ge-6.20-1-x86_64.sha512sum";
+        let expected_data = FetchSpecifiedReleaseTestData::new(
+            given_tag,
+            expected_tag,
+            TagKind::wine(),
+            gzip,
+            checksum,
+            WINE_GE_RELEASE_TAGS_URL,
+            &WINE_GE,
+        );
+
+        fetch_latest_wine_or_lol_release_test(FetchLatestReleaseTestData::new(
+            &*WINE_GE_TAGS,
+            &WINE_GE_TAGS_URL.to_owned(),
+            expected_data,
+        ));
+    }
+
+    #[test]
+    fn fetch_latest_wine_ge_lol_release() {
+        let expected_tag = "6.16-GE-3-LoL";
+        let given_tag = None;
+        let gzip = "wine-lutris-ge-6.16-3-lol-x86_64.tar.gz";
+        let checksum = "wine-lutris-ge-6.16-3-lol-x86_64.sha512sum";
+        let expected_data = FetchSpecifiedReleaseTestData::new(
+            given_tag,
+            expected_tag,
+            TagKind::lol(),
+            gzip,
+            checksum,
+            WINE_GE_RELEASE_TAGS_URL,
+            &WINE_GE_LOL,
+        );
+        fetch_latest_wine_or_lol_release_test(FetchLatestReleaseTestData::new(
+            &*WINE_GE_LOL_TAGS,
+            &WINE_GE_TAGS_URL.to_owned(),
+            expected_data,
+        ));
+    }
+
+    #[test]
+    fn fetch_latest_wine_ge_lol_version_from_second_tags_page() {
+        let tag = "6.16-GE-3-LoL";
+        let kind = TagKind::lol();
+        let gzip_file_name = "wine-lutris-ge-6.16-3-lol-x86_64.tar.gz";
+        let checksum_file_name = "wine-lutris-ge-6.16-3-lol-x86_64.sha512sum";
+
+        let server = MockServer::start();
+
+        let first_page_tags = server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}", WINE_GE_TAGS_URL))
+                .query_param("page", "1");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body_from_file(&*WINE_GE_TAGS);
+        });
+
+        let second_page_tags = server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}", WINE_GE_TAGS_URL))
+                .query_param("page", "2");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body_from_file(&*WINE_GE_LOL_TAGS);
+        });
+
+        let release_mock = server.mock(|when, then| {
+            when.method(GET).path(format!("/{}/{}", WINE_GE_RELEASE_TAGS_URL, tag));
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(mock_url(&kind, &server.base_url()));
+        });
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+
+        let release = tool_downloader.fetch_release(None, TagKind::lol()).unwrap();
+        let gzip = release.tar_asset().unwrap();
+        let checksum = release.checksum_asset().unwrap();
+
+        first_page_tags.assert();
+        second_page_tags.assert();
+        release_mock.assert();
+
+        assert_eq!(release.tag_name, tag);
+        assert_eq!(gzip.name, gzip_file_name);
+        assert_eq!(
+            gzip.browser_download_url,
+            download_url(Some(&server.base_url()), tag, &kind, gzip_file_name)
+        );
+        assert_eq!(gzip.content_type, APPLICATION_GZIP);
+        assert_eq!(checksum.name, checksum_file_name);
+        assert_eq!(
+            checksum.browser_download_url,
+            download_url(Some(&server.base_url()), tag, &kind, checksum_file_name)
+        );
+        assert_eq!(checksum.content_type, APPLICATION_OCTET_STREAM);
+    }
+
+    #[test]
+    fn fetch_latest_version_but_could_not_find_latest_tag() {
+        let server = MockServer::start();
+
+        let first_page_tags = server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}", WINE_GE_TAGS_URL))
+                .query_param("page", "1");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body_from_file(&*WINE_GE_TAGS);
+        });
+
+        let second_page_tags = server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}", WINE_GE_TAGS_URL))
+                .query_param("page", "2");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body_from_file(&*NO_TAGS);
+        });
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+
+        let release = tool_downloader.fetch_release(None, TagKind::lol());
+        assert!(release.is_err());
+        let err = release.err().unwrap();
+        assert!(
+            matches!(err, GithubError::NoTags),
+            "Result contains unexpected error: {:?}",
+            err
+        );
+
+        first_page_tags.assert();
+        second_page_tags.assert();
+    }
+
+    #[test]
+    fn fetch_latest_for_all_kinds_with_one_missing_kind() {
+        let server = MockServer::start();
+
+        let proton_mock = server.mock(|when, then| {
+            when.method(GET).path(format!("/{}", PROTON_GE_RELEASE_LATEST_URL));
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body_from_file(&*PROTON_GE);
+        });
+        let tags_mock = server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}", WINE_GE_TAGS_URL))
+                .query_param("page", "1");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .json_body(serde_json::json!([{ "name": "6.20-GE-1" }, { "name": "6.16-GE-3-LoL" }]));
+        });
+        let wine_mock = server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}/6.20-GE-1", WINE_GE_RELEASE_TAGS_URL));
+            then.status(404);
+        });
+        let lol_mock = server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}/6.16-GE-3-LoL", WINE_GE_RELEASE_TAGS_URL));
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body_from_file(&*WINE_GE_LOL);
+        });
+
+        let github_downloader = StatusCheckingGithubDownloader(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(Box::new(github_downloader));
+
+        let releases = tool_downloader.fetch_latest_for_all_kinds();
+
+        let kinds: Vec<TagKind> = releases.iter().map(|(kind, _)| *kind).collect();
+        assert_eq!(kinds, TagKind::values());
+        assert_eq!(releases[0].1.as_ref().unwrap().tag_name, "6.20-GE-1");
+        assert!(matches!(releases[1].1, Err(GithubError::StatusNotOk { .. })));
+        assert_eq!(releases[2].1.as_ref().unwrap().tag_name, "6.16-GE-3-LoL");
+
+        proton_mock.assert();
+        tags_mock.assert_hits(2);
+        wine_mock.assert();
+        lol_mock.assert();
+    }
+
+    #[test]
+    fn fetch_proton_ge_releases_page() {
+        let server = MockServer::start();
+
+        let releases_mock = server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}", PROTON_GE_RELEASES_URL))
+                .query_param("page", "2");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body_from_file(&*PROTON_GE_RELEASES);
+        });
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+
+        let releases = tool_downloader.fetch_releases(TagKind::Proton, 2).unwrap();
+        releases_mock.assert();
+
+        assert_eq!(releases.len(), 2);
+        assert_eq!(releases[0].tag_name, "GE-Proton7-8");
+        assert_eq!(
+            releases[0].published_at.map(timeutil::format_rfc3339).as_deref(),
+            Some("2022-03-20T18:30:12Z")
+        );
+        assert_eq!(releases[0].tar_asset().unwrap().size, Some(401383591));
+        assert_eq!(releases[1].tag_name, "6.20-GE-1");
+        assert_eq!(releases[1].published_at, None);
+    }
+
+    #[test]
+    fn fetch_tagged_wine_ge_releases_page() {
+        let server = MockServer::start();
+
+        let releases_mock = server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}", WINE_GE_RELEASES_URL))
+                .query_param("page", "1");
+            then.status(200).header("Content-Type", "application/json").body(
+                r#"[
+                    {"tag_name": "6.21-GE-1", "published_at": "2021-11-14T20:02:58Z", "assets": []},
+                    {"tag_name": "6.16-GE-3-LoL", "published_at": "2021-09-02T14:07:53Z", "assets": []},
+                    {"tag_name": "6.20-GE-1", "published_at": "not a date", "assets": []}
+                ]"#,
+            );
+        });
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+
+        let releases = tool_downloader.fetch_tagged_releases(TagKind::wine(), 1).unwrap();
+        releases_mock.assert();
+
+        assert_eq!(releases.len(), 2);
+        assert_eq!(releases[0].tag, Tag::new("6.21-GE-1"));
+        assert_eq!(releases[0].kind, TagKind::wine());
+        assert_eq!(
+            releases[0].published_at.map(timeutil::format_rfc3339).as_deref(),
+            Some("2021-11-14T20:02:58Z")
+        );
+        assert_eq!(releases[1].tag, Tag::new("6.20-GE-1"));
+        assert_eq!(releases[1].published_at, None);
+    }
+
+    #[test]
+    fn fetch_wine_ge_releases_page_past_the_last_page() {
+        let server = MockServer::start();
+
+        let releases_mock = server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}", WINE_GE_RELEASES_URL))
+                .query_param("page", "3");
+            then.status(200).header("Content-Type", "application/json").body("[]");
+        });
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+
+        let releases = tool_downloader.fetch_releases(TagKind::lol(), 3).unwrap();
+        releases_mock.assert();
+        assert!(releases.is_empty());
+    }
+
+    fn fetch_release_content_test(test_data: FetchReleaseContentTestData) {
+        let FetchReleaseContentTestData {
+            expected_tag,
+            kind,
+            compressed_tar_file_name: gzip_file_name,
+            checksum_file_name,
+            release_url,
+        } = test_data;
+
+        let server = MockServer::start();
+
+        let release_mock = server.mock(|when, then| {
+            when.method(GET).path(format!("/{}/{}", release_url, expected_tag));
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(mock_url(&kind, &server.base_url()));
+        });
+
+        let gzip_asset = server.mock(|when, then| {
+            when.method(GET)
+                .path(download_url_without_server(&expected_tag, &kind, &gzip_file_name));
+            then.status(200)
+                .header("Content-Type", "application/gzip")
+                .body_from_file(&*TEST_TAR_GZ);
+        });
+
+        let checksum_asset = server.mock(|when, then| {
+            when.method(GET)
+                .path(download_url_without_server(&expected_tag, &kind, &checksum_file_name));
+            then.status(200)
+                .header("Content-Type", "application/octet-stream")
+                .body(test_checksum(&gzip_file_name));
+        });
+
+        // TODO: Assertions could be improved here, however, that will require changes to the test structure. In
+        //  general some things (APPLICATION_XZ) have changed since these tests were written.
+        let mut progress_wrapper = MockProgressWrapper::new();
+        progress_wrapper.expect_finish().never();
+        progress_wrapper.expect_wrap().never();
+        progress_wrapper.expect_init().once().returning(|_, _| {
+            let mut initialized_prog_wrapper = MockProgressWrapper::new();
+            initialized_prog_wrapper.expect_init().never();
+            initialized_prog_wrapper.expect_wrap().once().returning(|reader| reader);
+            initialized_prog_wrapper.expect_finish().once().returning(|_| ());
+
+            Box::new(initialized_prog_wrapper)
+        });
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+
+        let request = DownloadRequest::new(Some(expected_tag), kind, Box::new(progress_wrapper), true);
+        let fetched_assets = tool_downloader.download_release_assets(request).unwrap();
+
+        release_mock.assert();
+        gzip_asset.assert();
+        checksum_asset.assert();
+
+        let expected_gzip_content = std::fs::read(&*TEST_TAR_GZ).unwrap();
+        let expected_checksum_content = test_checksum(&gzip_file_name);
+
+        let downloaded_tar = fetched_assets.compressed_archive;
+        let downloaded_checksum = fetched_assets.checksum.unwrap();
+
+        assert_eq!(downloaded_tar.compressed_content, expected_gzip_content);
+        assert_eq!(downloaded_tar.file_name, gzip_file_name);
+        assert_eq!(downloaded_checksum.checksum, expected_checksum_content);
+        assert_eq!(downloaded_checksum.file_name, checksum_file_name);
+    }
+
+    #[test_case(Some("GE-Proton7-8"), TagKind::Proton => format!("{}/GE-Proton7-8", *GITHUB_PROTON_GE_TAG_URL))]
+    #[test_case(Some("6.16-GE-3-LoL"), TagKind::lol() => format!("{}/6.16-GE-3-LoL", *GITHUB_WINE_GE_RELEASE_TAG_URL))]
+    #[test_case(Some("wine ge (custom)+8"), TagKind::wine() => format!("{}/wine%20ge%20%28custom%29%2B8", *GITHUB_WINE_GE_RELEASE_TAG_URL))]
+    #[test_case(Some("Übersetzung/7"), TagKind::Proton => format!("{}/%C3%9Cbersetzung%2F7", *GITHUB_PROTON_GE_TAG_URL); "non-ASCII tag with slash")]
+    #[test_case(None, TagKind::Proton => String::from(&*GITHUB_PROTON_GE_LATEST_URL))]
+    fn create_release_url_with_encoded_tag(tag: Option<&str>, kind: TagKind) -> String {
+        GeDownloader::default().create_url(tag, &kind).unwrap()
+    }
+
+    #[test]
+    fn download_assets_with_special_characters_in_name() {
+        let tag = "wine ge (custom)+8";
+        let archive_name = "wine ge (custom)+8.tar.xz";
+        let checksum_name = "wine ge (custom)+8.sha512sum";
+        let server = MockServer::start();
+
+        let release = format!(
+            r#"{{
+                "tag_name": "{tag}",
+                "assets": [
+                    {{
+                        "name": "{checksum_name}",
+                        "content_type": "application/octet-stream",
+                        "browser_download_url": "{host}/GloriousEggroll/wine-ge-custom/releases/download/wine%20ge%20%28custom%29%2B8/wine%20ge%20%28custom%29%2B8.sha512sum"
+                    }},
+                    {{
+                        "name": "{archive_name}",
+                        "content_type": "application/x-xz",
+                        "browser_download_url": "{host}/GloriousEggroll/wine-ge-custom/releases/download/wine%20ge%20%28custom%29%2B8/wine%20ge%20%28custom%29%2B8.tar.xz"
+                    }}
+                ]
+            }}"#,
+            host = server.base_url()
+        );
+        let release_mock = server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}/wine%20ge%20%28custom%29%2B8", WINE_GE_RELEASE_TAGS_URL));
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(release);
+        });
+        let archive_mock = server.mock(|when, then| {
+            when.method(GET).path(
+                "/GloriousEggroll/wine-ge-custom/releases/download/wine%20ge%20%28custom%29%2B8/wine%20ge%20%28custom%29%2B8.tar.xz",
+            );
+            then.status(200).body_from_file(&*TEST_TAR_GZ);
+        });
+        let checksum_mock = server.mock(|when, then| {
+            when.method(GET).path(
+                "/GloriousEggroll/wine-ge-custom/releases/download/wine%20ge%20%28custom%29%2B8/wine%20ge%20%28custom%29%2B8.sha512sum",
+            );
+            then.status(200).body(test_checksum(archive_name));
+        });
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+
+        let request = DownloadRequest::new(
+            Some(String::from(tag)),
+            TagKind::wine(),
+            Box::new(tracked_progress_wrapper()),
+            true,
+        );
+        let assets = tool_downloader.download_release_assets(request).unwrap();
+
+        release_mock.assert();
+        archive_mock.assert();
+        checksum_mock.assert();
+
+        assert_eq!(assets.tag, tag);
+        assert_eq!(assets.compressed_archive.file_name, archive_name);
+        assert_eq!(assets.checksum.unwrap().file_name, checksum_name);
+        assert_eq!(Tag::from_file_name(&assets.compressed_archive.file_name).str(), tag);
+    }
+
+    fn tracked_progress_wrapper() -> MockProgressWrapper {
+        let mut progress_wrapper = MockProgressWrapper::new();
+        progress_wrapper.expect_init().once().returning(|_, _| {
+            let mut initialized_prog_wrapper = MockProgressWrapper::new();
+            initialized_prog_wrapper.expect_wrap().once().returning(|reader| reader);
+            initialized_prog_wrapper.expect_finish().once().returning(|_| ());
+            Box::new(initialized_prog_wrapper)
+        });
+        progress_wrapper
+    }
+
+    #[test]
+    fn resolve_download_plan_without_downloading_assets() {
+        let tag = "6.20-GE-1";
+        let kind = TagKind::Proton;
+        let server = MockServer::start();
+
+        let release_mock = server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}/{}", PROTON_GE_RELEASE_TAGS_URL, tag));
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(mock_url(&kind, &server.base_url()));
+        });
+        let gzip_asset = server.mock(|when, then| {
+            when.method(GET)
+                .path(download_url_without_server(tag, &kind, "Proton-6.20-GE-1.tar.gz"));
+            then.status(200).body_from_file(&*TEST_TAR_GZ);
+        });
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+
+        let request = DownloadRequest::new(
+            Some(String::from(tag)),
+            kind,
+            Box::new(MockProgressWrapper::new()),
+            true,
+        );
+        let plan = tool_downloader.resolve(&request).unwrap();
+
+        release_mock.assert();
+        gzip_asset.assert_hits(0);
+
+        assert_eq!(plan.tag, tag);
+        assert_eq!(plan.kind, kind);
+        assert_eq!(plan.archive.name, "Proton-6.20-GE-1.tar.gz");
+        assert_eq!(plan.archive.size, Some(255));
+        assert_eq!(
+            plan.archive.url,
+            download_url(Some(&server.base_url()), tag, &kind, "Proton-6.20-GE-1.tar.gz")
+        );
+        assert_eq!(plan.checksum.unwrap().name, "Proton-6.20-GE-1.sha512sum");
+        assert!(plan.checksum_available);
+    }
+
+    #[test]
+    fn execute_deserialized_download_plan() {
+        let tag = "6.20-GE-1";
+        let kind = TagKind::Proton;
+        let server = MockServer::start();
+
+        let release_mock = server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}/{}", PROTON_GE_RELEASE_TAGS_URL, tag));
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(mock_url(&kind, &server.base_url()));
+        });
+        let gzip_asset = server.mock(|when, then| {
+            when.method(GET)
+                .path(download_url_without_server(tag, &kind, "Proton-6.20-GE-1.tar.gz"));
+            then.status(200).body_from_file(&*TEST_TAR_GZ);
+        });
+        let checksum_asset = server.mock(|when, then| {
+            when.method(GET)
+                .path(download_url_without_server(tag, &kind, "Proton-6.20-GE-1.sha512sum"));
+            then.status(200).body(test_checksum("Proton-6.20-GE-1.tar.gz"));
+        });
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+
+        let request = DownloadRequest::new(
+            Some(String::from(tag)),
+            kind,
+            Box::new(MockProgressWrapper::new()),
+            false,
+        );
+        let plan = tool_downloader.resolve(&request).unwrap();
+        let plan: DownloadPlan = serde_json::from_str(&serde_json::to_string(&plan).unwrap()).unwrap();
+
+        let assets = tool_downloader
+            .execute(&plan, Box::new(tracked_progress_wrapper()))
+            .unwrap();
+
+        release_mock.assert_hits(2);
+        gzip_asset.assert();
+        checksum_asset.assert_hits(0);
+
+        assert_eq!(assets.tag, tag);
+        assert_eq!(
+            assets.compressed_archive.compressed_content,
+            std::fs::read(&*TEST_TAR_GZ).unwrap()
+        );
+        assert_eq!(assets.compressed_archive.compression, Some(Compression::Gzip));
+        assert!(assets.checksum.is_none());
+    }
+
+    #[test]
+    fn execute_outdated_download_plan() {
+        let tag = "6.20-GE-1";
+        let kind = TagKind::Proton;
+        let server = MockServer::start();
+
+        let release_mock = server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}/{}", PROTON_GE_RELEASE_TAGS_URL, tag));
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(mock_url(&kind, &server.base_url()));
+        });
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+
+        let request = DownloadRequest::new(
+            Some(String::from(tag)),
+            kind,
+            Box::new(MockProgressWrapper::new()),
+            true,
+        );
+        let mut plan = tool_downloader.resolve(&request).unwrap();
+        plan.archive.url = format!("{}/removed.tar.gz", server.base_url());
+
+        let result = tool_downloader.execute(&plan, Box::new(MockProgressWrapper::new()));
+
+        release_mock.assert_hits(2);
+        assert!(matches!(result, Err(GithubError::OutdatedDownloadPlan { .. })));
+    }
+
+    /// Sink that fails every write.
+    struct FailingSink;
+
+    impl Write for FailingSink {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::new(ErrorKind::StorageFull, "disk is full"))
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn mock_proton_release_with_assets<'a>(server: &'a MockServer, tag: &str) -> [httpmock::Mock<'a>; 3] {
+        let kind = TagKind::Proton;
+        let release_mock = server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}/{}", PROTON_GE_RELEASE_TAGS_URL, tag));
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(mock_url(&kind, &server.base_url()));
+        });
+        let gzip_asset = server.mock(|when, then| {
+            when.method(GET)
+                .path(download_url_without_server(tag, &kind, "Proton-6.20-GE-1.tar.gz"));
+            then.status(200)
+                .header("Content-Type", APPLICATION_GZIP)
+                .body_from_file(&*TEST_TAR_GZ);
+        });
+        let checksum_asset = server.mock(|when, then| {
+            when.method(GET)
+                .path(download_url_without_server(tag, &kind, "Proton-6.20-GE-1.sha512sum"));
+            then.status(200).body(test_checksum("Proton-6.20-GE-1.tar.gz"));
+        });
+        [release_mock, gzip_asset, checksum_asset]
+    }
+
+    #[test]
+    fn download_release_assets_from_custom_api_url() {
+        let tag = "6.20-GE-1";
+        let kind = TagKind::Proton;
+        let server = MockServer::start();
+        let release_mock = server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/api/v3/{}/{}", PROTON_GE_RELEASE_TAGS_URL, tag))
+                .header("Authorization", "Bearer secret-token");
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(mock_url(&kind, &server.base_url()));
+        });
+        let gzip_asset = server.mock(|when, then| {
+            when.method(GET)
+                .path(download_url_without_server(tag, &kind, "Proton-6.20-GE-1.tar.gz"));
+            then.status(200)
+                .header("Content-Type", APPLICATION_GZIP)
+                .body_from_file(&*TEST_TAR_GZ);
+        });
+        let checksum_asset = server.mock(|when, then| {
+            when.method(GET)
+                .path(download_url_without_server(tag, &kind, "Proton-6.20-GE-1.sha512sum"));
+            then.status(200).body(test_checksum("Proton-6.20-GE-1.tar.gz"));
+        });
+
+        let tool_downloader = GeDownloader::builder()
+            .with_api_url(server.url("/api/v3/"))
+            .with_token("secret-token")
+            .with_retry_policy(RetryPolicy::none())
+            .build()
+            .unwrap();
+        let request = DownloadRequest::new(Some(String::from(tag)), kind, Box::new(NoProgress), true);
+        let assets = tool_downloader.download_release_assets(request).unwrap();
+
+        release_mock.assert();
+        gzip_asset.assert();
+        checksum_asset.assert();
+        assert_eq!(assets.tag, tag);
+        assert!(assets.checksum.is_some());
+        assert_eq!(assets.verification, Verification::ChecksumFile);
+    }
+
+    /// Download a release whose checksum file `checksum_name` has the content of the test resource `fixture`.
+    fn download_release_with_checksum_file(
+        fixture: &str,
+        checksum_name: &str,
+    ) -> Result<DownloadedAssets, GithubError> {
+        let tag = "GE-Proton9-1";
+        let kind = TagKind::Proton;
+        let server = MockServer::start();
+        let content = std::fs::read(&*TEST_TAR_GZ).unwrap();
+        let checksum = std::fs::read(format!("{}/{}", *ASSETS, fixture)).unwrap();
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}/{}", PROTON_GE_RELEASE_TAGS_URL, tag));
+            then.status(200).json_body(serde_json::json!({
+                "tag_name": tag,
+                "assets": [{
+                    "name": "test.tar.gz",
+                    "content_type": APPLICATION_GZIP,
+                    "browser_download_url": download_url(Some(&server.base_url()), tag, &kind, "test.tar.gz"),
+                }, {
+                    "name": checksum_name,
+                    "content_type": APPLICATION_OCTET_STREAM,
+                    "browser_download_url": download_url(Some(&server.base_url()), tag, &kind, checksum_name),
+                }],
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(download_url_without_server(tag, &kind, "test.tar.gz"));
+            then.status(200).body(&content);
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(download_url_without_server(tag, &kind, checksum_name));
+            then.status(200).body(&checksum);
+        });
+
+        let tool_downloader = GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url())));
+        let request = DownloadRequest::new(Some(String::from(tag)), kind, Box::new(NoProgress), true);
+        tool_downloader.download_release_assets(request)
+    }
+
+    #[test_case("test-gz.sha512sum", "test.sha512sum" => Algorithm::Sha512; "sha512")]
+    #[test_case("test-gz.sha256sum", "test.sha256sum" => Algorithm::Sha256; "sha256")]
+    fn verify_archive_with_checksum_file_of_algorithm(fixture: &str, checksum_name: &str) -> Algorithm {
+        let assets = download_release_with_checksum_file(fixture, checksum_name).unwrap();
+
+        assert_eq!(assets.verification, Verification::ChecksumFile);
+        assets.checksum.unwrap().algorithm.unwrap()
+    }
+
+    #[test]
+    fn fail_if_checksum_file_has_digest_of_other_algorithm() {
+        let err = download_release_with_checksum_file("test-gz.sha256sum", "test.sha512sum")
+            .err()
+            .unwrap();
+
+        assert!(matches!(
+            err,
+            GithubError::VerificationFailed {
+                source: ChecksumError::MalformedChecksumFile { .. },
+                ..
+            }
+        ));
+    }
+
+    /// Download a release without a checksum file, whose archive asset has the `digest`.
+    fn download_release_with_asset_digest(
+        digest: Option<String>,
+        missing_checksum: MissingChecksumPolicy,
+    ) -> Result<DownloadedAssets, GithubError> {
+        let tag = "GE-Proton9-1";
+        let kind = TagKind::Proton;
+        let server = MockServer::start();
+        let content = std::fs::read(&*TEST_TAR_GZ).unwrap();
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}/{}", PROTON_GE_RELEASE_TAGS_URL, tag));
+            then.status(200).json_body(serde_json::json!({
+                "tag_name": tag,
+                "assets": [{
+                    "name": "GE-Proton9-1.tar.gz",
+                    "content_type": APPLICATION_GZIP,
+                    "browser_download_url": download_url(Some(&server.base_url()), tag, &kind, "GE-Proton9-1.tar.gz"),
+                    "size": content.len(),
+                    "digest": digest,
+                }],
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(download_url_without_server(tag, &kind, "GE-Proton9-1.tar.gz"));
+            then.status(200).body(&content);
+        });
+
+        let tool_downloader = GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url())));
+        let request = DownloadRequest::new(Some(String::from(tag)), kind, Box::new(NoProgress), true)
+            .with_missing_checksum(missing_checksum);
+        tool_downloader.download_release_assets(request)
+    }
+
+    #[test_case(MissingChecksumPolicy::Continue; "continue without checksum")]
+    #[test_case(MissingChecksumPolicy::Fail; "fail without checksum")]
+    fn verify_archive_with_asset_digest(missing_checksum: MissingChecksumPolicy) {
+        let content = std::fs::read(&*TEST_TAR_GZ).unwrap();
+        let digest = format!("sha256:{}", digest::sha256(&content));
+
+        let assets = download_release_with_asset_digest(Some(digest), missing_checksum).unwrap();
+
+        assert!(assets.checksum.is_none());
+        assert_eq!(assets.verification, Verification::AssetDigest(Algorithm::Sha256));
+        assert_eq!(assets.compressed_archive.compressed_content, content);
+    }
+
+    #[test]
+    fn fail_if_archive_does_not_match_asset_digest() {
+        let digest = format!("sha256:{}", digest::sha256(b"other archive"));
+
+        let err = download_release_with_asset_digest(Some(digest), MissingChecksumPolicy::Continue)
+            .err()
+            .unwrap();
+
+        assert!(matches!(
+            err,
+            GithubError::VerificationFailed { ref file_name, source: ChecksumError::Mismatch { .. } }
+                if file_name == "GE-Proton9-1.tar.gz"
+        ));
+    }
+
+    #[test_case(MissingChecksumPolicy::Continue => true; "continue without checksum")]
+    #[test_case(MissingChecksumPolicy::Fail => false; "fail without checksum")]
+    fn download_archive_without_checksum_and_asset_digest(missing_checksum: MissingChecksumPolicy) -> bool {
+        match download_release_with_asset_digest(None, missing_checksum) {
+            Ok(assets) => {
+                assert_eq!(assets.verification, Verification::Unverified);
+                true
+            }
+            Err(err) => {
+                assert!(matches!(err, GithubError::MissingChecksumAsset { ref tag } if tag == "GE-Proton9-1"));
+                false
+            }
+        }
+    }
+
+    #[test_case("https://github.example.com/api/v3" => "https://github.example.com/api/v3/repos/GloriousEggroll/proton-ge-custom/releases?page=1"; "without slash")]
+    #[test_case("https://github.example.com/api/v3/" => "https://github.example.com/api/v3/repos/GloriousEggroll/proton-ge-custom/releases?page=1"; "with slash")]
+    fn create_releases_url_with_custom_api_url(api_url: &str) -> String {
+        GeDownloader::default()
+            .with_api_url(api_url)
+            .create_releases_url(&TagKind::Proton, 1)
+    }
+
+    #[test_case(true; "with checksum")]
+    #[test_case(false; "without checksum")]
+    fn stream_archive_into_sink(download_checksum: bool) {
+        let tag = "6.20-GE-1";
+        let server = MockServer::start();
+        let [release_mock, gzip_asset, checksum_asset] = mock_proton_release_with_assets(&server, tag);
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+
+        let request = DownloadRequest::new(
+            Some(String::from(tag)),
+            TagKind::Proton,
+            Box::new(tracked_progress_wrapper()),
+            download_checksum,
+        );
+        let mut sink = Vec::new();
+        let metadata = tool_downloader.download_archive_to(request, &mut sink).unwrap();
+
+        release_mock.assert();
+        gzip_asset.assert();
+        checksum_asset.assert_hits(usize::from(download_checksum));
+
+        let expected_gzip_content = std::fs::read(&*TEST_TAR_GZ).unwrap();
+        assert_eq!(sink, expected_gzip_content);
+        assert_eq!(metadata.tag, tag);
+        assert_eq!(metadata.file_name, "Proton-6.20-GE-1.tar.gz");
+        assert_eq!(metadata.size, expected_gzip_content.len() as u64);
+        assert_eq!(metadata.content_type.as_deref(), Some(APPLICATION_GZIP));
+        assert_eq!(metadata.checksum.is_some(), download_checksum);
+    }
+
+    fn mock_slow_proton_assets(server: &MockServer, tag: &str, checksum_status: u16, delay: Duration) {
+        let kind = TagKind::Proton;
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}/{}", PROTON_GE_RELEASE_TAGS_URL, tag));
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(mock_url(&kind, &server.base_url()));
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(download_url_without_server(tag, &kind, "Proton-6.20-GE-1.tar.gz"));
+            then.status(200).delay(delay).body_from_file(&*TEST_TAR_GZ);
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(download_url_without_server(tag, &kind, "Proton-6.20-GE-1.sha512sum"));
+            then.status(checksum_status)
+                .delay(delay)
+                .body(test_checksum("Proton-6.20-GE-1.tar.gz"));
+        });
+    }
+
+    fn permissive_progress_wrapper() -> MockProgressWrapper {
+        let mut progress_wrapper = MockProgressWrapper::new();
+        progress_wrapper.expect_init().returning(|_, _| {
+            let mut initialized_prog_wrapper = MockProgressWrapper::new();
+            initialized_prog_wrapper.expect_wrap().returning(|reader| reader);
+            initialized_prog_wrapper.expect_finish().returning(|_| ());
+            Box::new(initialized_prog_wrapper)
+        });
+        progress_wrapper
+    }
+
+    #[test]
+    fn download_archive_and_checksum_concurrently() {
+        let tag = "6.20-GE-1";
+        let server = MockServer::start();
+        mock_slow_proton_assets(&server, tag, 200, Duration::from_millis(500));
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+
+        let request = DownloadRequest::new(
+            Some(String::from(tag)),
+            TagKind::Proton,
+            Box::new(tracked_progress_wrapper()),
+            true,
+        );
+        let start = Instant::now();
+        let assets = tool_downloader.download_release_assets(request).unwrap();
+
+        assert!(start.elapsed() < Duration::from_millis(950));
+        assert!(assets.checksum.is_some());
+    }
+
+    #[test]
+    fn report_checksum_error_while_downloading_archive() {
+        let tag = "6.20-GE-1";
+        let server = MockServer::start();
+        mock_slow_proton_assets(&server, tag, 404, Duration::from_millis(100));
+
+        let github_downloader = Box::new(StatusCheckingGithubDownloader(MockGithubDownloader::new(
+            server.base_url(),
+        )));
+        let tool_downloader = GeDownloader::new(github_downloader).with_retry_policy(RetryPolicy::none());
+
+        let request = DownloadRequest::new(
+            Some(String::from(tag)),
+            TagKind::Proton,
+            Box::new(permissive_progress_wrapper()),
+            true,
+        );
+        let err = tool_downloader.download_release_assets(request).err().unwrap();
+
+        assert!(matches!(
+            err,
+            GithubError::StatusNotOk {
+                status: StatusCode::NOT_FOUND,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn stream_archive_into_failing_sink() {
+        let tag = "6.20-GE-1";
+        let server = MockServer::start();
+        let _mocks = mock_proton_release_with_assets(&server, tag);
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+
+        let mut progress_wrapper = MockProgressWrapper::new();
+        progress_wrapper.expect_init().once().returning(|_, _| {
+            let mut initialized_prog_wrapper = MockProgressWrapper::new();
+            initialized_prog_wrapper.expect_wrap().once().returning(|reader| reader);
+            initialized_prog_wrapper.expect_finish().never();
+            Box::new(initialized_prog_wrapper)
+        });
+        let request = DownloadRequest::new(
+            Some(String::from(tag)),
+            TagKind::Proton,
+            Box::new(progress_wrapper),
+            false,
+        );
+        let err = tool_downloader
+            .download_archive_to(request, &mut FailingSink)
+            .err()
+            .unwrap();
+
+        assert!(matches!(err, GithubError::WriteError { ref file_name, .. } if file_name == "Proton-6.20-GE-1.tar.gz"));
+    }
+
+    #[test]
+    fn download_release_assets_into_directory() {
+        let tag = "6.20-GE-1";
+        let tmp_dir = TempDir::new().unwrap();
+        let server = MockServer::start();
+        let [release_mock, gzip_asset, checksum_asset] = mock_proton_release_with_assets(&server, tag);
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+
+        let request = DownloadRequest::new(
+            Some(String::from(tag)),
+            TagKind::Proton,
+            Box::new(tracked_progress_wrapper()),
+            true,
+        );
+        let paths = tool_downloader
+            .download_release_assets_to_dir(request, tmp_dir.path())
+            .unwrap();
+
+        release_mock.assert();
+        gzip_asset.assert();
+        checksum_asset.assert();
+
+        assert_eq!(paths.tag, tag);
+        assert_eq!(paths.archive, tmp_dir.child("Proton-6.20-GE-1.tar.gz").path());
+        assert_eq!(
+            paths.checksum.unwrap(),
+            tmp_dir.child("Proton-6.20-GE-1.sha512sum").path()
+        );
+        let expected_gzip_content = std::fs::read(&*TEST_TAR_GZ).unwrap();
+        assert_eq!(
+            std::fs::read(tmp_dir.child("Proton-6.20-GE-1.tar.gz").path()).unwrap(),
+            expected_gzip_content
+        );
+        tmp_dir
+            .child("Proton-6.20-GE-1.sha512sum")
+            .assert(test_checksum("Proton-6.20-GE-1.tar.gz").as_str());
+    }
+
+    #[test]
+    fn resolve_assets_without_downloading_them() {
+        let tag = "6.20-GE-1";
+        let server = MockServer::start();
+        let [release_mock, gzip_asset, checksum_asset] = mock_proton_release_with_assets(&server, tag);
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+
+        let assets = tool_downloader.resolve_assets(&Tag::new(tag), TagKind::Proton).unwrap();
+
+        release_mock.assert();
+        gzip_asset.assert_hits(0);
+        checksum_asset.assert_hits(0);
+        assert_eq!(assets.tag, tag);
+        assert_eq!(assets.archive.name, "Proton-6.20-GE-1.tar.gz");
+        assert_eq!(
+            assets.archive.browser_download_url,
+            download_url(
+                Some(&server.base_url()),
+                tag,
+                &TagKind::Proton,
+                "Proton-6.20-GE-1.tar.gz"
+            )
+        );
+        assert_eq!(assets.archive.size, Some(255));
+        let checksum = assets.checksum.unwrap();
+        assert_eq!(checksum.name, "Proton-6.20-GE-1.sha512sum");
+        assert_eq!(
+            checksum.browser_download_url,
+            download_url(
+                Some(&server.base_url()),
+                tag,
+                &TagKind::Proton,
+                "Proton-6.20-GE-1.sha512sum"
+            )
+        );
+    }
+
+    #[test_case(true; "with checksum")]
+    #[test_case(false; "without checksum")]
+    fn download_and_extract_archive(download_checksum: bool) {
+        let tag = "6.20-GE-1";
+        let tmp_dir = TempDir::new().unwrap();
+        let server = MockServer::start();
+        let [release_mock, gzip_asset, checksum_asset] = mock_proton_release_with_assets(&server, tag);
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+
+        let request = DownloadRequest::new(
+            Some(String::from(tag)),
+            TagKind::Proton,
+            Box::new(tracked_progress_wrapper()),
+            download_checksum,
+        );
+        let tool = tool_downloader.download_and_extract(request, tmp_dir.path()).unwrap();
+
+        release_mock.assert();
+        gzip_asset.assert();
+        checksum_asset.assert_hits(usize::from(download_checksum));
+
+        assert_eq!(tool.path, tmp_dir.child("test").path());
+        assert_eq!(tool.compression, Compression::Gzip);
+        tmp_dir
+            .child("test/nested/nested.txt")
+            .assert(predicate::path::exists());
+        tmp_dir
+            .child("Proton-6.20-GE-1.tar.gz")
+            .assert(predicate::path::missing());
+    }
+
+    #[test]
+    fn remove_extracted_files_if_checksum_does_not_match() {
+        let tag = "6.20-GE-1";
+        let tmp_dir = TempDir::new().unwrap();
+        let server = MockServer::start();
+        mock_proton_release_with_wrong_checksum(&server, tag);
+
+        let tool_downloader = GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url())));
+        let request = DownloadRequest::new(Some(String::from(tag)), TagKind::Proton, Box::new(NoProgress), true);
+        let err = tool_downloader
+            .download_and_extract(request, tmp_dir.path())
+            .err()
+            .unwrap();
+
+        assert!(is_checksum_mismatch(&err));
+        assert_eq!(std::fs::read_dir(tmp_dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn fail_to_extract_invalid_archive() {
+        let tag = "6.20-GE-1";
+        let kind = TagKind::Proton;
+        let tmp_dir = TempDir::new().unwrap();
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}/{}", PROTON_GE_RELEASE_TAGS_URL, tag));
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(mock_url(&kind, &server.base_url()));
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(download_url_without_server(tag, &kind, "Proton-6.20-GE-1.tar.gz"));
+            then.status(200).body(vec![0; 255]);
+        });
+
+        let tool_downloader = GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url())));
+        let request = DownloadRequest::new(Some(String::from(tag)), kind, Box::new(NoProgress), false);
+        let err = tool_downloader
+            .download_and_extract(request, tmp_dir.path())
+            .err()
+            .unwrap();
+
+        assert!(matches!(
+            err,
+            GithubError::ExtractionFailed { ref file_name, .. } if file_name == "Proton-6.20-GE-1.tar.gz"
+        ));
+        assert_eq!(err.category(), ErrorCategory::Extraction);
+        assert_eq!(std::fs::read_dir(tmp_dir.path()).unwrap().count(), 0);
+    }
+
+    #[test_case(false; "keep existing file")]
+    #[test_case(true; "overwrite existing file")]
+    fn download_release_assets_into_directory_with_existing_file(overwrite: bool) {
+        let tag = "6.20-GE-1";
+        let tmp_dir = TempDir::new().unwrap();
+        tmp_dir.child("Proton-6.20-GE-1.tar.gz").write_str("existing").unwrap();
+        let server = MockServer::start();
+        let [_, gzip_asset, _] = mock_proton_release_with_assets(&server, tag);
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+
+        let progress_wrapper = if overwrite {
+            tracked_progress_wrapper()
+        } else {
+            MockProgressWrapper::new()
+        };
+        let request = DownloadRequest::new(
+            Some(String::from(tag)),
+            TagKind::Proton,
+            Box::new(progress_wrapper),
+            false,
+        )
+        .with_overwrite(overwrite);
+        let result = tool_downloader.download_release_assets_to_dir(request, tmp_dir.path());
+
+        if overwrite {
+            assert!(result.is_ok());
+            gzip_asset.assert();
+            let expected_gzip_content = std::fs::read(&*TEST_TAR_GZ).unwrap();
+            assert_eq!(
+                std::fs::read(tmp_dir.child("Proton-6.20-GE-1.tar.gz").path()).unwrap(),
+                expected_gzip_content
+            );
+        } else {
+            let err = result.err().unwrap();
+            assert!(matches!(err, GithubError::FileExists { ref path } if path.ends_with("Proton-6.20-GE-1.tar.gz")));
+            gzip_asset.assert_hits(0);
+            tmp_dir.child("Proton-6.20-GE-1.tar.gz").assert("existing");
+        }
+    }
+
+    #[test]
+    fn reject_truncated_archive_download() {
+        let tag = "6.20-GE-1";
+        let server = MockServer::start();
+        let kind = TagKind::Proton;
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}/{}", PROTON_GE_RELEASE_TAGS_URL, tag));
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(mock_url(&kind, &server.base_url()));
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(download_url_without_server(tag, &kind, "Proton-6.20-GE-1.tar.gz"));
+            then.status(200).body(&std::fs::read(&*TEST_TAR_GZ).unwrap()[..100]);
+        });
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+
+        let mut progress_wrapper = MockProgressWrapper::new();
+        progress_wrapper.expect_init().once().returning(|_, _| {
+            let mut initialized_prog_wrapper = MockProgressWrapper::new();
+            initialized_prog_wrapper.expect_wrap().once().returning(|reader| reader);
+            initialized_prog_wrapper.expect_finish().never();
+            Box::new(initialized_prog_wrapper)
+        });
+        let request = DownloadRequest::new(Some(String::from(tag)), kind, Box::new(progress_wrapper), false);
+        let err = tool_downloader
+            .download_archive_to(request, &mut Vec::new())
+            .err()
+            .unwrap();
+
+        assert!(matches!(
+            err,
+            GithubError::TruncatedDownload { ref file_name, expected: 255, received: 100 }
+                if file_name == "Proton-6.20-GE-1.tar.gz"
+        ));
+    }
+
+    #[test_case(200 => Some(true); "existing release")]
+    #[test_case(404 => Some(false); "missing release")]
+    #[test_case(500 => None; "server error")]
+    fn check_if_release_exists(status: u16) -> Option<bool> {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}/GE-Proton7-8", PROTON_GE_RELEASE_TAGS_URL));
+            then.status(status)
+                .header("Content-Type", "application/json")
+                .body(mock_url(&TagKind::Proton, &server.base_url()));
+        });
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader).with_retry_policy(RetryPolicy::none());
+        let result = tool_downloader.release_exists(&Tag::new("GE-Proton7-8"), TagKind::Proton);
+
+        mock.assert();
+        result.ok()
+    }
+
+    #[test_case(200 => Some(true); "uploaded archive")]
+    #[test_case(404 => Some(false); "missing archive")]
+    #[test_case(503 => None; "server error")]
+    fn check_if_archive_is_available(status: u16) -> Option<bool> {
+        let server = MockServer::start();
+        let path = download_url_without_server("GE-Proton7-8", &TagKind::Proton, "GE-Proton7-8.tar.gz");
+        let mock = server.mock(|when, then| {
+            when.method(httpmock::Method::HEAD).path(&path);
+            then.status(status);
+        });
+        let release = GeRelease::new(
+            String::from("GE-Proton7-8"),
+            vec![GeAsset::new(
+                "GE-Proton7-8.tar.gz",
+                APPLICATION_GZIP,
+                &server.url(&path),
+            )],
+        );
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader).with_retry_policy(RetryPolicy::none());
+        let result = tool_downloader.asset_available(&release);
+
+        mock.assert();
+        result.ok()
+    }
+
+    #[test]
+    fn archive_of_release_without_assets_is_not_available() {
+        let release = GeRelease::new(String::from("GE-Proton7-8"), Vec::new());
+        assert!(!GeDownloader::default().asset_available(&release).unwrap());
+    }
+
+    /// Reader that fails like an interrupted connection.
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::new(ErrorKind::ConnectionReset, "connection reset"))
+        }
+    }
+
+    #[test]
+    fn remove_partial_files_if_the_download_into_directory_fails() {
+        let tag = "6.20-GE-1";
+        let tmp_dir = TempDir::new().unwrap();
+        let server = MockServer::start();
+        let [_, gzip_asset, checksum_asset] = mock_proton_release_with_assets(&server, tag);
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+
+        let mut progress_wrapper = MockProgressWrapper::new();
+        progress_wrapper.expect_init().once().returning(|_, _| {
+            let mut initialized_prog_wrapper = MockProgressWrapper::new();
+            initialized_prog_wrapper
+                .expect_wrap()
+                .once()
+                .returning(|_| Box::new(FailingReader));
+            initialized_prog_wrapper.expect_finish().never();
+            Box::new(initialized_prog_wrapper)
+        });
+        let request = DownloadRequest::new(
+            Some(String::from(tag)),
+            TagKind::Proton,
+            Box::new(progress_wrapper),
+            true,
+        );
+        let err = tool_downloader
+            .download_release_assets_to_dir(request, tmp_dir.path())
+            .err()
+            .unwrap();
+
+        gzip_asset.assert();
+        checksum_asset.assert();
+        assert!(matches!(err, GithubError::TransferError { .. }));
+        tmp_dir
+            .child("Proton-6.20-GE-1.tar.gz")
+            .assert(predicate::path::missing());
+        tmp_dir
+            .child("Proton-6.20-GE-1.sha512sum")
+            .assert(predicate::path::missing());
+    }
+
+    /// Size of the Proton archive asset in `proton-ge-release.json`.
+    const PROTON_ARCHIVE_SIZE: u64 = 255;
+    const PARTIAL_LEN: usize = 100;
+
+    /// Mock the release and the checksum, with a checksum file that names the archive of the release.
+    fn mock_resumable_proton_release(server: &MockServer, tag: &str) {
+        let kind = TagKind::Proton;
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}/{}", PROTON_GE_RELEASE_TAGS_URL, tag));
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(mock_url(&kind, &server.base_url()));
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(download_url_without_server(tag, &kind, "Proton-6.20-GE-1.sha512sum"));
+            then.status(200).body(test_checksum("Proton-6.20-GE-1.tar.gz"));
+        });
     }
 
-    struct FetchReleaseContentTestData {
-        expected_tag: String,
-        kind: TagKind,
-        compressed_tar_file_name: String,
-        checksum_file_name: String,
-        release_url: String,
+    fn resume_request(tag: &str) -> DownloadRequest {
+        DownloadRequest::new(
+            Some(String::from(tag)),
+            TagKind::Proton,
+            Box::new(tracked_progress_wrapper()),
+            true,
+        )
     }
 
-    impl FetchReleaseContentTestData {
-        pub fn new<S: Into<String>>(
-            tag: S,
-            kind: &TagKind,
-            compressed_tar_file_name: S,
-            checksum_file_name: S,
-            release_url: S,
-        ) -> Self {
-            let expected_tag = tag.into();
-            let kind = kind.clone();
-            let compressed_tar_file_name = compressed_tar_file_name.into();
-            let checksum_file_name = checksum_file_name.into();
-            let release_url = release_url.into();
+    #[test]
+    fn resume_archive_download_with_partial_content() {
+        let tag = "6.20-GE-1";
+        let tmp_dir = TempDir::new().unwrap();
+        let expected_gzip_content = std::fs::read(&*TEST_TAR_GZ).unwrap();
+        let archive = tmp_dir.child("Proton-6.20-GE-1.tar.gz");
+        archive.write_binary(&expected_gzip_content[..PARTIAL_LEN]).unwrap();
 
-            FetchReleaseContentTestData {
-                expected_tag,
-                kind,
-                compressed_tar_file_name,
-                checksum_file_name,
-                release_url,
-            }
-        }
-    }
+        let server = MockServer::start();
+        mock_resumable_proton_release(&server, tag);
+        let range_mock = server.mock(|when, then| {
+            when.method(GET)
+                .path(download_url_without_server(
+                    tag,
+                    &TagKind::Proton,
+                    "Proton-6.20-GE-1.tar.gz",
+                ))
+                .header("Range", format!("bytes={}-", PARTIAL_LEN));
+            then.status(206)
+                .header("Content-Type", APPLICATION_GZIP)
+                .header(
+                    "Content-Range",
+                    format!(
+                        "bytes {}-{}/{}",
+                        PARTIAL_LEN,
+                        PROTON_ARCHIVE_SIZE - 1,
+                        PROTON_ARCHIVE_SIZE
+                    ),
+                )
+                .body(&expected_gzip_content[PARTIAL_LEN..]);
+        });
 
-    struct MockGithubDownloader {
-        host: String,
+        let tool_downloader = GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url())));
+        let metadata = tool_downloader
+            .resume_archive_download(resume_request(tag), archive.path())
+            .unwrap();
+
+        range_mock.assert();
+        assert_eq!(std::fs::read(archive.path()).unwrap(), expected_gzip_content);
+        assert_eq!(metadata.resumed_from, Some(PARTIAL_LEN as u64));
+        assert_eq!(metadata.size, expected_gzip_content.len() as u64);
+        assert_eq!(metadata.content_type.as_deref(), Some(APPLICATION_GZIP));
+        assert!(metadata.checksum.is_some());
     }
 
-    impl MockGithubDownloader {
-        pub fn new<S: Into<String>>(host: S) -> Self {
-            MockGithubDownloader { host: host.into() }
+    #[test_case(true; "server ignores range")]
+    #[test_case(false; "no partial file")]
+    fn resume_archive_download_with_full_content(partial_file: bool) {
+        let tag = "6.20-GE-1";
+        let tmp_dir = TempDir::new().unwrap();
+        let expected_gzip_content = std::fs::read(&*TEST_TAR_GZ).unwrap();
+        let archive = tmp_dir.child("Proton-6.20-GE-1.tar.gz");
+        if partial_file {
+            archive.write_str("stale partial download").unwrap();
         }
-    }
 
-    impl GithubDownload for MockGithubDownloader {
-        fn download_from_url(&self, url: &str) -> Result<Response, GithubError> {
-            let find_index = match url.find("repos") {
-                Some(i) => i,
-                None => url.find("G").unwrap(),
-            };
+        let server = MockServer::start();
+        mock_resumable_proton_release(&server, tag);
+        let gzip_asset = server.mock(|when, then| {
+            when.method(GET).path(download_url_without_server(
+                tag,
+                &TagKind::Proton,
+                "Proton-6.20-GE-1.tar.gz",
+            ));
+            then.status(200)
+                .header("Content-Type", APPLICATION_GZIP)
+                .body(&expected_gzip_content);
+        });
 
-            let target = url.split_at(find_index).1;
-            let mocked_url = format!("{}/{}", self.host, target);
+        let tool_downloader = GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url())));
+        let metadata = tool_downloader
+            .resume_archive_download(resume_request(tag), archive.path())
+            .unwrap();
 
-            match reqwest::blocking::get(&mocked_url) {
-                Ok(resp) => Ok(resp),
-                Err(err) => panic!("Get request failed during integration test: {:?}", err),
-            }
-        }
+        gzip_asset.assert();
+        assert_eq!(std::fs::read(archive.path()).unwrap(), expected_gzip_content);
+        assert_eq!(metadata.resumed_from, None);
+        assert_eq!(metadata.size, expected_gzip_content.len() as u64);
     }
 
-    fn fetch_release_test(test_data: FetchSpecifiedReleaseTestData) {
+    #[test]
+    fn remove_corrupted_resumed_archive() {
+        let tag = "6.20-GE-1";
+        let tmp_dir = TempDir::new().unwrap();
+        let expected_gzip_content = std::fs::read(&*TEST_TAR_GZ).unwrap();
+        let archive = tmp_dir.child("Proton-6.20-GE-1.tar.gz");
+        archive.write_binary(&[0; PARTIAL_LEN]).unwrap();
+
         let server = MockServer::start();
+        mock_resumable_proton_release(&server, tag);
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(download_url_without_server(
+                    tag,
+                    &TagKind::Proton,
+                    "Proton-6.20-GE-1.tar.gz",
+                ))
+                .header("Range", format!("bytes={}-", PARTIAL_LEN));
+            then.status(206)
+                .header(
+                    "Content-Range",
+                    format!("bytes {}-{}/*", PARTIAL_LEN, PROTON_ARCHIVE_SIZE - 1),
+                )
+                .body(&expected_gzip_content[PARTIAL_LEN..]);
+        });
+
+        let tool_downloader = GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url())));
+        let err = tool_downloader
+            .resume_archive_download(resume_request(tag), archive.path())
+            .err()
+            .unwrap();
+
+        assert!(matches!(
+            err,
+            GithubError::VerificationFailed { ref file_name, source: ChecksumError::Mismatch { .. } }
+                if file_name == "Proton-6.20-GE-1.tar.gz"
+        ));
+        archive.assert(predicate::path::missing());
+    }
 
+    #[test_case(Some("bytes 100-199/200"), Some(200) => true; "remainder")]
+    #[test_case(Some("bytes 100-199/*"), Some(200) => true; "unknown total")]
+    #[test_case(Some("bytes 100-199/200"), None => true; "unknown asset size")]
+    #[test_case(Some("bytes 0-199/200"), Some(200) => false; "whole asset")]
+    #[test_case(Some("bytes 100-149/200"), Some(200) => false; "part of the remainder")]
+    #[test_case(Some("bytes 100-299/300"), Some(200) => false; "other total")]
+    #[test_case(Some("bytes */200"), Some(200) => false; "unsatisfied range")]
+    #[test_case(Some("bytes 100-99/100"), Some(100) => false; "partial file larger than asset")]
+    #[test_case(None, Some(200) => false; "missing header")]
+    fn check_content_range(content_range: Option<&str>, size: Option<u64>) -> bool {
+        is_remainder(content_range, 100, size)
+    }
+
+    #[test]
+    fn fetch_release_with_etag() {
+        let server = MockServer::start();
         let mock = server.mock(|when, then| {
-            when.method(GET).path(format!("/{}", &test_data.github_resource));
+            when.method(GET)
+                .path(format!("/{}/6.20-GE-1", PROTON_GE_RELEASE_TAGS_URL));
             then.status(200)
                 .header("Content-Type", "application/json")
-                .body_from_file(&test_data.body_content_file);
+                .header("ETag", "\"v1\"")
+                .body(mock_url(&TagKind::Proton, &server.base_url()));
         });
 
-        let github_downloader = Box::new(MockGithubDownloader::new(String::from(server.base_url())));
-        let tool_downloader = GeDownloader::new(github_downloader);
-
-        let release = tool_downloader
-            .fetch_release(test_data.given_tag, test_data.kind)
+        let tool_downloader = GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url())));
+        let result = tool_downloader
+            .fetch_release_if_modified(Some(String::from("6.20-GE-1")), TagKind::Proton, None)
             .unwrap();
-        let gzip = release.tar_asset();
-        let checksum = release.checksum_asset();
-        assert_eq!(release.tag_name, test_data.expected_tag);
-        assert_eq!(gzip.name, test_data.gzip_name);
-        assert_eq!(gzip.browser_download_url, test_data.gzip_download_url);
-        assert_eq!(gzip.content_type, APPLICATION_GZIP);
-        assert_eq!(checksum.name, test_data.checksum_name);
-        assert_eq!(checksum.browser_download_url, test_data.checksum_download_url);
-        assert_eq!(checksum.content_type, APPLICATION_OCTET_STREAM);
 
         mock.assert();
+        assert_eq!(result.etag(), Some("\"v1\""));
+        assert_eq!(result.release().unwrap().tag_name, "6.20-GE-1");
     }
 
-    fn fetch_latest_wine_or_lol_release_test(test_data: FetchLatestReleaseTestData) {
+    #[test]
+    fn fetch_unmodified_release() {
         let server = MockServer::start();
-        let FetchLatestReleaseTestData {
-            expected_specific_release: test_data,
-            tags_body_from_file,
-            github_tags_resource,
-        } = test_data;
-
-        let tags_mock = server.mock(|when, then| {
+        let mock = server.mock(|when, then| {
             when.method(GET)
-                .path(format!("/{}", github_tags_resource))
-                .query_param("page", "1");
-            then.status(200)
-                .header("Content-Type", "application/json")
-                .body_from_file(tags_body_from_file);
+                .path(format!("/{}/6.20-GE-1", PROTON_GE_RELEASE_TAGS_URL))
+                .header("If-None-Match", "\"v1\"");
+            then.status(304).header("ETag", "\"v1\"");
         });
 
-        let release_mock = server.mock(|when, then| {
-            when.method(GET).path(format!("/{}", &test_data.github_resource));
+        let tool_downloader = GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url())));
+        let result = tool_downloader
+            .fetch_release_if_modified(Some(String::from("6.20-GE-1")), TagKind::Proton, Some("\"v1\""))
+            .unwrap();
+
+        mock.assert();
+        assert!(matches!(result, ConditionalRelease::NotModified { ref etag } if etag == "\"v1\""));
+    }
+
+    #[test]
+    fn fetch_modified_release_with_outdated_etag() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}/6.20-GE-1", PROTON_GE_RELEASE_TAGS_URL))
+                .header("If-None-Match", "\"v1\"");
             then.status(200)
                 .header("Content-Type", "application/json")
-                .body_from_file(&test_data.body_content_file);
+                .header("ETag", "\"v2\"")
+                .body(mock_url(&TagKind::Proton, &server.base_url()));
         });
 
-        let github_downloader = Box::new(MockGithubDownloader::new(String::from(server.base_url())));
-        let tool_downloader = GeDownloader::new(github_downloader);
-
-        let release = tool_downloader
-            .fetch_release(test_data.given_tag, test_data.kind)
+        let tool_downloader = GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url())));
+        let result = tool_downloader
+            .fetch_release_if_modified(Some(String::from("6.20-GE-1")), TagKind::Proton, Some("\"v1\""))
             .unwrap();
-        let gzip = release.tar_asset();
-        let checksum = release.checksum_asset();
 
-        release_mock.assert();
-        tags_mock.assert();
+        mock.assert();
+        assert_eq!(result.etag(), Some("\"v2\""));
+        assert!(result.release().is_some());
+    }
 
-        assert_eq!(release.tag_name, test_data.expected_tag);
-        assert_eq!(gzip.name, test_data.gzip_name);
-        assert_eq!(gzip.browser_download_url, test_data.gzip_download_url);
-        assert_eq!(gzip.content_type, APPLICATION_GZIP);
-        assert_eq!(checksum.name, test_data.checksum_name);
-        assert_eq!(checksum.browser_download_url, test_data.checksum_download_url);
-        assert_eq!(checksum.content_type, APPLICATION_OCTET_STREAM);
+    fn tags_json(names: &[&str]) -> String {
+        let tags: Vec<String> = names.iter().map(|name| format!(r#"{{"name": "{}"}}"#, name)).collect();
+        format!("[{}]", tags.join(","))
     }
 
-    #[test]
-    fn fetch_proton_ge_release() {
-        let expected_tag = "6.20-GE-1";
-        let given_tag = Some(expected_tag);
-        let gzip = "Proton-6.20-GE-1.tar.gz";
-        let checksum = "Proton-6.20-GE-1.sha512sum";
-        fetch_release_test(FetchSpecifiedReleaseTestData::new(
-            given_tag,
-            expected_tag,
-            TagKind::Proton,
-            gzip,
-            checksum,
-            PROTON_GE_RELEASE_TAGS_URL,
-            &*PROTON_GE,
-        ));
+    fn is_first_page(request: &HttpMockRequest) -> bool {
+        !request.query_params.iter().flatten().any(|(name, _)| name == "page")
     }
 
-    #[test]
-    fn fetch_wine_ge_release() {
-        let expected_tag = "6.20-GE-1";
-        let given_tag = Some(expected_tag);
-        let gzip = "wine-lutris-ge-6.20-1-x86_64.tar.gz";
-        let checksum = "wine-lutris-ge-6.20-1-x86_64.sha512sum";
-        fetch_release_test(FetchSpecifiedReleaseTestData::new(
-            given_tag,
-            expected_tag,
-            TagKind::wine(),
-            gzip,
-            checksum,
-            WINE_GE_RELEASE_TAGS_URL,
-            &*WINE_GE,
-        ));
+    fn mock_tag_pages<'a>(server: &'a MockServer, tags_url: &str, pages: &[&[&str]]) -> Vec<httpmock::Mock<'a>> {
+        pages
+            .iter()
+            .enumerate()
+            .map(|(i, names)| {
+                let page = i + 1;
+                let body = tags_json(names);
+                let next = (page < pages.len()).then(|| {
+                    format!(
+                        r#"<{}/{}?per_page=2&page={}>; rel="next", <{}/{}?per_page=2&page=1>; rel="first""#,
+                        server.base_url(),
+                        tags_url,
+                        page + 1,
+                        server.base_url(),
+                        tags_url
+                    )
+                });
+                server.mock(|when, then| {
+                    let when = when
+                        .method(GET)
+                        .path(format!("/{}", tags_url))
+                        .query_param("per_page", "2");
+                    if page == 1 {
+                        when.matches(is_first_page);
+                    } else {
+                        when.query_param("page", page.to_string());
+                    }
+                    let then = then.status(200).header("Content-Type", "application/json").body(body);
+                    if let Some(next) = next {
+                        then.header("Link", next);
+                    }
+                })
+            })
+            .collect()
+    }
+
+    #[test_case(None => vec!["GE-Proton8-3", "GE-Proton8-2", "GE-Proton8-1", "GE-Proton7-55", "GE-Proton7-54"]; "all tags")]
+    #[test_case(Some(3) => vec!["GE-Proton8-3", "GE-Proton8-2", "GE-Proton8-1"]; "limit within a page")]
+    #[test_case(Some(10) => vec!["GE-Proton8-3", "GE-Proton8-2", "GE-Proton8-1", "GE-Proton7-55", "GE-Proton7-54"]; "limit beyond last page")]
+    fn fetch_tags_of_all_pages(limit: Option<usize>) -> Vec<String> {
+        let server = MockServer::start();
+        let mocks = mock_tag_pages(
+            &server,
+            PROTON_GE_TAGS_URL,
+            &[
+                &["GE-Proton8-3", "GE-Proton8-2"],
+                &["GE-Proton8-1", "GE-Proton7-55"],
+                &["GE-Proton7-54"],
+            ],
+        );
+
+        let tool_downloader =
+            GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url()))).with_page_size(2);
+        let tags = tool_downloader.fetch_tags(TagKind::Proton, limit).unwrap();
+
+        let expected_requests = match limit {
+            Some(limit) => limit.div_ceil(2),
+            None => 3,
+        }
+        .min(3);
+        for (i, mock) in mocks.iter().enumerate() {
+            mock.assert_hits(usize::from(i < expected_requests));
+        }
+        tags.into_iter().map(String::from).collect()
+    }
+
+    #[test_case(TagKind::wine() => vec!["7.0-GE-1", "6.23-GE-1"]; "wine")]
+    #[test_case(TagKind::lol() => vec!["7.0-GE-1-LoL", "6.16-GE-3-LoL"]; "lol")]
+    fn fetch_tags_of_wine_kind(kind: TagKind) -> Vec<String> {
+        let server = MockServer::start();
+        mock_tag_pages(
+            &server,
+            WINE_GE_TAGS_URL,
+            &[&["7.0-GE-1-LoL", "7.0-GE-1"], &["6.23-GE-1", "6.16-GE-3-LoL"]],
+        );
+
+        let tool_downloader =
+            GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url()))).with_page_size(2);
+        let tags = tool_downloader.fetch_tags(kind, None).unwrap();
+        tags.into_iter().map(String::from).collect()
+    }
+
+    #[test_case(TagKind::lol() => vec!["7.0-GE-1-LoL", "6.16-GE-3-LoL"]; "overridden kind")]
+    #[test_case(TagKind::wine() => Vec::<String>::new(); "official kind")]
+    fn fetch_tags_from_custom_source(kind: TagKind) -> Vec<String> {
+        let server = MockServer::start();
+        mock_tag_pages(
+            &server,
+            "repos/mirror-org/lol-mirror/tags",
+            &[&["7.0-GE-1-LoL", "7.0-GE-1"], &["6.16-GE-3-LoL"]],
+        );
+        mock_tag_pages(&server, WINE_GE_TAGS_URL, &[&[]]);
+
+        let tool_downloader = GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url())))
+            .with_page_size(2)
+            .with_sources([(TagKind::lol(), RepositorySource::new("mirror-org", "lol-mirror"))]);
+        let tags = tool_downloader.fetch_tags(kind, None).unwrap();
+        tags.into_iter().map(String::from).collect()
     }
 
     #[test]
-    fn fetch_wine_lol_ge_release() {
-        let expected_tag = "6.16-GE-3-LoL";
-        let given_tag = Some(expected_tag);
-        let gzip = "wine-lutris-ge-6.16-3-lol-x86_64.tar.gz";
-        let checksum = "wine-lutris-ge-6.16-3-lol-x86_64.sha512sum";
-        fetch_release_test(FetchSpecifiedReleaseTestData::new(
-            given_tag,
-            expected_tag,
-            TagKind::lol(),
-            gzip,
-            checksum,
-            WINE_GE_RELEASE_TAGS_URL,
-            &*WINE_GE_LOL,
-        ));
+    fn fetch_all_tags_sorted_and_skip_unparsable_names() {
+        let server = MockServer::start();
+        mock_tag_pages(
+            &server,
+            PROTON_GE_TAGS_URL,
+            &[
+                &["GE-Proton8-1", "lol"],
+                &["GE-Proton8-3", "7.0rc-GE"],
+                &["GE-Proton7-55"],
+            ],
+        );
+
+        let tool_downloader =
+            GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url()))).with_page_size(2);
+        let fetched = tool_downloader.fetch_all_tags(TagKind::Proton).unwrap();
+
+        let tags: Vec<String> = fetched.tags.into_iter().map(String::from).collect();
+        assert_eq!(tags, vec!["GE-Proton8-3", "GE-Proton8-1", "GE-Proton7-55"]);
+        let skipped: Vec<&str> = fetched.skipped.iter().map(TagParseError::tag).collect();
+        assert_eq!(skipped, vec!["lol", "7.0rc-GE"]);
+    }
+
+    #[test_case(true => Some(String::from("## Fixes\n\n* Fixed the launcher of Forza Horizon 5")); "with notes")]
+    #[test_case(false => None; "without notes")]
+    fn fetch_release_notes(with_notes: bool) -> Option<String> {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}/6.20-GE-1", PROTON_GE_RELEASE_TAGS_URL));
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(mock_url(&TagKind::Proton, &server.base_url()));
+        });
+
+        let tool_downloader = GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url())));
+        let tag = Some(String::from("6.20-GE-1"));
+        let release = if with_notes {
+            tool_downloader.fetch_release(tag, TagKind::Proton)
+        } else {
+            tool_downloader.fetch_release_without_notes(tag, TagKind::Proton)
+        }
+        .unwrap();
+        release.changelog().map(String::from)
     }
 
     #[test]
-    fn fetch_latest_proton_release() {
-        let expected_tag = "6.20-GE-1";
-        let given_tag = None;
-        let gzip = "Proton-6.20-GE-1.tar.gz";
-        let checksum = "Proton-6.20-GE-1.sha512sum";
-        fetch_release_test(FetchSpecifiedReleaseTestData::new(
-            given_tag,
-            expected_tag,
+    fn download_release_without_archive_asset() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}/GE-Proton9-1", PROTON_GE_RELEASE_TAGS_URL));
+            then.status(200).header("Content-Type", "application/json").body(
+                r#"{"tag_name": "GE-Proton9-1", "assets": [
+                    {"name": "GE-Proton9-1.sha512sum", "content_type": "application/octet-stream", "browser_download_url": "https://github.com/GE-Proton9-1.sha512sum"}
+                ]}"#,
+            );
+        });
+
+        let tool_downloader = GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url())));
+        let request = DownloadRequest::new(
+            Some(String::from("GE-Proton9-1")),
             TagKind::Proton,
-            gzip,
-            checksum,
-            PROTON_GE_RELEASE_LATEST_URL,
-            &*PROTON_GE,
-        ));
+            Box::new(MockProgressWrapper::new()),
+            true,
+        );
+        let err = tool_downloader.download_release_assets(request).err().unwrap();
+
+        assert!(matches!(err, GithubError::MissingArchiveAsset { ref tag } if tag == "GE-Proton9-1"));
+        assert_eq!(err.category(), ErrorCategory::NotFound);
+    }
+
+    #[test_case(MissingChecksumPolicy::Continue => true; "continue without checksum")]
+    #[test_case(MissingChecksumPolicy::Fail => false; "fail without checksum")]
+    fn plan_release_without_checksum_asset(missing_checksum: MissingChecksumPolicy) -> bool {
+        let release = GeRelease::new(
+            String::from("GE-Proton9-1"),
+            vec![GeAsset::new(
+                "GE-Proton9-1.tar.gz",
+                APPLICATION_GZIP,
+                "https://github.com/GE-Proton9-1.tar.gz",
+            )],
+        );
+        let request = DownloadRequest::new(None, TagKind::Proton, Box::new(NoProgress), true)
+            .with_missing_checksum(missing_checksum);
+
+        match request.plan(&release) {
+            Ok(plan) => {
+                assert_eq!(plan.checksum, None);
+                true
+            }
+            Err(err) => {
+                assert!(matches!(err, GithubError::MissingChecksumAsset { ref tag } if tag == "GE-Proton9-1"));
+                false
+            }
+        }
+    }
+
+    fn mock_proton_release_with_wrong_checksum(server: &MockServer, tag: &str) {
+        let kind = TagKind::Proton;
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}/{}", PROTON_GE_RELEASE_TAGS_URL, tag));
+            then.status(200)
+                .header("Content-Type", "application/json")
+                .body(mock_url(&kind, &server.base_url()));
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(download_url_without_server(tag, &kind, "Proton-6.20-GE-1.tar.gz"));
+            then.status(200).body_from_file(&*TEST_TAR_GZ);
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(download_url_without_server(tag, &kind, "Proton-6.20-GE-1.sha512sum"));
+            then.status(200)
+                .body(format!("{}  Proton-6.20-GE-1.tar.gz\n", "0".repeat(128)));
+        });
+    }
+
+    fn is_checksum_mismatch(err: &GithubError) -> bool {
+        matches!(
+            err,
+            GithubError::VerificationFailed {
+                file_name,
+                source: ChecksumError::Mismatch { .. },
+            } if file_name == "Proton-6.20-GE-1.tar.gz"
+        )
+    }
+
+    #[test_case(true; "verify")]
+    #[test_case(false; "without verification")]
+    fn download_release_assets_with_wrong_checksum(verify: bool) {
+        let tag = "6.20-GE-1";
+        let server = MockServer::start();
+        mock_proton_release_with_wrong_checksum(&server, tag);
+
+        let tool_downloader = GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url())));
+        let request = DownloadRequest::new(Some(String::from(tag)), TagKind::Proton, Box::new(NoProgress), true)
+            .with_verify(verify);
+        let result = tool_downloader.download_release_assets(request);
+
+        if verify {
+            let err = result.err().unwrap();
+            assert!(is_checksum_mismatch(&err));
+            assert_eq!(err.category(), ErrorCategory::Verification);
+        } else {
+            assert!(result.unwrap().checksum.is_some());
+        }
     }
 
     #[test]
-    fn fetch_latest_wine_ge_release() {
-        let expected_tag = "6.20-GE-1";
-        let given_tag = None;
-        let gzip = "wine-lutris-ge-6.20-1-x86_64.tar.gz";
-        let checksum = "wine-lutris-ge-6.20-1-x86_64.sha512sum";
-        let expected_data = FetchSpecifiedReleaseTestData::new(
-            given_tag,
-            expected_tag,
-            TagKind::wine(),
-            gzip,
-            checksum,
-            WINE_GE_RELEASE_TAGS_URL,
-            &*WINE_GE,
-        );
+    fn stream_archive_with_wrong_checksum() {
+        let tag = "6.20-GE-1";
+        let server = MockServer::start();
+        mock_proton_release_with_wrong_checksum(&server, tag);
 
-        fetch_latest_wine_or_lol_release_test(FetchLatestReleaseTestData::new(
-            &*WINE_GE_TAGS,
-            &WINE_GE_TAGS_URL.to_owned(),
-            expected_data,
-        ));
+        let tool_downloader = GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url())));
+        let request = DownloadRequest::new(Some(String::from(tag)), TagKind::Proton, Box::new(NoProgress), true);
+        let mut sink = Vec::new();
+        let err = tool_downloader.download_archive_to(request, &mut sink).err().unwrap();
+
+        assert!(is_checksum_mismatch(&err));
     }
 
     #[test]
-    fn fetch_latest_wine_ge_lol_release() {
-        let expected_tag = "6.16-GE-3-LoL";
-        let given_tag = None;
-        let gzip = "wine-lutris-ge-6.16-3-lol-x86_64.tar.gz";
-        let checksum = "wine-lutris-ge-6.16-3-lol-x86_64.sha512sum";
-        let expected_data = FetchSpecifiedReleaseTestData::new(
-            given_tag,
-            expected_tag,
-            TagKind::lol(),
-            gzip,
-            checksum,
-            WINE_GE_RELEASE_TAGS_URL,
-            &*WINE_GE_LOL,
-        );
-        fetch_latest_wine_or_lol_release_test(FetchLatestReleaseTestData::new(
-            &*WINE_GE_LOL_TAGS,
-            &WINE_GE_TAGS_URL.to_owned(),
-            expected_data,
-        ));
+    fn remove_assets_with_wrong_checksum_from_dir() {
+        let tag = "6.20-GE-1";
+        let server = MockServer::start();
+        mock_proton_release_with_wrong_checksum(&server, tag);
+        let tmp_dir = TempDir::new().unwrap();
+
+        let tool_downloader = GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url())));
+        let request = DownloadRequest::new(Some(String::from(tag)), TagKind::Proton, Box::new(NoProgress), true);
+        let err = tool_downloader
+            .download_release_assets_to_dir(request, tmp_dir.path())
+            .err()
+            .unwrap();
+
+        assert!(is_checksum_mismatch(&err));
+        tmp_dir
+            .child("Proton-6.20-GE-1.tar.gz")
+            .assert(predicate::path::missing());
+        tmp_dir
+            .child("Proton-6.20-GE-1.sha512sum")
+            .assert(predicate::path::missing());
     }
 
     #[test]
-    fn fetch_latest_wine_ge_lol_version_from_second_tags_page() {
-        let tag = "6.16-GE-3-LoL";
-        let kind = TagKind::lol();
-        let gzip_file_name = "wine-lutris-ge-6.16-3-lol-x86_64.tar.gz";
-        let checksum_file_name = "wine-lutris-ge-6.16-3-lol-x86_64.sha512sum";
-
+    fn fetch_release_from_custom_source() {
         let server = MockServer::start();
+        let release_mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/repos/mirror-org/proton-mirror/releases/tags/GE-Proton9-1");
+            then.status(200).header("Content-Type", "application/json").body(
+                r#"{"tag_name": "GE-Proton9-1", "assets": [
+                    {"name": "sha512sums.txt", "content_type": "text/plain", "browser_download_url": "https://mirror/sha512sums.txt"},
+                    {"name": "proton-mirror-9-1.tgz", "content_type": "application/x-gzip", "browser_download_url": "https://mirror/proton-mirror-9-1.tgz"}
+                ]}"#,
+            );
+        });
 
-        let first_page_tags = server.mock(|when, then| {
+        let tool_downloader = GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url())))
+            .with_sources([(TagKind::Proton, RepositorySource::new("mirror-org", "proton-mirror"))]);
+        let release = tool_downloader
+            .fetch_release(Some(String::from("GE-Proton9-1")), TagKind::Proton)
+            .unwrap();
+
+        release_mock.assert();
+        assert_eq!(release.tar_asset().unwrap().name, "proton-mirror-9-1.tgz");
+        assert_eq!(release.checksum_asset().unwrap().name, "sha512sums.txt");
+    }
+
+    #[test]
+    fn fetch_tags_fails_if_a_page_fails() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
             when.method(GET)
-                .path(format!("/{}", WINE_GE_TAGS_URL))
-                .query_param("page", "1");
+                .path(format!("/{}", PROTON_GE_TAGS_URL))
+                .matches(is_first_page);
             then.status(200)
                 .header("Content-Type", "application/json")
-                .body_from_file(&*WINE_GE_TAGS);
+                .header(
+                    "Link",
+                    format!(
+                        r#"<{}/{}?per_page=2&page=2>; rel="next""#,
+                        server.base_url(),
+                        PROTON_GE_TAGS_URL
+                    ),
+                )
+                .body(tags_json(&["GE-Proton8-3", "GE-Proton8-2"]));
         });
-
-        let second_page_tags = server.mock(|when, then| {
+        server.mock(|when, then| {
             when.method(GET)
-                .path(format!("/{}", WINE_GE_TAGS_URL))
+                .path(format!("/{}", PROTON_GE_TAGS_URL))
                 .query_param("page", "2");
-            then.status(200)
-                .header("Content-Type", "application/json")
-                .body_from_file(&*WINE_GE_LOL_TAGS);
+            then.status(200).body("not json");
         });
 
+        let tool_downloader =
+            GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url()))).with_page_size(2);
+        let result = tool_downloader.fetch_tags(TagKind::Proton, None);
+
+        assert!(matches!(result, Err(GithubError::ReqwestError { .. })));
+    }
+
+    #[test_case("7.8.0", &["GE-Proton7-9", "GE-Proton7-8", "GE-Proton7-7"] => Some(String::from("GE-Proton7-8")); "proton")]
+    #[test_case("6.20.1", &["6.21-GE-1", "6.20-GE-1", "6.20-GE-1-LoL"] => Some(String::from("6.20-GE-1")); "prefer same identifier")]
+    #[test_case("6.20.1-LoL", &["6.20-GE-1", "6.20-GE-1-LoL"] => Some(String::from("6.20-GE-1-LoL")); "lol")]
+    #[test_case("7.0.0", &["7.0rc3-GE-1", "7.0rc2-GE-1"] => None; "different patch version")]
+    #[test_case("7.0.1", &["7.0rc3-GE-1", "7.0rc2-GE-1"] => Some(String::from("7.0rc3-GE-1")); "highest other identifier")]
+    #[test_case("8.0.0", &["GE-Proton7-8"] => None; "missing version")]
+    fn find_tag_with_semver(semver: &str, tags: &[&str]) -> Option<String> {
+        let semver: SemVer = semver.parse().unwrap();
+        let tags = tags.iter().map(|tag| Tag::new(*tag)).collect();
+        find_tag_by_semver(tags, &semver).map(String::from)
+    }
+
+    #[test]
+    fn fetch_release_by_semver() {
+        let server = MockServer::start();
+        mock_tag_pages(
+            &server,
+            PROTON_GE_TAGS_URL,
+            &[&["6.21-GE-2", "6.20-GE-1"], &["6.19-GE-2"]],
+        );
         let release_mock = server.mock(|when, then| {
-            when.method(GET).path(format!("/{}/{}", WINE_GE_RELEASE_TAGS_URL, tag));
+            when.method(GET)
+                .path(format!("/{}/6.20-GE-1", PROTON_GE_RELEASE_TAGS_URL));
             then.status(200)
                 .header("Content-Type", "application/json")
-                .body(mock_url(&kind, &server.base_url()));
+                .body(mock_url(&TagKind::Proton, &server.base_url()));
         });
 
-        let github_downloader = Box::new(MockGithubDownloader::new(String::from(server.base_url())));
-        let tool_downloader = GeDownloader::new(github_downloader);
-
-        let release = tool_downloader.fetch_release(None, TagKind::lol()).unwrap();
-        let gzip = release.tar_asset();
-        let checksum = release.checksum_asset();
+        let tool_downloader =
+            GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url()))).with_page_size(2);
+        let release = tool_downloader
+            .fetch_release_by_semver(&"6.20.1".parse().unwrap(), TagKind::Proton)
+            .unwrap();
 
-        first_page_tags.assert();
-        second_page_tags.assert();
         release_mock.assert();
+        assert_eq!(release.tag_name, "6.20-GE-1");
+    }
 
-        assert_eq!(release.tag_name, tag);
-        assert_eq!(gzip.name, gzip_file_name);
-        assert_eq!(
-            gzip.browser_download_url,
-            download_url(Some(&server.base_url()), tag, &kind, gzip_file_name)
-        );
-        assert_eq!(gzip.content_type, APPLICATION_GZIP);
-        assert_eq!(checksum.name, checksum_file_name);
-        assert_eq!(
-            checksum.browser_download_url,
-            download_url(Some(&server.base_url()), tag, &kind, checksum_file_name)
+    #[test]
+    fn fetch_release_by_missing_semver() {
+        let server = MockServer::start();
+        mock_tag_pages(&server, PROTON_GE_TAGS_URL, &[&["6.21-GE-2", "6.20-GE-1"]]);
+
+        let tool_downloader =
+            GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url()))).with_page_size(2);
+        let err = tool_downloader
+            .fetch_release_by_semver(&"5.0.0".parse().unwrap(), TagKind::Proton)
+            .unwrap_err();
+
+        assert!(
+            matches!(err, GithubError::VersionNotFound { ref semver, kind: TagKind::Proton } if semver.str() == "5.0.0")
         );
-        assert_eq!(checksum.content_type, APPLICATION_OCTET_STREAM);
+        assert_eq!(err.category(), ErrorCategory::NotFound);
     }
 
-    #[test]
-    fn fetch_latest_version_but_could_not_find_latest_tag() {
+    fn release_json(tag: &str, draft: bool, with_assets: bool) -> String {
+        let assets = if with_assets {
+            format!(
+                r#"[{{"name": "{tag}.tar.gz", "content_type": "application/gzip", "browser_download_url": "https://github.com/{tag}.tar.gz"}}]"#
+            )
+        } else {
+            String::from("[]")
+        };
+        format!(r#"{{"tag_name": "{tag}", "draft": {draft}, "assets": {assets}}}"#)
+    }
+
+    #[test_case(3 => vec!["GE-Proton7-10", "GE-Proton7-9", "GE-Proton7-8"]; "fewer than available")]
+    #[test_case(10 => vec!["GE-Proton7-10", "GE-Proton7-9", "GE-Proton7-8", "GE-Proton7-7"]; "more than available")]
+    fn list_latest_releases(count: usize) -> Vec<String> {
         let server = MockServer::start();
+        let pages = [
+            vec![
+                release_json("GE-Proton7-11", true, true),
+                release_json("GE-Proton7-9", false, false),
+                release_json("GE-Proton7-10", false, true),
+            ],
+            vec![
+                release_json("GE-Proton7-8", false, true),
+                release_json("GE-Proton7-7", false, true),
+            ],
+            vec![],
+        ];
+        let mocks: Vec<_> = pages
+            .iter()
+            .enumerate()
+            .map(|(i, releases)| {
+                server.mock(|when, then| {
+                    when.method(GET)
+                        .path(format!("/{}", PROTON_GE_RELEASES_URL))
+                        .query_param("page", (i + 1).to_string());
+                    then.status(200)
+                        .header("Content-Type", "application/json")
+                        .body(format!("[{}]", releases.join(",")));
+                })
+            })
+            .collect();
 
-        let first_page_tags = server.mock(|when, then| {
-            when.method(GET)
-                .path(format!("/{}", WINE_GE_TAGS_URL))
-                .query_param("page", "1");
-            then.status(200)
-                .header("Content-Type", "application/json")
-                .body_from_file(&*WINE_GE_TAGS);
-        });
+        let tool_downloader = GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url())));
+        let releases = tool_downloader.list_latest_releases(TagKind::Proton, count).unwrap();
 
-        let second_page_tags = server.mock(|when, then| {
-            when.method(GET)
-                .path(format!("/{}", WINE_GE_TAGS_URL))
-                .query_param("page", "2");
-            then.status(200)
-                .header("Content-Type", "application/json")
-                .body_from_file(&*NO_TAGS);
-        });
+        mocks[0].assert();
+        mocks[1].assert();
+        mocks[2].assert_hits(usize::from(count > 4));
+        let without_assets = releases
+            .iter()
+            .find(|release| release.tag_name == "GE-Proton7-9")
+            .unwrap();
+        assert!(without_assets.find_tar_asset().is_none());
+        releases.into_iter().map(|release| release.tag_name).collect()
+    }
 
-        let github_downloader = Box::new(MockGithubDownloader::new(String::from(server.base_url())));
-        let tool_downloader = GeDownloader::new(github_downloader);
+    fn mock_release_pages(server: &MockServer, pages: &[Vec<String>]) {
+        for (i, releases) in pages.iter().enumerate() {
+            server.mock(|when, then| {
+                when.method(GET)
+                    .path(format!("/{}", PROTON_GE_RELEASES_URL))
+                    .query_param("page", (i + 1).to_string());
+                then.status(200)
+                    .header("Content-Type", "application/json")
+                    .body(format!("[{}]", releases.join(",")));
+            });
+        }
+    }
 
-        let release = tool_downloader.fetch_release(None, TagKind::lol());
-        assert!(release.is_err());
-        let err = release.err().unwrap();
-        assert!(
-            matches!(err, GithubError::NoTags),
-            "Result contains unexpected error: {:?}",
-            err
+    fn prerelease_json(tag: &str) -> String {
+        release_json(tag, false, true).replacen(r#""draft": false"#, r#""draft": false, "prerelease": true"#, 1)
+    }
+
+    #[test_case(true => vec!["GE-Proton8-1-rc2", "GE-Proton7-10", "GE-Proton7-9"]; "with prereleases")]
+    #[test_case(false => vec!["GE-Proton7-10", "GE-Proton7-9", "GE-Proton7-8"]; "without prereleases")]
+    fn list_latest_releases_filtered(include_prereleases: bool) -> Vec<String> {
+        let server = MockServer::start();
+        mock_release_pages(
+            &server,
+            &[
+                vec![
+                    prerelease_json("GE-Proton8-1-rc2"),
+                    release_json("GE-Proton7-11", true, true),
+                    release_json("GE-Proton7-10", false, true),
+                ],
+                vec![
+                    release_json("GE-Proton7-9", false, true),
+                    release_json("GE-Proton7-8", false, true),
+                ],
+                vec![],
+            ],
         );
 
-        first_page_tags.assert();
-        second_page_tags.assert();
+        let tool_downloader = GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url())));
+        let releases = tool_downloader
+            .list_latest_releases_filtered(TagKind::Proton, 3, include_prereleases)
+            .unwrap();
+
+        releases.into_iter().map(|release| release.tag_name).collect()
     }
 
-    fn fetch_release_content_test(test_data: FetchReleaseContentTestData) {
-        let FetchReleaseContentTestData {
-            expected_tag,
-            kind,
-            compressed_tar_file_name: gzip_file_name,
-            checksum_file_name,
-            release_url,
-        } = test_data;
+    #[test_case(true => "GE-Proton8-1-rc2"; "with prereleases")]
+    #[test_case(false => "GE-Proton7-10"; "without prereleases")]
+    fn fetch_latest_release_with_prerelease_as_newest(include_prereleases: bool) -> String {
+        let server = MockServer::start();
+        mock_release_pages(
+            &server,
+            &[
+                vec![
+                    prerelease_json("GE-Proton8-1-rc2"),
+                    release_json("GE-Proton8-1", true, true),
+                    release_json("GE-Proton7-10", false, true),
+                ],
+                vec![],
+            ],
+        );
 
+        let tool_downloader = GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url())));
+        let release = tool_downloader
+            .fetch_latest_release(TagKind::Proton, include_prereleases)
+            .unwrap();
+
+        assert!(!release.draft);
+        release.tag_name
+    }
+
+    #[test]
+    fn fetch_latest_release_without_stable_release() {
         let server = MockServer::start();
+        mock_release_pages(&server, &[vec![prerelease_json("GE-Proton8-1-rc2")], vec![]]);
 
-        let release_mock = server.mock(|when, then| {
-            when.method(GET).path(format!("/{}/{}", release_url, expected_tag));
-            then.status(200)
-                .header("Content-Type", "application/json")
-                .body(mock_url(&kind, &server.base_url()));
-        });
+        let tool_downloader = GeDownloader::new(Box::new(MockGithubDownloader::new(server.base_url())));
+        let err = tool_downloader
+            .fetch_latest_release(TagKind::Proton, false)
+            .unwrap_err();
 
-        let gzip_asset = server.mock(|when, then| {
-            when.method(GET)
-                .path(download_url_without_server(&expected_tag, &kind, &gzip_file_name));
-            then.status(200)
-                .header("Content-Type", "application/gzip")
-                .body_from_file(&*TEST_TAR_GZ);
-        });
+        assert!(matches!(err, GithubError::NoTags));
+    }
 
-        let checksum_asset = server.mock(|when, then| {
-            when.method(GET)
-                .path(download_url_without_server(&expected_tag, &kind, &checksum_file_name));
-            then.status(200)
-                .header("Content-Type", "application/octet-stream")
-                .body_from_file(&*TEST_SHA512SUM);
+    #[test_case(r#"<https://api.github.com/tags?page=2>; rel="next", <https://api.github.com/tags?page=5>; rel="last""# => Some(String::from("https://api.github.com/tags?page=2")); "next and last")]
+    #[test_case(r#"<https://api.github.com/tags?page=1>; rel="prev", <https://api.github.com/tags?page=1>; rel="first""# => None; "last page")]
+    #[test_case("" => None; "empty")]
+    fn parse_next_page_url(link: &str) -> Option<String> {
+        next_page_url(link)
+    }
+
+    fn downloader_without_retry_delay() -> GeDownloader {
+        GeDownloader::default().with_retry_policy(RetryPolicy {
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+            ..RetryPolicy::default()
+        })
+    }
+
+    #[test]
+    fn retry_request_on_server_error() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/releases");
+            then.status(503);
         });
 
-        // TODO: Assertions could be improved here, however, that will require changes to the test structure. In
-        //  general some things (APPLICATION_XZ) have changed since these tests were written.
-        let mut progress_wrapper = MockProgressWrapper::new();
-        progress_wrapper.expect_finish().never();
-        progress_wrapper.expect_wrap().never();
-        progress_wrapper.expect_init().once().returning(|_, _| {
-            let mut initialized_prog_wrapper = MockProgressWrapper::new();
-            initialized_prog_wrapper.expect_init().never();
-            initialized_prog_wrapper.expect_wrap().once().returning(|reader| reader);
-            initialized_prog_wrapper.expect_finish().once().returning(|_| ());
+        let err = downloader_without_retry_delay()
+            .download_from_url(&server.url("/releases"))
+            .err()
+            .unwrap();
 
-            Box::new(initialized_prog_wrapper)
+        mock.assert_hits(3);
+        assert!(matches!(err, GithubError::RetriesExhausted { attempts: 3, ref source }
+            if matches!(**source, GithubError::StatusNotOk { .. })));
+        assert_eq!(err.category(), ErrorCategory::Network);
+    }
+
+    #[test]
+    fn do_not_retry_request_on_not_found() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/releases");
+            then.status(404);
         });
 
-        let github_downloader = Box::new(MockGithubDownloader::new(String::from(server.base_url())));
+        let err = downloader_without_retry_delay()
+            .download_from_url(&server.url("/releases"))
+            .err()
+            .unwrap();
+
+        mock.assert_hits(1);
+        assert!(matches!(err, GithubError::StatusNotOk { .. }));
+    }
+
+    /// Progress wrapper that shares its updates with the test.
+    struct SharedProgressWrapper {
+        updates: Arc<Mutex<Vec<DownloadProgress>>>,
+    }
+
+    impl ReadProgressWrapper for SharedProgressWrapper {
+        fn init(self: Box<Self>, _: u64, _: &GeAsset) -> Box<dyn ReadProgressWrapper> {
+            self
+        }
+
+        fn wrap(&self, reader: Box<dyn Read>) -> Box<dyn Read> {
+            reader
+        }
+
+        fn finish(&self, _: &GeAsset) {}
+
+        fn update(&mut self, progress: DownloadProgress) {
+            self.updates.lock().unwrap().push(progress);
+        }
+
+        fn progress_interval(&self) -> Duration {
+            Duration::ZERO
+        }
+    }
+
+    #[test]
+    fn report_download_progress() {
+        let tag = "6.20-GE-1";
+        let server = MockServer::start();
+        let _mocks = mock_proton_release_with_assets(&server, tag);
+
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
         let tool_downloader = GeDownloader::new(github_downloader);
 
-        let request = DownloadRequest::new(Some(expected_tag), kind, Box::new(progress_wrapper), false);
-        let fetched_assets = tool_downloader.download_release_assets(request).unwrap();
+        let updates = Arc::default();
+        let progress_wrapper = SharedProgressWrapper {
+            updates: Arc::clone(&updates),
+        };
+        let request = DownloadRequest::new(
+            Some(String::from(tag)),
+            TagKind::Proton,
+            Box::new(progress_wrapper),
+            false,
+        );
+        tool_downloader.download_release_assets(request).unwrap();
 
-        release_mock.assert();
-        gzip_asset.assert();
-        checksum_asset.assert();
+        let expected_size = std::fs::read(&*TEST_TAR_GZ).unwrap().len() as u64;
+        let updates = updates.lock().unwrap();
+        assert!(!updates.is_empty());
+        assert!(updates.windows(2).all(|pair| pair[0].bytes_read <= pair[1].bytes_read));
+        assert!(updates
+            .iter()
+            .all(|progress| progress.total_bytes == Some(expected_size)));
+        assert_eq!(updates.last().unwrap().bytes_read, expected_size);
+    }
 
-        let expected_gzip_content = std::fs::read(&*TEST_TAR_GZ).unwrap();
-        let expected_checksum_content = std::fs::read_to_string(&*TEST_SHA512SUM).unwrap();
+    #[test]
+    fn limit_download_rate() {
+        let tag = "6.20-GE-1";
+        let archive = vec![0; 300_000];
+        let server = MockServer::start();
+        let release_mock = server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{}/{}", PROTON_GE_RELEASE_TAGS_URL, tag));
+            then.status(200).json_body(serde_json::json!({
+                "tag_name": tag,
+                "assets": [{
+                    "name": "Proton-6.20-GE-1.tar.gz",
+                    "content_type": APPLICATION_GZIP,
+                    "browser_download_url": download_url(Some(&server.base_url()), tag, &TagKind::Proton, "Proton-6.20-GE-1.tar.gz"),
+                    "size": archive.len(),
+                }],
+            }));
+        });
+        let archive_mock = server.mock(|when, then| {
+            when.method(GET).path(download_url_without_server(
+                tag,
+                &TagKind::Proton,
+                "Proton-6.20-GE-1.tar.gz",
+            ));
+            then.status(200).body(&archive);
+        });
 
-        let downloaded_tar = fetched_assets.compressed_archive;
-        let downloaded_checksum = fetched_assets.checksum.unwrap();
+        let github_downloader = Box::new(MockGithubDownloader::new(server.base_url()));
+        let tool_downloader = GeDownloader::new(github_downloader);
+        let updates = Arc::new(Mutex::new(Vec::new()));
+        let progress_wrapper = SharedProgressWrapper {
+            updates: Arc::clone(&updates),
+        };
+        let request = DownloadRequest::new(
+            Some(String::from(tag)),
+            TagKind::Proton,
+            Box::new(progress_wrapper),
+            false,
+        )
+        .with_max_bytes_per_sec(Some(200_000));
 
-        assert_eq!(downloaded_tar.compressed_content, expected_gzip_content);
-        assert_eq!(downloaded_tar.file_name, gzip_file_name);
-        assert_eq!(downloaded_checksum.checksum, expected_checksum_content);
-        assert_eq!(downloaded_checksum.file_name, checksum_file_name);
+        let started = Instant::now();
+        let mut sink = Vec::new();
+        let metadata = tool_downloader.download_archive_to(request, &mut sink).unwrap();
+        let elapsed = started.elapsed();
+
+        release_mock.assert();
+        archive_mock.assert();
+        assert_eq!(metadata.size, 300_000);
+        assert!(elapsed >= Duration::from_millis(1200), "download took {:?}", elapsed);
+        assert!(elapsed < Duration::from_secs(5), "download took {:?}", elapsed);
+        let last_update = *updates.lock().unwrap().last().unwrap();
+        assert_eq!(last_update.bytes_read, 300_000);
+        assert!(last_update.bytes_per_second().unwrap() < 250_000.0);
     }
 
     #[test]
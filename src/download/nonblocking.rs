@@ -0,0 +1,872 @@
+//! Async variant of the GE release downloader.
+//!
+//! This module is only available with the `async` feature. `AsyncGeDownloader` provides the same functionality as
+//! `GeDownloader` with non-blocking requests, so it can be used from an async runtime without spawning threads for
+//! downloads. Dropping a returned future cancels the request.
+//!
+//! The blocking `GeDownloader` is not implemented on top of this downloader, because that would require every user of
+//! the blocking API to run an async runtime. Both downloaders share the URL construction, the selection of the latest
+//! Wine GE tag and the asset validation of `DownloadPlan`.
+//!
+//! Futures that use a `ReadProgressWrapper` (`execute`, `download_release_assets` and `download_archive_to`) are not
+//! `Send`, because the wrapper is not `Send`. They can be run with a single threaded runtime or with a local task set.
+//!
+//! The rate limit of `DownloadRequest::max_bytes_per_sec` waits with `tokio::time::sleep`, so a limited download must
+//! run on a Tokio runtime with the time driver enabled.
+use std::future::{poll_fn, Future};
+use std::io::{Cursor, Write};
+use std::pin::pin;
+use std::task::Poll;
+
+use reqwest::header::{HeaderMap, ACCEPT, USER_AGENT};
+use reqwest::{Client, Response};
+
+use crate::digest::{Algorithm, Digest};
+use crate::download::plan::DownloadPlan;
+use crate::download::progress::ProgressCounter;
+use crate::download::response::{
+    ArchiveMetadata, CompatibilityToolTag, DownloadedArchive, DownloadedAssets, DownloadedChecksum, GeAsset, GeRelease,
+};
+use crate::download::source::{RepositorySource, RepositorySources};
+use crate::download::throttle::AsyncThrottle;
+use crate::download::{
+    check_size, copy_asset, latest_wine_ge_tag, normalize_api_url, release_tag_url, verify_download, DownloadRequest,
+    HashingWriter, ReadProgressWrapper, GITHUB_API_URL,
+};
+use crate::error::GithubError;
+use crate::tag::{Tag, TagKind, WineTagKind};
+
+/// Async downloader for GE releases.
+///
+/// The methods mirror the methods of `GeDownload` and return the same types. Unlike `GeDownloader`, a response with
+/// an HTTP status other than `200` is returned as `GithubError::ReqwestError`, whose `category` is derived from the
+/// status of the response.
+pub struct AsyncGeDownloader {
+    client: Client,
+    api_url: String,
+    sources: RepositorySources,
+}
+
+impl AsyncGeDownloader {
+    /// Create a downloader that sends its API requests to `https://api.github.com`.
+    pub fn new() -> Self {
+        AsyncGeDownloader::with_api_url(GITHUB_API_URL)
+    }
+
+    /// Create a downloader that sends its API requests to `api_url` instead of `https://api.github.com`.
+    pub fn with_api_url<S: Into<String>>(api_url: S) -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert(USER_AGENT, "ge_man-lib".parse().unwrap());
+        headers.insert(ACCEPT, "application/vnd.github.v3+json".parse().unwrap());
+
+        let client = Client::builder().default_headers(headers).build().unwrap();
+        AsyncGeDownloader {
+            client,
+            api_url: normalize_api_url(&api_url.into()),
+            sources: RepositorySources::default(),
+        }
+    }
+
+    /// Download the releases of the given `TagKind`s from other repositories. See `GeDownloader::with_sources`.
+    pub fn with_sources<I: IntoIterator<Item = (TagKind, RepositorySource)>>(mut self, sources: I) -> Self {
+        self.sources.extend(sources);
+        self
+    }
+
+    /// Get release information for a GitHub release.
+    ///
+    /// If the `tag` is a `None` this method will fetch the latest release. See `GeDownloader::fetch_release` for the
+    /// relevant APIs.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error in the following situations:
+    /// * The GitHub API returned no tags
+    /// * The GitHub API returned a not OK HTTP response
+    /// * Reqwest could not fetch the resource from GitHub or could not convert it into a struct
+    pub async fn fetch_release(&self, tag: Option<String>, kind: TagKind) -> Result<GeRelease, GithubError> {
+        let url = self.create_url(tag.as_deref(), &kind).await?;
+        let release = self.download_from_url(&url).await?.json::<GeRelease>().await?;
+        Ok(release)
+    }
+
+    /// Get a single page of the release history for a GE version kind.
+    ///
+    /// See `GeDownloader::fetch_releases` for details about the pages.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error in the following situations:
+    /// * The GitHub API returned a not OK HTTP response
+    /// * Reqwest could not fetch the resource from GitHub or could not convert it into a struct
+    pub async fn fetch_releases(&self, kind: TagKind, page: u8) -> Result<Vec<GeRelease>, GithubError> {
+        let url = format!("{}/releases?page={}", self.repository_url(&kind), page);
+        let releases = self.download_from_url(&url).await?.json::<Vec<GeRelease>>().await?;
+        Ok(releases)
+    }
+
+    /// Get release information without the release notes. See `GeDownload::fetch_release_without_notes`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the errors of `fetch_release`.
+    pub async fn fetch_release_without_notes(
+        &self,
+        tag: Option<String>,
+        kind: TagKind,
+    ) -> Result<GeRelease, GithubError> {
+        self.fetch_release(tag, kind).await.map(GeRelease::without_notes)
+    }
+
+    /// Get a page of the release history without the release notes. See `GeDownload::fetch_releases_without_notes`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the errors of `fetch_releases`.
+    pub async fn fetch_releases_without_notes(&self, kind: TagKind, page: u8) -> Result<Vec<GeRelease>, GithubError> {
+        let releases = self.fetch_releases(kind, page).await?;
+        Ok(releases.into_iter().map(GeRelease::without_notes).collect())
+    }
+
+    /// Resolve the assets of a GE version release without downloading them.
+    ///
+    /// See `GeDownload::resolve`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the errors of `fetch_release` and an error if the release has no assets.
+    pub async fn resolve(&self, request: &DownloadRequest) -> Result<DownloadPlan, GithubError> {
+        self.resolve_release(request).await.map(|(_, plan)| plan)
+    }
+
+    /// Download the assets of a `DownloadPlan`.
+    ///
+    /// See `GeDownloader::execute`. The archive and the checksum are downloaded concurrently on the current task.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the errors of `fetch_release` and an error if an asset of the plan is no longer available.
+    pub async fn execute(
+        &self,
+        plan: &DownloadPlan,
+        progress_wrapper: Box<dyn ReadProgressWrapper>,
+    ) -> Result<DownloadedAssets, GithubError> {
+        let release = self.fetch_release(Some(plan.tag.clone()), plan.kind).await?;
+        self.execute_for_release(plan, &release, progress_wrapper).await
+    }
+
+    /// Download the assets of a GE version release.
+    ///
+    /// See `GeDownloader::download_release_assets`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the errors of `resolve` and `execute`.
+    pub async fn download_release_assets(&self, request: DownloadRequest) -> Result<DownloadedAssets, GithubError> {
+        let (release, plan) = self.resolve_release(&request).await?;
+        self.execute_for_release(&plan, &release, request.progress_wrapper)
+            .await
+    }
+
+    /// Download the archive of a GE version release into `sink`.
+    ///
+    /// See `GeDownload::download_archive_to`. Writing into the `sink` blocks, so a slow sink blocks the async runtime.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the errors of `resolve` and an error if the archive could not be read from the response
+    /// or could not be written into the `sink`.
+    pub async fn download_archive_to(
+        &self,
+        request: DownloadRequest,
+        sink: &mut dyn Write,
+    ) -> Result<ArchiveMetadata, GithubError> {
+        let (release, plan) = self.resolve_release(&request).await?;
+        let (archive_asset, checksum_asset) = plan.validate(&release)?;
+
+        let mut sink = HashingWriter::new(sink, plan.hash_algorithm());
+        let ((size, content_type), checksum) = try_join(
+            self.stream_archive(
+                request.progress_wrapper,
+                archive_asset,
+                plan.max_bytes_per_sec,
+                &mut sink,
+            ),
+            self.download_optional_checksum(checksum_asset),
+        )
+        .await?;
+        let file_name = archive_asset.file_name();
+        let verification = verify_download(&file_name, sink.finish().1, checksum.as_ref(), &plan)?;
+
+        Ok(ArchiveMetadata {
+            tag: plan.tag,
+            file_name,
+            size,
+            content_type,
+            checksum,
+            resumed_from: None,
+            verification,
+        })
+    }
+
+    async fn download_from_url(&self, url: &str) -> Result<Response, GithubError> {
+        let response = self.client.get(url).send().await?.error_for_status()?;
+        Ok(response)
+    }
+
+    fn repository_url(&self, kind: &TagKind) -> String {
+        self.sources.api_url(kind, &self.api_url)
+    }
+
+    async fn create_url(&self, tag: Option<&str>, kind: &TagKind) -> Result<String, GithubError> {
+        let repository_url = self.repository_url(kind);
+        let release_tags_url = format!("{}/releases/tags", repository_url);
+        match (kind, tag) {
+            (TagKind::Proton, Some(tag)) => Ok(release_tag_url(&release_tags_url, tag)),
+            (TagKind::Proton, None) => Ok(format!("{}/releases/latest", repository_url)),
+            (TagKind::Wine { .. }, Some(tag)) => Ok(release_tag_url(&release_tags_url, tag)),
+            (TagKind::Wine { kind }, None) => {
+                let latest = self.find_latest_wine_ge_release_tag(&repository_url, kind).await?;
+                Ok(release_tag_url(&release_tags_url, latest.str()))
+            }
+        }
+    }
+
+    async fn find_latest_wine_ge_release_tag(
+        &self,
+        repository_url: &str,
+        kind: &WineTagKind,
+    ) -> Result<Tag, GithubError> {
+        let mut page = 1;
+        loop {
+            let url = format!("{}/tags?page={}", repository_url, page);
+            let tag_names: Vec<String> = self
+                .download_from_url(&url)
+                .await?
+                .json::<Vec<CompatibilityToolTag>>()
+                .await?
+                .into_iter()
+                .map(Into::into)
+                .collect();
+
+            if tag_names.is_empty() {
+                return Err(GithubError::NoTags);
+            }
+
+            if let Some(t) = latest_wine_ge_tag(tag_names, kind) {
+                return Ok(t);
+            }
+            page += 1
+        }
+    }
+
+    async fn resolve_release(&self, request: &DownloadRequest) -> Result<(GeRelease, DownloadPlan), GithubError> {
+        let release = self.fetch_release(request.tag.clone(), request.kind).await?;
+        if release.assets.is_empty() {
+            return Err(GithubError::ReleaseHasNoAssets {
+                tag: release.tag_name,
+                kind: request.kind,
+            });
+        }
+
+        let plan = request.plan(&release)?;
+        Ok((release, plan))
+    }
+
+    async fn execute_for_release(
+        &self,
+        plan: &DownloadPlan,
+        release: &GeRelease,
+        progress_wrapper: Box<dyn ReadProgressWrapper>,
+    ) -> Result<DownloadedAssets, GithubError> {
+        let (archive_asset, checksum_asset) = plan.validate(release)?;
+
+        let ((downloaded_archive, digest), downloaded_checksum) = try_join(
+            self.download_archive(
+                progress_wrapper,
+                archive_asset,
+                plan.hash_algorithm(),
+                plan.max_bytes_per_sec,
+            ),
+            self.download_optional_checksum(checksum_asset),
+        )
+        .await?;
+        let verification = verify_download(
+            &downloaded_archive.file_name,
+            digest,
+            downloaded_checksum.as_ref(),
+            plan,
+        )?;
+
+        Ok(
+            DownloadedAssets::new(plan.tag.clone(), downloaded_archive, downloaded_checksum)
+                .with_verification(verification),
+        )
+    }
+
+    /// Download the archive of `asset` with at most `max_bytes_per_sec`, if set. Every received chunk is passed through
+    /// the `progress_wrapper`. The digest of the archive is computed with the `hash` algorithm while it is received, if
+    /// it is set.
+    async fn download_archive(
+        &self,
+        progress_wrapper: Box<dyn ReadProgressWrapper>,
+        asset: &GeAsset,
+        hash: Option<Algorithm>,
+        max_bytes_per_sec: Option<u64>,
+    ) -> Result<(DownloadedArchive, Option<Digest>), GithubError> {
+        let mut response = self.download_from_url(&asset.browser_download_url).await?;
+
+        let content_length = response.content_length();
+        let tar_size = content_length.or(asset.size).unwrap_or_default();
+        let mut compressed_archive = HashingWriter::new(Vec::with_capacity(tar_size as usize), hash);
+
+        let file_name = asset.file_name();
+        let mut progress_wrapper = progress_wrapper.init(tar_size, asset);
+        let mut counter = ProgressCounter::new(content_length, progress_wrapper.progress_interval());
+        let mut throttle = AsyncThrottle::new(max_bytes_per_sec);
+        let mut size = 0;
+        while let Some(chunk) = response.chunk().await? {
+            throttle.consume(chunk.len()).await;
+            let mut reader = progress_wrapper.wrap(Box::new(Cursor::new(chunk)));
+            let read = copy_asset(&mut reader, &mut compressed_archive, &file_name)?;
+            counter.advance(read, progress_wrapper.as_mut());
+            size += read;
+        }
+        counter.complete(progress_wrapper.as_mut());
+        check_size(&file_name, asset.size, size)?;
+        progress_wrapper.finish(asset);
+
+        let (compressed_archive, digest) = compressed_archive.finish();
+        let archive = DownloadedArchive::new(compressed_archive, file_name);
+        let archive = match asset.compression() {
+            Some(compression) => archive.with_compression(compression),
+            None => archive,
+        };
+        Ok((archive, digest))
+    }
+
+    /// Stream the archive of `asset` into `sink` with at most `max_bytes_per_sec`, if set. Returns the number of
+    /// written bytes and the content type.
+    async fn stream_archive(
+        &self,
+        progress_wrapper: Box<dyn ReadProgressWrapper>,
+        asset: &GeAsset,
+        max_bytes_per_sec: Option<u64>,
+        sink: &mut dyn Write,
+    ) -> Result<(u64, Option<String>), GithubError> {
+        let mut response = self.download_from_url(&asset.browser_download_url).await?;
+        let content_length = response.content_length();
+        let tar_size = content_length.or(asset.size).unwrap_or_default();
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(String::from);
+
+        let file_name = asset.file_name();
+        let mut progress_wrapper = progress_wrapper.init(tar_size, asset);
+        let mut counter = ProgressCounter::new(content_length, progress_wrapper.progress_interval());
+        let mut throttle = AsyncThrottle::new(max_bytes_per_sec);
+        let mut size = 0;
+        while let Some(chunk) = response.chunk().await? {
+            throttle.consume(chunk.len()).await;
+            let mut reader = progress_wrapper.wrap(Box::new(Cursor::new(chunk)));
+            let copied = copy_asset(&mut reader, sink, &file_name)?;
+            counter.advance(copied, progress_wrapper.as_mut());
+            size += copied;
+        }
+        counter.complete(progress_wrapper.as_mut());
+        check_size(&file_name, asset.size, size)?;
+        progress_wrapper.finish(asset);
+
+        Ok((size, content_type))
+    }
+
+    async fn download_optional_checksum(
+        &self,
+        asset: Option<&GeAsset>,
+    ) -> Result<Option<DownloadedChecksum>, GithubError> {
+        match asset {
+            Some(asset) => self.download_checksum(asset).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    async fn download_checksum(&self, asset: &GeAsset) -> Result<DownloadedChecksum, GithubError> {
+        let checksum_str = self
+            .download_from_url(&asset.browser_download_url)
+            .await?
+            .text()
+            .await?;
+        Ok(DownloadedChecksum::new(checksum_str, asset.file_name()))
+    }
+}
+
+/// Run the `archive` and `checksum` downloads concurrently.
+///
+/// Returns as soon as one of the downloads fails. The other download is dropped and thereby cancelled.
+async fn try_join<A, C>(
+    archive: impl Future<Output = Result<A, GithubError>>,
+    checksum: impl Future<Output = Result<C, GithubError>>,
+) -> Result<(A, C), GithubError> {
+    let mut archive = pin!(archive);
+    let mut checksum = pin!(checksum);
+    let mut archive_output = None;
+    let mut checksum_output = None;
+    poll_fn(|cx| {
+        if archive_output.is_none() {
+            match archive.as_mut().poll(cx) {
+                Poll::Ready(Ok(output)) => archive_output = Some(output),
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => {}
+            }
+        }
+        if checksum_output.is_none() {
+            match checksum.as_mut().poll(cx) {
+                Poll::Ready(Ok(output)) => checksum_output = Some(output),
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => {}
+            }
+        }
+
+        match (archive_output.take(), checksum_output.take()) {
+            (Some(archive), Some(checksum)) => Poll::Ready(Ok((archive, checksum))),
+            (archive, checksum) => {
+                archive_output = archive;
+                checksum_output = checksum;
+                Poll::Pending
+            }
+        }
+    })
+    .await
+}
+
+impl Default for AsyncGeDownloader {
+    fn default() -> Self {
+        AsyncGeDownloader::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    use httpmock::Method::GET;
+    use httpmock::MockServer;
+
+    use crate::download::progress::NoProgress;
+    use crate::download::tests::{
+        download_url, download_url_without_server, mock_url, test_checksum, PROTON_GE_RELEASES, PROTON_GE_RELEASES_URL,
+        PROTON_GE_RELEASE_TAGS_URL, TEST_TAR_GZ, WINE_GE_RELEASE_TAGS_URL, WINE_GE_TAGS, WINE_GE_TAGS_URL,
+    };
+    use crate::error::{ChecksumError, ErrorCategory};
+
+    use super::*;
+
+    /// Progress wrapper that counts the wrapped bytes.
+    struct CountingProgressWrapper {
+        bytes: Arc<AtomicUsize>,
+    }
+
+    struct CountingReader {
+        reader: Box<dyn Read>,
+        bytes: Arc<AtomicUsize>,
+    }
+
+    impl Read for CountingReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let read = self.reader.read(buf)?;
+            self.bytes.fetch_add(read, Ordering::SeqCst);
+            Ok(read)
+        }
+    }
+
+    impl ReadProgressWrapper for CountingProgressWrapper {
+        fn init(self: Box<Self>, _: u64, _: &GeAsset) -> Box<dyn ReadProgressWrapper> {
+            self
+        }
+
+        fn wrap(&self, reader: Box<dyn Read>) -> Box<dyn Read> {
+            Box::new(CountingReader {
+                reader,
+                bytes: Arc::clone(&self.bytes),
+            })
+        }
+
+        fn finish(&self, _: &GeAsset) {}
+    }
+
+    #[tokio::test]
+    async fn fetch_proton_ge_release() {
+        let server = MockServer::start_async().await;
+        let release_mock = server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path(format!("/{}/6.20-GE-1", PROTON_GE_RELEASE_TAGS_URL))
+                    .header("User-Agent", "ge_man-lib");
+                then.status(200)
+                    .header("Content-Type", "application/json")
+                    .body(mock_url(&TagKind::Proton, &server.base_url()));
+            })
+            .await;
+
+        let downloader = AsyncGeDownloader::with_api_url(server.base_url());
+        let release = downloader
+            .fetch_release(Some(String::from("6.20-GE-1")), TagKind::Proton)
+            .await
+            .unwrap();
+
+        release_mock.assert_async().await;
+        assert_eq!(release.tag_name, "6.20-GE-1");
+        assert_eq!(release.tar_asset().unwrap().name, "Proton-6.20-GE-1.tar.gz");
+    }
+
+    #[tokio::test]
+    async fn fetch_proton_ge_release_from_custom_source() {
+        let server = MockServer::start_async().await;
+        let release_mock = server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path("/repos/mirror-org/proton-mirror/releases/tags/6.20-GE-1");
+                then.status(200)
+                    .header("Content-Type", "application/json")
+                    .body(mock_url(&TagKind::Proton, &server.base_url()));
+            })
+            .await;
+
+        let downloader = AsyncGeDownloader::with_api_url(server.base_url())
+            .with_sources([(TagKind::Proton, RepositorySource::new("mirror-org", "proton-mirror"))]);
+        let release = downloader
+            .fetch_release(Some(String::from("6.20-GE-1")), TagKind::Proton)
+            .await
+            .unwrap();
+
+        release_mock.assert_async().await;
+        assert_eq!(release.tag_name, "6.20-GE-1");
+    }
+
+    #[tokio::test]
+    async fn fetch_latest_wine_ge_release() {
+        let server = MockServer::start_async().await;
+        let tags_mock = server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path(format!("/{}", WINE_GE_TAGS_URL))
+                    .query_param("page", "1");
+                then.status(200)
+                    .header("Content-Type", "application/json")
+                    .body_from_file(&*WINE_GE_TAGS);
+            })
+            .await;
+        let release_mock = server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path(format!("/{}/6.20-GE-1", WINE_GE_RELEASE_TAGS_URL));
+                then.status(200)
+                    .header("Content-Type", "application/json")
+                    .body(mock_url(&TagKind::wine(), &server.base_url()));
+            })
+            .await;
+
+        let downloader = AsyncGeDownloader::with_api_url(server.base_url());
+        let release = downloader.fetch_release(None, TagKind::wine()).await.unwrap();
+
+        tags_mock.assert_async().await;
+        release_mock.assert_async().await;
+        assert_eq!(release.tag_name, "6.20-GE-1");
+    }
+
+    #[tokio::test]
+    async fn fetch_proton_ge_releases_page() {
+        let server = MockServer::start_async().await;
+        let releases_mock = server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path(format!("/{}", PROTON_GE_RELEASES_URL))
+                    .query_param("page", "2");
+                then.status(200)
+                    .header("Content-Type", "application/json")
+                    .body_from_file(&*PROTON_GE_RELEASES);
+            })
+            .await;
+
+        let downloader = AsyncGeDownloader::with_api_url(server.base_url());
+        let releases = downloader.fetch_releases(TagKind::Proton, 2).await.unwrap();
+
+        releases_mock.assert_async().await;
+        assert_eq!(releases.len(), 2);
+        assert_eq!(releases[0].tag_name, "GE-Proton7-8");
+    }
+
+    #[tokio::test]
+    async fn fetch_missing_release() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path(format!("/{}/7.0-GE-1", WINE_GE_RELEASE_TAGS_URL));
+                then.status(404);
+            })
+            .await;
+
+        let downloader = AsyncGeDownloader::with_api_url(server.base_url());
+        let err = downloader
+            .fetch_release(Some(String::from("7.0-GE-1")), TagKind::wine())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, GithubError::ReqwestError { .. }));
+        assert_eq!(err.category(), ErrorCategory::NotFound);
+    }
+
+    async fn download_release_assets_test(tag: &str, kind: TagKind, release_url: &str, archive: &str, checksum: &str) {
+        let server = MockServer::start_async().await;
+        let release_mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path(format!("/{}/{}", release_url, tag));
+                then.status(200)
+                    .header("Content-Type", "application/json")
+                    .body(mock_url(&kind, &server.base_url()));
+            })
+            .await;
+        let archive_mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path(download_url_without_server(tag, &kind, archive));
+                then.status(200)
+                    .header("Content-Type", "application/gzip")
+                    .body_from_file(&*TEST_TAR_GZ);
+            })
+            .await;
+        let checksum_mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path(download_url_without_server(tag, &kind, checksum));
+                then.status(200)
+                    .header("Content-Type", "application/octet-stream")
+                    .body(test_checksum(archive));
+            })
+            .await;
+
+        let bytes = Arc::new(AtomicUsize::new(0));
+        let progress_wrapper = CountingProgressWrapper {
+            bytes: Arc::clone(&bytes),
+        };
+        let downloader = AsyncGeDownloader::with_api_url(server.base_url());
+        let request = DownloadRequest::new(Some(String::from(tag)), kind, Box::new(progress_wrapper), true);
+        let assets = downloader.download_release_assets(request).await.unwrap();
+
+        release_mock.assert_async().await;
+        archive_mock.assert_async().await;
+        checksum_mock.assert_async().await;
+
+        let expected_archive = std::fs::read(&*TEST_TAR_GZ).unwrap();
+        assert_eq!(assets.tag, tag);
+        assert_eq!(assets.compressed_archive.compressed_content, expected_archive);
+        assert_eq!(assets.compressed_archive.file_name, archive);
+        assert_eq!(bytes.load(Ordering::SeqCst), expected_archive.len());
+
+        let downloaded_checksum = assets.checksum.unwrap();
+        assert_eq!(downloaded_checksum.checksum, test_checksum(archive));
+        assert_eq!(downloaded_checksum.file_name, checksum);
+    }
+
+    #[tokio::test]
+    async fn download_proton_ge_release_assets() {
+        download_release_assets_test(
+            "6.20-GE-1",
+            TagKind::Proton,
+            PROTON_GE_RELEASE_TAGS_URL,
+            "Proton-6.20-GE-1.tar.gz",
+            "Proton-6.20-GE-1.sha512sum",
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn stream_proton_ge_archive_into_sink() {
+        let tag = "6.20-GE-1";
+        let kind = TagKind::Proton;
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path(format!("/{}/{}", PROTON_GE_RELEASE_TAGS_URL, tag));
+                then.status(200)
+                    .header("Content-Type", "application/json")
+                    .body(mock_url(&kind, &server.base_url()));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path(download_url_without_server(tag, &kind, "Proton-6.20-GE-1.tar.gz"));
+                then.status(200)
+                    .header("Content-Type", "application/gzip")
+                    .body_from_file(&*TEST_TAR_GZ);
+            })
+            .await;
+
+        let bytes = Arc::new(AtomicUsize::new(0));
+        let progress_wrapper = CountingProgressWrapper {
+            bytes: Arc::clone(&bytes),
+        };
+        let downloader = AsyncGeDownloader::with_api_url(server.base_url());
+        let request = DownloadRequest::new(Some(String::from(tag)), kind, Box::new(progress_wrapper), false);
+        let mut sink = Vec::new();
+        let metadata = downloader.download_archive_to(request, &mut sink).await.unwrap();
+
+        let expected_archive = std::fs::read(&*TEST_TAR_GZ).unwrap();
+        assert_eq!(sink, expected_archive);
+        assert_eq!(metadata.size, expected_archive.len() as u64);
+        assert_eq!(metadata.file_name, "Proton-6.20-GE-1.tar.gz");
+        assert_eq!(metadata.content_type.as_deref(), Some("application/gzip"));
+        assert!(metadata.checksum.is_none());
+        assert_eq!(bytes.load(Ordering::SeqCst), expected_archive.len());
+    }
+
+    #[tokio::test]
+    async fn download_release_assets_with_wrong_checksum() {
+        let tag = "6.20-GE-1";
+        let kind = TagKind::Proton;
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path(format!("/{}/{}", PROTON_GE_RELEASE_TAGS_URL, tag));
+                then.status(200)
+                    .header("Content-Type", "application/json")
+                    .body(mock_url(&kind, &server.base_url()));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path(download_url_without_server(tag, &kind, "Proton-6.20-GE-1.tar.gz"));
+                then.status(200).body_from_file(&*TEST_TAR_GZ);
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path(download_url_without_server(tag, &kind, "Proton-6.20-GE-1.sha512sum"));
+                then.status(200)
+                    .body(format!("{}  Proton-6.20-GE-1.tar.gz\n", "0".repeat(128)));
+            })
+            .await;
+
+        let downloader = AsyncGeDownloader::with_api_url(server.base_url());
+        let request = DownloadRequest::new(Some(String::from(tag)), kind, Box::new(NoProgress), true);
+        let err = downloader.download_release_assets(request).await.err().unwrap();
+
+        assert!(matches!(
+            err,
+            GithubError::VerificationFailed {
+                ref file_name,
+                source: ChecksumError::Mismatch { .. },
+            } if file_name == "Proton-6.20-GE-1.tar.gz"
+        ));
+    }
+
+    #[tokio::test]
+    async fn limit_download_rate() {
+        let tag = "6.20-GE-1";
+        let kind = TagKind::Proton;
+        let archive = vec![0; 300_000];
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path(format!("/{}/{}", PROTON_GE_RELEASE_TAGS_URL, tag));
+                then.status(200).json_body(serde_json::json!({
+                    "tag_name": tag,
+                    "assets": [{
+                        "name": "Proton-6.20-GE-1.tar.gz",
+                        "content_type": "application/gzip",
+                        "browser_download_url": download_url(Some(&server.base_url()), tag, &kind, "Proton-6.20-GE-1.tar.gz"),
+                        "size": archive.len(),
+                    }],
+                }));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path(download_url_without_server(tag, &kind, "Proton-6.20-GE-1.tar.gz"));
+                then.status(200).body(&archive);
+            })
+            .await;
+
+        let bytes = Arc::new(AtomicUsize::new(0));
+        let progress_wrapper = CountingProgressWrapper {
+            bytes: Arc::clone(&bytes),
+        };
+        let downloader = AsyncGeDownloader::with_api_url(server.base_url());
+        let request = DownloadRequest::new(Some(String::from(tag)), kind, Box::new(progress_wrapper), false)
+            .with_max_bytes_per_sec(Some(200_000));
+
+        let started = Instant::now();
+        let mut sink = Vec::new();
+        let metadata = downloader.download_archive_to(request, &mut sink).await.unwrap();
+        let elapsed = started.elapsed();
+
+        assert_eq!(metadata.size, 300_000);
+        assert_eq!(bytes.load(Ordering::SeqCst), 300_000);
+        assert!(elapsed >= Duration::from_millis(1200), "download took {:?}", elapsed);
+        assert!(elapsed < Duration::from_secs(5), "download took {:?}", elapsed);
+    }
+
+    #[tokio::test]
+    async fn download_wine_ge_release_assets() {
+        download_release_assets_test(
+            "6.16-GE-3-LoL",
+            TagKind::lol(),
+            WINE_GE_RELEASE_TAGS_URL,
+            "wine-lutris-ge-6.16-3-lol-x86_64.tar.gz",
+            "wine-lutris-ge-6.16-3-lol-x86_64.sha512sum",
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn cancel_archive_download_when_checksum_fails() {
+        let tag = "6.20-GE-1";
+        let kind = TagKind::Proton;
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path(format!("/{}/{}", PROTON_GE_RELEASE_TAGS_URL, tag));
+                then.status(200)
+                    .header("Content-Type", "application/json")
+                    .body(mock_url(&kind, &server.base_url()));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path(download_url_without_server(tag, &kind, "Proton-6.20-GE-1.tar.gz"));
+                then.status(200)
+                    .delay(Duration::from_secs(5))
+                    .body_from_file(&*TEST_TAR_GZ);
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path(download_url_without_server(tag, &kind, "Proton-6.20-GE-1.sha512sum"));
+                then.status(404);
+            })
+            .await;
+
+        let bytes = Arc::new(AtomicUsize::new(0));
+        let progress_wrapper = CountingProgressWrapper { bytes };
+        let downloader = AsyncGeDownloader::with_api_url(server.base_url());
+        let request = DownloadRequest::new(Some(String::from(tag)), kind, Box::new(progress_wrapper), true);
+        let start = Instant::now();
+        let err = downloader.download_release_assets(request).await.err().unwrap();
+
+        assert!(start.elapsed() < Duration::from_secs(4));
+        assert_eq!(err.category(), ErrorCategory::NotFound);
+    }
+}
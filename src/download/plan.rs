@@ -0,0 +1,320 @@
+//! Resolved download information for a GE version release.
+//!
+//! A `DownloadPlan` contains everything that is known about a download before any asset bytes are transferred. This
+//! allows a consumer to present the download to a user (e.g. for confirmation) before executing it with
+//! `GeDownload::execute`.
+use serde::{Deserialize, Serialize};
+
+use crate::digest::{Algorithm, AssetDigest};
+use crate::download::response::{GeAsset, GeRelease};
+use crate::error::GithubError;
+use crate::tag::TagKind;
+
+/// An asset which is going to be downloaded.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PlannedAsset {
+    /// File name of the asset. This name is also used for the downloaded file.
+    pub name: String,
+    /// URL the asset is downloaded from.
+    pub url: String,
+    /// Content type of the asset as reported by GitHub.
+    pub content_type: String,
+    /// Size of the asset in bytes.
+    pub size: Option<u64>,
+}
+
+impl PlannedAsset {
+    fn matches(&self, asset: &GeAsset) -> bool {
+        self.name.eq(&asset.name) && self.url.eq(&asset.browser_download_url)
+    }
+}
+
+impl From<&GeAsset> for PlannedAsset {
+    fn from(asset: &GeAsset) -> Self {
+        PlannedAsset {
+            name: asset.name.clone(),
+            url: asset.browser_download_url.clone(),
+            content_type: asset.content_type.clone(),
+            size: asset.size,
+        }
+    }
+}
+
+/// The resolved assets of a download request.
+///
+/// A plan is created with `GeDownload::resolve` and can be executed with `GeDownload::execute`. This struct supports
+/// `serde`'s serialization and deserialization traits, so a plan can be resolved and executed by different processes.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DownloadPlan {
+    /// Tag name of the release.
+    pub tag: String,
+    /// The GE version kind of the release.
+    pub kind: TagKind,
+    /// The compressed archive to download.
+    pub archive: PlannedAsset,
+    /// The checksum file to download.
+    ///
+    /// This is `None` if the checksum was not requested or if the release provides no checksum file.
+    pub checksum: Option<PlannedAsset>,
+    /// Does the release provide a checksum file.
+    pub checksum_available: bool,
+    /// Should the archive be verified with the checksum while it is downloaded. This has no effect if the plan
+    /// contains no checksum.
+    ///
+    /// Plans that were serialized without this field are verified.
+    #[serde(default = "verify_by_default")]
+    pub verify: bool,
+    /// Maximum download rate of the archive in bytes per second. `None` does not limit the rate.
+    #[serde(default)]
+    pub max_bytes_per_sec: Option<u64>,
+    /// Digest of the archive as reported by the GitHub API, e.g. `sha256:<hex>`.
+    ///
+    /// It is only set if the checksum was requested, but the release provides no checksum file. The archive is then
+    /// verified with this digest instead.
+    #[serde(default)]
+    pub fallback_digest: Option<String>,
+}
+
+fn verify_by_default() -> bool {
+    true
+}
+
+impl DownloadPlan {
+    /// Create a plan for the assets of a `release`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns `GithubError::MissingArchiveAsset` if the `release` contains no archive asset.
+    pub fn from_release(release: &GeRelease, kind: TagKind, download_checksum: bool) -> Result<Self, GithubError> {
+        let archive = release.tar_asset()?;
+        let checksum = release.find_checksum_asset();
+        let fallback_digest = archive
+            .digest
+            .clone()
+            .filter(|_| download_checksum && checksum.is_none());
+
+        Ok(DownloadPlan {
+            tag: release.tag_name.clone(),
+            kind,
+            archive: PlannedAsset::from(archive),
+            checksum: checksum.filter(|_| download_checksum).map(PlannedAsset::from),
+            checksum_available: checksum.is_some(),
+            verify: true,
+            max_bytes_per_sec: None,
+            fallback_digest,
+        })
+    }
+
+    /// Set if the archive should be verified with the checksum.
+    pub fn with_verify(mut self, verify: bool) -> Self {
+        self.verify = verify;
+        self
+    }
+
+    /// Set the maximum download rate of the archive in bytes per second. `None` does not limit the rate.
+    pub fn with_max_bytes_per_sec(mut self, max_bytes_per_sec: Option<u64>) -> Self {
+        self.max_bytes_per_sec = max_bytes_per_sec;
+        self
+    }
+
+    /// Get the parsed `fallback_digest`, if the archive can be verified with it.
+    pub(crate) fn expected_digest(&self) -> Option<AssetDigest> {
+        self.fallback_digest.as_deref().and_then(AssetDigest::parse)
+    }
+
+    /// Algorithm of the digest that is computed while the archive is downloaded, or `None` if the downloaded archive
+    /// is not verified.
+    ///
+    /// The archive is verified with the checksum file if the plan contains one and with the `fallback_digest`
+    /// otherwise. The algorithm of the checksum file is detected from its name, see
+    /// `Algorithm::from_checksum_file_name`. If the name contains no known algorithm, the archive is hashed with
+    /// SHA-512 and its verification fails with `ChecksumError::UnknownAlgorithm`.
+    pub(crate) fn hash_algorithm(&self) -> Option<Algorithm> {
+        if !self.verify {
+            None
+        } else if let Some(checksum) = &self.checksum {
+            Some(Algorithm::from_checksum_file_name(&checksum.name).unwrap_or(Algorithm::Sha512))
+        } else {
+            self.expected_digest().map(|digest| digest.algorithm())
+        }
+    }
+
+    /// Total size of all assets in this plan in bytes.
+    ///
+    /// Returns `None` if the size of any asset is unknown.
+    pub fn total_size(&self) -> Option<u64> {
+        let checksum_size = match &self.checksum {
+            Some(checksum) => checksum.size?,
+            None => 0,
+        };
+        self.archive.size.map(|size| size + checksum_size)
+    }
+
+    /// Get the assets of `release` that correspond to the assets of this plan.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the `release` does not contain an asset of this plan anymore or if the asset
+    /// now has a different download URL.
+    pub(crate) fn validate<'a>(
+        &self,
+        release: &'a GeRelease,
+    ) -> Result<(&'a GeAsset, Option<&'a GeAsset>), GithubError> {
+        let archive = self.find_planned_asset(release, &self.archive)?;
+        let checksum = match &self.checksum {
+            Some(checksum) => Some(self.find_planned_asset(release, checksum)?),
+            None => None,
+        };
+        Ok((archive, checksum))
+    }
+
+    fn find_planned_asset<'a>(
+        &self,
+        release: &'a GeRelease,
+        planned: &PlannedAsset,
+    ) -> Result<&'a GeAsset, GithubError> {
+        release
+            .assets
+            .iter()
+            .find(|asset| planned.matches(asset))
+            .ok_or_else(|| GithubError::OutdatedDownloadPlan {
+                tag: self.tag.clone(),
+                asset: planned.name.clone(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use crate::download::mime::{APPLICATION_GZIP, APPLICATION_OCTET_STREAM};
+
+    use super::*;
+
+    fn release() -> GeRelease {
+        let mut archive = GeAsset::new(
+            "GE-Proton7-8.tar.gz",
+            APPLICATION_GZIP,
+            "https://host/GE-Proton7-8.tar.gz",
+        );
+        archive.size = Some(401383591);
+        let mut checksum = GeAsset::new(
+            "GE-Proton7-8.sha512sum",
+            APPLICATION_OCTET_STREAM,
+            "https://host/GE-Proton7-8.sha512sum",
+        );
+        checksum.size = Some(151);
+        GeRelease::new(String::from("GE-Proton7-8"), vec![checksum, archive])
+    }
+
+    #[test]
+    fn create_plan_with_checksum() {
+        let plan = DownloadPlan::from_release(&release(), TagKind::Proton, true).unwrap();
+
+        assert_eq!(plan.tag, "GE-Proton7-8");
+        assert_eq!(plan.kind, TagKind::Proton);
+        assert_eq!(plan.archive.name, "GE-Proton7-8.tar.gz");
+        assert_eq!(plan.archive.url, "https://host/GE-Proton7-8.tar.gz");
+        assert_eq!(plan.archive.size, Some(401383591));
+        assert_eq!(plan.checksum.as_ref().unwrap().name, "GE-Proton7-8.sha512sum");
+        assert!(plan.checksum_available);
+        assert_eq!(plan.total_size(), Some(401383742));
+    }
+
+    #[test]
+    fn create_plan_without_checksum() {
+        let plan = DownloadPlan::from_release(&release(), TagKind::Proton, false).unwrap();
+
+        assert_eq!(plan.checksum, None);
+        assert!(plan.checksum_available);
+        assert_eq!(plan.total_size(), Some(401383591));
+    }
+
+    #[test]
+    fn create_plan_for_release_without_checksum_asset() {
+        let mut release = release();
+        release.assets.retain(|asset| asset.name.ends_with(".tar.gz"));
+
+        let plan = DownloadPlan::from_release(&release, TagKind::Proton, true).unwrap();
+        assert_eq!(plan.checksum, None);
+        assert!(!plan.checksum_available);
+    }
+
+    const SHA256_DIGEST: &str = "sha256:ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    #[test_case(true, true, true => (false, Some(Algorithm::Sha512)); "checksum file available")]
+    #[test_case(false, true, true => (true, Some(Algorithm::Sha256)); "asset digest without checksum file")]
+    #[test_case(false, false, true => (false, None); "checksum not requested")]
+    #[test_case(false, true, false => (true, None); "verification disabled")]
+    fn plan_fallback_digest(with_checksum: bool, download_checksum: bool, verify: bool) -> (bool, Option<Algorithm>) {
+        let mut release = release();
+        release
+            .assets
+            .retain(|asset| with_checksum || asset.name.ends_with(".tar.gz"));
+        for asset in &mut release.assets {
+            asset.digest = Some(String::from(SHA256_DIGEST));
+        }
+
+        let plan = DownloadPlan::from_release(&release, TagKind::Proton, download_checksum)
+            .unwrap()
+            .with_verify(verify);
+        (plan.fallback_digest.is_some(), plan.hash_algorithm())
+    }
+
+    #[test]
+    fn create_plan_for_release_without_archive_asset() {
+        let mut release = release();
+        release.assets.retain(|asset| asset.name.ends_with(".sha512sum"));
+
+        let result = DownloadPlan::from_release(&release, TagKind::Proton, true);
+        assert!(matches!(result, Err(GithubError::MissingArchiveAsset { tag }) if tag == release.tag_name));
+    }
+
+    #[test]
+    fn serialization_round_trip() {
+        let plan = DownloadPlan::from_release(&release(), TagKind::lol(), true).unwrap();
+
+        let json = serde_json::to_string(&plan).unwrap();
+        let deserialized: DownloadPlan = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized, plan);
+    }
+
+    #[test]
+    fn verify_plan_without_verify_field() {
+        let plan = DownloadPlan::from_release(&release(), TagKind::Proton, true)
+            .unwrap()
+            .with_verify(false);
+        let mut json = serde_json::to_value(&plan).unwrap();
+        json.as_object_mut().unwrap().remove("verify");
+
+        let deserialized: DownloadPlan = serde_json::from_value(json).unwrap();
+        assert!(deserialized.verify);
+    }
+
+    #[test]
+    fn unlimited_plan_without_rate_limit_field() {
+        let plan = DownloadPlan::from_release(&release(), TagKind::Proton, true)
+            .unwrap()
+            .with_max_bytes_per_sec(Some(1_000));
+        let mut json = serde_json::to_value(&plan).unwrap();
+        json.as_object_mut().unwrap().remove("max_bytes_per_sec");
+
+        let deserialized: DownloadPlan = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized.max_bytes_per_sec, None);
+    }
+
+    #[test]
+    fn validate_plan_against_changed_release() {
+        let plan = DownloadPlan::from_release(&release(), TagKind::Proton, true).unwrap();
+        let mut release = release();
+        release.assets[1].browser_download_url = String::from("https://host/moved.tar.gz");
+
+        let result = plan.validate(&release);
+        assert!(matches!(
+            result,
+            Err(GithubError::OutdatedDownloadPlan { tag, asset }) if tag == "GE-Proton7-8" && asset == "GE-Proton7-8.tar.gz"
+        ));
+    }
+}
@@ -0,0 +1,264 @@
+//! Progress information of an asset download.
+//!
+//! A `ReadProgressWrapper` receives a `DownloadProgress` through `ReadProgressWrapper::update` while an archive is
+//! downloaded. The progress contains the number of bytes that were read so far, the total size of the archive and the
+//! time since the download started, which is enough to calculate the download rate and the remaining time.
+use std::io::Read;
+use std::time::{Duration, Instant};
+
+use crate::download::response::GeAsset;
+use crate::download::ReadProgressWrapper;
+
+/// Progress of an asset download.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DownloadProgress {
+    /// Number of bytes that were read so far.
+    pub bytes_read: u64,
+    /// Size of the asset in bytes as reported by the `Content-Length` header of the download response.
+    ///
+    /// This is `None` if the server did not send a `Content-Length` header.
+    pub total_bytes: Option<u64>,
+    /// Time since the download started. It is measured with a monotonic clock.
+    pub elapsed: Duration,
+}
+
+impl DownloadProgress {
+    /// Get the average download rate in bytes per second.
+    ///
+    /// Returns `None` if no time has elapsed yet.
+    pub fn bytes_per_second(&self) -> Option<f64> {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 {
+            Some(self.bytes_read as f64 / seconds)
+        } else {
+            None
+        }
+    }
+}
+
+/// A `ReadProgressWrapper` that does not track the progress of a download.
+///
+/// This is the progress wrapper of a `DownloadRequest` created with `DownloadRequest::builder` if no other wrapper is
+/// set.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct NoProgress;
+
+impl ReadProgressWrapper for NoProgress {
+    fn init(self: Box<Self>, _len: u64, _asset: &GeAsset) -> Box<dyn ReadProgressWrapper> {
+        self
+    }
+
+    fn wrap(&self, reader: Box<dyn Read>) -> Box<dyn Read> {
+        reader
+    }
+
+    fn finish(&self, _asset: &GeAsset) {}
+}
+
+/// Counts the bytes of a download and reports them to a `ReadProgressWrapper`.
+///
+/// The first read bytes are reported immediately. Further updates are reported once the `interval` of the wrapper has
+/// passed since the last update. When the download is complete, the final number of bytes is always reported.
+pub(crate) struct ProgressCounter {
+    started: Instant,
+    interval: Duration,
+    total_bytes: Option<u64>,
+    bytes_read: u64,
+    last_update: Option<(Instant, u64)>,
+}
+
+impl ProgressCounter {
+    pub(crate) fn new(total_bytes: Option<u64>, interval: Duration) -> Self {
+        ProgressCounter {
+            started: Instant::now(),
+            interval,
+            total_bytes,
+            bytes_read: 0,
+            last_update: None,
+        }
+    }
+
+    /// Count `read` bytes and report the progress if the interval has passed.
+    pub(crate) fn advance(&mut self, read: u64, progress_wrapper: &mut dyn ReadProgressWrapper) {
+        self.bytes_read += read;
+        let now = Instant::now();
+        let is_due = match self.last_update {
+            Some((last, _)) => now.duration_since(last) >= self.interval,
+            None => true,
+        };
+        if is_due {
+            self.report(now, progress_wrapper);
+        }
+    }
+
+    /// Report the final progress, unless it was already reported.
+    pub(crate) fn complete(&mut self, progress_wrapper: &mut dyn ReadProgressWrapper) {
+        let is_reported = matches!(self.last_update, Some((_, bytes)) if bytes == self.bytes_read);
+        if !is_reported {
+            self.report(Instant::now(), progress_wrapper);
+        }
+    }
+
+    fn report(&mut self, now: Instant, progress_wrapper: &mut dyn ReadProgressWrapper) {
+        self.last_update = Some((now, self.bytes_read));
+        progress_wrapper.update(DownloadProgress {
+            bytes_read: self.bytes_read,
+            total_bytes: self.total_bytes,
+            elapsed: now.duration_since(self.started),
+        });
+    }
+}
+
+/// Reader that reports the progress of every read to a `ReadProgressWrapper`.
+pub(crate) struct ProgressReader<'a> {
+    reader: Box<dyn Read>,
+    counter: ProgressCounter,
+    progress_wrapper: &'a mut dyn ReadProgressWrapper,
+}
+
+impl<'a> ProgressReader<'a> {
+    pub(crate) fn new(
+        reader: Box<dyn Read>,
+        total_bytes: Option<u64>,
+        progress_wrapper: &'a mut dyn ReadProgressWrapper,
+    ) -> Self {
+        let counter = ProgressCounter::new(total_bytes, progress_wrapper.progress_interval());
+        ProgressReader {
+            reader,
+            counter,
+            progress_wrapper,
+        }
+    }
+}
+
+impl Read for ProgressReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.reader.read(buf)?;
+        if read == 0 && !buf.is_empty() {
+            self.counter.complete(self.progress_wrapper);
+        } else {
+            self.counter.advance(read as u64, self.progress_wrapper);
+        }
+        Ok(read)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    /// Progress wrapper that records every update.
+    struct RecordingProgressWrapper {
+        interval: Duration,
+        updates: Vec<DownloadProgress>,
+    }
+
+    impl RecordingProgressWrapper {
+        fn new(interval: Duration) -> Self {
+            RecordingProgressWrapper {
+                interval,
+                updates: Vec::new(),
+            }
+        }
+
+        fn bytes_read(&self) -> Vec<u64> {
+            self.updates.iter().map(|progress| progress.bytes_read).collect()
+        }
+    }
+
+    impl ReadProgressWrapper for RecordingProgressWrapper {
+        fn init(self: Box<Self>, _: u64, _: &GeAsset) -> Box<dyn ReadProgressWrapper> {
+            self
+        }
+
+        fn wrap(&self, reader: Box<dyn Read>) -> Box<dyn Read> {
+            reader
+        }
+
+        fn finish(&self, _: &GeAsset) {}
+
+        fn update(&mut self, progress: DownloadProgress) {
+            self.updates.push(progress);
+        }
+
+        fn progress_interval(&self) -> Duration {
+            self.interval
+        }
+    }
+
+    #[test]
+    fn report_every_read_without_interval() {
+        let mut progress_wrapper = RecordingProgressWrapper::new(Duration::ZERO);
+        let mut counter = ProgressCounter::new(Some(6), Duration::ZERO);
+        counter.advance(2, &mut progress_wrapper);
+        counter.advance(4, &mut progress_wrapper);
+        counter.complete(&mut progress_wrapper);
+
+        assert_eq!(progress_wrapper.bytes_read(), vec![2, 6]);
+        assert!(progress_wrapper
+            .updates
+            .iter()
+            .all(|progress| progress.total_bytes == Some(6)));
+    }
+
+    #[test]
+    fn report_first_and_final_progress_within_interval() {
+        let mut progress_wrapper = RecordingProgressWrapper::new(Duration::from_secs(3600));
+        let mut counter = ProgressCounter::new(None, Duration::from_secs(3600));
+        counter.advance(2, &mut progress_wrapper);
+        counter.advance(4, &mut progress_wrapper);
+        counter.advance(1, &mut progress_wrapper);
+        counter.complete(&mut progress_wrapper);
+
+        assert_eq!(progress_wrapper.bytes_read(), vec![2, 7]);
+        assert!(progress_wrapper
+            .updates
+            .iter()
+            .all(|progress| progress.total_bytes.is_none()));
+    }
+
+    #[test]
+    fn report_empty_download() {
+        let mut progress_wrapper = RecordingProgressWrapper::new(Duration::ZERO);
+        let mut counter = ProgressCounter::new(Some(0), Duration::ZERO);
+        counter.complete(&mut progress_wrapper);
+
+        assert_eq!(progress_wrapper.bytes_read(), vec![0]);
+    }
+
+    #[test]
+    fn progress_reader_reports_final_progress_at_end_of_file() {
+        let content = vec![7; 10_000];
+        let mut progress_wrapper = RecordingProgressWrapper::new(Duration::from_secs(3600));
+
+        let mut read = Vec::new();
+        ProgressReader::new(Box::new(Cursor::new(content.clone())), None, &mut progress_wrapper)
+            .read_to_end(&mut read)
+            .unwrap();
+
+        assert_eq!(read, content);
+        assert_eq!(progress_wrapper.updates.last().unwrap().bytes_read, 10_000);
+        let elapsed: Vec<Duration> = progress_wrapper.updates.iter().map(|p| p.elapsed).collect();
+        assert!(elapsed.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn bytes_per_second() {
+        let progress = DownloadProgress {
+            bytes_read: 500,
+            total_bytes: None,
+            elapsed: Duration::from_millis(250),
+        };
+        assert_eq!(progress.bytes_per_second(), Some(2000.0));
+        assert_eq!(
+            DownloadProgress {
+                elapsed: Duration::ZERO,
+                ..progress
+            }
+            .bytes_per_second(),
+            None
+        );
+    }
+}
@@ -1,38 +1,185 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use percent_encoding::percent_decode_str;
 use serde::Deserialize;
 
+use crate::archive;
+use crate::archive::{Compression, ExtractOptions, ExtractedTool};
+use crate::digest;
+use crate::digest::{Algorithm, AssetDigest, ChecksumFile, Digest};
+use crate::error::{ChecksumError, ExtractError, GithubError, TagParseError};
+use crate::tag::{Tag, TagKind, WineTagKind};
+
+/// File extensions of the archive assets of a release.
+const TAR_EXTENSIONS: [&str; 3] = [".tar.gz", ".tar.xz", ".tar.zst"];
+const CHECKSUM_EXTENSIONS: [&str; 2] = [".sha512sum", ".sha256sum"];
+
 /// The compressed archive of the compatibility tool and file name.
 ///
 /// For GE Proton the archive is provided as a `tar.gz` file.<br>
 /// For Wine GE the archive is provide as a `tar.xz` file.
+#[derive(Clone, PartialEq, Eq)]
 pub struct DownloadedArchive {
     pub compressed_content: Vec<u8>,
     pub file_name: String,
+    /// Compression format of the archive, see `GeAsset::compression`.
+    ///
+    /// The compression is `None` if it could not be determined from the asset the archive was downloaded from.
+    pub compression: Option<Compression>,
 }
 
 impl DownloadedArchive {
+    /// Create a downloaded archive whose compression format is determined by its `file_name`.
     pub fn new(compressed_content: Vec<u8>, file_name: String) -> Self {
         DownloadedArchive {
+            compression: Compression::from_file_name(&file_name),
             compressed_content,
             file_name,
         }
     }
+
+    /// Set the compression format of the archive.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Extract the archive into the directory `dest`, e.g. Steam's `compatibilitytools.d` directory.
+    ///
+    /// The archive is decompressed with its `compression`, or with the format detected from the content of the archive
+    /// if the `compression` is unknown. Permissions and symbolic links are preserved. See
+    /// `archive::extract_with_compression` for how malicious archives and failed extractions are handled.
+    ///
+    /// # Errors
+    ///
+    /// * `ExtractError::UnknownCompression` if the archive is not a gzip, xz or zstd compressed archive
+    /// * The errors of `archive::extract_with_compression`
+    pub fn extract_to(&self, dest: &Path) -> Result<ExtractedTool, ExtractError> {
+        let compression = self
+            .compression
+            .or_else(|| Compression::from_magic_bytes(&self.compressed_content))
+            .ok_or_else(|| ExtractError::UnknownCompression {
+                file_name: self.file_name.clone(),
+            })?;
+        let path = archive::extract_with_compression(
+            compression,
+            self.compressed_content.as_slice(),
+            dest,
+            &ExtractOptions::default(),
+        )?;
+        Ok(ExtractedTool { path, compression })
+    }
+
+    /// Write the compressed archive into the directory `dir` under its `file_name` and return the path of the written
+    /// file. An existing file with the same name is overwritten.
+    ///
+    /// # Errors
+    ///
+    /// * `io::ErrorKind::InvalidInput` if the `file_name` is empty, `.`, `..` or contains a path separator, e.g.
+    ///   `../evil.tar.gz`
+    /// * The errors of `fs::write`
+    pub fn write_to_dir(&self, dir: &Path) -> io::Result<PathBuf> {
+        let is_plain_name =
+            !matches!(self.file_name.as_str(), "" | "." | "..") && !self.file_name.contains(['/', '\\']);
+        if !is_plain_name {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("the archive file name {:?} is not a plain file name", self.file_name),
+            ));
+        }
+
+        let path = dir.join(&self.file_name);
+        fs::write(&path, &self.compressed_content)?;
+        Ok(path)
+    }
+
+    /// Get the lowercase hex representation of the SHA-512 digest of the compressed archive.
+    pub fn sha512(&self) -> String {
+        digest::sha512(&self.compressed_content).to_hex()
+    }
+
+    /// Does the compressed archive match the `checksum` file?
+    ///
+    /// The digest and the file name are checked like in `DownloadedAssets::verify_checksum`. A malformed checksum file
+    /// does not match any archive.
+    pub fn matches_checksum(&self, checksum: &DownloadedChecksum) -> bool {
+        checksum.verify(&self.file_name, &self.compressed_content).is_ok()
+    }
 }
 
 /// The expected checksum of a compatibility tool and the checksum file name.
 ///
-/// The checksum is provided as a `sha512sum` file.
+/// The checksum is provided as a `sha512sum` or `sha256sum` file.
+#[derive(Clone, PartialEq, Eq)]
 pub struct DownloadedChecksum {
     pub checksum: String,
     pub file_name: String,
+    /// Algorithm of the checksum file. `DownloadedChecksum::new` detects it from the `file_name` with
+    /// `Algorithm::from_checksum_file_name`. It is `None` if the file name contains no known algorithm.
+    pub algorithm: Option<Algorithm>,
 }
 
 impl DownloadedChecksum {
     pub fn new(checksum: String, file_name: String) -> Self {
-        DownloadedChecksum { checksum, file_name }
+        let algorithm = Algorithm::from_checksum_file_name(&file_name);
+        DownloadedChecksum {
+            checksum,
+            file_name,
+            algorithm,
+        }
+    }
+
+    /// Set the algorithm of the checksum file, e.g. if it can not be detected from its file name.
+    pub fn with_algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = Some(algorithm);
+        self
+    }
+
+    /// Parse the content of the checksum file with its `algorithm`.
+    ///
+    /// # Errors
+    ///
+    /// * `ChecksumError::UnknownAlgorithm` if the `algorithm` is `None`
+    /// * `ChecksumError::MalformedChecksumFile` if `checksum` is not in the format of a checksum file of the
+    ///   `algorithm`, see `ChecksumFile::parse_with`
+    pub fn parsed(&self) -> Result<ChecksumFile, ChecksumError> {
+        Ok(ChecksumFile::parse_with(&self.checksum, self.known_algorithm()?)?)
+    }
+
+    /// Verify the `content` of the archive `file_name` with this checksum.
+    ///
+    /// The digest of the `content` is computed with the `algorithm` of the checksum. See `archive::verify_named` for
+    /// how the file name is checked.
+    ///
+    /// # Errors
+    ///
+    /// * `ChecksumError::UnknownAlgorithm` if the `algorithm` is `None`
+    /// * The errors of `archive::verify_named` if the archive does not match the checksum file
+    pub fn verify(&self, file_name: &str, content: &[u8]) -> Result<(), ChecksumError> {
+        let algorithm = self.known_algorithm()?;
+        self.verify_digest(file_name, &digest::digest(algorithm, content))
+    }
+
+    /// Verify the `digest` of the archive `file_name`, which was computed with the `algorithm` of this checksum.
+    pub(crate) fn verify_digest(&self, file_name: &str, digest: &Digest) -> Result<(), ChecksumError> {
+        let checksum_file = ChecksumFile::parse_with(&self.checksum, self.known_algorithm()?)?;
+        archive::verify_checksum_file(file_name, digest, &checksum_file)
+    }
+
+    fn known_algorithm(&self) -> Result<Algorithm, ChecksumError> {
+        self.algorithm.ok_or_else(|| ChecksumError::UnknownAlgorithm {
+            file_name: self.file_name.clone(),
+        })
     }
 }
 
 /// Assets of a GE Proton or Wine GE release.
+#[derive(Clone, PartialEq, Eq)]
 pub struct DownloadedAssets {
     /// Tag name of the release.
     pub tag: String,
@@ -43,6 +190,8 @@ pub struct DownloadedAssets {
     /// The `checksum` for a archive can be `None` if `download_checksum` in `DownloadRequest` is
     /// set to false.
     pub checksum: Option<DownloadedChecksum>,
+    /// How the compressed archive was verified while it was downloaded.
+    pub verification: Verification,
 }
 
 impl DownloadedAssets {
@@ -51,55 +200,401 @@ impl DownloadedAssets {
             tag,
             compressed_archive,
             checksum,
+            verification: Verification::Unverified,
+        }
+    }
+
+    /// Set how the compressed archive was verified.
+    pub fn with_verification(mut self, verification: Verification) -> Self {
+        self.verification = verification;
+        self
+    }
+
+    /// Verify the compressed archive with the downloaded checksum.
+    ///
+    /// The digest of the archive must match the digest in the checksum file and the file name in the checksum file must
+    /// be the file name of the archive. The digest is computed with the algorithm of the checksum file, see
+    /// `DownloadedChecksum::verify`.
+    ///
+    /// # Errors
+    ///
+    /// * `ChecksumError::MissingChecksum` if no checksum was downloaded
+    /// * The errors of `DownloadedChecksum::verify` if the archive does not match the checksum file
+    pub fn verify_checksum(&self) -> Result<(), ChecksumError> {
+        let checksum = self
+            .checksum
+            .as_ref()
+            .ok_or_else(|| ChecksumError::MissingChecksum { tag: self.tag.clone() })?;
+        checksum.verify(
+            &self.compressed_archive.file_name,
+            &self.compressed_archive.compressed_content,
+        )
+    }
+}
+
+/// Metadata of an archive that was streamed into a caller-provided sink.
+///
+/// The archive content itself is not part of this struct, it was written into the sink passed to
+/// `GeDownload::download_archive_to`.
+pub struct ArchiveMetadata {
+    /// Tag name of the release.
+    pub tag: String,
+    /// File name of the archive.
+    pub file_name: String,
+    /// Number of bytes that were written into the sink.
+    ///
+    /// For a resumed download this is the size of the whole file, including the bytes that existed before.
+    pub size: u64,
+    /// Value of the `Content-Type` header of the download response.
+    pub content_type: Option<String>,
+    /// The checksum of the archive.
+    ///
+    /// Checksum files are small, so they are downloaded into memory. The `checksum` is `None` if `download_checksum`
+    /// in `DownloadRequest` is set to false.
+    pub checksum: Option<DownloadedChecksum>,
+    /// Number of bytes of a partial file the download was resumed from with
+    /// `GeDownload::resume_archive_download`.
+    ///
+    /// The `resumed_from` is `None` if the whole archive was downloaded.
+    pub resumed_from: Option<u64>,
+    /// How the archive was verified.
+    pub verification: Verification,
+}
+
+/// Paths of the assets that were downloaded into a directory with `GeDownload::download_release_assets_to_dir`.
+pub struct DownloadedAssetPaths {
+    /// Tag name of the release.
+    pub tag: String,
+    /// Path of the compressed archive.
+    pub archive: PathBuf,
+    /// Path of the checksum file.
+    ///
+    /// The `checksum` is `None` if `download_checksum` in `DownloadRequest` is set to false.
+    pub checksum: Option<PathBuf>,
+    /// How the archive was verified.
+    pub verification: Verification,
+}
+
+/// How a downloaded archive was verified.
+///
+/// An archive is verified with the checksum file of its release, a `sha512sum` or `sha256sum` file whose algorithm is
+/// detected from its file name. If the release has no checksum file, the digest GitHub reports for the archive asset
+/// is used instead, see `GeAsset::digest`. A mismatch of either fails the download with
+/// `GithubError::VerificationFailed`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Verification {
+    /// The archive was not verified. Either the verification or the checksum was not requested, or the release
+    /// provides neither a checksum file nor an asset digest.
+    #[default]
+    Unverified,
+    /// The archive matches the checksum file of the release.
+    ChecksumFile,
+    /// The archive matches the digest of its asset, which was computed with the contained algorithm.
+    AssetDigest(Algorithm),
+}
+
+impl Verification {
+    /// Was the archive verified with any method.
+    pub fn is_verified(&self) -> bool {
+        *self != Verification::Unverified
+    }
+}
+
+/// The assets of a release as resolved by `GeDownload::resolve_assets`, without downloading them.
+///
+/// The assets contain everything that is needed to download them with another tool: the download URL, the file name
+/// and the size.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResolvedAssets {
+    /// Tag name of the release.
+    pub tag: String,
+    /// The compressed archive, see `GeRelease::tar_asset`.
+    pub archive: GeAsset,
+    /// The checksum file, see `GeRelease::find_checksum_asset`. This is `None` if the release has no checksum file.
+    pub checksum: Option<GeAsset>,
+}
+
+impl ResolvedAssets {
+    /// Resolve the archive and checksum assets of `release`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns `GithubError::MissingArchiveAsset` if the `release` contains no archive asset.
+    pub fn from_release(release: &GeRelease) -> Result<Self, GithubError> {
+        Ok(ResolvedAssets {
+            tag: release.tag_name.clone(),
+            archive: release.tar_asset()?.clone(),
+            checksum: release.find_checksum_asset().cloned(),
+        })
+    }
+}
+
+/// Result of a conditional release request with `GeDownload::fetch_release_if_modified`.
+#[derive(Debug)]
+pub enum ConditionalRelease {
+    /// The release changed since the `ETag` was returned, or no `ETag` was provided.
+    Modified {
+        release: GeRelease,
+        /// `ETag` of the release. Pass it to the next request to check if the release changed. This is `None` if
+        /// the response had no `ETag` header.
+        etag: Option<String>,
+    },
+    /// The release did not change. GitHub does not count these responses against the rate limit.
+    NotModified {
+        /// `ETag` of the unchanged release.
+        etag: String,
+    },
+}
+
+impl ConditionalRelease {
+    /// Get the `ETag` to use for the next request.
+    pub fn etag(&self) -> Option<&str> {
+        match self {
+            ConditionalRelease::Modified { etag, .. } => etag.as_deref(),
+            ConditionalRelease::NotModified { etag } => Some(etag),
+        }
+    }
+
+    /// Get the fetched release, or `None` if the release did not change.
+    pub fn release(self) -> Option<GeRelease> {
+        match self {
+            ConditionalRelease::Modified { release, .. } => Some(release),
+            ConditionalRelease::NotModified { .. } => None,
         }
     }
 }
 
 /// Represents a GitHub API release.
 ///
-/// Only the `tag_name` and `assets` of the release are relevant for us. Too see the APIs from which this struct is
-/// constructed from see the documentation of `GeDownloader::fetch_release`.
-#[derive(Debug, Deserialize)]
+/// Only the `tag_name`, `published_at`, the `draft` and `prerelease` flags, `body` and `assets` of the release are
+/// relevant for us. Too see the APIs from which this struct is constructed from see the documentation of
+/// `GeDownloader::fetch_release`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
 pub struct GeRelease {
     pub tag_name: String,
+    /// Publication date of the release. The RFC 3339 timestamp of the GitHub API is parsed with
+    /// `timeutil::parse_rfc3339`.
+    ///
+    /// This is `None` for unpublished releases, for data that was persisted without this field and for malformed
+    /// timestamps. A malformed timestamp does not fail the deserialization of the release.
+    #[serde(default, with = "crate::timeutil::lenient_option_rfc3339")]
+    pub published_at: Option<SystemTime>,
+    /// Is the release an unpublished draft. Drafts are only visible to authenticated maintainers of the repository.
+    #[serde(default)]
+    pub draft: bool,
+    /// Is the release a prerelease, e.g. a release candidate that is not meant for every user yet.
+    #[serde(default)]
+    pub prerelease: bool,
+    /// Release notes in Markdown, which list the fixes of a GE build.
+    ///
+    /// This is `None` if the release has no notes or if they were dropped with `GeRelease::without_notes`.
+    #[serde(default)]
+    pub body: Option<String>,
     pub assets: Vec<GeAsset>,
 }
 
 impl GeRelease {
     pub fn new(tag_name: String, assets: Vec<GeAsset>) -> Self {
-        GeRelease { tag_name, assets }
+        GeRelease {
+            tag_name,
+            published_at: None,
+            draft: false,
+            prerelease: false,
+            body: None,
+            assets,
+        }
+    }
+
+    /// Is the release neither a draft nor a prerelease.
+    pub fn is_stable(&self) -> bool {
+        !self.draft && !self.prerelease
+    }
+
+    /// Get the release notes without leading and trailing whitespace, or `None` if the release has no notes.
+    pub fn changelog(&self) -> Option<&str> {
+        self.body.as_deref().map(str::trim).filter(|body| !body.is_empty())
+    }
+
+    /// Drop the release notes of this release.
+    ///
+    /// Release notes can be tens of kilobytes of Markdown. Callers that keep many releases in memory can drop them
+    /// when they are not needed.
+    pub fn without_notes(mut self) -> Self {
+        self.body = None;
+        self
+    }
+
+    /// Check if this release belongs to the provided `kind`.
+    ///
+    /// Wine GE and Wine GE (LoL) releases are published in the same repository and can only be told apart by their
+    /// tag name. Proton GE releases are published in their own repository, therefore, every release is assumed to be
+    /// a Proton GE release if `kind` is `TagKind::Proton`.
+    pub fn is_kind(&self, kind: &TagKind) -> bool {
+        match kind {
+            TagKind::Proton => true,
+            TagKind::Wine { kind } => match kind {
+                WineTagKind::WineGe => !self.tag_name.contains("LoL"),
+                WineTagKind::LolWineGe => self.tag_name.contains("LoL"),
+            },
+        }
+    }
+
+    /// Get the tag, `kind` and publication date of this release.
+    pub fn tagged(&self, kind: TagKind) -> TaggedRelease {
+        TaggedRelease::new(Tag::new(self.tag_name.as_str()), kind, self.published_at)
     }
 
     fn is_checksum_asset(asset: &GeAsset) -> bool {
-        asset.name.contains(".sha512sum")
+        let name = asset.name.to_ascii_lowercase();
+        CHECKSUM_EXTENSIONS.iter().any(|extension| name.contains(extension))
     }
 
     fn is_tar_asset(asset: &GeAsset) -> bool {
-        asset.name.contains(".tar.gz")
+        let name = asset.name.to_ascii_lowercase();
+        TAR_EXTENSIONS.iter().any(|extension| name.ends_with(extension))
     }
 
-    pub fn checksum_asset(&self) -> &GeAsset {
+    fn has_checksum_name(asset: &GeAsset) -> bool {
+        Algorithm::from_checksum_file_name(&asset.name).is_some()
+    }
+
+    fn has_archive_content_type(asset: &GeAsset) -> bool {
+        asset.parsed_content_type().is_archive()
+    }
+
+    /// Get the checksum asset of this release.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GithubError::MissingChecksumAsset` if the release has no checksum asset, e.g. because the assets of a
+    /// new release are not uploaded yet.
+    pub fn checksum_asset(&self) -> Result<&GeAsset, GithubError> {
+        self.find_checksum_asset()
+            .ok_or_else(|| GithubError::MissingChecksumAsset {
+                tag: self.tag_name.clone(),
+            })
+    }
+
+    /// Get the checksum asset of this release, or `None` if it has none.
+    ///
+    /// The checksum is the asset with the extension `.sha512sum` or `.sha256sum`. Releases of forks whose checksum has
+    /// another name, like `sha512sums.txt`, fall back to the first asset whose name contains `sha512` or `sha256` and
+    /// that is not an archive by its extension.
+    pub fn find_checksum_asset(&self) -> Option<&GeAsset> {
         self.assets
             .iter()
             .find(|asset| GeRelease::is_checksum_asset(asset))
-            .unwrap()
+            .or_else(|| {
+                self.assets
+                    .iter()
+                    .find(|asset| GeRelease::has_checksum_name(asset) && !GeRelease::is_tar_asset(asset))
+            })
+    }
+
+    /// Get the archive asset of this release.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GithubError::MissingArchiveAsset` if the release has no archive asset, e.g. because the assets of a
+    /// new release are not uploaded yet.
+    pub fn tar_asset(&self) -> Result<&GeAsset, GithubError> {
+        self.find_tar_asset().ok_or_else(|| GithubError::MissingArchiveAsset {
+            tag: self.tag_name.clone(),
+        })
     }
 
-    pub fn tar_asset(&self) -> &GeAsset {
-        self.assets.iter().find(|asset| GeRelease::is_tar_asset(asset)).unwrap()
+    /// Get the archive asset of this release, or `None` if it has none.
+    ///
+    /// The archive is the asset with the extension `.tar.gz`, `.tar.xz` or `.tar.zst`. The content type is not
+    /// considered for these assets, as GitHub often reports archives as `application/octet-stream`. Releases of forks
+    /// whose archive has another name fall back to the first asset with the content type of a gzip, xz, zstd or tar
+    /// archive. Assets named like a checksum are never picked by their content type.
+    pub fn find_tar_asset(&self) -> Option<&GeAsset> {
+        self.assets
+            .iter()
+            .find(|asset| GeRelease::is_tar_asset(asset))
+            .or_else(|| {
+                self.assets
+                    .iter()
+                    .find(|asset| GeRelease::has_archive_content_type(asset) && !GeRelease::has_checksum_name(asset))
+            })
     }
 }
 
+/// The tag of a release together with its kind and publication date.
+///
+/// Use `TaggedRelease::cmp_by_version` or `TaggedRelease::cmp_by_published_at` with `sort_by` to order releases by
+/// version or by date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaggedRelease {
+    pub tag: Tag,
+    pub kind: TagKind,
+    /// Publication date of the release, see `GeRelease::published_at`.
+    pub published_at: Option<SystemTime>,
+}
+
+impl TaggedRelease {
+    pub fn new(tag: Tag, kind: TagKind, published_at: Option<SystemTime>) -> Self {
+        TaggedRelease {
+            tag,
+            kind,
+            published_at,
+        }
+    }
+
+    /// Compare the releases by their tags, see `Ord` of `Tag`.
+    pub fn cmp_by_version(&self, other: &TaggedRelease) -> Ordering {
+        self.tag.cmp(&other.tag)
+    }
+
+    /// Compare the releases by their publication date. Releases without a publication date are ordered before all
+    /// other releases. Releases with the same publication date are ordered by their tags.
+    pub fn cmp_by_published_at(&self, other: &TaggedRelease) -> Ordering {
+        self.published_at
+            .cmp(&other.published_at)
+            .then_with(|| self.cmp_by_version(other))
+    }
+
+    /// Was the release published before `time`. Releases without a publication date are never older.
+    pub fn published_before(&self, time: SystemTime) -> bool {
+        self.published_at.is_some_and(|published_at| published_at < time)
+    }
+}
+
+/// The tags of a kind as returned by `GeDownload::fetch_all_tags`.
+///
+/// Tag names that could not be parsed with `Tag::try_new` are not part of `tags`. Their errors are kept in `skipped`,
+/// so they can be logged. `TagParseError::tag` returns the skipped name.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FetchedTags {
+    /// The parsed tags, sorted from newest to oldest.
+    pub tags: Vec<Tag>,
+    /// The errors of the tag names that were skipped, in the order of the tags endpoint.
+    pub skipped: Vec<TagParseError>,
+}
+
 /// An asset of a GitHub release.
 ///
 /// This struct contains the URL from which the asset file can be downloaded from. Additionally, it contains the
 /// content type of the file and the file name.
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
 pub struct GeAsset {
     /// File name of the asset.
     pub name: String,
     pub content_type: String,
     pub browser_download_url: String,
+    /// Size of the asset in bytes.
+    ///
+    /// Downloads of an asset with a known size fail with `GithubError::TruncatedDownload` if they receive a different
+    /// number of bytes.
+    pub size: Option<u64>,
+    /// Upload date of the asset, parsed like `GeRelease::published_at`.
+    #[serde(default, with = "crate::timeutil::lenient_option_rfc3339")]
+    pub created_at: Option<SystemTime>,
+    /// Digest of the asset as reported by the GitHub API, e.g. `sha256:<hex>`. Older API responses do not contain
+    /// it. See `GeAsset::parsed_digest`.
+    #[serde(default)]
+    pub digest: Option<String>,
 }
 
 impl GeAsset {
@@ -108,20 +603,153 @@ impl GeAsset {
             name: name.into(),
             content_type: content_type.into(),
             browser_download_url: browser_download_url.into(),
+            size: None,
+            created_at: None,
+            digest: None,
+        }
+    }
+
+    /// Set the size of the asset in bytes.
+    pub fn with_size(mut self, size: u64) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Set the upload date of the asset.
+    pub fn with_created_at(mut self, created_at: SystemTime) -> Self {
+        self.created_at = Some(created_at);
+        self
+    }
+
+    /// Set the digest of the asset in the format `<algorithm>:<hex>`.
+    pub fn with_digest<S: Into<String>>(mut self, digest: S) -> Self {
+        self.digest = Some(digest.into());
+        self
+    }
+
+    /// Get the parsed `digest` of the asset.
+    ///
+    /// This is `None` if the API did not report a digest, or if it has an unknown algorithm or is malformed, see
+    /// `AssetDigest::parse`.
+    pub fn parsed_digest(&self) -> Option<AssetDigest> {
+        self.digest.as_deref().and_then(AssetDigest::parse)
+    }
+
+    /// Get the parsed `content_type` of the asset.
+    pub fn parsed_content_type(&self) -> AssetContentType {
+        AssetContentType::from(self.content_type.as_str())
+    }
+
+    /// Get the compression format of this asset, if it is an archive.
+    ///
+    /// The format is detected from the extension of the `name` and, if the extension is unknown, from the
+    /// `content_type`.
+    pub fn compression(&self) -> Option<Compression> {
+        Compression::from_file_name(&self.name).or_else(|| self.parsed_content_type().compression())
+    }
+
+    /// Get a file name for this asset that is safe to use as a path component.
+    ///
+    /// The asset `name` is returned as is, including spaces and non-ASCII characters, as long as it is a valid file
+    /// name. If the `name` is empty, the file name is derived from the percent-decoded last segment of the
+    /// `browser_download_url`. Path separators and NUL characters are replaced with `_`. An empty name as well as the
+    /// names `.` and `..` become `_`, so the resulting name can never point outside of a directory it is joined with.
+    pub fn file_name(&self) -> String {
+        let name = if self.name.is_empty() {
+            let segment = self
+                .browser_download_url
+                .split(['?', '#'])
+                .next()
+                .and_then(|url| url.rsplit('/').next())
+                .unwrap_or_default();
+            percent_decode_str(segment).decode_utf8_lossy().into_owned()
+        } else {
+            self.name.clone()
+        };
+
+        match name.as_str() {
+            "" | "." | ".." => String::from("_"),
+            _ => name.replace(['/', '\\', '\0'], "_"),
         }
     }
 }
 
-impl Clone for GeAsset {
-    fn clone(&self) -> Self {
-        GeAsset {
-            name: self.name.clone(),
-            content_type: self.content_type.clone(),
-            browser_download_url: self.content_type.clone(),
+/// The content type of a release asset as reported by the GitHub API.
+///
+/// Parsing ignores parameters like `; charset=binary` and the case of the media type. Aliases like `application/x-gzip`
+/// and `binary/octet-stream` are parsed into the same variant as their common form.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AssetContentType {
+    /// `application/gzip` or `application/x-gzip`.
+    Gzip,
+    /// `application/x-xz`.
+    Xz,
+    /// `application/zstd` or `application/x-zstd`.
+    Zstd,
+    /// An uncompressed tar archive, `application/x-tar` or `application/x-gtar`.
+    Tar,
+    /// `application/octet-stream` or `binary/octet-stream`. GitHub reports many assets with this content type, so it
+    /// tells nothing about the asset.
+    OctetStream,
+    /// Any other media type in lowercase and without parameters, e.g. `text/plain`.
+    Other(String),
+}
+
+impl AssetContentType {
+    /// Get the compression format of an archive with this content type. Returns `None` for uncompressed tar archives
+    /// and other content types.
+    pub fn compression(&self) -> Option<Compression> {
+        match self {
+            AssetContentType::Gzip => Some(Compression::Gzip),
+            AssetContentType::Xz => Some(Compression::Xz),
+            AssetContentType::Zstd => Some(Compression::Zstd),
+            _ => None,
+        }
+    }
+
+    /// Is this the content type of a compressed or uncompressed tar archive.
+    pub fn is_archive(&self) -> bool {
+        self.compression().is_some() || *self == AssetContentType::Tar
+    }
+
+    /// Get the media type of this content type, e.g. `application/gzip`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            AssetContentType::Gzip => "application/gzip",
+            AssetContentType::Xz => "application/x-xz",
+            AssetContentType::Zstd => "application/zstd",
+            AssetContentType::Tar => "application/x-tar",
+            AssetContentType::OctetStream => "application/octet-stream",
+            AssetContentType::Other(media_type) => media_type,
         }
     }
 }
 
+impl From<&str> for AssetContentType {
+    fn from(content_type: &str) -> Self {
+        let media_type = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        match media_type.as_str() {
+            "application/gzip" | "application/x-gzip" => AssetContentType::Gzip,
+            "application/x-xz" => AssetContentType::Xz,
+            "application/zstd" | "application/x-zstd" => AssetContentType::Zstd,
+            "application/x-tar" | "application/x-gtar" => AssetContentType::Tar,
+            "application/octet-stream" | "binary/octet-stream" => AssetContentType::OctetStream,
+            _ => AssetContentType::Other(media_type),
+        }
+    }
+}
+
+impl Display for AssetContentType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// Newtype for GitHub API tag name deserialization.
 #[derive(Debug, Deserialize)]
 pub(crate) struct CompatibilityToolTag {
@@ -130,12 +758,16 @@ pub(crate) struct CompatibilityToolTag {
 
 impl From<CompatibilityToolTag> for String {
     fn from(tag_name: CompatibilityToolTag) -> Self {
-        String::from(tag_name.name)
+        tag_name.name
     }
 }
 
 #[cfg(test)]
 mod ge_release_tests {
+    use std::time::Duration;
+
+    use test_case::test_case;
+
     use crate::download::mime::{APPLICATION_GZIP, APPLICATION_OCTET_STREAM, BINARY_OCTET_STREAM};
 
     use super::*;
@@ -149,7 +781,7 @@ mod ge_release_tests {
         ];
         let release = GeRelease::new(tag, assets);
 
-        let checksum_asset = release.checksum_asset();
+        let checksum_asset = release.checksum_asset().unwrap();
         assert_eq!(checksum_asset.name, "Proton-6.20-GE-1.sha512sum");
         assert_eq!(checksum_asset.content_type, APPLICATION_OCTET_STREAM);
         assert_eq!(checksum_asset.browser_download_url, "octet");
@@ -164,12 +796,427 @@ mod ge_release_tests {
         ];
         let release = GeRelease::new(tag, assets);
 
-        let checksum_asset = release.checksum_asset();
+        let checksum_asset = release.checksum_asset().unwrap();
         assert_eq!(checksum_asset.name, "Proton-6.20-GE-1.sha512sum");
         assert_eq!(checksum_asset.content_type, BINARY_OCTET_STREAM);
         assert_eq!(checksum_asset.browser_download_url, "octet");
     }
 
+    #[test]
+    fn release_with_only_checksum_asset() {
+        let assets = vec![GeAsset::new(
+            "GE-Proton9-1.sha512sum",
+            APPLICATION_OCTET_STREAM,
+            "checksum",
+        )];
+        let release = GeRelease::new(String::from("GE-Proton9-1"), assets);
+
+        assert_eq!(release.checksum_asset().unwrap().browser_download_url, "checksum");
+        assert!(matches!(release.tar_asset(), Err(GithubError::MissingArchiveAsset { tag }) if tag == "GE-Proton9-1"));
+    }
+
+    #[test]
+    fn release_with_only_archive_asset() {
+        let assets = vec![GeAsset::new("GE-Proton9-1.tar.gz", APPLICATION_GZIP, "archive")];
+        let release = GeRelease::new(String::from("GE-Proton9-1"), assets);
+
+        assert_eq!(release.tar_asset().unwrap().browser_download_url, "archive");
+        assert!(
+            matches!(release.checksum_asset(), Err(GithubError::MissingChecksumAsset { tag }) if tag == "GE-Proton9-1")
+        );
+    }
+
+    #[test]
+    fn find_assets_of_fork_with_other_file_names() {
+        let assets = vec![
+            GeAsset::new("README.md", "text/markdown", "readme"),
+            GeAsset::new("sha512sums.txt", "text/plain", "checksum"),
+            GeAsset::new("proton-fork-9.tgz", "application/x-gzip", "archive"),
+        ];
+        let release = GeRelease::new(String::from("GE-Proton9-1"), assets);
+
+        assert_eq!(release.tar_asset().unwrap().browser_download_url, "archive");
+        assert_eq!(release.checksum_asset().unwrap().browser_download_url, "checksum");
+    }
+
+    #[test_case("GE-Proton9-1.sha256sum"; "sha256sum")]
+    #[test_case("sha256sums.txt"; "sha256sums")]
+    fn find_sha256_checksum_asset(checksum_name: &str) {
+        let assets = vec![
+            GeAsset::new("GE-Proton9-1.tar.gz", APPLICATION_GZIP, "archive"),
+            GeAsset::new(checksum_name, "text/plain", "checksum"),
+        ];
+        let release = GeRelease::new(String::from("GE-Proton9-1"), assets);
+
+        assert_eq!(release.checksum_asset().unwrap().browser_download_url, "checksum");
+    }
+
+    #[test]
+    fn prefer_assets_with_official_file_names() {
+        let assets = vec![
+            GeAsset::new("sources.tgz", APPLICATION_GZIP, "sources"),
+            GeAsset::new("sha512-of-sources", APPLICATION_OCTET_STREAM, "sources checksum"),
+            GeAsset::new("GE-Proton9-1.sha512sum", APPLICATION_OCTET_STREAM, "checksum"),
+            GeAsset::new("GE-Proton9-1.tar.gz", APPLICATION_GZIP, "archive"),
+        ];
+        let release = GeRelease::new(String::from("GE-Proton9-1"), assets);
+
+        assert_eq!(release.tar_asset().unwrap().browser_download_url, "archive");
+        assert_eq!(release.checksum_asset().unwrap().browser_download_url, "checksum");
+    }
+
+    #[test_case("GE-Proton9-1.tar.gz"; "gzip")]
+    #[test_case("GE-Proton9-1.tar.zst"; "zstd")]
+    fn find_octet_stream_archive_before_checksum(archive_name: &str) {
+        let assets = vec![
+            GeAsset::new(archive_name, APPLICATION_OCTET_STREAM, "archive"),
+            GeAsset::new("GE-Proton9-1.sha512sum", APPLICATION_OCTET_STREAM, "checksum"),
+        ];
+        let release = GeRelease::new(String::from("GE-Proton9-1"), assets);
+
+        assert_eq!(release.tar_asset().unwrap().browser_download_url, "archive");
+        assert_eq!(release.checksum_asset().unwrap().browser_download_url, "checksum");
+    }
+
+    #[test_case("GE-Proton9-1.tar.gz"; "gzip")]
+    #[test_case("GE-Proton9-1.tar.xz"; "xz")]
+    #[test_case("GE-Proton9-1.tar.zst"; "zstd")]
+    fn classify_octet_stream_assets_by_extension(archive_name: &str) {
+        let assets = vec![
+            GeAsset::new("GE-Proton9-1.sha512sum", APPLICATION_OCTET_STREAM, "checksum"),
+            GeAsset::new("GE-Proton9-1-sources", APPLICATION_OCTET_STREAM, "sources"),
+            GeAsset::new(archive_name, APPLICATION_OCTET_STREAM, "archive"),
+        ];
+        let release = GeRelease::new(String::from("GE-Proton9-1"), assets);
+
+        assert_eq!(release.tar_asset().unwrap().browser_download_url, "archive");
+        assert_eq!(release.checksum_asset().unwrap().browser_download_url, "checksum");
+    }
+
+    #[test]
+    fn classify_assets_with_uppercase_extensions() {
+        let assets = vec![
+            GeAsset::new("GE-Proton9-1.SHA512SUM", APPLICATION_OCTET_STREAM, "checksum"),
+            GeAsset::new("GE-Proton9-1.TAR.GZ", APPLICATION_OCTET_STREAM, "archive"),
+        ];
+        let release = GeRelease::new(String::from("GE-Proton9-1"), assets);
+
+        assert_eq!(release.tar_asset().unwrap().browser_download_url, "archive");
+        assert_eq!(release.checksum_asset().unwrap().browser_download_url, "checksum");
+    }
+
+    #[test]
+    fn checksum_with_archive_content_type_is_not_an_archive() {
+        let assets = vec![
+            GeAsset::new("sha512sums", APPLICATION_GZIP, "checksum"),
+            GeAsset::new("proton-fork-9.tgz", "application/x-gzip", "archive"),
+        ];
+        let release = GeRelease::new(String::from("GE-Proton9-1"), assets);
+
+        assert_eq!(release.tar_asset().unwrap().browser_download_url, "archive");
+        assert_eq!(release.checksum_asset().unwrap().browser_download_url, "checksum");
+    }
+
+    #[test]
+    fn archive_with_checksum_name_is_not_a_checksum() {
+        let assets = vec![
+            GeAsset::new("proton-sha512-fix.tar.gz", APPLICATION_OCTET_STREAM, "archive"),
+            GeAsset::new("sha512sums.txt", "text/plain", "checksum"),
+        ];
+        let release = GeRelease::new(String::from("GE-Proton9-1"), assets);
+
+        assert_eq!(release.tar_asset().unwrap().browser_download_url, "archive");
+        assert_eq!(release.checksum_asset().unwrap().browser_download_url, "checksum");
+    }
+
+    #[test]
+    fn resolve_assets_of_release_without_checksum() {
+        let archive = GeAsset::new("GE-Proton9-1.tar.gz", APPLICATION_GZIP, "archive");
+        let release = GeRelease::new(String::from("GE-Proton9-1"), vec![archive.clone()]);
+
+        let assets = ResolvedAssets::from_release(&release).unwrap();
+
+        assert_eq!(assets.tag, "GE-Proton9-1");
+        assert_eq!(assets.archive, archive);
+        assert_eq!(assets.checksum, None);
+    }
+
+    #[test]
+    fn resolve_assets_of_release_without_archive() {
+        let checksum = GeAsset::new("GE-Proton9-1.sha512sum", APPLICATION_OCTET_STREAM, "checksum");
+        let release = GeRelease::new(String::from("GE-Proton9-1"), vec![checksum]);
+
+        let result = ResolvedAssets::from_release(&release);
+        assert!(matches!(result, Err(GithubError::MissingArchiveAsset { tag }) if tag == "GE-Proton9-1"));
+    }
+
+    #[test]
+    fn checksum_of_archive_is_not_an_archive() {
+        let assets = vec![
+            GeAsset::new("GE-Proton9-1.tar.gz.sha512sum", APPLICATION_OCTET_STREAM, "checksum"),
+            GeAsset::new("GE-Proton9-1.tar.gz", APPLICATION_OCTET_STREAM, "archive"),
+        ];
+        let release = GeRelease::new(String::from("GE-Proton9-1"), assets);
+
+        assert_eq!(release.tar_asset().unwrap().browser_download_url, "archive");
+        assert_eq!(release.checksum_asset().unwrap().browser_download_url, "checksum");
+    }
+
+    #[test]
+    fn find_zstd_archive_of_fork_by_content_type() {
+        let assets = vec![
+            GeAsset::new("sha512sums.txt", "text/plain", "checksum"),
+            GeAsset::new("proton-fork-9", "application/zstd", "archive"),
+        ];
+        let release = GeRelease::new(String::from("GE-Proton9-1"), assets);
+
+        let archive_asset = release.tar_asset().unwrap();
+        assert_eq!(archive_asset.browser_download_url, "archive");
+        assert_eq!(archive_asset.compression(), Some(Compression::Zstd));
+    }
+
+    #[test]
+    fn find_tar_archive_of_fork_by_content_type_with_parameter() {
+        let assets = vec![
+            GeAsset::new("sha512sums.txt", "text/plain; charset=utf-8", "checksum"),
+            GeAsset::new("proton-fork-9", "application/x-tar; charset=binary", "archive"),
+        ];
+        let release = GeRelease::new(String::from("GE-Proton9-1"), assets);
+
+        let archive_asset = release.tar_asset().unwrap();
+        assert_eq!(archive_asset.browser_download_url, "archive");
+        assert_eq!(archive_asset.parsed_content_type(), AssetContentType::Tar);
+        assert_eq!(archive_asset.compression(), None);
+    }
+
+    #[test_case("application/gzip" => AssetContentType::Gzip; "gzip")]
+    #[test_case("application/x-gzip" => AssetContentType::Gzip; "gzip alias")]
+    #[test_case("application/gzip; charset=binary" => AssetContentType::Gzip; "trailing parameter")]
+    #[test_case(" Application/X-XZ " => AssetContentType::Xz; "uppercase with whitespace")]
+    #[test_case("application/x-zstd" => AssetContentType::Zstd; "zstd")]
+    #[test_case("application/x-gtar" => AssetContentType::Tar; "tar")]
+    #[test_case(APPLICATION_OCTET_STREAM => AssetContentType::OctetStream; "octet stream")]
+    #[test_case(BINARY_OCTET_STREAM => AssetContentType::OctetStream; "binary octet stream")]
+    #[test_case("Text/Plain; charset=utf-8" => AssetContentType::Other(String::from("text/plain")); "other")]
+    #[test_case("" => AssetContentType::Other(String::new()); "empty")]
+    fn parse_asset_content_type(content_type: &str) -> AssetContentType {
+        AssetContentType::from(content_type)
+    }
+
+    #[test_case(AssetContentType::Gzip => (Some(Compression::Gzip), true); "gzip")]
+    #[test_case(AssetContentType::Tar => (None, true); "tar")]
+    #[test_case(AssetContentType::OctetStream => (None, false); "octet stream")]
+    #[test_case(AssetContentType::Other(String::from("text/plain")) => (None, false); "other")]
+    fn classify_asset_content_type(content_type: AssetContentType) -> (Option<Compression>, bool) {
+        assert_eq!(AssetContentType::from(content_type.as_str()), content_type);
+        (content_type.compression(), content_type.is_archive())
+    }
+
+    #[test_case("GE-Proton9-1.tar.gz", APPLICATION_OCTET_STREAM => Some(Compression::Gzip); "gzip with octet stream")]
+    #[test_case("GE-Proton9-1.tar.xz", APPLICATION_GZIP => Some(Compression::Xz); "extension before content type")]
+    #[test_case("GE-Proton9-1", "application/x-zstd" => Some(Compression::Zstd); "zstd content type")]
+    #[test_case("GE-Proton9-1.sha512sum", APPLICATION_OCTET_STREAM => None; "checksum")]
+    fn asset_compression(name: &str, content_type: &str) -> Option<Compression> {
+        GeAsset::new(name, content_type, "url").compression()
+    }
+
+    #[test_case("GE-Proton7-8", TagKind::Proton => true)]
+    #[test_case("6.20-GE-1", TagKind::wine() => true)]
+    #[test_case("6.20-GE-1", TagKind::lol() => false)]
+    #[test_case("6.16-GE-3-LoL", TagKind::wine() => false)]
+    #[test_case("6.16-GE-3-LoL", TagKind::lol() => true)]
+    fn release_is_kind(tag: &str, kind: TagKind) -> bool {
+        GeRelease::new(String::from(tag), Vec::new()).is_kind(&kind)
+    }
+
+    #[test_case(r#""2022-03-20T18:30:12Z""# => true; "valid date")]
+    #[test_case(r#""20.03.2022""# => false; "malformed date")]
+    #[test_case(r#"1647801012"# => false; "number")]
+    #[test_case("null" => false; "null")]
+    fn deserialize_release_with_published_at(published_at: &str) -> bool {
+        let json = format!(
+            r#"{{"tag_name":"GE-Proton7-8","published_at":{},"assets":[]}}"#,
+            published_at
+        );
+        let release: GeRelease = serde_json::from_str(&json).unwrap();
+        assert_eq!(release.tag_name, "GE-Proton7-8");
+        release.published_at.is_some()
+    }
+
+    #[test_case(r#""Fixes:\n* Elden Ring\n""# => Some(String::from("Fixes:\n* Elden Ring")); "markdown")]
+    #[test_case(r#""  \r\n""# => None; "whitespace")]
+    #[test_case("null" => None; "null")]
+    fn deserialize_release_with_body(body: &str) -> Option<String> {
+        let json = format!(r#"{{"tag_name":"GE-Proton7-8","body":{},"assets":[]}}"#, body);
+        let release: GeRelease = serde_json::from_str(&json).unwrap();
+        release.changelog().map(String::from)
+    }
+
+    #[test_case(r#""draft":false,"prerelease":false"# => (false, false, true); "stable")]
+    #[test_case(r#""draft":false,"prerelease":true"# => (false, true, false); "prerelease")]
+    #[test_case(r#""draft":true,"prerelease":false"# => (true, false, false); "draft")]
+    #[test_case(r#""body":null"# => (false, false, true); "missing flags")]
+    fn deserialize_release_flags(flags: &str) -> (bool, bool, bool) {
+        let json = format!(r#"{{"tag_name":"GE-Proton7-8",{},"assets":[]}}"#, flags);
+        let release: GeRelease = serde_json::from_str(&json).unwrap();
+        (release.draft, release.prerelease, release.is_stable())
+    }
+
+    #[test]
+    fn drop_release_notes() {
+        let json = r#"{"tag_name":"GE-Proton7-8","body":"Notes","assets":[]}"#;
+        let release: GeRelease = serde_json::from_str(json).unwrap();
+        assert_eq!(release.changelog(), Some("Notes"));
+        assert_eq!(release.without_notes().body, None);
+    }
+
+    #[test]
+    fn deserialize_asset_with_size_and_created_at() {
+        let json = r#"{"name":"GE-Proton7-8.tar.gz","content_type":"application/gzip","size":427333272,
+            "created_at":"2022-03-20T18:30:12Z","browser_download_url":"https://host/GE-Proton7-8.tar.gz"}"#;
+        let asset: GeAsset = serde_json::from_str(json).unwrap();
+        assert_eq!(asset.size, Some(427333272));
+        assert_eq!(
+            asset.created_at,
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1647801012))
+        );
+
+        let clone = asset.clone();
+        assert_eq!(clone.browser_download_url, "https://host/GE-Proton7-8.tar.gz");
+        assert_eq!(clone.created_at, asset.created_at);
+        assert_eq!(clone.digest, None);
+    }
+
+    #[test_case(r#","digest":"sha256:ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad""# => Some(Algorithm::Sha256); "sha256")]
+    #[test_case(r#","digest":"md5:900150983cd24fb0d6963f7d28e17f72""# => None; "unknown algorithm")]
+    #[test_case(r#","digest":null"# => None; "null")]
+    #[test_case("" => None; "missing")]
+    fn deserialize_asset_with_digest(digest: &str) -> Option<Algorithm> {
+        let json = format!(
+            r#"{{"name":"GE-Proton7-8.tar.gz","content_type":"application/gzip","size":3,"browser_download_url":"url"{}}}"#,
+            digest
+        );
+        let asset: GeAsset = serde_json::from_str(&json).unwrap();
+        asset.parsed_digest().map(|digest| digest.algorithm())
+    }
+
+    #[test]
+    fn clone_release() {
+        let created_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1647801012);
+        let mut release = GeRelease::new(
+            String::from("GE-Proton7-8"),
+            vec![GeAsset::new(
+                "GE-Proton7-8.tar.gz",
+                APPLICATION_GZIP,
+                "https://host/GE-Proton7-8.tar.gz",
+            )
+            .with_size(255)
+            .with_created_at(created_at)],
+        );
+        release.published_at = Some(created_at);
+        release.draft = true;
+        release.prerelease = true;
+        release.body = Some(String::from("Fixes"));
+
+        let clone = release.clone();
+        assert_eq!(clone.tag_name, "GE-Proton7-8");
+        assert_eq!(clone.published_at, Some(created_at));
+        assert!(clone.draft);
+        assert!(clone.prerelease);
+        assert_eq!(clone.body.as_deref(), Some("Fixes"));
+        let asset = &clone.assets[0];
+        assert_eq!(asset.name, "GE-Proton7-8.tar.gz");
+        assert_eq!(asset.content_type, APPLICATION_GZIP);
+        assert_eq!(asset.browser_download_url, "https://host/GE-Proton7-8.tar.gz");
+        assert_eq!(asset.size, Some(255));
+        assert_eq!(asset.created_at, Some(created_at));
+        assert_eq!(clone, release);
+    }
+
+    #[test]
+    fn create_asset_with_size_and_created_at() {
+        let created_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1647801012);
+        let asset = GeAsset::new(
+            "GE-Proton7-8.tar.gz",
+            APPLICATION_GZIP,
+            "https://host/GE-Proton7-8.tar.gz",
+        )
+        .with_size(255)
+        .with_created_at(created_at);
+        assert_eq!(asset.size, Some(255));
+        assert_eq!(asset.created_at, Some(created_at));
+    }
+
+    fn tagged(tag: &str, published_at: Option<u64>) -> TaggedRelease {
+        let mut release = GeRelease::new(String::from(tag), Vec::new());
+        release.published_at = published_at.map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+        release.tagged(TagKind::Proton)
+    }
+
+    #[test]
+    fn sort_tagged_releases() {
+        let mut releases = [
+            tagged("GE-Proton7-8", Some(200)),
+            tagged("GE-Proton7-4", None),
+            tagged("GE-Proton7-1", Some(300)),
+            tagged("GE-Proton7-9", Some(200)),
+        ];
+
+        releases.sort_by(TaggedRelease::cmp_by_version);
+        let tags: Vec<&str> = releases.iter().map(|release| release.tag.str().as_str()).collect();
+        assert_eq!(tags, ["GE-Proton7-1", "GE-Proton7-4", "GE-Proton7-8", "GE-Proton7-9"]);
+
+        releases.sort_by(TaggedRelease::cmp_by_published_at);
+        let tags: Vec<&str> = releases.iter().map(|release| release.tag.str().as_str()).collect();
+        assert_eq!(tags, ["GE-Proton7-4", "GE-Proton7-8", "GE-Proton7-9", "GE-Proton7-1"]);
+    }
+
+    #[test_case(Some(100) => true)]
+    #[test_case(Some(200) => false)]
+    #[test_case(None => false)]
+    fn tagged_release_published_before(published_at: Option<u64>) -> bool {
+        tagged("GE-Proton7-8", published_at).published_before(SystemTime::UNIX_EPOCH + Duration::from_secs(200))
+    }
+
+    #[test]
+    fn get_assets_with_special_characters_in_name() {
+        let tag = String::from("8");
+        let assets = vec![
+            GeAsset::new(
+                "wine ge (custom)+8.sha512sum",
+                APPLICATION_OCTET_STREAM,
+                "https://host/8/wine.ge.custom.8.sha512sum",
+            ),
+            GeAsset::new(
+                "wine ge (custom)+8.tar.xz",
+                APPLICATION_OCTET_STREAM,
+                "https://host/8/wine%20ge%20%28custom%29%2B8.tar.xz",
+            ),
+        ];
+        let release = GeRelease::new(tag, assets);
+
+        let archive_asset = release.tar_asset().unwrap();
+        assert_eq!(archive_asset.name, "wine ge (custom)+8.tar.xz");
+        assert_eq!(archive_asset.file_name(), "wine ge (custom)+8.tar.xz");
+        assert_eq!(
+            archive_asset.browser_download_url,
+            "https://host/8/wine%20ge%20%28custom%29%2B8.tar.xz"
+        );
+        assert_eq!(release.checksum_asset().unwrap().name, "wine ge (custom)+8.sha512sum");
+    }
+
+    #[test_case("wine ge (custom)+8.tar.xz", "https://host/a.tar.xz" => "wine ge (custom)+8.tar.xz")]
+    #[test_case("Übersetzung 7.tar.gz", "https://host/a.tar.gz" => "Übersetzung 7.tar.gz"; "non-ASCII name is kept")]
+    #[test_case("", "https://host/8/wine%20ge%20%28custom%29%2B8.tar.xz" => "wine ge (custom)+8.tar.xz")]
+    #[test_case("", "https://host/8/%C3%9Cbersetzung.tar.gz?raw=true" => "Übersetzung.tar.gz"; "non-ASCII name is decoded from URL")]
+    #[test_case("", "https://host/8/..%2Fevil.tar.gz" => ".._evil.tar.gz")]
+    #[test_case("../evil.tar.gz", "https://host/a.tar.gz" => ".._evil.tar.gz")]
+    #[test_case("..", "https://host/a.tar.gz" => "_")]
+    #[test_case("", "https://host/8/" => "_")]
+    fn asset_file_name(name: &str, browser_download_url: &str) -> String {
+        GeAsset::new(name, APPLICATION_GZIP, browser_download_url).file_name()
+    }
+
     #[test]
     fn get_archive_asset() {
         let tag = String::from("6.20-GE-1");
@@ -179,9 +1226,217 @@ mod ge_release_tests {
         ];
         let release = GeRelease::new(tag, assets);
 
-        let gzip_asset = release.tar_asset();
+        let gzip_asset = release.tar_asset().unwrap();
         assert_eq!(gzip_asset.name, "Proton-6.20-GE-1.tar.gz");
         assert_eq!(gzip_asset.content_type, APPLICATION_GZIP);
         assert_eq!(gzip_asset.browser_download_url, "gzip");
     }
 }
+
+#[cfg(test)]
+mod downloaded_assets_tests {
+    use std::fs;
+
+    use test_case::test_case;
+
+    use crate::error::ChecksumParseError;
+
+    use super::*;
+
+    fn checksum_named(checksum: &str, file_name: &str) -> DownloadedChecksum {
+        DownloadedChecksum::new(String::from(checksum), String::from(file_name))
+    }
+
+    fn downloaded_assets(archive_name: &str, checksum: Option<&str>) -> DownloadedAssets {
+        let archive = DownloadedArchive::new(
+            fs::read("test_resources/assets/test.tar.gz").unwrap(),
+            String::from(archive_name),
+        );
+        let checksum = checksum.map(|checksum| checksum_named(checksum, "test-gz.sha512sum"));
+        DownloadedAssets::new(String::from("GE-Proton7-8"), archive, checksum)
+    }
+
+    #[test]
+    fn compare_downloaded_assets() {
+        let assets = downloaded_assets("GE-Proton7-8.tar.gz", Some("checksum"));
+        assert!(assets.clone() == assets);
+
+        let mut changed = assets.clone();
+        changed.compressed_archive.compressed_content.push(0);
+        assert!(changed != assets);
+        assert!(downloaded_assets("GE-Proton7-8.tar.gz", None) != assets);
+    }
+
+    #[test_case("test-gz.sha512sum", "test-gz.sha512sum" => Ok(()); "sha512")]
+    #[test_case("test-gz.sha256sum", "test-gz.sha256sum" => Ok(()); "sha256")]
+    #[test_case(
+        "test-gz.sha512sum", "test-gz.checksum" => Err(ChecksumError::UnknownAlgorithm { file_name: String::from("test-gz.checksum") });
+        "unknown extension"
+    )]
+    #[test_case(
+        "test-gz.sha256sum", "test-gz.sha512sum" => Err(ChecksumError::MalformedChecksumFile { source: ChecksumParseError::InvalidDigestLength { length: 64, expected: 128 } });
+        "digest of other algorithm"
+    )]
+    fn verify_downloaded_checksum(fixture: &str, file_name: &str) -> Result<(), ChecksumError> {
+        let checksum = fs::read_to_string(format!("test_resources/assets/{}", fixture)).unwrap();
+        let mut assets = downloaded_assets("test.tar.gz", None);
+        assets.checksum = Some(checksum_named(&checksum, file_name));
+        assets.verify_checksum()
+    }
+
+    #[test_case("GE-Proton7-8.sha512sum" => Some(Algorithm::Sha512); "sha512")]
+    #[test_case("GE-Proton7-8.sha256sum" => Some(Algorithm::Sha256); "sha256")]
+    #[test_case("GE-Proton7-8.checksum" => None; "unknown extension")]
+    fn detect_checksum_algorithm(file_name: &str) -> Option<Algorithm> {
+        checksum_named("", file_name).algorithm
+    }
+
+    #[test]
+    fn verify_checksum_with_explicit_algorithm() {
+        let checksum = fs::read_to_string("test_resources/assets/test-gz.sha256sum").unwrap();
+        let checksum = checksum_named(&checksum, "checksum.txt").with_algorithm(Algorithm::Sha256);
+        let content = fs::read("test_resources/assets/test.tar.gz").unwrap();
+
+        assert_eq!(checksum.verify("test.tar.gz", &content), Ok(()));
+        assert_eq!(checksum.parsed().unwrap().algorithm(), Algorithm::Sha256);
+    }
+
+    #[test]
+    fn verify_checksum_for_other_archive() {
+        let checksum = fs::read_to_string("test_resources/assets/test-gz.sha512sum").unwrap();
+        let err = downloaded_assets("GE-Proton7-8.tar.gz", Some(&checksum))
+            .verify_checksum()
+            .unwrap_err();
+        assert!(matches!(err, ChecksumError::FileNameMismatch { .. }));
+    }
+
+    #[test]
+    fn parse_downloaded_checksum() {
+        let checksum = fs::read_to_string("test_resources/assets/test-gz.sha512sum").unwrap();
+        let parsed = DownloadedChecksum::new(checksum, String::from("test-gz.sha512sum"))
+            .parsed()
+            .unwrap();
+        assert_eq!(parsed.file_name(), Some("test.tar.gz"));
+        assert!(!parsed.is_binary());
+    }
+
+    #[test]
+    fn parse_downloaded_checksum_of_unknown_algorithm() {
+        let checksum = fs::read_to_string("test_resources/assets/test-gz.sha512sum").unwrap();
+        let err = checksum_named(&checksum, "checksum.txt").parsed().unwrap_err();
+        assert_eq!(
+            err,
+            ChecksumError::UnknownAlgorithm {
+                file_name: String::from("checksum.txt")
+            }
+        );
+    }
+
+    #[test]
+    fn verify_without_downloaded_checksum() {
+        let err = downloaded_assets("test.tar.gz", None).verify_checksum().unwrap_err();
+        assert_eq!(
+            err,
+            ChecksumError::MissingChecksum {
+                tag: String::from("GE-Proton7-8")
+            }
+        );
+    }
+
+    #[test_case("test.tar.gz", "test_resources/assets/test.tar.gz" => Compression::Gzip; "gzip")]
+    #[test_case("test.tar.xz", "test_resources/assets/test.tar.xz" => Compression::Xz; "xz")]
+    #[test_case("test", "test_resources/assets/test.tar.xz" => Compression::Xz; "xz without extension")]
+    #[test_case("test.tar.zst", "test_resources/assets/test.tar.zst" => Compression::Zstd; "zstd")]
+    fn extract_downloaded_archive(file_name: &str, path: &str) -> Compression {
+        let tmp_dir = assert_fs::TempDir::new().unwrap();
+        let archive = DownloadedArchive::new(fs::read(path).unwrap(), String::from(file_name));
+
+        let extracted = archive.extract_to(tmp_dir.path()).unwrap();
+
+        assert_eq!(extracted.path, tmp_dir.path().join("test"));
+        assert_eq!(
+            fs::read_to_string(extracted.path.join("nested/nested.txt"))
+                .unwrap()
+                .len(),
+            12
+        );
+        extracted.compression
+    }
+
+    #[test]
+    fn extract_downloaded_archive_with_compression_of_asset() {
+        let tmp_dir = assert_fs::TempDir::new().unwrap();
+        let archive = DownloadedArchive::new(
+            fs::read("test_resources/assets/test.tar.zst").unwrap(),
+            String::from("proton-fork-9"),
+        );
+        assert_eq!(archive.compression, None);
+
+        let extracted = archive
+            .with_compression(Compression::Zstd)
+            .extract_to(tmp_dir.path())
+            .unwrap();
+
+        assert_eq!(extracted.path, tmp_dir.path().join("test"));
+        assert_eq!(extracted.compression, Compression::Zstd);
+    }
+
+    #[test]
+    fn write_downloaded_archive_to_dir() {
+        let tmp_dir = assert_fs::TempDir::new().unwrap();
+        let archive = DownloadedArchive::new(b"archive".to_vec(), String::from("GE-Proton7-8.tar.gz"));
+
+        let path = archive.write_to_dir(tmp_dir.path()).unwrap();
+
+        assert_eq!(path, tmp_dir.path().join("GE-Proton7-8.tar.gz"));
+        assert_eq!(fs::read(path).unwrap(), b"archive");
+    }
+
+    #[test_case("../evil.tar.gz"; "parent directory")]
+    #[test_case("/tmp/evil.tar.gz"; "absolute path")]
+    #[test_case("nested/evil.tar.gz"; "nested path")]
+    #[test_case("..\\evil.tar.gz"; "windows separator")]
+    #[test_case(".."; "parent directory name")]
+    #[test_case("."; "current directory name")]
+    #[test_case(""; "empty name")]
+    fn do_not_write_archive_with_path_in_file_name(file_name: &str) {
+        let tmp_dir = assert_fs::TempDir::new().unwrap();
+        let dir = tmp_dir.path().join("dir");
+        fs::create_dir(&dir).unwrap();
+        let archive = DownloadedArchive::new(b"archive".to_vec(), String::from(file_name));
+
+        let err = archive.write_to_dir(&dir).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(fs::read_dir(tmp_dir.path()).unwrap().count(), 1);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn sha512_of_downloaded_archive() {
+        let archive = DownloadedArchive::new(b"abc".to_vec(), String::from("abc.tar.gz"));
+        assert_eq!(
+            archive.sha512(),
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+        );
+    }
+
+    #[test_case("test.tar.gz", &fs::read_to_string("test_resources/assets/test-gz.sha512sum").unwrap() => true; "matching checksum")]
+    #[test_case("GE-Proton7-8.tar.gz", &fs::read_to_string("test_resources/assets/test-gz.sha512sum").unwrap() => false; "checksum of other file")]
+    #[test_case("test.tar.gz", &format!("{}  test.tar.gz", "ab".repeat(64)) => false; "other digest")]
+    #[test_case("test.tar.gz", "not a checksum" => false; "malformed checksum")]
+    fn archive_matches_checksum(file_name: &str, checksum: &str) -> bool {
+        let archive = downloaded_assets(file_name, None).compressed_archive;
+        archive.matches_checksum(&checksum_named(checksum, "test-gz.sha512sum"))
+    }
+
+    #[test]
+    fn extract_archive_with_unknown_compression() {
+        let tmp_dir = assert_fs::TempDir::new().unwrap();
+        let archive = DownloadedArchive::new(b"not an archive".to_vec(), String::from("test.zip"));
+
+        let err = archive.extract_to(tmp_dir.path()).unwrap_err();
+
+        assert!(matches!(err, ExtractError::UnknownCompression { file_name } if file_name == "test.zip"));
+    }
+}
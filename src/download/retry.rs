@@ -0,0 +1,257 @@
+//! Retries of failed GitHub requests.
+//!
+//! A `RetryPolicy` decides which failures of a request are retried and how long to wait between two attempts. The
+//! delay grows exponentially with every attempt and is randomized, so that many clients which failed at the same time
+//! do not retry at the same time.
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+use crate::error::GithubError;
+
+/// Policy for retrying failed requests to GitHub.
+///
+/// `GeDownloader` retries the requests for release information, checksums and the start of archive downloads with its
+/// policy. An archive download that fails after bytes were passed to the `ReadProgressWrapper` is not retried.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use ge_man_lib::download::retry::RetryPolicy;
+/// use ge_man_lib::download::GeDownloader;
+///
+/// let policy = RetryPolicy {
+///     max_attempts: 5,
+///     base_delay: Duration::from_secs(1),
+///     ..RetryPolicy::default()
+/// };
+/// let downloader = GeDownloader::default().with_retry_policy(policy);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts of a request, including the first attempt. `0` and `1` disable retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry. The delay is doubled for every further retry.
+    pub base_delay: Duration,
+    /// Upper bound of the delay between two attempts.
+    pub max_delay: Duration,
+    /// Decide if an error is retried. Defaults to `RetryPolicy::is_transient`.
+    pub retry_on: fn(&GithubError) -> bool,
+}
+
+impl RetryPolicy {
+    /// Policy that never retries a request.
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        }
+    }
+
+    /// Is `err` caused by a failure that may not occur again.
+    ///
+    /// Connection errors, timeouts, interrupted or truncated transfers and the HTTP statuses `429` and `5xx` are transient. Every
+    /// other error, for example a `404`, is not.
+    pub fn is_transient(err: &GithubError) -> bool {
+        match err {
+            GithubError::ReqwestError { source } => match source.status() {
+                Some(status) => is_transient_status(status),
+                None => source.is_connect() || source.is_timeout() || source.is_body(),
+            },
+            GithubError::StatusNotOk { status, .. } => is_transient_status(*status),
+            GithubError::TransferError { .. } | GithubError::TruncatedDownload { .. } | GithubError::Timeout { .. } => {
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Get the delay before the retry that follows the failed `attempt`. The first attempt is `1`.
+    ///
+    /// The delay is a random duration between half and all of `base_delay * 2^(attempt - 1)`, but at most
+    /// `max_delay`.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let delay = self.base_delay.saturating_mul(1 << exponent).min(self.max_delay);
+
+        let half = delay / 2;
+        let jitter = RandomState::new().build_hasher().finish() % (half.as_nanos() as u64).saturating_add(1);
+        half + Duration::from_nanos(jitter)
+    }
+
+    /// Run `operation` until it succeeds, fails with an error that is not retried or `max_attempts` is reached.
+    ///
+    /// If all attempts failed with retried errors, the last error is returned as `GithubError::RetriesExhausted`.
+    pub(crate) fn retry<T, F>(&self, mut operation: F) -> Result<T, GithubError>
+    where
+        F: FnMut() -> Result<T, GithubError>,
+    {
+        let mut attempt = 1;
+        loop {
+            let err = match operation() {
+                Ok(value) => return Ok(value),
+                Err(err) => err,
+            };
+
+            if !(self.retry_on)(&err) {
+                return Err(err);
+            }
+            if attempt >= self.max_attempts {
+                return if attempt > 1 {
+                    Err(GithubError::RetriesExhausted {
+                        attempts: attempt,
+                        source: Box::new(err),
+                    })
+                } else {
+                    Err(err)
+                };
+            }
+
+            std::thread::sleep(self.delay(attempt));
+            attempt += 1;
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts with a delay of 500 milliseconds before the first retry and at most 10 seconds between two
+    /// attempts.
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+            retry_on: RetryPolicy::is_transient,
+        }
+    }
+}
+
+fn is_transient_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use reqwest::blocking::Response;
+    use test_case::test_case;
+
+    use super::*;
+
+    fn policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+            ..RetryPolicy::default()
+        }
+    }
+
+    fn status_error(status: u16) -> GithubError {
+        GithubError::status_not_ok(Response::from(
+            http::Response::builder().status(status).body("").unwrap(),
+        ))
+    }
+
+    #[test_case(502 => true)]
+    #[test_case(503 => true)]
+    #[test_case(429 => true)]
+    #[test_case(404 => false)]
+    #[test_case(403 => false)]
+    fn transient_status(status: u16) -> bool {
+        RetryPolicy::is_transient(&status_error(status))
+    }
+
+    #[test]
+    fn transient_errors() {
+        let transfer_error = GithubError::TransferError {
+            file_name: String::new(),
+            source: std::io::Error::new(std::io::ErrorKind::ConnectionReset, "connection reset"),
+        };
+        assert!(RetryPolicy::is_transient(&transfer_error));
+        let truncated = GithubError::TruncatedDownload {
+            file_name: String::new(),
+            expected: 255,
+            received: 100,
+        };
+        assert!(RetryPolicy::is_transient(&truncated));
+        assert!(!RetryPolicy::is_transient(&GithubError::NoTags));
+    }
+
+    #[test]
+    fn succeed_after_transient_failures() {
+        let calls = Cell::new(0);
+        let result = policy(3).retry(|| {
+            calls.set(calls.get() + 1);
+            if calls.get() < 3 {
+                Err(status_error(503))
+            } else {
+                Ok(calls.get())
+            }
+        });
+
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[test]
+    fn report_attempts_when_retries_are_exhausted() {
+        let calls = Cell::new(0);
+        let err = policy(4)
+            .retry(|| -> Result<(), GithubError> {
+                calls.set(calls.get() + 1);
+                Err(status_error(502))
+            })
+            .unwrap_err();
+
+        assert_eq!(calls.get(), 4);
+        assert!(matches!(err, GithubError::RetriesExhausted { attempts: 4, .. }));
+    }
+
+    #[test]
+    fn fail_immediately_on_permanent_error() {
+        let calls = Cell::new(0);
+        let err = policy(4)
+            .retry(|| -> Result<(), GithubError> {
+                calls.set(calls.get() + 1);
+                Err(status_error(404))
+            })
+            .unwrap_err();
+
+        assert_eq!(calls.get(), 1);
+        assert!(matches!(err, GithubError::StatusNotOk { .. }));
+    }
+
+    #[test]
+    fn return_error_unchanged_without_retries() {
+        let err = RetryPolicy::none()
+            .retry(|| -> Result<(), GithubError> { Err(status_error(503)) })
+            .unwrap_err();
+
+        assert!(matches!(err, GithubError::StatusNotOk { .. }));
+    }
+
+    #[test_case(1, 50, 100; "first retry")]
+    #[test_case(2, 100, 200; "second retry")]
+    #[test_case(5, 150, 300; "capped retry")]
+    #[test_case(100, 150, 300; "large attempt")]
+    fn delay_is_within_bounds(attempt: u32, min: u128, max: u128) {
+        let policy = RetryPolicy {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(300),
+            ..RetryPolicy::default()
+        };
+        let delay = policy.delay(attempt).as_millis();
+        assert!(
+            (min..=max).contains(&delay),
+            "{} is not within {}..={}",
+            delay,
+            min,
+            max
+        );
+    }
+}
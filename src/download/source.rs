@@ -0,0 +1,132 @@
+//! GitHub repositories the GE releases are downloaded from.
+//!
+//! By default Proton GE is downloaded from `GloriousEggroll/proton-ge-custom` and Wine GE as well as Wine GE (LoL) from
+//! `GloriousEggroll/wine-ge-custom`. A `RepositorySource` points a `TagKind` to another repository, e.g. a fork or an
+//! internal mirror, with `GeDownloader::with_sources`.
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use percent_encoding::utf8_percent_encode;
+use serde::{Deserialize, Serialize};
+
+use crate::download::PATH_SEGMENT;
+use crate::tag::TagKind;
+
+const OFFICIAL_OWNER: &str = "GloriousEggroll";
+const PROTON_GE_REPOSITORY: &str = "proton-ge-custom";
+const WINE_GE_REPOSITORY: &str = "wine-ge-custom";
+
+/// The owner and name of a GitHub repository which publishes GE releases.
+///
+/// The assets of a release in a fork do not need the exact file names of the official releases. The archive and the
+/// checksum are selected as described in `GeRelease::find_tar_asset` and `GeRelease::find_checksum_asset`.
+///
+/// # Examples
+///
+/// ```
+/// use ge_man_lib::download::source::RepositorySource;
+/// use ge_man_lib::download::GeDownloader;
+/// use ge_man_lib::tag::TagKind;
+///
+/// let mirror = RepositorySource::new("my-org", "proton-ge-mirror");
+/// let downloader = GeDownloader::default().with_sources([(TagKind::Proton, mirror)]);
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RepositorySource {
+    /// User or organization that owns the repository.
+    pub owner: String,
+    /// Name of the repository.
+    pub repository: String,
+}
+
+impl RepositorySource {
+    pub fn new<O: Into<String>, R: Into<String>>(owner: O, repository: R) -> Self {
+        RepositorySource {
+            owner: owner.into(),
+            repository: repository.into(),
+        }
+    }
+
+    /// Get the official GloriousEggroll repository of `kind`.
+    pub fn official(kind: &TagKind) -> Self {
+        let repository = match kind {
+            TagKind::Proton => PROTON_GE_REPOSITORY,
+            TagKind::Wine { .. } => WINE_GE_REPOSITORY,
+        };
+        RepositorySource::new(OFFICIAL_OWNER, repository)
+    }
+
+    /// Create the URL of this repository in the GitHub API at `api_url`, e.g.
+    /// `https://api.github.com/repos/GloriousEggroll/proton-ge-custom`.
+    pub(crate) fn api_url(&self, api_url: &str) -> String {
+        format!(
+            "{}/repos/{}/{}",
+            api_url,
+            utf8_percent_encode(&self.owner, PATH_SEGMENT),
+            utf8_percent_encode(&self.repository, PATH_SEGMENT)
+        )
+    }
+}
+
+impl Display for RepositorySource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.owner, self.repository)
+    }
+}
+
+/// The `RepositorySource` of every `TagKind`. Kinds without an override use `RepositorySource::official`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RepositorySources {
+    overrides: BTreeMap<TagKind, RepositorySource>,
+}
+
+impl RepositorySources {
+    pub(crate) fn extend<I: IntoIterator<Item = (TagKind, RepositorySource)>>(&mut self, sources: I) {
+        self.overrides.extend(sources);
+    }
+
+    /// Get the URL of the repository of `kind` in the GitHub API at `api_url`.
+    pub(crate) fn api_url(&self, kind: &TagKind, api_url: &str) -> String {
+        match self.overrides.get(kind) {
+            Some(source) => source.api_url(api_url),
+            None => RepositorySource::official(kind).api_url(api_url),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case(TagKind::Proton => "https://api.github.com/repos/GloriousEggroll/proton-ge-custom")]
+    #[test_case(TagKind::wine() => "https://api.github.com/repos/GloriousEggroll/wine-ge-custom")]
+    #[test_case(TagKind::lol() => "https://api.github.com/repos/GloriousEggroll/wine-ge-custom")]
+    fn official_sources(kind: TagKind) -> String {
+        RepositorySources::default().api_url(&kind, "https://api.github.com")
+    }
+
+    #[test]
+    fn override_source_of_single_kind() {
+        let mut sources = RepositorySources::default();
+        sources.extend([(TagKind::lol(), RepositorySource::new("mirror org", "lol"))]);
+
+        assert_eq!(
+            sources.api_url(&TagKind::lol(), "http://localhost"),
+            "http://localhost/repos/mirror%20org/lol"
+        );
+        assert_eq!(
+            sources.api_url(&TagKind::wine(), "http://localhost"),
+            "http://localhost/repos/GloriousEggroll/wine-ge-custom"
+        );
+    }
+
+    #[test]
+    fn display_source() {
+        assert_eq!(
+            RepositorySource::official(&TagKind::Proton).to_string(),
+            "GloriousEggroll/proton-ge-custom"
+        );
+    }
+}
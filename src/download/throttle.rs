@@ -0,0 +1,203 @@
+//! Bandwidth limiting of asset downloads.
+//!
+//! A `ThrottledReader` limits the rate at which an asset is read from its response with a token bucket. The bucket
+//! holds the bytes of a tenth of a second, so a download usually does not sleep longer than that between two reads.
+//! This keeps a cancelled download responsive and the progress of a `ReadProgressWrapper` close to the real
+//! throughput.
+//!
+//! The `AsyncGeDownloader` receives its downloads in chunks instead of reading them. An `AsyncThrottle` uses the same
+//! token bucket, but waits for the received chunks with an async sleep, so it does not block the async runtime.
+use std::io::Read;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Number of buckets that are refilled per second. Every bucket holds the bytes that may be read in its share of a
+/// second.
+const BUCKETS_PER_SECOND: u64 = 10;
+
+/// Reader that reads at most `bytes_per_sec` bytes per second from `reader`.
+pub(crate) struct ThrottledReader<R> {
+    reader: R,
+    bucket: Option<TokenBucket>,
+}
+
+impl<R> ThrottledReader<R> {
+    /// Limit `reader` to `max_bytes_per_sec`. A limit of `None` reads without a limit.
+    pub(crate) fn new(reader: R, max_bytes_per_sec: Option<u64>) -> Self {
+        ThrottledReader {
+            reader,
+            bucket: max_bytes_per_sec.map(TokenBucket::new),
+        }
+    }
+}
+
+impl<R: Read> Read for ThrottledReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let Some(bucket) = &mut self.bucket else {
+            return self.reader.read(buf);
+        };
+
+        let len = bucket.acquire(buf.len());
+        let read = self.reader.read(&mut buf[..len])?;
+        bucket.release(len - read);
+        Ok(read)
+    }
+}
+
+/// Rate limit for chunks that are received by an async download.
+#[cfg(feature = "async")]
+pub(crate) struct AsyncThrottle {
+    bucket: Option<TokenBucket>,
+}
+
+#[cfg(feature = "async")]
+impl AsyncThrottle {
+    /// Limit the received chunks to `max_bytes_per_sec`. A limit of `None` does not wait.
+    pub(crate) fn new(max_bytes_per_sec: Option<u64>) -> Self {
+        AsyncThrottle {
+            bucket: max_bytes_per_sec.map(TokenBucket::new),
+        }
+    }
+
+    /// Wait until a received chunk of `len` bytes fits into the rate limit.
+    pub(crate) async fn consume(&mut self, len: usize) {
+        let Some(bucket) = &mut self.bucket else {
+            return;
+        };
+
+        let mut remaining = len;
+        while remaining > 0 {
+            if let Some(wait) = bucket.time_to_wait(remaining) {
+                tokio::time::sleep(wait).await;
+            }
+            remaining -= bucket.take(remaining);
+        }
+    }
+}
+
+/// Token bucket with one token per byte.
+struct TokenBucket {
+    bytes_per_sec: u64,
+    capacity: u64,
+    tokens: u64,
+    refilled: Instant,
+}
+
+impl TokenBucket {
+    fn new(bytes_per_sec: u64) -> Self {
+        let bytes_per_sec = bytes_per_sec.max(1);
+        let capacity = (bytes_per_sec / BUCKETS_PER_SECOND).max(1);
+        TokenBucket {
+            bytes_per_sec,
+            capacity,
+            tokens: capacity,
+            refilled: Instant::now(),
+        }
+    }
+
+    /// Wait until tokens are available and take up to `wanted` of them. Returns the number of taken tokens, which is
+    /// at least one if `wanted` is not zero.
+    fn acquire(&mut self, wanted: usize) -> usize {
+        if wanted == 0 {
+            return 0;
+        }
+
+        if let Some(wait) = self.time_to_wait(wanted) {
+            thread::sleep(wait);
+        }
+        self.take(wanted)
+    }
+
+    /// Get the time to wait until tokens for `wanted` bytes are available, if the bucket is empty.
+    fn time_to_wait(&mut self, wanted: usize) -> Option<Duration> {
+        self.refill();
+        (self.tokens == 0).then(|| self.time_until_refilled(self.capacity.min(wanted as u64)))
+    }
+
+    /// Take up to `wanted` tokens without waiting. Returns the number of taken tokens, which is at least one.
+    fn take(&mut self, wanted: usize) -> usize {
+        self.refill();
+        let taken = self.tokens.min(wanted as u64).max(1);
+        self.tokens = self.tokens.saturating_sub(taken);
+        taken as usize
+    }
+
+    /// Put back `unused` tokens that were acquired but not read.
+    fn release(&mut self, unused: usize) {
+        self.tokens = (self.tokens + unused as u64).min(self.capacity);
+    }
+
+    fn refill(&mut self) {
+        let elapsed = self.refilled.elapsed();
+        let added = elapsed.as_nanos() * u128::from(self.bytes_per_sec) / 1_000_000_000;
+        if added == 0 {
+            return;
+        }
+
+        // Only the time of the added tokens is consumed, so no fraction of a token is lost.
+        let added = u64::try_from(added).unwrap_or(u64::MAX);
+        self.refilled += duration_of(added, self.bytes_per_sec);
+        self.tokens = self.tokens.saturating_add(added);
+        if self.tokens >= self.capacity {
+            self.tokens = self.capacity;
+            self.refilled = Instant::now();
+        }
+    }
+
+    fn time_until_refilled(&self, tokens: u64) -> Duration {
+        duration_of(tokens.saturating_sub(self.tokens), self.bytes_per_sec).saturating_sub(self.refilled.elapsed())
+    }
+}
+
+/// Time it takes to read `bytes` at `bytes_per_sec`.
+fn duration_of(bytes: u64, bytes_per_sec: u64) -> Duration {
+    Duration::from_nanos((u128::from(bytes) * 1_000_000_000 / u128::from(bytes_per_sec)) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Cursor};
+
+    use super::*;
+
+    #[test]
+    fn read_without_limit() {
+        let mut reader = ThrottledReader::new(Cursor::new(vec![1; 100_000]), None);
+        let mut buf = Vec::new();
+        let started = Instant::now();
+
+        reader.read_to_end(&mut buf).unwrap();
+
+        assert_eq!(buf.len(), 100_000);
+        assert!(started.elapsed() < Duration::from_millis(100));
+    }
+
+    #[test]
+    fn limit_read_rate() {
+        let mut reader = ThrottledReader::new(Cursor::new(vec![1; 50_000]), Some(100_000));
+        let mut buf = Vec::new();
+        let started = Instant::now();
+
+        reader.read_to_end(&mut buf).unwrap();
+
+        let elapsed = started.elapsed();
+        assert_eq!(buf.len(), 50_000);
+        assert!(elapsed >= Duration::from_millis(350), "read took {:?}", elapsed);
+        assert!(elapsed < Duration::from_secs(2), "read took {:?}", elapsed);
+    }
+
+    #[test]
+    fn read_at_most_one_bucket() {
+        let mut reader = ThrottledReader::new(io::repeat(1), Some(1_000));
+        let mut buf = [0; 1_000];
+
+        assert_eq!(reader.read(&mut buf).unwrap(), 100);
+    }
+
+    #[test]
+    fn limit_below_buckets_per_second() {
+        let mut bucket = TokenBucket::new(1);
+        assert_eq!(bucket.acquire(10), 1);
+        assert_eq!(bucket.tokens, 0);
+    }
+}
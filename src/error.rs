@@ -1,10 +1,54 @@
 //! Possible errors that can be thrown by this crate.
-use std::io;
+use std::fmt::{Display, Formatter};
+use std::io::{self, Read};
+use std::path::PathBuf;
 
 use reqwest::blocking::Response;
+use reqwest::StatusCode;
 use thiserror::Error;
 
-use crate::tag::TagKind;
+use crate::tag::{SemVer, TagKind};
+
+/// Maximum number of bytes of a response body that are kept in `GithubError::StatusNotOk`.
+const STATUS_BODY_LIMIT: u64 = 4096;
+
+/// A coarse category of an error of this crate.
+///
+/// Categories allow consumers to react to errors (e.g. by mapping them to exit codes) without matching every variant
+/// of every error enum. Every error of this crate provides its category with a `category()` method. The category of
+/// an existing error variant does not change within a major version.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// A network request failed or GitHub responded with an unexpected status. Retrying later may succeed.
+    Network,
+    /// GitHub rejected a request because of its rate limit.
+    RateLimited,
+    /// A requested resource like a release, tag or asset does not exist.
+    NotFound,
+    /// A downloaded file could not be verified.
+    Verification,
+    /// An archive could not be extracted.
+    Extraction,
+    /// The Steam config could not be read or is missing required data.
+    SteamConfig,
+    /// The Lutris config could not be read or is missing required data.
+    LutrisConfig,
+    /// A value provided by the caller could not be parsed or is not valid.
+    InvalidInput,
+    /// An unexpected error like a response that could not be deserialized.
+    Internal,
+}
+
+impl ErrorCategory {
+    fn from_status(status: StatusCode) -> Self {
+        match status {
+            StatusCode::NOT_FOUND => ErrorCategory::NotFound,
+            StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS => ErrorCategory::RateLimited,
+            _ => ErrorCategory::Network,
+        }
+    }
+}
 
 /// Error for Steam config related problems.
 #[derive(Error, Debug)]
@@ -20,6 +64,17 @@ pub enum SteamConfigError {
     },
 }
 
+impl SteamConfigError {
+    /// Get the category of this error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            SteamConfigError::NoDefaultCompatToolAttribute | SteamConfigError::IoError { .. } => {
+                ErrorCategory::SteamConfig
+            }
+        }
+    }
+}
+
 /// Error for Lutris config related errors.
 #[derive(Error, Debug)]
 pub enum LutrisConfigError {
@@ -34,6 +89,15 @@ pub enum LutrisConfigError {
     },
 }
 
+impl LutrisConfigError {
+    /// Get the category of this error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            LutrisConfigError::NoVersionAttribute | LutrisConfigError::IoError { .. } => ErrorCategory::LutrisConfig,
+        }
+    }
+}
+
 /// Errors for `serde` related issues.
 #[derive(Error, Debug)]
 pub enum DeserializeError {
@@ -41,10 +105,19 @@ pub enum DeserializeError {
     #[error("Could not convert Github JSON response into a struct")]
     FailedToConvertToStruct {
         #[from]
-        source: Box<dyn std::error::Error>,
+        source: Box<dyn std::error::Error + Send + Sync>,
     },
 }
 
+impl DeserializeError {
+    /// Get the category of this error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            DeserializeError::FailedToConvertToStruct { .. } => ErrorCategory::Internal,
+        }
+    }
+}
+
 /// Errors for GitHub API or `reqwest` related errors.
 #[derive(Debug, Error)]
 pub enum GithubError {
@@ -56,25 +129,772 @@ pub enum GithubError {
     },
     /// Reqwest could not fetch a resource from the GitHub API.
     #[error("Failed to fetch resource from GitHub API")]
-    ReqwestError {
-        #[from]
-        source: reqwest::Error,
-    },
+    ReqwestError { source: reqwest::Error },
+    /// The proxy rejected a request because the proxy credentials are missing or wrong.
+    #[error("The proxy requires authentication")]
+    ProxyAuthenticationRequired,
+    /// The proxy URL of a `GeDownloaderBuilder` is not valid.
+    #[error("Invalid proxy URL {url}")]
+    InvalidProxy { url: String, source: reqwest::Error },
+    /// A request did not complete within its configured timeout.
+    #[error("Request timed out while {phase}")]
+    Timeout { phase: TimeoutPhase },
     /// The GitHub API returned no release tags.
     #[error("No tags could be found")]
     NoTags,
     /// The GitHub API returned no assets for the fetched release.
     #[error("For {tag} {kind} the release has no assets")]
     ReleaseHasNoAssets { tag: String, kind: TagKind },
+    /// The release has no archive asset, e.g. because its assets are not uploaded yet.
+    #[error("The release {tag} has no archive asset")]
+    MissingArchiveAsset { tag: String },
+    /// The release has no checksum asset, e.g. because its assets are not uploaded yet.
+    #[error("The release {tag} has no checksum asset")]
+    MissingChecksumAsset { tag: String },
+    /// An asset of a download plan is no longer part of its release or has a different download URL.
+    #[error("The download plan for {tag} is outdated: asset {asset} is no longer available")]
+    OutdatedDownloadPlan { tag: String, asset: String },
+    /// The content of an asset could not be read from the download response.
+    #[error("Failed to read the content of asset {file_name}")]
+    TransferError { file_name: String, source: io::Error },
+    /// The download of the asset `file_name` received another number of bytes than the size of the asset.
+    #[error("Received {received} bytes of asset {file_name} but expected {expected} bytes")]
+    TruncatedDownload {
+        file_name: String,
+        expected: u64,
+        received: u64,
+    },
+    /// No release of `kind` has the version `semver`.
+    #[error("No {kind} release with version {semver} exists")]
+    VersionNotFound { semver: SemVer, kind: TagKind },
+    /// The file an asset should be downloaded to already exists.
+    #[error("The file {} already exists", path.display())]
+    FileExists { path: PathBuf },
+    /// The content of an asset could not be written into the sink provided by the caller.
+    #[error("Failed to write the content of asset {file_name}")]
+    WriteError { file_name: String, source: io::Error },
+    /// The downloaded asset `file_name` does not match the checksum of the release.
+    #[error("The downloaded asset {file_name} does not match its checksum")]
+    VerificationFailed { file_name: String, source: ChecksumError },
+    /// The downloaded archive `file_name` could not be extracted.
+    #[error("Failed to extract the archive {file_name}")]
+    ExtractionFailed { file_name: String, source: ExtractError },
+    /// A request failed with a transient error and was retried until the `attempts` of the `RetryPolicy` were used up.
+    /// The `source` is the error of the last attempt.
+    #[error("Request failed after {attempts} attempts")]
+    RetriesExhausted { attempts: u32, source: Box<GithubError> },
+    /// The download was cancelled before it was complete, for example by a `BatchCancelHandle`.
+    #[error("The download was cancelled")]
+    Cancelled,
     /// The response of the GitHub API is not HTTP code 200 (OK).
-    #[error("HTTP response status was not OK (200)")]
-    StatusNotOk(Response),
+    ///
+    /// The `body` contains the text of the response, like the error message of the GitHub API. It is read when the
+    /// error is created and truncated to 4 KiB.
+    #[error("HTTP response status {status} of {url} was not OK (200)")]
+    StatusNotOk {
+        status: StatusCode,
+        url: String,
+        body: String,
+    },
+}
+
+impl GithubError {
+    /// Get the category of this error.
+    ///
+    /// Errors caused by an HTTP status are categorized by the status: `404` is `ErrorCategory::NotFound`, `403` and
+    /// `429` are `ErrorCategory::RateLimited` and every other status is `ErrorCategory::Network`.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            GithubError::SerdeDeserializeError { .. } => ErrorCategory::Internal,
+            GithubError::ReqwestError { source } => match source.status() {
+                Some(status) => ErrorCategory::from_status(status),
+                None => ErrorCategory::Network,
+            },
+            GithubError::NoTags
+            | GithubError::ReleaseHasNoAssets { .. }
+            | GithubError::MissingArchiveAsset { .. }
+            | GithubError::MissingChecksumAsset { .. }
+            | GithubError::OutdatedDownloadPlan { .. } => ErrorCategory::NotFound,
+            GithubError::VersionNotFound { .. } => ErrorCategory::NotFound,
+            GithubError::FileExists { .. }
+            | GithubError::ProxyAuthenticationRequired
+            | GithubError::InvalidProxy { .. } => ErrorCategory::InvalidInput,
+            GithubError::TransferError { .. }
+            | GithubError::TruncatedDownload { .. }
+            | GithubError::Timeout { .. }
+            | GithubError::Cancelled => ErrorCategory::Network,
+            GithubError::WriteError { .. } => ErrorCategory::Internal,
+            GithubError::VerificationFailed { source, .. } => source.category(),
+            GithubError::ExtractionFailed { source, .. } => source.category(),
+            GithubError::RetriesExhausted { source, .. } => source.category(),
+            GithubError::StatusNotOk { status, .. } => ErrorCategory::from_status(*status),
+        }
+    }
+}
+
+impl GithubError {
+    /// Create a `GithubError::StatusNotOk` from a `response` with an unexpected status.
+    ///
+    /// The body of the `response` is read up to `STATUS_BODY_LIMIT` bytes. Invalid UTF-8 is replaced and a body that
+    /// can not be read is left empty.
+    pub(crate) fn status_not_ok(response: Response) -> Self {
+        let status = response.status();
+        let url = response.url().to_string();
+        let mut body = Vec::new();
+        let _ = response.take(STATUS_BODY_LIMIT).read_to_end(&mut body);
+        GithubError::StatusNotOk {
+            status,
+            url,
+            body: String::from_utf8_lossy(&body).into_owned(),
+        }
+    }
+
+    /// Create the error for a failed read of the content of the asset `file_name`.
+    ///
+    /// Reads that failed because of a timeout are returned as `GithubError::Timeout`.
+    pub(crate) fn transfer(file_name: &str, source: io::Error) -> Self {
+        let is_timeout = source.kind() == io::ErrorKind::TimedOut
+            || source
+                .get_ref()
+                .and_then(|err| err.downcast_ref::<reqwest::Error>())
+                .is_some_and(reqwest::Error::is_timeout);
+        if is_timeout {
+            GithubError::Timeout {
+                phase: TimeoutPhase::Read,
+            }
+        } else {
+            GithubError::TransferError {
+                file_name: String::from(file_name),
+                source,
+            }
+        }
+    }
+}
+
+impl From<reqwest::Error> for GithubError {
+    /// Timeouts are converted into `GithubError::Timeout` and a rejected proxy authentication into
+    /// `GithubError::ProxyAuthenticationRequired`. Every other error is converted into `GithubError::ReqwestError`.
+    fn from(source: reqwest::Error) -> Self {
+        if source.is_connect() && is_proxy_authentication_error(&source) {
+            return GithubError::ProxyAuthenticationRequired;
+        }
+        if !source.is_timeout() {
+            return GithubError::ReqwestError { source };
+        }
+
+        let phase = if source.is_connect() {
+            TimeoutPhase::Connect
+        } else if source.is_body() || source.is_decode() {
+            TimeoutPhase::Read
+        } else {
+            TimeoutPhase::Response
+        };
+        GithubError::Timeout { phase }
+    }
+}
+
+/// Did the proxy reject the tunnel to an HTTPS server with `407 Proxy Authentication Required`.
+///
+/// Reqwest reports this only through the message of the error that caused the failed connection.
+fn is_proxy_authentication_error(err: &reqwest::Error) -> bool {
+    let mut source = std::error::Error::source(err);
+    while let Some(err) = source {
+        if err.to_string() == "proxy authentication required" {
+            return true;
+        }
+        source = err.source();
+    }
+    false
+}
+
+/// The phase of a request in which a `GithubError::Timeout` occurred.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TimeoutPhase {
+    /// The connection to the server could not be established in time.
+    Connect,
+    /// The server did not respond in time, or a request to the GitHub API exceeded its total time limit.
+    Response,
+    /// No content of the response body was received in time.
+    Read,
+}
+
+impl Display for TimeoutPhase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeoutPhase::Connect => f.write_str("connecting"),
+            TimeoutPhase::Response => f.write_str("waiting for the response"),
+            TimeoutPhase::Read => f.write_str("reading the response body"),
+        }
+    }
+}
+
+/// Error for when a `DownloadRequestBuilder` can not build a `DownloadRequest`.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DownloadRequestError {
+    /// No `TagKind` was set with `DownloadRequestBuilder::with_kind`.
+    #[error("The kind of the requested release is missing")]
+    MissingKind,
+    /// The tag is empty or consists only of whitespace. Leave the tag unset to download the latest release.
+    #[error("The tag of the requested release is empty")]
+    EmptyTag,
+}
+
+impl DownloadRequestError {
+    /// Get the category of this error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            DownloadRequestError::MissingKind | DownloadRequestError::EmptyTag => ErrorCategory::InvalidInput,
+        }
+    }
 }
 
 /// Error for when a `TagKind` can not be created.
-#[derive(Debug, Error)]
+#[derive(Debug, Error, PartialEq, Eq)]
 pub enum TagKindError {
-    /// A `TagKind` could not be created from the provide string.
-    #[error("Could not create TagKind from provided string.")]
-    UnknownString,
+    /// A `TagKind` could not be created from the provided string.
+    #[error("Could not create TagKind from provided string \"{0}\".")]
+    UnknownString(String),
+}
+
+impl TagKindError {
+    /// Get the category of this error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            TagKindError::UnknownString(_) => ErrorCategory::InvalidInput,
+        }
+    }
+}
+
+/// Error for when a `Tag` can not be created from a tag string.
+///
+/// Every variant contains the original tag string. Positions are byte offsets into the tag string, starting at 0.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TagParseError {
+    /// The tag contains no version number at all and missing numbers must not be filled in.
+    #[error("Tag \"{tag}\" contains no version number")]
+    NoVersionNumbers { tag: String },
+    /// The tag contains fewer than three version numbers and missing numbers must not be filled in.
+    #[error("Tag \"{tag}\" contains only {found} of 3 version numbers")]
+    MissingVersionNumbers { tag: String, found: usize },
+    /// The tag contains a release candidate marker (`rc`) that is not followed by a number.
+    #[error("Tag \"{tag}\" has a release candidate marker without a number at position {position}")]
+    MissingReleaseCandidateNumber { tag: String, position: usize },
+    /// A number of the tag is too large for its version component and must not be capped.
+    #[error("Tag \"{tag}\" has a {component} version number at position {position} that is too large: {number}")]
+    VersionNumberOverflow {
+        tag: String,
+        position: usize,
+        component: &'static str,
+        number: String,
+    },
+    /// The tag does not look like a tag of the requested `TagKind`.
+    #[error("Tag \"{tag}\" is not a {kind} tag")]
+    KindMismatch { tag: String, kind: TagKind },
+}
+
+impl TagParseError {
+    /// Get the category of this error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            TagParseError::NoVersionNumbers { .. }
+            | TagParseError::MissingVersionNumbers { .. }
+            | TagParseError::MissingReleaseCandidateNumber { .. }
+            | TagParseError::VersionNumberOverflow { .. }
+            | TagParseError::KindMismatch { .. } => ErrorCategory::InvalidInput,
+        }
+    }
+
+    /// Get the tag string that could not be parsed.
+    pub fn tag(&self) -> &str {
+        match self {
+            TagParseError::NoVersionNumbers { tag }
+            | TagParseError::MissingVersionNumbers { tag, .. }
+            | TagParseError::MissingReleaseCandidateNumber { tag, .. }
+            | TagParseError::VersionNumberOverflow { tag, .. }
+            | TagParseError::KindMismatch { tag, .. } => tag,
+        }
+    }
+
+    /// Get the position of the problem in the tag string, if the error refers to a specific part of it.
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            TagParseError::MissingReleaseCandidateNumber { position, .. }
+            | TagParseError::VersionNumberOverflow { position, .. } => Some(*position),
+            TagParseError::NoVersionNumbers { .. }
+            | TagParseError::MissingVersionNumbers { .. }
+            | TagParseError::KindMismatch { .. } => None,
+        }
+    }
+}
+
+/// Error for when a `SemVer` can not be parsed from a version string.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SemVerError {
+    /// The version string ended before all three version numbers were found.
+    #[error("Version \"{input}\" has no {component} version number")]
+    MissingComponent { input: String, component: &'static str },
+    /// A version number is not a number or is too large.
+    #[error("The {component} version number of \"{input}\" is not a valid number")]
+    InvalidComponent { input: String, component: &'static str },
+    /// The version string contains a `-` but no identifier after it.
+    #[error("Version \"{input}\" has an empty identifier")]
+    EmptyIdentifier { input: String },
+    /// The identifier contains characters other than ASCII letters and digits.
+    #[error("Version \"{input}\" has an invalid identifier")]
+    InvalidIdentifier { input: String },
+    /// The build numbers after the `+` are not dot separated numbers.
+    #[error("Version \"{input}\" has invalid build numbers")]
+    InvalidBuild { input: String },
+}
+
+impl SemVerError {
+    /// Get the category of this error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            SemVerError::MissingComponent { .. }
+            | SemVerError::InvalidComponent { .. }
+            | SemVerError::EmptyIdentifier { .. }
+            | SemVerError::InvalidIdentifier { .. }
+            | SemVerError::InvalidBuild { .. } => ErrorCategory::InvalidInput,
+        }
+    }
+}
+
+/// Error for when a `TagRange` can not be parsed from a string.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TagRangeError {
+    /// A bound of the range is empty, e.g. in `">=7.20, "`.
+    #[error("Tag range \"{input}\" contains an empty bound")]
+    EmptyBound { input: String },
+    /// A bound does not start with one of the comparators `=`, `>`, `>=`, `<` or `<=`.
+    #[error("Bound \"{bound}\" does not start with a comparator")]
+    MissingComparator { bound: String },
+    /// The version of a bound is not a valid version.
+    #[error("Bound \"{bound}\" contains an invalid version: {source}")]
+    InvalidVersion { bound: String, source: SemVerError },
+}
+
+impl TagRangeError {
+    /// Get the category of this error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            TagRangeError::EmptyBound { .. }
+            | TagRangeError::MissingComparator { .. }
+            | TagRangeError::InvalidVersion { .. } => ErrorCategory::InvalidInput,
+        }
+    }
+}
+
+/// Error for when a `TagPattern` can not be parsed from a string.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TagPatternError {
+    /// The pattern is empty.
+    #[error("Tag pattern is empty")]
+    Empty,
+    /// The pattern has more than three version numbers.
+    #[error("Tag pattern \"{pattern}\" contains more than 3 version numbers")]
+    TooManyComponents { pattern: String },
+    /// A version number of the pattern is neither a number nor the wildcard `*`.
+    #[error("Tag pattern \"{pattern}\" contains the invalid version number \"{component}\"")]
+    InvalidComponent { pattern: String, component: String },
+    /// The pattern contains a `-` but no identifier after it.
+    #[error("Tag pattern \"{pattern}\" has an empty identifier")]
+    EmptyIdentifier { pattern: String },
+    /// The identifier contains characters other than ASCII letters, digits and `*`.
+    #[error("Tag pattern \"{pattern}\" has an invalid identifier")]
+    InvalidIdentifier { pattern: String },
+}
+
+impl TagPatternError {
+    /// Get the category of this error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            TagPatternError::Empty
+            | TagPatternError::TooManyComponents { .. }
+            | TagPatternError::InvalidComponent { .. }
+            | TagPatternError::EmptyIdentifier { .. }
+            | TagPatternError::InvalidIdentifier { .. } => ErrorCategory::InvalidInput,
+        }
+    }
+}
+
+/// Error for when a timestamp can not be parsed.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TimestampError {
+    /// The timestamp is not an RFC 3339 date-time like `2022-03-20T18:30:12Z`.
+    #[error("Timestamp \"{input}\" is not a valid RFC 3339 date-time: {reason}")]
+    InvalidFormat { input: String, reason: &'static str },
+}
+
+impl TimestampError {
+    /// Get the category of this error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            TimestampError::InvalidFormat { .. } => ErrorCategory::InvalidInput,
+        }
+    }
+}
+
+/// Error for when a release archive can not be extracted.
+#[derive(Debug, Error)]
+pub enum ExtractError {
+    /// The compression format of the archive could not be determined from its file name or content.
+    #[error("Unknown compression format of archive {file_name}")]
+    UnknownCompression { file_name: String },
+    /// An entry of the archive would be extracted outside of the destination directory.
+    #[error("Archive entry {} would be extracted outside of the destination", path.display())]
+    PathTraversal { path: PathBuf },
+    /// The archive contains no entries.
+    #[error("The archive is empty")]
+    EmptyArchive,
+    /// The archive could not be decompressed or its contents could not be written.
+    #[error("IO error occurred - Inspect the source for more information")]
+    IoError {
+        #[from]
+        source: io::Error,
+    },
+}
+
+impl ExtractError {
+    /// Get the category of this error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            ExtractError::UnknownCompression { .. }
+            | ExtractError::PathTraversal { .. }
+            | ExtractError::EmptyArchive
+            | ExtractError::IoError { .. } => ErrorCategory::Extraction,
+        }
+    }
+}
+
+/// Error for when a `ChecksumFile` can not be parsed.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ChecksumParseError {
+    /// The checksum file contains no checksum line.
+    #[error("Checksum file is empty")]
+    Empty,
+    /// The checksum file contains more than one checksum line.
+    #[error("Checksum file contains {count} checksums instead of one")]
+    MultipleEntries { count: usize },
+    /// The digest does not have the number of hex digits of a digest of the checksum algorithm.
+    #[error("Digest has {length} characters instead of {expected}")]
+    InvalidDigestLength { length: usize, expected: usize },
+    /// The digest contains characters that are not hex digits.
+    #[error("Digest \"{digest}\" is not hex encoded")]
+    InvalidDigest { digest: String },
+    /// The digest is not followed by two spaces or a space and `*`.
+    #[error("Digest is not followed by \"  \" or \" *\"")]
+    InvalidSeparator,
+    /// The separator is not followed by a file name.
+    #[error("Checksum file contains a separator but no file name")]
+    EmptyFileName,
+}
+
+impl ChecksumParseError {
+    /// Get the category of this error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            ChecksumParseError::Empty
+            | ChecksumParseError::MultipleEntries { .. }
+            | ChecksumParseError::InvalidDigestLength { .. }
+            | ChecksumParseError::InvalidDigest { .. }
+            | ChecksumParseError::InvalidSeparator
+            | ChecksumParseError::EmptyFileName => ErrorCategory::Verification,
+        }
+    }
+}
+
+/// Error for when a downloaded archive does not match its checksum file.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ChecksumError {
+    /// The digest of the archive differs from the digest in the checksum file.
+    #[error("Checksum mismatch: expected {expected} but the archive has {actual}")]
+    Mismatch { expected: String, actual: String },
+    /// The checksum file could not be parsed.
+    #[error("Checksum file is malformed: {source}")]
+    MalformedChecksumFile {
+        #[from]
+        source: ChecksumParseError,
+    },
+    /// The file name in the checksum file is not the file name of the archive.
+    #[error("Checksum file is for {found} but the archive is {expected}")]
+    FileNameMismatch { expected: String, found: String },
+    /// The checksum file was not downloaded.
+    #[error("No checksum was downloaded for {tag}")]
+    MissingChecksum { tag: String },
+    /// The algorithm of the checksum file can not be detected from its file name, see
+    /// `Algorithm::from_checksum_file_name`.
+    #[error("The checksum algorithm of {file_name} is unknown")]
+    UnknownAlgorithm { file_name: String },
+}
+
+impl ChecksumError {
+    /// Get the category of this error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            ChecksumError::Mismatch { .. }
+            | ChecksumError::MalformedChecksumFile { .. }
+            | ChecksumError::FileNameMismatch { .. }
+            | ChecksumError::MissingChecksum { .. }
+            | ChecksumError::UnknownAlgorithm { .. } => ErrorCategory::Verification,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    fn response(status: u16) -> Response {
+        Response::from(http::Response::builder().status(status).body("").unwrap())
+    }
+
+    fn status_error(status: u16) -> GithubError {
+        GithubError::status_not_ok(response(status))
+    }
+
+    fn io_error() -> io::Error {
+        io::Error::new(io::ErrorKind::NotFound, "missing")
+    }
+
+    #[test_case(SteamConfigError::NoDefaultCompatToolAttribute => ErrorCategory::SteamConfig)]
+    #[test_case(SteamConfigError::IoError { source: io_error() } => ErrorCategory::SteamConfig)]
+    fn steam_config_error_category(err: SteamConfigError) -> ErrorCategory {
+        err.category()
+    }
+
+    #[test_case(LutrisConfigError::NoVersionAttribute => ErrorCategory::LutrisConfig)]
+    #[test_case(LutrisConfigError::IoError { source: io_error() } => ErrorCategory::LutrisConfig)]
+    fn lutris_config_error_category(err: LutrisConfigError) -> ErrorCategory {
+        err.category()
+    }
+
+    #[test]
+    fn deserialize_error_category() {
+        let err = DeserializeError::FailedToConvertToStruct {
+            source: Box::new(io_error()),
+        };
+        assert_eq!(err.category(), ErrorCategory::Internal);
+    }
+
+    #[test_case(GithubError::from(serde_json::from_str::<u8>("").unwrap_err()) => ErrorCategory::Internal; "serde")]
+    #[test_case(GithubError::from(reqwest::blocking::get("no-url").unwrap_err()) => ErrorCategory::Network; "reqwest")]
+    #[test_case(
+        GithubError::from(response(404).error_for_status().unwrap_err()) => ErrorCategory::NotFound;
+        "reqwest status not found"
+    )]
+    #[test_case(GithubError::NoTags => ErrorCategory::NotFound; "no tags")]
+    #[test_case(
+        GithubError::ReleaseHasNoAssets { tag: String::from("GE-Proton7-8"), kind: TagKind::Proton } => ErrorCategory::NotFound;
+        "release has no assets"
+    )]
+    #[test_case(
+        GithubError::MissingArchiveAsset { tag: String::from("GE-Proton7-8") } => ErrorCategory::NotFound;
+        "missing archive asset"
+    )]
+    #[test_case(
+        GithubError::MissingChecksumAsset { tag: String::from("GE-Proton7-8") } => ErrorCategory::NotFound;
+        "missing checksum asset"
+    )]
+    #[test_case(
+        GithubError::OutdatedDownloadPlan { tag: String::from("GE-Proton7-8"), asset: String::new() } => ErrorCategory::NotFound;
+        "outdated download plan"
+    )]
+    #[test_case(
+        GithubError::VersionNotFound { semver: "7.8.0".parse().unwrap(), kind: TagKind::Proton } => ErrorCategory::NotFound;
+        "version not found"
+    )]
+    #[test_case(
+        GithubError::FileExists { path: PathBuf::from("GE-Proton7-8.tar.gz") } => ErrorCategory::InvalidInput;
+        "file exists"
+    )]
+    #[test_case(
+        GithubError::TransferError { file_name: String::new(), source: io_error() } => ErrorCategory::Network;
+        "transfer error"
+    )]
+    #[test_case(
+        GithubError::TruncatedDownload { file_name: String::new(), expected: 255, received: 100 } => ErrorCategory::Network;
+        "truncated download"
+    )]
+    #[test_case(
+        GithubError::WriteError { file_name: String::new(), source: io_error() } => ErrorCategory::Internal;
+        "write error"
+    )]
+    #[test_case(
+        GithubError::VerificationFailed {
+            file_name: String::new(),
+            source: ChecksumError::Mismatch { expected: String::new(), actual: String::new() }
+        } => ErrorCategory::Verification;
+        "verification failed"
+    )]
+    #[test_case(
+        GithubError::ExtractionFailed { file_name: String::new(), source: ExtractError::EmptyArchive } => ErrorCategory::Extraction;
+        "extraction failed"
+    )]
+    #[test_case(
+        GithubError::RetriesExhausted { attempts: 3, source: Box::new(status_error(429)) } => ErrorCategory::RateLimited;
+        "retries exhausted"
+    )]
+    #[test_case(GithubError::Timeout { phase: TimeoutPhase::Read } => ErrorCategory::Network; "timeout")]
+    #[test_case(GithubError::ProxyAuthenticationRequired => ErrorCategory::InvalidInput; "proxy authentication required")]
+    #[test_case(GithubError::Cancelled => ErrorCategory::Network; "cancelled")]
+    #[test_case(status_error(404) => ErrorCategory::NotFound; "status not found")]
+    #[test_case(status_error(403) => ErrorCategory::RateLimited; "status forbidden")]
+    #[test_case(status_error(429) => ErrorCategory::RateLimited; "status too many requests")]
+    #[test_case(status_error(502) => ErrorCategory::Network; "status bad gateway")]
+    fn github_error_category(err: GithubError) -> ErrorCategory {
+        err.category()
+    }
+
+    #[test]
+    fn status_not_ok_contains_status_url_and_body() {
+        let response = http::Response::builder()
+            .status(403)
+            .body(r#"{"message": "API rate limit exceeded"}"#)
+            .unwrap();
+
+        let err = GithubError::status_not_ok(Response::from(response));
+
+        let GithubError::StatusNotOk { status, url, body } = &err else {
+            panic!("unexpected error {:?}", err);
+        };
+        assert_eq!(*status, StatusCode::FORBIDDEN);
+        assert_eq!(body, r#"{"message": "API rate limit exceeded"}"#);
+        assert_eq!(
+            err.to_string(),
+            format!("HTTP response status 403 Forbidden of {} was not OK (200)", url)
+        );
+    }
+
+    #[test]
+    fn status_not_ok_truncates_body() {
+        let response = http::Response::builder().status(502).body("a".repeat(10_000)).unwrap();
+
+        let err = GithubError::status_not_ok(Response::from(response));
+
+        assert!(matches!(err, GithubError::StatusNotOk { ref body, .. } if body.len() == 4096));
+    }
+
+    fn assert_send_sync<T: Send + Sync + 'static>() {}
+
+    #[test]
+    fn errors_are_send_and_sync() {
+        assert_send_sync::<ErrorCategory>();
+        assert_send_sync::<SteamConfigError>();
+        assert_send_sync::<LutrisConfigError>();
+        assert_send_sync::<DeserializeError>();
+        assert_send_sync::<GithubError>();
+        assert_send_sync::<TimeoutPhase>();
+        assert_send_sync::<DownloadRequestError>();
+        assert_send_sync::<TagKindError>();
+        assert_send_sync::<TagParseError>();
+        assert_send_sync::<SemVerError>();
+        assert_send_sync::<TagRangeError>();
+        assert_send_sync::<TagPatternError>();
+        assert_send_sync::<TimestampError>();
+        assert_send_sync::<ExtractError>();
+        assert_send_sync::<ChecksumParseError>();
+        assert_send_sync::<ChecksumError>();
+    }
+
+    #[test_case(ChecksumError::Mismatch { expected: String::new(), actual: String::new() }; "mismatch")]
+    #[test_case(ChecksumError::MalformedChecksumFile { source: ChecksumParseError::Empty }; "malformed checksum file")]
+    #[test_case(ChecksumError::FileNameMismatch { expected: String::new(), found: String::new() }; "file name mismatch")]
+    #[test_case(ChecksumError::MissingChecksum { tag: String::new() }; "missing checksum")]
+    #[test_case(ChecksumError::UnknownAlgorithm { file_name: String::new() }; "unknown algorithm")]
+    fn checksum_error_category(err: ChecksumError) {
+        assert_eq!(err.category(), ErrorCategory::Verification);
+    }
+
+    #[test_case(ExtractError::UnknownCompression { file_name: String::new() }; "unknown compression")]
+    #[test_case(ExtractError::PathTraversal { path: PathBuf::from("../evil") }; "path traversal")]
+    #[test_case(ExtractError::EmptyArchive; "empty archive")]
+    #[test_case(ExtractError::IoError { source: io_error() }; "io")]
+    fn extract_error_category(err: ExtractError) {
+        assert_eq!(err.category(), ErrorCategory::Extraction);
+    }
+
+    #[test_case(ChecksumParseError::Empty; "empty")]
+    #[test_case(ChecksumParseError::MultipleEntries { count: 2 }; "multiple entries")]
+    #[test_case(ChecksumParseError::InvalidDigestLength { length: 0, expected: 128 }; "invalid digest length")]
+    #[test_case(ChecksumParseError::InvalidDigest { digest: String::new() }; "invalid digest")]
+    #[test_case(ChecksumParseError::InvalidSeparator; "invalid separator")]
+    #[test_case(ChecksumParseError::EmptyFileName; "empty file name")]
+    fn checksum_parse_error_category(err: ChecksumParseError) {
+        assert_eq!(err.category(), ErrorCategory::Verification);
+    }
+
+    #[test_case(io::Error::new(io::ErrorKind::TimedOut, "timed out") => true; "timed out")]
+    #[test_case(io::Error::new(io::ErrorKind::ConnectionReset, "reset") => false; "connection reset")]
+    fn transfer_error_for_timeout(source: io::Error) -> bool {
+        matches!(
+            GithubError::transfer("GE-Proton7-8.tar.gz", source),
+            GithubError::Timeout {
+                phase: TimeoutPhase::Read
+            }
+        )
+    }
+
+    #[test]
+    fn input_error_categories() {
+        let semver_err = SemVerError::EmptyIdentifier { input: String::new() };
+        let categories = [
+            TagKindError::UnknownString(String::new()).category(),
+            DownloadRequestError::MissingKind.category(),
+            DownloadRequestError::EmptyTag.category(),
+            TagParseError::NoVersionNumbers { tag: String::new() }.category(),
+            TagParseError::MissingVersionNumbers {
+                tag: String::new(),
+                found: 0,
+            }
+            .category(),
+            TagParseError::MissingReleaseCandidateNumber {
+                tag: String::new(),
+                position: 0,
+            }
+            .category(),
+            TagParseError::VersionNumberOverflow {
+                tag: String::new(),
+                position: 0,
+                component: "major",
+                number: String::new(),
+            }
+            .category(),
+            TagParseError::KindMismatch {
+                tag: String::new(),
+                kind: TagKind::Proton,
+            }
+            .category(),
+            semver_err.category(),
+            TagRangeError::EmptyBound { input: String::new() }.category(),
+            TagRangeError::MissingComparator { bound: String::new() }.category(),
+            TagRangeError::InvalidVersion {
+                bound: String::new(),
+                source: semver_err,
+            }
+            .category(),
+            TimestampError::InvalidFormat {
+                input: String::new(),
+                reason: "",
+            }
+            .category(),
+            TagPatternError::Empty.category(),
+        ];
+
+        assert!(categories
+            .iter()
+            .all(|category| *category == ErrorCategory::InvalidInput));
+    }
+
+    #[test_case("1.2" ; "missing component")]
+    #[test_case("1.x.3" ; "invalid component")]
+    #[test_case("1.2.3-" ; "empty identifier")]
+    #[test_case("1.2.3-a b" ; "invalid identifier")]
+    #[test_case("1.2.3+x" ; "invalid build")]
+    fn semver_error_category(version: &str) {
+        let err = version.parse::<crate::tag::SemVer>().unwrap_err();
+        assert_eq!(err.category(), ErrorCategory::InvalidInput);
+    }
 }
@@ -0,0 +1,332 @@
+//! Export the release history of a GE version kind.
+//!
+//! This module flattens the paginated GitHub release listing into simple records and provides serializers for JSON
+//! lines and CSV. The serializers write the fields in the order they are requested, which allows consumers to select
+//! only the columns they are interested in.
+use std::time::SystemTime;
+
+use serde_json::Value;
+
+use crate::download::response::GeRelease;
+use crate::download::GeDownload;
+use crate::error::GithubError;
+use crate::tag::{Tag, TagKind};
+use crate::timeutil;
+
+/// A flat representation of a single GE release.
+///
+/// Metadata that is missing in the GitHub release (e.g. releases without an archive asset) is represented by `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseRecord {
+    /// Tag name of the release.
+    pub tag: String,
+    /// Semantic version of the tag name, or `None` if the tag name contains no valid version (see `Tag::try_new`).
+    pub semver: Option<String>,
+    /// Publication date of the release. It is exported as an RFC 3339 timestamp in UTC.
+    pub published_at: Option<SystemTime>,
+    /// Size of the compressed archive in bytes.
+    pub size: Option<u64>,
+}
+
+impl ReleaseRecord {
+    fn value(&self, field: &RecordField) -> Option<String> {
+        match field {
+            RecordField::Tag => Some(self.tag.clone()),
+            RecordField::SemVer => self.semver.clone(),
+            RecordField::PublishedAt => self.published_at.map(timeutil::format_rfc3339),
+            RecordField::Size => self.size.map(|size| size.to_string()),
+        }
+    }
+
+    fn json_value(&self, field: &RecordField) -> Value {
+        match field {
+            RecordField::Size => self.size.map(Value::from).unwrap_or(Value::Null),
+            _ => self.value(field).map(Value::from).unwrap_or(Value::Null),
+        }
+    }
+}
+
+impl From<&GeRelease> for ReleaseRecord {
+    fn from(release: &GeRelease) -> Self {
+        ReleaseRecord {
+            tag: release.tag_name.clone(),
+            semver: Tag::try_new(&release.tag_name).ok().map(|tag| tag.semver().str()),
+            published_at: release.published_at,
+            size: release.find_tar_asset().and_then(|asset| asset.size),
+        }
+    }
+}
+
+/// A field of a `ReleaseRecord` that can be selected for export.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RecordField {
+    Tag,
+    SemVer,
+    PublishedAt,
+    Size,
+}
+
+impl RecordField {
+    /// Get all fields in their default export order.
+    pub fn values() -> Vec<RecordField> {
+        vec![
+            RecordField::Tag,
+            RecordField::SemVer,
+            RecordField::PublishedAt,
+            RecordField::Size,
+        ]
+    }
+
+    /// Get the name of the field used as CSV header and JSON key.
+    pub fn name(&self) -> &'static str {
+        match self {
+            RecordField::Tag => "tag",
+            RecordField::SemVer => "semver",
+            RecordField::PublishedAt => "published_at",
+            RecordField::Size => "size",
+        }
+    }
+}
+
+/// Fetch the complete release history for a GE version kind.
+///
+/// This walks the pages of the release listing until GitHub returns an empty page or the last page (`u8::MAX`) is
+/// reached. Releases which do not belong to `kind` are skipped. The records are returned in the order provided by
+/// GitHub (newest first).
+///
+/// # Errors
+///
+/// This method returns an error if any page of the release history could not be fetched.
+pub fn release_history(downloader: &dyn GeDownload, kind: TagKind) -> Result<Vec<ReleaseRecord>, GithubError> {
+    let mut records = Vec::new();
+    for page in 1..=u8::MAX {
+        let releases = downloader.fetch_releases(kind, page)?;
+        if releases.is_empty() {
+            break;
+        }
+
+        records.extend(
+            releases
+                .iter()
+                .filter(|release| release.is_kind(&kind))
+                .map(ReleaseRecord::from),
+        );
+    }
+    Ok(records)
+}
+
+/// Serialize records as JSON lines.
+///
+/// Every record is written as a JSON object on its own line containing the selected `fields` in the given order.
+/// Missing metadata is written as `null`.
+pub fn to_json_lines(records: &[ReleaseRecord], fields: &[RecordField]) -> String {
+    let mut output = String::new();
+    for record in records {
+        let members: Vec<String> = fields
+            .iter()
+            .map(|field| format!("{}:{}", Value::from(field.name()), record.json_value(field)))
+            .collect();
+        output.push('{');
+        output.push_str(&members.join(","));
+        output.push_str("}\n");
+    }
+    output
+}
+
+/// Serialize records as CSV.
+///
+/// The first line contains the names of the selected `fields`. Values are quoted when they contain a comma, a
+/// double quote or a line break. Missing metadata is written as an empty value.
+pub fn to_csv(records: &[ReleaseRecord], fields: &[RecordField]) -> String {
+    let mut output = csv_line(fields.iter().map(|field| String::from(field.name())));
+    for record in records {
+        output.push_str(&csv_line(
+            fields.iter().map(|field| record.value(field).unwrap_or_default()),
+        ));
+    }
+    output
+}
+
+fn csv_line(values: impl Iterator<Item = String>) -> String {
+    let mut line = values
+        .map(|value| csv_escape(&value))
+        .collect::<Vec<String>>()
+        .join(",");
+    line.push('\n');
+    line
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        String::from(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::download::fake::FakeGeDownloader;
+    use crate::download::response::GeAsset;
+
+    use super::*;
+
+    type ReleaseData = (&'static str, Option<&'static str>, Option<u64>);
+
+    fn downloader(kind: TagKind, releases: &[ReleaseData]) -> FakeGeDownloader {
+        releases
+            .iter()
+            .fold(FakeGeDownloader::new(), |downloader, (tag, published_at, size)| {
+                downloader.with_release(kind, release(tag, *published_at, *size))
+            })
+    }
+
+    fn release(tag: &str, published_at: Option<&str>, size: Option<u64>) -> GeRelease {
+        let mut archive = GeAsset::new(
+            format!("{}.tar.gz", tag),
+            String::from("application/gzip"),
+            String::new(),
+        );
+        archive.size = size;
+        let mut release = GeRelease::new(String::from(tag), vec![archive]);
+        release.published_at = published_at.map(|published_at| timeutil::parse_rfc3339(published_at).unwrap());
+        release
+    }
+
+    fn history() -> Vec<ReleaseRecord> {
+        let downloader = downloader(
+            TagKind::Proton,
+            &[
+                ("GE-Proton7-8", Some("2022-03-20T18:30:12Z"), Some(427333272)),
+                ("GE-Proton7-4", Some("2022-02-26T01:12:44Z"), None),
+                ("7.0rc3-GE-1", None, Some(398112)),
+                ("6.20-GE-1", None, None),
+            ],
+        );
+        release_history(&downloader, TagKind::Proton).unwrap()
+    }
+
+    #[test]
+    fn release_history_returns_newest_first() {
+        let records = history();
+
+        let tags: Vec<&str> = records.iter().map(|r| r.tag.as_str()).collect();
+        assert_eq!(tags, vec!["GE-Proton7-8", "GE-Proton7-4", "7.0rc3-GE-1", "6.20-GE-1"]);
+    }
+
+    #[test]
+    fn release_history_walks_all_pages() {
+        let tags: Vec<String> = (1..=45).rev().map(|patch| format!("GE-Proton7-{}", patch)).collect();
+        let downloader = tags.iter().fold(FakeGeDownloader::new(), |downloader, tag| {
+            downloader.with_release(TagKind::Proton, release(tag, None, None))
+        });
+
+        let records = release_history(&downloader, TagKind::Proton).unwrap();
+
+        let exported: Vec<&str> = records.iter().map(|r| r.tag.as_str()).collect();
+        assert_eq!(exported, tags);
+    }
+
+    #[test]
+    fn release_history_skips_releases_of_other_kinds() {
+        let downloader = downloader(
+            TagKind::lol(),
+            &[
+                ("6.20-GE-1", None, None),
+                ("6.19-GE-2", None, None),
+                ("6.16-GE-3-LoL", None, None),
+            ],
+        );
+        let records = release_history(&downloader, TagKind::lol()).unwrap();
+
+        let tags: Vec<&str> = records.iter().map(|r| r.tag.as_str()).collect();
+        assert_eq!(tags, vec!["6.16-GE-3-LoL"]);
+    }
+
+    #[test]
+    fn release_history_uses_archive_size() {
+        let records = history();
+
+        assert_eq!(
+            records[0],
+            ReleaseRecord {
+                tag: String::from("GE-Proton7-8"),
+                semver: Some(String::from("7.8.0")),
+                published_at: Some(timeutil::parse_rfc3339("2022-03-20T18:30:12Z").unwrap()),
+                size: Some(427333272),
+            }
+        );
+    }
+
+    #[test]
+    fn export_json_lines() {
+        let json = to_json_lines(&history(), &RecordField::values());
+        assert_eq!(
+            json,
+            r#"{"tag":"GE-Proton7-8","semver":"7.8.0","published_at":"2022-03-20T18:30:12Z","size":427333272}
+{"tag":"GE-Proton7-4","semver":"7.4.0","published_at":"2022-02-26T01:12:44Z","size":null}
+{"tag":"7.0rc3-GE-1","semver":"7.0.1-rc3","published_at":null,"size":398112}
+{"tag":"6.20-GE-1","semver":"6.20.1","published_at":null,"size":null}
+"#
+        );
+    }
+
+    #[test]
+    fn export_json_lines_with_selected_fields() {
+        let json = to_json_lines(&history()[..2], &[RecordField::Size, RecordField::Tag]);
+        assert_eq!(
+            json,
+            r#"{"size":427333272,"tag":"GE-Proton7-8"}
+{"size":null,"tag":"GE-Proton7-4"}
+"#
+        );
+    }
+
+    #[test]
+    fn export_csv() {
+        let csv = to_csv(&history(), &RecordField::values());
+        assert_eq!(
+            csv,
+            "tag,semver,published_at,size
+GE-Proton7-8,7.8.0,2022-03-20T18:30:12Z,427333272
+GE-Proton7-4,7.4.0,2022-02-26T01:12:44Z,
+7.0rc3-GE-1,7.0.1-rc3,,398112
+6.20-GE-1,6.20.1,,
+"
+        );
+    }
+
+    #[test]
+    fn export_csv_with_selected_fields() {
+        let csv = to_csv(&history(), &[RecordField::SemVer]);
+        assert_eq!(csv, "semver\n7.8.0\n7.4.0\n7.0.1-rc3\n6.20.1\n");
+    }
+
+    #[test]
+    fn export_csv_quotes_special_characters() {
+        let records = vec![ReleaseRecord {
+            tag: String::from("tag,with \"quotes\""),
+            semver: Some(String::from("1.0.0")),
+            published_at: None,
+            size: None,
+        }];
+
+        let csv = to_csv(&records, &[RecordField::Tag, RecordField::SemVer]);
+        assert_eq!(csv, "tag,semver\n\"tag,with \"\"quotes\"\"\",1.0.0\n");
+    }
+
+    #[test]
+    fn export_tag_without_version() {
+        let records = vec![ReleaseRecord::from(&release("lol", None, None))];
+
+        assert_eq!(records[0].semver, None);
+        assert_eq!(
+            to_csv(&records, &[RecordField::Tag, RecordField::SemVer]),
+            "tag,semver\nlol,\n"
+        );
+        assert_eq!(
+            to_json_lines(&records, &[RecordField::Tag, RecordField::SemVer]),
+            "{\"tag\":\"lol\",\"semver\":null}\n"
+        );
+    }
+}
@@ -1,5 +1,29 @@
+//! Common functionality used by GE-Man to manage GE Proton and Wine GE versions.
+//!
+//! # API stability
+//!
+//! This crate follows [Semantic Versioning](https://semver.org/spec/v2.0.0.html). The following parts of the public API
+//! are covered by the compile checks in `tests/api_surface.rs`, so an accidental breaking change fails the test suite:
+//! * every item of the `prelude` module
+//! * the constructors and accessors of `tag::Tag`, `tag::SemVer` and `range::TagRange`
+//! * the functions of the `archive` module
+//! * the `Send` and `Sync` bounds of `GeDownloader`, `Tag` and the error types
+//!
+//! Other modules (`capabilities`, `digest`, `export` and `timeutil`) are public as well, but may still change in minor
+//! releases before version 1.0.0. The same applies to the `arbitrary` module, which is only available with the
+//! `proptest` feature, to the `download::nonblocking` module, which is only available with the `async` feature, and
+//! to the `download::fake` module, which is only available with the `test-util` feature.
+
+#[cfg(any(test, feature = "proptest"))]
+pub mod arbitrary;
 pub mod archive;
+pub mod capabilities;
 pub mod config;
+pub mod digest;
 pub mod download;
 pub mod error;
+pub mod export;
+pub mod prelude;
+pub mod range;
 pub mod tag;
+pub mod timeutil;
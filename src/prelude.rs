@@ -0,0 +1,27 @@
+//! Re-exports of the types most consumers need.
+//!
+//! ```
+//! use ge_man_lib::prelude::*;
+//!
+//! let tag = Tag::new("GE-Proton7-8");
+//! assert_eq!(TagKind::from_tag(&tag), Some(TagKind::Proton));
+//! ```
+pub use crate::archive::{Compression, ExtractOptions, ExtractedTool};
+pub use crate::config::{LutrisConfig, SteamConfig};
+pub use crate::digest::{Algorithm, AssetDigest, ChecksumFile};
+pub use crate::download::batch::{BatchCancelHandle, BatchDownload, BatchItem};
+pub use crate::download::builder::{DownloadRequestBuilder, GeDownloaderBuilder, ProxyConfig, Timeouts};
+pub use crate::download::plan::DownloadPlan;
+pub use crate::download::progress::{DownloadProgress, NoProgress};
+pub use crate::download::response::{
+    ArchiveMetadata, AssetContentType, ConditionalRelease, DownloadedAssetPaths, DownloadedAssets, FetchedTags,
+    GeAsset, GeRelease, ResolvedAssets, TaggedRelease, Verification,
+};
+pub use crate::download::source::RepositorySource;
+pub use crate::download::{DownloadRequest, GeDownload, GeDownloader, MissingChecksumPolicy, ReadProgressWrapper};
+pub use crate::error::{
+    ChecksumError, ChecksumParseError, DownloadRequestError, ErrorCategory, ExtractError, GithubError,
+    LutrisConfigError, SemVerError, SteamConfigError, TagKindError, TagParseError, TagPatternError, TimeoutPhase,
+};
+pub use crate::range::{TagPattern, TagRange};
+pub use crate::tag::{Identifier, SemVer, Tag, TagIterExt, TagKind, TagParseOptions, VersionBump, WineTagKind};
@@ -0,0 +1,516 @@
+//! Version constraints for GE releases.
+//!
+//! A `TagRange` describes which versions are acceptable, e.g. "at least GE-Proton7-20 but below 8". It can be parsed
+//! from strings like `">=7.20, <8"` or built from `VersionBound`s. A `TagPattern` filters versions with wildcards like
+//! `7.*` or `*-LoL`.
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::{TagPatternError, TagRangeError};
+use crate::tag::{SemVer, Tag};
+
+const VERSION_NUMBER_COUNT: usize = 3;
+
+/// The comparison a `VersionBound` applies to a version.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Comparator {
+    Exact,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+impl Comparator {
+    fn str(&self) -> &'static str {
+        match self {
+            Comparator::Exact => "=",
+            Comparator::Greater => ">",
+            Comparator::GreaterOrEqual => ">=",
+            Comparator::Less => "<",
+            Comparator::LessOrEqual => "<=",
+        }
+    }
+
+    fn matches(&self, ordering: Ordering) -> bool {
+        match self {
+            Comparator::Exact => ordering.is_eq(),
+            Comparator::Greater => ordering.is_gt(),
+            Comparator::GreaterOrEqual => ordering.is_ge(),
+            Comparator::Less => ordering.is_lt(),
+            Comparator::LessOrEqual => ordering.is_le(),
+        }
+    }
+}
+
+/// A single bound of a `TagRange`, e.g. `>=7.20.0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionBound {
+    pub comparator: Comparator,
+    pub version: SemVer,
+}
+
+impl VersionBound {
+    pub fn new(comparator: Comparator, version: SemVer) -> Self {
+        VersionBound { comparator, version }
+    }
+
+    fn matches(&self, semver: &SemVer) -> bool {
+        self.comparator
+            .matches(precedence(semver).cmp(&precedence(&self.version)))
+    }
+}
+
+impl Display for VersionBound {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.comparator.str(), self.version)
+    }
+}
+
+impl FromStr for VersionBound {
+    type Err = TagRangeError;
+
+    /// Parse a bound like `>=7.20`.
+    ///
+    /// Missing minor and patch version numbers are treated as `0`, so `<8` is the same bound as `<8.0.0`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bound = s.trim();
+        let (comparator, version) = [
+            Comparator::GreaterOrEqual,
+            Comparator::LessOrEqual,
+            Comparator::Greater,
+            Comparator::Less,
+            Comparator::Exact,
+        ]
+        .into_iter()
+        .find_map(|comparator| {
+            bound
+                .strip_prefix(comparator.str())
+                .map(|version| (comparator, version.trim()))
+        })
+        .ok_or_else(|| TagRangeError::MissingComparator {
+            bound: String::from(bound),
+        })?;
+
+        let (numbers, identifier) = match version.split_once('-') {
+            Some((numbers, identifier)) => (numbers, Some(identifier)),
+            None => (version, None),
+        };
+        let mut version = String::from(numbers);
+        for _ in numbers.split('.').count()..VERSION_NUMBER_COUNT {
+            version.push_str(".0");
+        }
+        if let Some(identifier) = identifier {
+            version.push('-');
+            version.push_str(identifier);
+        }
+
+        let version = version.parse().map_err(|source| TagRangeError::InvalidVersion {
+            bound: String::from(bound),
+            source,
+        })?;
+        Ok(VersionBound::new(comparator, version))
+    }
+}
+
+/// A version constraint consisting of multiple `VersionBound`s.
+///
+/// A `Tag` satisfies a range if its version matches all bounds. Versions are compared by their version numbers and
+/// release candidate numbers, where a release candidate is lower than the final release of the same version
+/// (`7.0.0-rc3 < 7.0.0`). Other identifiers like `LoL` or `MF` mark a different build of the same version and are
+/// ignored during comparison.
+///
+/// Release candidates are only accepted if a bound explicitly allows them, i.e. a bound contains a release candidate
+/// of the same version numbers. Therefore, `7.0.0-rc3` does not satisfy `>=7.0.0-rc1, <8` but
+/// `7.0.0-rc3` satisfies `>=7.0.0-rc1`.
+///
+/// This struct supports `serde`'s serialization and deserialization traits. A range is serialized as its string
+/// representation, e.g. `">=7.20.0, <8.0.0"`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagRange {
+    bounds: Vec<VersionBound>,
+}
+
+impl TagRange {
+    /// Create a range from `bounds`. A range without bounds is satisfied by every version except release candidates.
+    pub fn new(bounds: Vec<VersionBound>) -> Self {
+        TagRange { bounds }
+    }
+
+    pub fn bounds(&self) -> &[VersionBound] {
+        &self.bounds
+    }
+
+    /// Does the version of `tag` satisfy all bounds of this range.
+    pub fn satisfies(&self, tag: &Tag) -> bool {
+        self.satisfies_semver(tag.semver())
+    }
+
+    /// Does `semver` satisfy all bounds of this range.
+    pub fn satisfies_semver(&self, semver: &SemVer) -> bool {
+        if semver.release_candidate().is_some() && !self.allows_release_candidate_of(semver) {
+            return false;
+        }
+        self.bounds.iter().all(|bound| bound.matches(semver))
+    }
+
+    fn allows_release_candidate_of(&self, semver: &SemVer) -> bool {
+        self.bounds.iter().any(|bound| {
+            bound.version.release_candidate().is_some() && version_numbers(&bound.version) == version_numbers(semver)
+        })
+    }
+}
+
+impl Display for TagRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let bounds: Vec<String> = self.bounds.iter().map(VersionBound::to_string).collect();
+        write!(f, "{}", bounds.join(", "))
+    }
+}
+
+impl FromStr for TagRange {
+    type Err = TagRangeError;
+
+    /// Parse a comma separated list of bounds like `">=7.20, <8"`. An empty string creates a range without bounds.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            return Ok(TagRange::default());
+        }
+
+        let bounds = s
+            .split(',')
+            .map(|bound| {
+                if bound.trim().is_empty() {
+                    Err(TagRangeError::EmptyBound { input: String::from(s) })
+                } else {
+                    bound.parse()
+                }
+            })
+            .collect::<Result<Vec<VersionBound>, TagRangeError>>()?;
+        Ok(TagRange::new(bounds))
+    }
+}
+
+impl Serialize for TagRange {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TagRange {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let str = String::deserialize(deserializer)?;
+        str.parse().map_err(D::Error::custom)
+    }
+}
+
+/// A component of a `TagPattern`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PatternComponent<T> {
+    Any,
+    Exact(T),
+}
+
+impl<T: PartialEq> PatternComponent<T> {
+    fn matches(&self, value: &T) -> bool {
+        match self {
+            PatternComponent::Any => true,
+            PatternComponent::Exact(expected) => expected == value,
+        }
+    }
+}
+
+impl<T: Display> Display for PatternComponent<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatternComponent::Any => write!(f, "*"),
+            PatternComponent::Exact(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// A wildcard pattern for versions, e.g. `7.*`, `6.20.*` or `*-LoL`.
+///
+/// A pattern consists of up to three dot separated version numbers optionally followed by a `-` and an identifier.
+/// Every version number can be replaced by the wildcard `*`. Version numbers that are left out are wildcards, so `7`
+/// and `7.*` are the same pattern. Patterns are matched against the semantic version of a `Tag` and not its string,
+/// therefore `GE-Proton7-8` matches `7.*`. Build numbers of a version are not considered.
+///
+/// The identifier of a version (e.g. `rc3`, `LoL` or `MF`) must match the identifier of the pattern:
+/// * A pattern without identifier only matches versions without identifier. `7.*` does not match `7.0.0-rc3` or
+///   `7.0.0-LoL`.
+/// * The identifier `*` matches versions with any or no identifier. `7.*-*` matches all of the versions above.
+/// * Any other identifier is matched case-insensitively and can contain `*` wildcards, e.g. `*-LoL` or `7.*-rc*`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagPattern {
+    major: PatternComponent<u8>,
+    minor: PatternComponent<u8>,
+    patch: PatternComponent<u8>,
+    identifier: Option<String>,
+}
+
+impl TagPattern {
+    /// Does the version of `tag` match this pattern.
+    pub fn matches(&self, tag: &Tag) -> bool {
+        self.matches_semver(tag.semver())
+    }
+
+    /// Does `semver` match this pattern.
+    pub fn matches_semver(&self, semver: &SemVer) -> bool {
+        let identifier = semver.parsed_identifier().map(|identifier| identifier.to_string());
+        let identifier_matches = match (self.identifier.as_deref(), identifier) {
+            (Some("*"), _) => true,
+            (Some(pattern), Some(identifier)) => glob_matches(pattern, &identifier),
+            (None, None) => true,
+            _ => false,
+        };
+
+        identifier_matches
+            && self.major.matches(&semver.major())
+            && self.minor.matches(&semver.minor())
+            && self.patch.matches(&semver.patch())
+    }
+}
+
+impl Display for TagPattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(identifier) = &self.identifier {
+            write!(f, "-{}", identifier)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TagPattern {
+    type Err = TagPatternError;
+
+    /// Parse a pattern like `7.*`, `6.20.*` or `*-LoL`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let pattern = s.trim();
+        if pattern.is_empty() {
+            return Err(TagPatternError::Empty);
+        }
+
+        let (numbers, identifier) = match pattern.split_once('-') {
+            Some((_, "")) => {
+                return Err(TagPatternError::EmptyIdentifier {
+                    pattern: String::from(s),
+                })
+            }
+            Some((_, identifier)) if !identifier.chars().all(|c| c.is_ascii_alphanumeric() || c == '*') => {
+                return Err(TagPatternError::InvalidIdentifier {
+                    pattern: String::from(s),
+                })
+            }
+            Some((numbers, identifier)) => (numbers, Some(String::from(identifier))),
+            None => (pattern, None),
+        };
+
+        let components: Vec<&str> = numbers.split('.').collect();
+        if components.len() > VERSION_NUMBER_COUNT {
+            return Err(TagPatternError::TooManyComponents {
+                pattern: String::from(s),
+            });
+        }
+        let components = components
+            .into_iter()
+            .map(|component| match component {
+                "*" => Ok(PatternComponent::Any),
+                _ if !component.is_empty() && component.chars().all(|c| c.is_ascii_digit()) => component
+                    .parse()
+                    .map(PatternComponent::Exact)
+                    .map_err(|_| TagPatternError::InvalidComponent {
+                        pattern: String::from(s),
+                        component: String::from(component),
+                    }),
+                _ => Err(TagPatternError::InvalidComponent {
+                    pattern: String::from(s),
+                    component: String::from(component),
+                }),
+            })
+            .collect::<Result<Vec<PatternComponent<u8>>, TagPatternError>>()?;
+
+        let mut components = components.into_iter();
+        let mut next_component = || components.next().unwrap_or(PatternComponent::Any);
+        Ok(TagPattern {
+            major: next_component(),
+            minor: next_component(),
+            patch: next_component(),
+            identifier,
+        })
+    }
+}
+
+/// Match `value` against `pattern`, where `*` in the pattern matches any sequence of characters. Letters are compared
+/// case-insensitively.
+fn glob_matches(pattern: &str, value: &str) -> bool {
+    let pattern = pattern.to_ascii_lowercase();
+    let value = value.to_ascii_lowercase();
+    let mut parts = pattern.split('*');
+    // `split` always returns at least one part.
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = value.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+fn version_numbers(semver: &SemVer) -> (u8, u8, u8) {
+    (semver.major(), semver.minor(), semver.patch())
+}
+
+fn precedence(semver: &SemVer) -> (u8, u8, u8, u64) {
+    let (major, minor, patch) = version_numbers(semver);
+    let release_candidate = semver.release_candidate().map(u64::from).unwrap_or(u64::MAX);
+    (major, minor, patch, release_candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case("7.*", "GE-Proton7-8" => true)]
+    #[test_case("7.*", "GE-Proton8-1" => false)]
+    #[test_case("7", "GE-Proton7-8" => true; "left out numbers are wildcards")]
+    #[test_case("6.20.*", "6.20-GE-1" => true)]
+    #[test_case("6.20.*", "6.21-GE-1" => false)]
+    #[test_case("*.20.1", "6.20-GE-1" => true)]
+    #[test_case("6.20.1", "6.20-GE-1" => true)]
+    #[test_case("8.26.0", "GE-Proton8-26-0-2" => true; "build numbers are ignored")]
+    #[test_case("7.*", "7.0rc3-GE-1" => false; "release candidate without identifier")]
+    #[test_case("7.*-*", "7.0rc3-GE-1" => true; "release candidate with any identifier")]
+    #[test_case("7.*-*", "GE-Proton7-8" => true; "release with any identifier")]
+    #[test_case("7.*-rc*", "7.0rc3-GE-1" => true; "release candidate with identifier wildcard")]
+    #[test_case("7.*-rc*", "GE-Proton7-8" => false; "release with identifier wildcard")]
+    #[test_case("*-LoL", "6.16-GE-3-LoL" => true)]
+    #[test_case("*-lol", "6.16-GE-3-LoL" => true; "case-insensitive identifier")]
+    #[test_case("*-LoL", "6.16-GE-3" => false)]
+    #[test_case("*-LoL", "5.11-GE-1-MF" => false)]
+    #[test_case("*", "5.11-GE-1-MF" => false; "wildcard without identifier")]
+    #[test_case("*-h*x", "GE-Proton8-25-hotfix" => true; "identifier with inner wildcard")]
+    fn matches_pattern(pattern: &str, tag: &str) -> bool {
+        pattern.parse::<TagPattern>().unwrap().matches(&Tag::new(tag))
+    }
+
+    #[test_case("7.*" => "7.*.*")]
+    #[test_case("*-LoL" => "*.*.*-LoL")]
+    #[test_case(" 6.20.1 " => "6.20.1")]
+    fn display_pattern(pattern: &str) -> String {
+        pattern.parse::<TagPattern>().unwrap().to_string()
+    }
+
+    #[test_case("" => TagPatternError::Empty; "empty")]
+    #[test_case("7.*-" => TagPatternError::EmptyIdentifier { pattern: String::from("7.*-") }; "empty identifier")]
+    #[test_case("7.*-L o" => TagPatternError::InvalidIdentifier { pattern: String::from("7.*-L o") }; "invalid identifier")]
+    #[test_case("7.1.2.3" => TagPatternError::TooManyComponents { pattern: String::from("7.1.2.3") }; "too many components")]
+    #[test_case("7.x" => TagPatternError::InvalidComponent { pattern: String::from("7.x"), component: String::from("x") }; "letter")]
+    #[test_case("7.2*" => TagPatternError::InvalidComponent { pattern: String::from("7.2*"), component: String::from("2*") }; "partial wildcard")]
+    #[test_case("7..1" => TagPatternError::InvalidComponent { pattern: String::from("7..1"), component: String::new() }; "empty component")]
+    #[test_case("256.*" => TagPatternError::InvalidComponent { pattern: String::from("256.*"), component: String::from("256") }; "number too large")]
+    fn parse_invalid_pattern(pattern: &str) -> TagPatternError {
+        pattern.parse::<TagPattern>().unwrap_err()
+    }
+
+    #[test_case(">=7.20, <8", "GE-Proton7-20" => true)]
+    #[test_case(">=7.20, <8", "GE-Proton7-42" => true)]
+    #[test_case(">=7.20, <8", "GE-Proton7-8" => false)]
+    #[test_case(">=7.20, <8", "GE-Proton8-1" => false)]
+    #[test_case(">7.20, <=8", "GE-Proton7-20" => false)]
+    #[test_case(">7.20, <=8", "GE-Proton8-0" => true)]
+    #[test_case("=6.20.1", "6.20-GE-1" => true)]
+    #[test_case("=6.20.1", "6.21-GE-1" => false)]
+    #[test_case("", "GE-Proton7-8" => true)]
+    fn satisfies_bounds(range: &str, tag: &str) -> bool {
+        range.parse::<TagRange>().unwrap().satisfies(&Tag::new(tag))
+    }
+
+    #[test_case(">=7.0.0", "7.0rc3-GE-1" => false)]
+    #[test_case(">=6.0, <8", "7.0rc3-GE-1" => false)]
+    #[test_case("", "7.0rc3-GE-1" => false)]
+    #[test_case(">=7.0.1-rc1", "7.0rc3-GE-1" => true)]
+    #[test_case(">=7.0.1-rc4", "7.0rc3-GE-1" => false)]
+    #[test_case("<7.0.1", "7.0rc3-GE-1" => false)]
+    #[test_case("<7.0.1, >=7.0.1-rc1", "7.0rc3-GE-1" => true)]
+    fn satisfies_bounds_with_release_candidate(range: &str, tag: &str) -> bool {
+        range.parse::<TagRange>().unwrap().satisfies(&Tag::new(tag))
+    }
+
+    #[test_case(">=6.16", "6.16-GE-3-LoL" => true)]
+    #[test_case("<=6.16.3", "6.16-GE-3-LoL" => true; "at most bound with LoL")]
+    #[test_case("=6.16.3", "6.16-GE-3-LoL" => true; "exact bound with LoL")]
+    #[test_case("=6.16.3-LoL", "6.16-GE-3" => true)]
+    #[test_case("<6.16.3", "6.16-GE-3-LoL" => false)]
+    fn satisfies_bounds_with_lol_identifier(range: &str, tag: &str) -> bool {
+        range.parse::<TagRange>().unwrap().satisfies(&Tag::new(tag))
+    }
+
+    #[test]
+    fn build_range_programmatically() {
+        let range = TagRange::new(vec![
+            VersionBound::new(Comparator::GreaterOrEqual, Tag::new("GE-Proton7-20").semver().clone()),
+            VersionBound::new(Comparator::Less, "8.0.0".parse().unwrap()),
+        ]);
+
+        assert_eq!(range, ">=7.20, <8".parse().unwrap());
+        assert!(range.satisfies(&Tag::new("GE-Proton7-35")));
+    }
+
+    #[test_case(">=7.20, <8" => ">=7.20.0, <8.0.0")]
+    #[test_case(" = 6.20.1-rc2 " => "=6.20.1-rc2")]
+    #[test_case("" => "")]
+    fn display_range(range: &str) -> String {
+        range.parse::<TagRange>().unwrap().to_string()
+    }
+
+    #[test_case(">=7.20, " => TagRangeError::EmptyBound { input: String::from(">=7.20, ") }; "with empty bound")]
+    #[test_case("7.20" => TagRangeError::MissingComparator { bound: String::from("7.20") }; "without comparator")]
+    #[test_case("~7.20" => TagRangeError::MissingComparator { bound: String::from("~7.20") }; "with unknown comparator")]
+    fn parse_invalid_range(range: &str) -> TagRangeError {
+        range.parse::<TagRange>().unwrap_err()
+    }
+
+    #[test]
+    fn parse_range_with_invalid_version() {
+        let err = ">=7.x".parse::<TagRange>().unwrap_err();
+        assert!(matches!(err, TagRangeError::InvalidVersion { ref bound, .. } if bound == ">=7.x"));
+        assert_eq!(
+            err.to_string(),
+            r#"Bound ">=7.x" contains an invalid version: The minor version number of "7.x.0" is not a valid number"#
+        );
+    }
+
+    #[test]
+    fn serialization_round_trip() {
+        let range: TagRange = ">=7.20, <8".parse().unwrap();
+
+        let json = serde_json::to_string(&range).unwrap();
+        assert_eq!(json, r#"">=7.20.0, <8.0.0""#);
+
+        let deserialized: TagRange = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, range);
+    }
+
+    #[test]
+    fn deserialize_invalid_range() {
+        let result = serde_json::from_str::<TagRange>(r#""7.20""#);
+        assert!(result.is_err());
+    }
+}
@@ -6,49 +6,161 @@ use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::path::Path;
+use std::str::FromStr;
+use std::sync::OnceLock;
 
-use lazy_static::lazy_static;
-use regex::{Captures, Match, Regex};
-use serde::{Deserialize, Serialize};
+use percent_encoding::percent_decode_str;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::error::TagKindError;
+use crate::error::{SemVerError, TagKindError, TagParseError};
 
 const PROTON: &str = "PROTON";
 const WINE: &str = "WINE";
 const LOL_WINE: &str = "LOL_WINE";
 
-const RELEASE_CANDIDATE_MARKER: &str = "rc";
-const FIRST_GROUP: usize = 1;
+const FILE_EXTENSIONS: [&str; 5] = [".tar.gz", ".tar.xz", ".tar.zst", ".sha512sum", ".sha256sum"];
+const PROTON_ARCHIVE_EXTENSION: &str = ".tar.gz";
+const WINE_ARCHIVE_EXTENSION: &str = ".tar.xz";
+const CHECKSUM_EXTENSION: &str = ".sha512sum";
+// Tags of GE releases start with this prefix since the renaming that happened with GE-Proton7.
+const RENAMED_TAG_PREFIX: &str = "GE-Proton";
+const PROTON_ASSET_PREFIX: &str = "Proton-";
 
-lazy_static! {
-    static ref NUMBERS: Regex = Regex::new(r"(\d+)").unwrap();
-    static ref TAG_MARKERS: Vec<String> = vec![String::from("rc"), String::from("LoL"), String::from("MF")];
-}
+const RELEASE_CANDIDATE_MARKER: &str = "rc";
+const LOL_MARKER: &str = "LoL";
+const MF_MARKER: &str = "MF";
+const VERSION_NUMBER_COUNT: usize = 3;
+const ARCHITECTURE_MARKERS: [&str; 4] = ["x86_64", "amd64", "i686", "i386"];
+// Words that appear in tags or file names but do not describe the version.
+const NON_VERSION_WORDS: [&str; 11] = [
+    "ge", "proton", "wine", "lutris", "lol", "mf", "rc", "tar", "gz", "xz", "zst",
+];
+const TAG_MARKERS: [&str; 2] = [LOL_MARKER, MF_MARKER];
 
 /// Struct used to contain semantic versioning information.
 ///
 /// The primary use of this struct is to extract information from version strings in the GitHub assets and to provide
 /// it a as a semantic version.
-#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+///
+/// Version numbers after the patch version are stored as build numbers. They are compared after the patch version, so
+/// `8.26.0` is lower than `8.26.0+2`.
+///
+/// Version numbers that are missing in a tag are filled with zeros. Which numbers were filled in can be checked with
+/// `SemVer::inferred_minor` and `SemVer::inferred_patch`. Inferred numbers are not considered when comparing versions.
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct SemVer {
     major: u8,
     minor: u8,
     patch: u8,
-    identifier: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    build: Vec<u32>,
+    identifier: Option<Identifier>,
+    #[serde(skip)]
+    inferred_numbers: usize,
 }
 
 impl SemVer {
-    fn new(major: u8, minor: u8, patch: u8, identifier: Option<String>) -> Self {
+    fn new(major: u8, minor: u8, patch: u8, identifier: Option<Identifier>) -> Self {
         SemVer {
             major,
             minor,
             patch,
+            build: Vec::new(),
             identifier,
+            inferred_numbers: 0,
+        }
+    }
+
+    /// Was the major version number missing in the tag and filled in with zero.
+    pub fn inferred_major(&self) -> bool {
+        self.inferred_numbers >= 3
+    }
+
+    /// Was the minor version number missing in the tag and filled in with zero.
+    pub fn inferred_minor(&self) -> bool {
+        self.inferred_numbers >= 2
+    }
+
+    /// Was the patch version number missing in the tag and filled in with zero.
+    pub fn inferred_patch(&self) -> bool {
+        self.inferred_numbers >= 1
+    }
+
+    /// Get the identifier of this `SemVer` as a string, e.g. `rc3` or `LoL`.
+    #[deprecated(note = "Use `SemVer::parsed_identifier` to match on the `Identifier` instead of its string.")]
+    pub fn identifier(&self) -> Option<String> {
+        self.identifier.as_ref().map(Identifier::to_string)
+    }
+
+    /// Get the identifier of this `SemVer`.
+    ///
+    /// Returns `None` if this `SemVer` has no identifier.
+    pub fn parsed_identifier(&self) -> Option<&Identifier> {
+        self.identifier.as_ref()
+    }
+
+    /// Get the release candidate number of this `SemVer`.
+    ///
+    /// Returns `None` if the identifier is not a release candidate identifier like `rc3`.
+    pub fn release_candidate(&self) -> Option<u32> {
+        match self.identifier {
+            Some(Identifier::ReleaseCandidate(number)) => Some(number),
+            _ => None,
         }
     }
 
-    pub fn identifier(&self) -> &Option<String> {
-        &self.identifier
+    /// Get the kind of the identifier of this `SemVer`.
+    ///
+    /// Returns `None` if this `SemVer` has no identifier.
+    pub fn identifier_kind(&self) -> Option<IdentifierKind> {
+        self.identifier.as_ref().map(Identifier::kind)
+    }
+
+    /// Classify the change from this `SemVer` to `other`.
+    ///
+    /// The most significant differing version number decides the kind of the bump. If all version numbers are equal
+    /// and only the identifiers differ, the change is `VersionBump::IdentifierOnly`. A final release is considered
+    /// newer than its release candidates, so `7.0.0-rc3` to `7.0.0` is an identifier only bump, and `7.0.0` to
+    /// `7.0.0-rc3` is a downgrade. Identifiers like `LoL` or `MF` mark a different build and not an older or newer one.
+    pub fn diff(&self, other: &SemVer) -> VersionBump {
+        let part = if self.major != other.major {
+            VersionPart::Major
+        } else if self.minor != other.minor {
+            VersionPart::Minor
+        } else if self.patch != other.patch {
+            VersionPart::Patch
+        } else if self.build != other.build {
+            VersionPart::Build
+        } else if self.identifier != other.identifier {
+            VersionPart::Identifier
+        } else {
+            return VersionBump::None;
+        };
+
+        let is_downgrade = match part {
+            VersionPart::Identifier => {
+                let rc_rank = |semver: &SemVer| match semver.identifier {
+                    Some(Identifier::ReleaseCandidate(rc)) => Some(rc as u64),
+                    None => Some(u64::MAX),
+                    Some(_) => None,
+                };
+                matches!((rc_rank(self), rc_rank(other)), (Some(from), Some(to)) if to < from)
+            }
+            _ => {
+                (other.major, other.minor, other.patch, &other.build)
+                    < (self.major, self.minor, self.patch, &self.build)
+            }
+        };
+
+        match (is_downgrade, part) {
+            (true, part) => VersionBump::Downgrade(part),
+            (false, VersionPart::Major) => VersionBump::Major,
+            (false, VersionPart::Minor) => VersionBump::Minor,
+            (false, VersionPart::Patch) => VersionBump::Patch,
+            (false, VersionPart::Build) => VersionBump::Build,
+            (false, VersionPart::Identifier) => VersionBump::IdentifierOnly,
+        }
     }
 
     pub fn major(&self) -> u8 {
@@ -63,18 +175,23 @@ impl SemVer {
         self.patch
     }
 
+    /// Get the version numbers that follow the patch version.
+    pub fn build(&self) -> &[u32] {
+        &self.build
+    }
+
     pub fn str(&self) -> String {
-        if self.identifier.is_some() {
-            format!(
-                "{}.{}.{}-{}",
-                self.major,
-                self.minor,
-                self.patch,
-                self.identifier.as_ref().unwrap()
-            )
-        } else {
-            format!("{}.{}.{}", self.major, self.minor, self.patch)
+        let mut str = format!("{}.{}.{}", self.major, self.minor, self.patch);
+        if let Some(identifier) = &self.identifier {
+            str.push('-');
+            str.push_str(&identifier.to_string());
+        }
+        if !self.build.is_empty() {
+            let build: Vec<String> = self.build.iter().map(u32::to_string).collect();
+            str.push('+');
+            str.push_str(&build.join("."));
         }
+        str
     }
 
     /// Create a `SemVer` type from a git tag.
@@ -83,308 +200,1420 @@ impl SemVer {
     /// compliant with the semver standard. An example of this is the tag "5.0-rc5-GE-1", it should be represented as
     /// "5.0.1-rc". At the moment, only the "rc" keyword has been observed in git tags and, therefore, only this keyword
     /// is explicitly handled differently.
-    fn from_git_tag(git_tag: &String) -> Self {
-        let number_captures: Vec<Captures> = NUMBERS.captures_iter(&git_tag).collect();
-
-        let semver = if git_tag.contains(RELEASE_CANDIDATE_MARKER) {
-            if let Some(rc_match) = SemVer::get_rc_match(&git_tag, &number_captures) {
-                let captures_without_rc: Vec<Captures> = number_captures
-                    .into_iter()
-                    .filter(|cap| cap.get(FIRST_GROUP).unwrap().ne(&rc_match))
-                    .collect();
-                let mut semver = SemVer::create_semver_from_regex(&captures_without_rc);
-                let rc_marker = format!("rc{}", rc_match.as_str());
-
-                semver.identifier = Some(rc_marker);
-                semver
-            } else {
-                panic!("Git tag is not parsable!");
-            }
+    fn from_git_tag(git_tag: &str) -> Self {
+        match SemVer::parse_git_tag(git_tag, false) {
+            Ok(semver) => semver,
+            Err(err) => panic!("Git tag is not parsable! {}", err),
+        }
+    }
+
+    /// Create a `SemVer` type from a git tag or return the reason why the tag can not be parsed.
+    ///
+    /// A release candidate marker without a number is always an error. In `strict` mode tags with missing version
+    /// numbers or with numbers that are too large for their version component are rejected as well, otherwise missing
+    /// numbers are filled in with zeros and large numbers are capped.
+    fn parse_git_tag(git_tag: &str, strict: bool) -> Result<Self, TagParseError> {
+        // Architecture names like "x86_64" in Lutris runner names (e.g. "lutris-GE-Proton8-26-x86_64") contain
+        // numbers that are not part of the version. Prefixes like "lutris-" or "wine-" contain no numbers. The names
+        // are masked instead of removed to keep the positions of the remaining characters.
+        let masked = ARCHITECTURE_MARKERS.iter().fold(String::from(git_tag), |tag, marker| {
+            tag.replace(marker, &"-".repeat(marker.len()))
+        });
+        let mut numbers = SemVer::scan_numbers(&masked);
+
+        let rc_number = match masked.find(RELEASE_CANDIDATE_MARKER) {
+            Some(marker_position) => match SemVer::find_rc_number(&masked, &numbers) {
+                Some(rc_idx) => Some(numbers.remove(rc_idx)),
+                None => {
+                    return Err(TagParseError::MissingReleaseCandidateNumber {
+                        tag: String::from(git_tag),
+                        position: marker_position,
+                    })
+                }
+            },
+            None => None,
+        };
+
+        if strict {
+            SemVer::check_numbers(git_tag, &numbers, rc_number)?;
+        }
+
+        let semver = if let Some((_, rc_number)) = rc_number {
+            let mut semver = SemVer::create_semver_from_numbers(&numbers);
+            semver.identifier = Some(Identifier::ReleaseCandidate(SemVer::parse_number(rc_number)));
+            semver
         } else {
-            let mut semver = SemVer::create_semver_from_regex(&number_captures);
+            let git_tag = masked.as_str();
+            let mut semver = SemVer::create_semver_from_numbers(&numbers);
 
-            for marker in &*TAG_MARKERS {
+            for marker in TAG_MARKERS {
                 if git_tag.contains(marker) {
-                    semver.identifier = Some(marker.to_owned());
+                    semver.identifier = Some(Identifier::from(marker));
                 }
             }
 
+            if semver.identifier.is_none() {
+                semver.identifier = SemVer::find_suffix(git_tag).map(Identifier::Other);
+            }
+
             semver
         };
 
-        semver
+        Ok(semver)
     }
 
-    fn create_semver_from_regex(captures: &[Captures]) -> Self {
-        let mut numbers: Vec<u8> = Vec::with_capacity(3);
-
-        for cap in captures {
-            numbers.push((&cap[1]).parse().unwrap())
+    /// Check that `numbers` contain all version numbers and that no number is too large for its version component.
+    fn check_numbers(
+        git_tag: &str,
+        numbers: &[(usize, &str)],
+        rc_number: Option<(usize, &str)>,
+    ) -> Result<(), TagParseError> {
+        match numbers.len() {
+            0 => {
+                return Err(TagParseError::NoVersionNumbers {
+                    tag: String::from(git_tag),
+                })
+            }
+            found if found < VERSION_NUMBER_COUNT => {
+                return Err(TagParseError::MissingVersionNumbers {
+                    tag: String::from(git_tag),
+                    found,
+                })
+            }
+            _ => (),
         }
 
-        // In the case that we do not have enough matches to fill the semver string we fill it with empty zeros.
-        let numbers_len = numbers.len();
-        if numbers_len < 3 {
-            for _ in numbers_len..3 {
-                numbers.push(0);
+        let components = ["major", "minor", "patch"];
+        let version_numbers = numbers
+            .iter()
+            .zip(components)
+            .map(|(number, component)| (number, component, u64::from(u8::MAX)));
+        let other_numbers = numbers
+            .iter()
+            .skip(VERSION_NUMBER_COUNT)
+            .map(|number| (number, "build", u64::from(u32::MAX)))
+            .chain(
+                rc_number
+                    .iter()
+                    .map(|number| (number, "release candidate", u64::from(u32::MAX))),
+            );
+
+        for (&(position, digits), component, max) in version_numbers.chain(other_numbers) {
+            if SemVer::parse_number::<u64>(digits) > max {
+                return Err(TagParseError::VersionNumberOverflow {
+                    tag: String::from(git_tag),
+                    position,
+                    component,
+                    number: String::from(digits),
+                });
             }
         }
-
-        SemVer::new(numbers[0], numbers[1], numbers[2], None)
+        Ok(())
     }
 
-    fn get_rc_match<'a>(git_tag: &String, number_captures: &Vec<Captures<'a>>) -> Option<Match<'a>> {
-        // Skip the first version number match because it might be the same number as the rc candidate.
-        for cap in number_captures.iter().skip(1) {
-            let version_number = &cap[FIRST_GROUP];
-            let rc_query = format!("{}{}", RELEASE_CANDIDATE_MARKER, version_number);
-            if git_tag.contains(&rc_query) {
-                // Since every match contains a single capture group, we always get the first capture group.
-                return Some(cap.get(FIRST_GROUP).unwrap().clone());
+    /// Get all runs of ASCII digits in `git_tag` together with their start index.
+    fn scan_numbers(git_tag: &str) -> Vec<(usize, &str)> {
+        let bytes = git_tag.as_bytes();
+        let mut numbers = Vec::new();
+        let mut idx = 0;
+
+        while idx < bytes.len() {
+            if bytes[idx].is_ascii_digit() {
+                let start = idx;
+                while idx < bytes.len() && bytes[idx].is_ascii_digit() {
+                    idx += 1;
+                }
+                numbers.push((start, &git_tag[start..idx]));
+            } else {
+                idx += 1;
             }
         }
-        None
+        numbers
     }
-}
 
-impl Display for SemVer {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.str())
+    /// Parse a run of ASCII digits. Numbers that do not fit into `T` are capped at the maximum of `T`.
+    fn parse_number<T: TryFrom<u64> + Bounded>(digits: &str) -> T {
+        let number = digits.bytes().fold(0u64, |number, digit| {
+            number.saturating_mul(10).saturating_add(u64::from(digit - b'0'))
+        });
+        T::try_from(number).unwrap_or(T::MAX)
     }
-}
 
-/// Struct for representing a GitHub release tag and providing an option to be transformed into a semantic version.
-///
-/// Internally this struct parses the given release tag to create a semantic version representation from it. This is
-/// done because it is much easier to perform comparisons between `6.20.1` and `7.8.0` than with `Proton-6.20-GE-1` and
-/// `GE-Proton7-8`.
-///
-/// This struct supports `serde`'s serialization and deserialization traits.
-#[derive(Clone, Serialize, Deserialize, Debug)]
-pub struct Tag {
-    // Alias for versions before ge-man-lib version 0.2.0.
-    #[serde(alias = "value")]
-    str: String,
-    semver: SemVer,
-}
-
-impl Tag {
-    pub fn new<S: Into<String>>(git_tag: S) -> Self {
-        let value = git_tag.into();
-        let semver = SemVer::from_git_tag(&value);
+    fn create_semver_from_numbers(numbers: &[(usize, &str)]) -> Self {
+        let mut version_numbers: Vec<u8> = numbers
+            .iter()
+            .take(VERSION_NUMBER_COUNT)
+            .map(|(_, digits)| SemVer::parse_number(digits))
+            .collect();
 
-        Tag { str: value, semver }
+        // In the case that we do not have enough matches to fill the semver string we fill it with empty zeros.
+        let inferred_numbers = VERSION_NUMBER_COUNT - version_numbers.len();
+        version_numbers.resize(VERSION_NUMBER_COUNT, 0);
+
+        let mut semver = SemVer::new(version_numbers[0], version_numbers[1], version_numbers[2], None);
+        semver.build = numbers
+            .iter()
+            .skip(VERSION_NUMBER_COUNT)
+            .map(|(_, digits)| SemVer::parse_number(digits))
+            .collect();
+        semver.inferred_numbers = inferred_numbers;
+        semver
     }
 
-    /// Get this `Tag` as a semantic version.
-    pub fn semver(&self) -> &SemVer {
-        &self.semver
+    /// Find a textual suffix like "hotfix" in `GE-Proton8-25-hotfix`.
+    ///
+    /// Any purely alphabetic part behind the last version number that is not a known word (e.g. "GE" or "tar") is
+    /// considered a suffix. If multiple suffixes exist, the last one is returned.
+    fn find_suffix(git_tag: &str) -> Option<String> {
+        let suffix_start = git_tag.rfind(|c: char| c.is_ascii_digit()).map_or(0, |idx| idx + 1);
+        git_tag[suffix_start..]
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .rfind(|part| {
+                !part.is_empty()
+                    && part.chars().all(|c| c.is_ascii_alphabetic())
+                    && !NON_VERSION_WORDS.iter().any(|word| part.eq_ignore_ascii_case(word))
+            })
+            .map(String::from)
     }
 
-    /// Get the string value of this `Tag`.
-    pub fn str(&self) -> &String {
-        &self.str
+    /// Find the index of the release candidate number, which is the number directly behind an "rc" marker.
+    fn find_rc_number(git_tag: &str, numbers: &[(usize, &str)]) -> Option<usize> {
+        // The first number is the major version and never a release candidate number.
+        numbers
+            .iter()
+            .enumerate()
+            .skip(1)
+            .find(|(_, (start, _))| git_tag[..*start].ends_with(RELEASE_CANDIDATE_MARKER))
+            .map(|(idx, _)| idx)
     }
 }
 
-impl Default for Tag {
-    fn default() -> Self {
-        Tag::new("")
-    }
+/// Integer types a version number can be parsed into.
+trait Bounded {
+    const MAX: Self;
 }
 
-impl From<String> for Tag {
-    fn from(s: String) -> Self {
-        Tag::new(&s)
-    }
+impl Bounded for u8 {
+    const MAX: Self = u8::MAX;
 }
 
-impl From<&str> for Tag {
-    fn from(s: &str) -> Self {
-        Tag::new(s)
-    }
+impl Bounded for u32 {
+    const MAX: Self = u32::MAX;
 }
 
-impl From<Option<String>> for Tag {
-    fn from(opt: Option<String>) -> Self {
-        match opt {
-            Some(str) => Tag::new(str),
-            None => Tag::default(),
-        }
+impl Bounded for u64 {
+    const MAX: Self = u64::MAX;
+}
+
+impl PartialEq for SemVer {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
     }
 }
 
-impl From<Option<&str>> for Tag {
-    fn from(opt: Option<&str>) -> Self {
-        match opt {
-            Some(str) => Tag::new(str),
-            None => Tag::default(),
-        }
+impl Eq for SemVer {}
+
+impl PartialOrd for SemVer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-impl AsRef<Path> for Tag {
-    fn as_ref(&self) -> &Path {
-        self.str.as_ref()
+impl Ord for SemVer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch, &self.build)
+            .cmp(&(other.major, other.minor, other.patch, &other.build))
+            .then_with(|| Identifier::cmp_optional(&self.identifier, &other.identifier))
     }
 }
 
-impl AsRef<str> for Tag {
-    fn as_ref(&self) -> &str {
-        self.str.as_ref()
+impl Hash for SemVer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.major, self.minor, self.patch, &self.build, &self.identifier).hash(state)
     }
 }
 
-impl Display for Tag {
+impl Display for SemVer {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.str)
+        write!(f, "{}", self.str())
     }
 }
 
-impl PartialEq<Tag> for Tag {
-    fn eq(&self, other: &Tag) -> bool {
-        self.semver.eq(other.semver())
+impl FromStr for SemVer {
+    type Err = SemVerError;
+
+    /// Parse a version string in the format produced by `SemVer::str`.
+    ///
+    /// The version string must consist of exactly three dot separated version numbers optionally followed by a `-`
+    /// and an identifier of ASCII letters and digits, e.g. `6.20.1` or `7.0.0-rc3`. Build numbers can be appended as
+    /// dot separated numbers after a `+`, e.g. `8.26.0+2`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SemVer::parse_version(s, false)
     }
 }
 
-impl PartialOrd<Tag> for Tag {
-    fn partial_cmp(&self, other: &Tag) -> Option<Ordering> {
-        self.semver.partial_cmp(other.semver())
+impl TryFrom<&str> for SemVer {
+    type Error = SemVerError;
+
+    /// Parse a version string like `SemVer::from_str`.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        SemVer::parse_version(value, false)
     }
 }
 
-impl Ord for Tag {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.semver.cmp(other.semver())
+impl TryFrom<String> for SemVer {
+    type Error = SemVerError;
+
+    /// Parse a version string like `SemVer::from_str`.
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        SemVer::parse_version(&value, false)
     }
 }
 
-impl Eq for Tag {}
+impl SemVer {
+    /// Parse a version string in which the minor and patch version numbers may be left out, e.g. `7.8` or `7-rc3`.
+    ///
+    /// Missing version numbers are filled in with zeros and are reported by `SemVer::inferred_minor` and
+    /// `SemVer::inferred_patch`. Otherwise the version string must have the format accepted by `SemVer::from_str`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the major version number is missing or if the version string is not valid.
+    pub fn parse_lenient(s: &str) -> Result<Self, SemVerError> {
+        SemVer::parse_version(s, true)
+    }
 
-impl From<Tag> for String {
-    fn from(tag: Tag) -> Self {
-        String::from(tag.str())
+    fn parse_version(s: &str, lenient: bool) -> Result<Self, SemVerError> {
+        let (version, build) = match s.split_once('+') {
+            Some((version, build)) => {
+                let build = build
+                    .split('.')
+                    .map(|number| match number.chars().all(|c| c.is_ascii_digit()) {
+                        true => number.parse().ok(),
+                        false => None,
+                    })
+                    .collect::<Option<Vec<u32>>>()
+                    .ok_or_else(|| SemVerError::InvalidBuild { input: String::from(s) })?;
+                (version, build)
+            }
+            None => (s, Vec::new()),
+        };
+
+        let (version, identifier) = match version.split_once('-') {
+            Some((_, "")) => return Err(SemVerError::EmptyIdentifier { input: String::from(s) }),
+            Some((_, identifier)) if !identifier.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                return Err(SemVerError::InvalidIdentifier { input: String::from(s) })
+            }
+            Some((version, identifier)) => (version, Some(Identifier::from(identifier))),
+            None => (version, None),
+        };
+
+        let mut numbers = version.splitn(3, '.');
+        let mut inferred_numbers = 0;
+        let mut next_number = |component: &'static str| -> Result<u8, SemVerError> {
+            let number = match numbers.next() {
+                Some(number) => number,
+                None if lenient => {
+                    inferred_numbers += 1;
+                    return Ok(0);
+                }
+                None => {
+                    return Err(SemVerError::MissingComponent {
+                        input: String::from(s),
+                        component,
+                    })
+                }
+            };
+            if !number.chars().all(|c| c.is_ascii_digit()) {
+                return Err(SemVerError::InvalidComponent {
+                    input: String::from(s),
+                    component,
+                });
+            }
+            number.parse().map_err(|_| SemVerError::InvalidComponent {
+                input: String::from(s),
+                component,
+            })
+        };
+
+        let major = next_number("major")?;
+        let minor = next_number("minor")?;
+        let patch = next_number("patch")?;
+        let mut semver = SemVer::new(major, minor, patch, identifier);
+        semver.build = build;
+        semver.inferred_numbers = inferred_numbers;
+        Ok(semver)
     }
 }
 
-impl Hash for Tag {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.str.hash(state)
+/// Compact `serde` representation of a `SemVer` as its display string.
+///
+/// A `SemVer` is serialized as a string like `6.20.1-rc3` instead of a struct. Deserialization accepts all strings
+/// that `SemVer::from_str` accepts.
+///
+/// # Examples
+///
+/// ```
+/// use ge_man_lib::tag::{SemVer, Tag};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct MinimumVersion {
+///     #[serde(with = "ge_man_lib::tag::semver_serde_string")]
+///     version: SemVer,
+/// }
+///
+/// let minimum = MinimumVersion { version: Tag::new("7.0rc3-GE-1").semver().clone() };
+/// assert_eq!(serde_json::to_string(&minimum).unwrap(), r#"{"version":"7.0.1-rc3"}"#);
+/// ```
+pub mod semver_serde_string {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::SemVer;
+
+    pub fn serialize<S: Serializer>(semver: &SemVer, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&semver.str())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<SemVer, D::Error> {
+        let str = String::deserialize(deserializer)?;
+        str.parse().map_err(D::Error::custom)
     }
 }
 
-/// Represents the kind of version for a `Tag`.
+/// The identifier of a `SemVer`.
 ///
-/// GE versions exists for both Proton and Wine. Additionally, for Wine also League of Legends specific versions
-/// exist. Therefore, all possible version kinds are represented by this enum.
+/// GE release tags use identifiers to mark release candidates (`7.0rc3-GE-1`), League of Legends builds
+/// (`6.16-GE-3-LoL`) and Media Foundation builds (`5.11-GE-1-MF`). Any other textual suffix of a tag (e.g. `hotfix` in
+/// `GE-Proton8-25-hotfix`) is kept as `Identifier::Other`.
 ///
-/// This enum supports `serde`'s serialization and deserialization traits.
-#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug)]
-#[serde(tag = "type")]
-pub enum TagKind {
-    Proton,
-    Wine { kind: WineTagKind },
+/// An `Identifier` is displayed and serialized as the string it was created from, e.g. `rc3`, `LoL` or `MF`.
+///
+/// Versions with equal version numbers are ordered by their identifier as follows:
+/// 1. release candidates, ordered by their number (`7.0.0-rc2 < 7.0.0-rc3`)
+/// 2. the release without identifier (`7.0.0-rc3 < 7.0.0`)
+/// 3. `LoL`, `MF` and other identifiers in this order. These mark distinct builds of the same version and are
+///    ordered after the release only to keep the order total.
+#[derive(Eq, PartialEq, Clone, Debug, Hash)]
+pub enum Identifier {
+    ReleaseCandidate(u32),
+    Lol,
+    Mf,
+    Other(String),
 }
 
-impl TagKind {
-    /// Create a Wine GE `TagKind`.
-    pub fn wine() -> TagKind {
-        TagKind::Wine {
-            kind: WineTagKind::WineGe,
+impl Identifier {
+    /// Get the kind of this identifier.
+    pub fn kind(&self) -> IdentifierKind {
+        match self {
+            Identifier::ReleaseCandidate(_) => IdentifierKind::ReleaseCandidate,
+            Identifier::Lol => IdentifierKind::Lol,
+            Identifier::Mf => IdentifierKind::Mf,
+            Identifier::Other(_) => IdentifierKind::Other,
         }
     }
 
-    /// Create a Wine GE LoL `TagKind`.
-    pub fn lol() -> TagKind {
-        TagKind::Wine {
-            kind: WineTagKind::LolWineGe,
+    fn parse_release_candidate(str: &str) -> Option<u32> {
+        let prefix = str.get(..RELEASE_CANDIDATE_MARKER.len())?;
+        let number = &str[RELEASE_CANDIDATE_MARKER.len()..];
+        if prefix.eq_ignore_ascii_case(RELEASE_CANDIDATE_MARKER)
+            && !number.is_empty()
+            && number.bytes().all(|byte| byte.is_ascii_digit())
+        {
+            number.parse().ok()
+        } else {
+            None
         }
     }
 
-    /// Get all possible values.
-    pub fn values() -> Vec<TagKind> {
-        vec![TagKind::Proton, TagKind::wine(), TagKind::lol()]
-    }
-
-    /// Get a "human readable" compatibility tool name for the `TagKind`.
-    pub fn compatibility_tool_name(&self) -> String {
-        let name = match self {
-            TagKind::Proton => "Proton GE",
-            TagKind::Wine { kind } => match kind {
-                WineTagKind::WineGe => "Wine GE",
-                WineTagKind::LolWineGe => "Wine GE (LoL)",
-            },
-        };
-        String::from(name)
+    fn rank(identifier: &Option<Identifier>) -> (u8, u32, &str) {
+        match identifier {
+            Some(Identifier::ReleaseCandidate(number)) => (0, *number, ""),
+            None => (1, 0, ""),
+            Some(Identifier::Lol) => (2, 0, ""),
+            Some(Identifier::Mf) => (3, 0, ""),
+            Some(Identifier::Other(str)) => (4, 0, str),
+        }
     }
 
-    /// Get a "human readable" compatibility tool kind text for the `TagKind`.
-    pub fn compatibility_tool_kind(&self) -> String {
-        let name = match self {
-            TagKind::Proton => "Proton",
-            TagKind::Wine { .. } => "Wine",
-        };
-        String::from(name)
+    fn cmp_optional(identifier: &Option<Identifier>, other: &Option<Identifier>) -> Ordering {
+        Identifier::rank(identifier).cmp(&Identifier::rank(other))
     }
+}
 
-    /// Get a 1:1 string representation of the enum name.
-    pub fn str(&self) -> String {
-        let name = match self {
-            TagKind::Proton => PROTON,
-            TagKind::Wine { kind } => match kind {
-                WineTagKind::WineGe => WINE,
-                WineTagKind::LolWineGe => LOL_WINE,
+impl From<&str> for Identifier {
+    /// Create an `Identifier` from its string.
+    ///
+    /// `rc` followed by a number is matched case-insensitively. `LoL` and `MF` are matched exactly, every other
+    /// string becomes `Identifier::Other`.
+    fn from(str: &str) -> Self {
+        match str {
+            LOL_MARKER => Identifier::Lol,
+            MF_MARKER => Identifier::Mf,
+            _ => match Identifier::parse_release_candidate(str) {
+                Some(number) => Identifier::ReleaseCandidate(number),
+                None => Identifier::Other(String::from(str)),
             },
-        };
-        String::from(name)
+        }
     }
+}
 
-    fn from_str(str: &str) -> Result<Self, TagKindError> {
-        let kind = match str {
-            PROTON => TagKind::Proton,
-            WINE => TagKind::wine(),
-            LOL_WINE => TagKind::lol(),
-            _ => return Err(TagKindError::UnknownString),
-        };
-        Ok(kind)
+impl Display for Identifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Identifier::ReleaseCandidate(number) => write!(f, "{}{}", RELEASE_CANDIDATE_MARKER, number),
+            Identifier::Lol => write!(f, "{}", LOL_MARKER),
+            Identifier::Mf => write!(f, "{}", MF_MARKER),
+            Identifier::Other(str) => write!(f, "{}", str),
+        }
     }
 }
 
-impl From<&WineTagKind> for TagKind {
-    fn from(kind: &WineTagKind) -> Self {
-        TagKind::Wine { kind: *kind }
+impl Serialize for Identifier {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
     }
 }
 
-impl From<WineTagKind> for TagKind {
-    fn from(kind: WineTagKind) -> Self {
-        TagKind::Wine { kind }
+impl<'de> Deserialize<'de> for Identifier {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let str = String::deserialize(deserializer)?;
+        Ok(Identifier::from(str.as_str()))
     }
 }
 
-impl TryFrom<&str> for TagKind {
-    type Error = TagKindError;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        TagKind::from_str(value)
-    }
+/// The kind of identifier a `SemVer` can have.
+///
+/// GE release tags use identifiers to mark release candidates (`7.0rc3-GE-1`), League of Legends builds
+/// (`6.16-GE-3-LoL`) and Media Foundation builds (`5.11-GE-1-MF`).
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum IdentifierKind {
+    ReleaseCandidate,
+    Lol,
+    Mf,
+    Other,
 }
 
-impl TryFrom<String> for TagKind {
-    type Error = TagKindError;
+/// A part of a `SemVer`.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum VersionPart {
+    Major,
+    Minor,
+    Patch,
+    Build,
+    Identifier,
+}
 
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        TagKind::from_str(&value)
+impl Display for VersionPart {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            VersionPart::Major => "major",
+            VersionPart::Minor => "minor",
+            VersionPart::Patch => "patch",
+            VersionPart::Build => "build",
+            VersionPart::Identifier => "identifier",
+        };
+        write!(f, "{}", name)
     }
 }
 
-impl Display for TagKind {
+/// The kind of change between two versions as returned by `SemVer::diff` and `Tag::diff`.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum VersionBump {
+    Major,
+    Minor,
+    Patch,
+    Build,
+    IdentifierOnly,
+    /// The other version is older. Contains the most significant part that differs.
+    Downgrade(VersionPart),
+    None,
+}
+
+impl Display for VersionBump {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.str())
+        match self {
+            VersionBump::Major => write!(f, "major update"),
+            VersionBump::Minor => write!(f, "minor update"),
+            VersionBump::Patch => write!(f, "patch update"),
+            VersionBump::Build => write!(f, "build update"),
+            VersionBump::IdentifierOnly => write!(f, "identifier update"),
+            VersionBump::Downgrade(part) => write!(f, "{} downgrade", part),
+            VersionBump::None => write!(f, "no change"),
+        }
     }
 }
 
-/// Represents a Wine GE or Wine GE LoL version.
+/// Options for `Tag::parse_with`.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct TagParseOptions {
+    /// Reject tags with fewer than three version numbers instead of filling in the missing numbers with zeros.
+    pub strict: bool,
+}
+
+/// Struct for representing a GitHub release tag and providing an option to be transformed into a semantic version.
+///
+/// Internally this struct parses the given release tag to create a semantic version representation from it. This is
+/// done because it is much easier to perform comparisons between `6.20.1` and `7.8.0` than with `Proton-6.20-GE-1` and
+/// `GE-Proton7-8`.
+///
+/// The semantic version is only created when it is first needed. A deserialized `Tag` uses the semantic version
+/// contained in the serialized data and does not parse its string again. If the serialized data contains no semantic
+/// version, it is created from the string on first use.
+///
+/// Tags are ordered by their semantic version. Different spellings of the same version (e.g. `6.16-GE-0-LoL` and
+/// `6.16-0-GE-LoL`) are ordered by their string, so sorting is deterministic regardless of the input order. `Eq` is
+/// consistent with `Ord`, therefore, two tags are only equal if their strings are equal. Use `Tag::cmp_semver_only` and
+/// `Tag::semver_eq` to compare only the semantic versions.
+///
+/// This struct supports `serde`'s serialization and deserialization traits. It is serialized as a struct with its
+/// string, semantic version and bound kind. For deserialization a plain tag string like `"GE-Proton7-8"` is accepted as
+/// well. The semantic version of a plain string is created immediately, so an unparsable string is reported as a
+/// deserialization error.
+#[derive(Clone, Debug)]
+pub struct Tag {
+    str: String,
+    semver: OnceLock<SemVer>,
+    kind: Option<TagKind>,
+}
+
+/// The serialized forms of a `Tag`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TagRepresentation {
+    String(String),
+    Struct(TagFields),
+}
+
+#[derive(Deserialize)]
+struct TagFields {
+    // Alias for versions before ge-man-lib version 0.2.0.
+    #[serde(alias = "value")]
+    str: String,
+    #[serde(default, deserialize_with = "deserialize_semver")]
+    semver: OnceLock<SemVer>,
+    #[serde(default)]
+    kind: Option<TagKind>,
+}
+
+fn deserialize_semver<'de, D: Deserializer<'de>>(deserializer: D) -> Result<OnceLock<SemVer>, D::Error> {
+    SemVer::deserialize(deserializer).map(OnceLock::from)
+}
+
+impl<'de> Deserialize<'de> for Tag {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let tag = match TagRepresentation::deserialize(deserializer)? {
+            TagRepresentation::String(str) => {
+                let semver = SemVer::parse_git_tag(&str, false).map_err(serde::de::Error::custom)?;
+                Tag {
+                    str,
+                    semver: OnceLock::from(semver),
+                    kind: None,
+                }
+            }
+            TagRepresentation::Struct(TagFields { str, semver, kind }) => Tag { str, semver, kind },
+        };
+        Ok(tag)
+    }
+}
+
+impl Serialize for Tag {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let field_count = if self.kind.is_some() { 3 } else { 2 };
+        let mut tag = serializer.serialize_struct("Tag", field_count)?;
+        tag.serialize_field("str", &self.str)?;
+        tag.serialize_field("semver", self.semver())?;
+        if let Some(kind) = &self.kind {
+            tag.serialize_field("kind", kind)?;
+        } else {
+            tag.skip_field("kind")?;
+        }
+        tag.end()
+    }
+}
+
+impl Tag {
+    pub fn new<S: Into<String>>(git_tag: S) -> Self {
+        Tag {
+            str: git_tag.into(),
+            semver: OnceLock::new(),
+            kind: None,
+        }
+    }
+
+    /// Create a `Tag` that is bound to a `TagKind`.
+    ///
+    /// The tag string is validated against `kind` with the heuristics of `TagKind::from_tag`. Tags whose kind can
+    /// not be inferred (e.g. `6.20-GE-1`) are accepted for every kind. The bound kind is available with `Tag::kind`
+    /// and is included in the serialized form of the `Tag`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the tag looks like a tag of another kind, e.g. `GE-Proton7-8` for
+    /// `TagKind::wine()` or `6.16-GE-3-LoL` for `TagKind::wine()`.
+    pub fn with_kind<S: Into<String>>(git_tag: S, kind: TagKind) -> Result<Self, TagParseError> {
+        let mut tag = Tag::new(git_tag);
+        if TagKind::from_tag(&tag).is_some_and(|inferred| inferred != kind) {
+            return Err(TagParseError::KindMismatch { tag: tag.str, kind });
+        }
+        tag.kind = Some(kind);
+        Ok(tag)
+    }
+
+    /// Create a `Tag` with the given parse `options`.
+    ///
+    /// The tag string is parsed immediately. In lenient mode this behaves like `Tag::new`, except that unparsable tags
+    /// return an error instead of panicking. In strict mode missing version numbers are not filled in with zeros and
+    /// numbers that are too large are not capped. Note that modern Proton GE tags like `GE-Proton7-8` only contain two
+    /// version numbers and are therefore rejected in strict mode.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the tag contains a release candidate marker without a number, e.g.
+    /// `7.0rc-GE`. In strict mode it also returns an error if the tag contains fewer than three version numbers or a
+    /// number that does not fit into its version component (e.g. a major version above 255).
+    pub fn parse_with<S: Into<String>>(git_tag: S, options: &TagParseOptions) -> Result<Self, TagParseError> {
+        let mut tag = Tag::new(git_tag);
+        let semver = SemVer::parse_git_tag(&tag.str, options.strict)?;
+        tag.semver = OnceLock::from(semver);
+        Ok(tag)
+    }
+
+    /// Create a `Tag` from a tag name of unknown quality, like the names returned by the tags endpoint of GitHub.
+    ///
+    /// The tag is parsed leniently like with `Tag::parse_with` and the default `TagParseOptions`, so missing version
+    /// numbers are filled in with zeros. Names without any version number, like the stray upstream tag `lol`, are
+    /// rejected instead of becoming version `0.0.0`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns `TagParseError::NoVersionNumbers` if the name contains no version number and
+    /// `TagParseError::MissingReleaseCandidateNumber` if it contains a release candidate marker without a number.
+    pub fn try_new<S: Into<String>>(git_tag: S) -> Result<Self, TagParseError> {
+        let tag = Tag::parse_with(git_tag, &TagParseOptions::default())?;
+        if tag.semver().inferred_major() {
+            return Err(TagParseError::NoVersionNumbers { tag: tag.str });
+        }
+        Ok(tag)
+    }
+
+    /// Create a `Tag` from the file name of a release asset.
+    ///
+    /// The file name is percent-decoded and the archive (`.tar.gz`, `.tar.xz`) or checksum (`.sha512sum`,
+    /// `.sha256sum`) extension is removed. For example, `GE-Proton7-8.tar.gz` becomes the `Tag` `GE-Proton7-8`.
+    pub fn from_file_name(file_name: &str) -> Self {
+        let file_name = percent_decode_str(file_name).decode_utf8_lossy();
+        let tag = FILE_EXTENSIONS
+            .iter()
+            .find_map(|extension| file_name.strip_suffix(extension))
+            .unwrap_or(&file_name);
+        Tag::new(tag)
+    }
+
+    /// Get the file name of the compressed archive of this tag's release for `kind`.
+    ///
+    /// The file names follow the conventions of the GE release assets:
+    /// * Proton GE: `GE-Proton7-8.tar.gz`. Releases before GE-Proton7 use the tag with a `Proton-` prefix, e.g.
+    ///   `Proton-6.20-GE-1.tar.gz`.
+    /// * Wine GE: `wine-lutris-GE-Proton8-26-x86_64.tar.xz`. Releases before the renaming drop the `GE` part of the
+    ///   tag, e.g. `wine-lutris-ge-6.20-1-x86_64.tar.xz` for `6.20-GE-1`.
+    /// * Wine GE (LoL): like Wine GE with a `-lol` suffix, e.g. `wine-lutris-ge-6.16-3-lol-x86_64.tar.xz` for
+    ///   `6.16-GE-3-LoL`.
+    ///
+    /// This only derives the file name from the tag, so no network request is made.
+    pub fn archive_file_name(&self, kind: TagKind) -> String {
+        let extension = match kind {
+            TagKind::Proton => PROTON_ARCHIVE_EXTENSION,
+            TagKind::Wine { .. } => WINE_ARCHIVE_EXTENSION,
+        };
+        format!("{}{}", self.asset_name(kind), extension)
+    }
+
+    /// Get the file name of the checksum file of this tag's release for `kind`.
+    ///
+    /// The checksum file has the name of the archive (see `Tag::archive_file_name`) with a `.sha512sum` extension
+    /// instead of the archive extension. This is the name used by the official releases. Releases may ship a
+    /// `.sha256sum` file instead, so the checksum asset of a downloaded release should be looked up with
+    /// `GeRelease::find_checksum_asset`, which finds both.
+    pub fn checksum_file_name(&self, kind: TagKind) -> String {
+        format!("{}{}", self.asset_name(kind), CHECKSUM_EXTENSION)
+    }
+
+    fn asset_name(&self, kind: TagKind) -> String {
+        let is_renamed = self.str.starts_with(RENAMED_TAG_PREFIX);
+        match kind {
+            TagKind::Proton if is_renamed || self.str.starts_with(PROTON_ASSET_PREFIX) => self.str.clone(),
+            TagKind::Proton => format!("{}{}", PROTON_ASSET_PREFIX, self.str),
+            TagKind::Wine { .. } if is_renamed => format!("wine-lutris-{}-x86_64", self.str),
+            TagKind::Wine { kind } => {
+                let version = self
+                    .str
+                    .split('-')
+                    .filter(|part| !part.eq_ignore_ascii_case("GE") && !part.eq_ignore_ascii_case(LOL_MARKER))
+                    .collect::<Vec<&str>>()
+                    .join("-");
+                let suffix = match kind {
+                    WineTagKind::WineGe => "",
+                    WineTagKind::LolWineGe => "-lol",
+                };
+                format!("wine-lutris-ge-{}{}-x86_64", version, suffix)
+            }
+        }
+    }
+
+    /// Get this `Tag` as a semantic version.
+    pub fn semver(&self) -> &SemVer {
+        self.semver.get_or_init(|| SemVer::from_git_tag(&self.str))
+    }
+
+    /// Get the string value of this `Tag`.
+    pub fn str(&self) -> &String {
+        &self.str
+    }
+
+    /// Get the tag string followed by the semantic version, e.g. `GE-Proton7-8 (7.8.0)`.
+    ///
+    /// This is the same as formatting the `Tag` with the alternate flag (`{:#}`).
+    pub fn display_verbose(&self) -> String {
+        format!("{:#}", self)
+    }
+
+    /// Get the `TagKind` this `Tag` was bound to with `Tag::with_kind`.
+    ///
+    /// Returns `None` for tags that were created without a kind.
+    pub fn kind(&self) -> Option<TagKind> {
+        self.kind
+    }
+
+    /// Get the kind of the identifier of this `Tag`.
+    ///
+    /// Returns `None` if this `Tag` has no identifier.
+    pub fn identifier_kind(&self) -> Option<IdentifierKind> {
+        self.semver().identifier_kind()
+    }
+
+    /// Check if this `Tag` is a release candidate (e.g. `7.0rc3-GE-1`).
+    pub fn is_release_candidate(&self) -> bool {
+        matches!(self.identifier_kind(), Some(IdentifierKind::ReleaseCandidate))
+    }
+
+    /// Check if this `Tag` is a League of Legends build (e.g. `6.16-GE-3-LoL`).
+    pub fn is_lol(&self) -> bool {
+        matches!(self.identifier_kind(), Some(IdentifierKind::Lol))
+    }
+
+    /// Check if this `Tag` is a Media Foundation build (e.g. `5.11-GE-1-MF`).
+    pub fn is_mf(&self) -> bool {
+        matches!(self.identifier_kind(), Some(IdentifierKind::Mf))
+    }
+
+    /// Compare the semantic versions of this tag and `other` without considering the tag strings.
+    ///
+    /// Unlike `Ord::cmp` this returns `Ordering::Equal` for different spellings of the same version, e.g.
+    /// `6.16-GE-0-LoL` and `6.16-0-GE-LoL`.
+    pub fn cmp_semver_only(&self, other: &Tag) -> Ordering {
+        self.semver().cmp(other.semver())
+    }
+
+    /// Check if this tag and `other` have the same semantic version, regardless of their tag strings.
+    ///
+    /// `PartialEq` and `Hash` of `Tag` use the tag string, so different spellings of the same version (e.g.
+    /// `6.16-GE-0-LoL` and `6.16-0-GE-LoL`) are different entries in a `HashSet`. Use this method to find or remove
+    /// such duplicates, e.g. with `Vec::dedup_by` on a sorted list.
+    pub fn semver_eq(&self, other: &Tag) -> bool {
+        self.semver() == other.semver()
+    }
+
+    /// Compare this tag of `self_kind` with `other` of `other_kind`.
+    ///
+    /// Versions of different kinds are not related, e.g. Wine GE `7.0rc3-GE-1` is not older than Proton GE
+    /// `GE-Proton7-8` in any meaningful way. Therefore, `None` is returned if the kinds differ. Tags of the same kind are
+    /// compared like `Ord::cmp`.
+    pub fn compare_with_kind(&self, self_kind: TagKind, other: &Tag, other_kind: TagKind) -> Option<Ordering> {
+        (self_kind == other_kind).then(|| self.cmp(other))
+    }
+
+    /// Classify the version change from this tag to `other`.
+    ///
+    /// See `SemVer::diff` for the classification rules.
+    pub fn diff(&self, other: &Tag) -> VersionBump {
+        self.semver().diff(other.semver())
+    }
+
+    /// Get a canonical tag string for this tag's version.
+    ///
+    /// Different tag strings of the same version (e.g. `6.16-GE-0-LoL` and `6.16-0-GE-LoL`) produce the same
+    /// canonical string. The version is rendered with the current upstream naming convention of `kind`:
+    /// * Proton GE: `GE-Proton{major}-{minor}`. Older Proton GE releases had a patch version, which is appended as
+    ///   `-{patch}` if it is not zero or if the version has build numbers.
+    /// * Wine GE and Wine GE (LoL): `{major}.{minor}-GE-{patch}`.
+    ///
+    /// Build numbers are appended as `-{number}` after the patch version. A release candidate number is placed behind
+    /// the minor version for Wine GE (e.g. `7.0rc3-GE-1`) and appended as `-rc{number}` for Proton GE. Any other
+    /// identifier (e.g. `LoL` or `MF`) is appended as `-{identifier}`.
+    pub fn normalized(&self, kind: TagKind) -> String {
+        Tag::canonical_str(self.semver(), kind)
+    }
+
+    /// Create a `Tag` for `semver` with the canonical tag string of `kind`.
+    ///
+    /// The tag string is rendered like `Tag::normalized`, so the semantic version of the created `Tag` is equal to
+    /// `semver`. For example, `7.0.1-rc3` becomes `7.0rc3-GE-1` for Wine GE and `GE-Proton7-0-1-rc3` for Proton GE.
+    pub fn from_semver(semver: &SemVer, kind: TagKind) -> Self {
+        Tag::new(Tag::canonical_str(semver, kind))
+    }
+
+    /// Get the tag of the next release after this tag with the canonical tag string of `kind`.
+    ///
+    /// The release number is the last version number of the naming convention of `kind`: the minor version for Proton
+    /// GE (`GE-Proton7-8` becomes `GE-Proton7-9`) and the patch version for Wine GE (`6.20-GE-1` becomes
+    /// `6.20-GE-2`). Lower version numbers and build numbers are dropped. Identifiers like `LoL` or `MF` are kept, so
+    /// `6.16-GE-3-LoL` becomes `6.16-GE-4-LoL`.
+    ///
+    /// The next release of a release candidate is the final release of the same version, e.g. `7.0rc3-GE-1` becomes
+    /// `7.0-GE-1`.
+    pub fn next_patch(&self, kind: TagKind) -> Tag {
+        let semver = self.semver();
+        let next = if semver.release_candidate().is_some() {
+            SemVer::new(semver.major(), semver.minor(), semver.patch(), None)
+        } else {
+            let identifier = semver.parsed_identifier().cloned();
+            match kind {
+                TagKind::Proton => SemVer::new(semver.major(), semver.minor().saturating_add(1), 0, identifier),
+                TagKind::Wine { .. } => SemVer::new(
+                    semver.major(),
+                    semver.minor(),
+                    semver.patch().saturating_add(1),
+                    identifier,
+                ),
+            }
+        };
+        Tag::from_semver(&next, kind)
+    }
+
+    /// Get the tag of the first release of the next version series after this tag with the canonical tag string of
+    /// `kind`.
+    ///
+    /// The series is the version number before the release number (see `Tag::next_patch`): the major version for
+    /// Proton GE and the minor version for Wine GE. Like upstream, the release number of the new series starts at 1,
+    /// so `GE-Proton7-8` becomes `GE-Proton8-1` and `6.20-GE-1` becomes `6.21-GE-1`. Build numbers and release
+    /// candidate numbers are dropped, other identifiers like `LoL` or `MF` are kept.
+    pub fn next_minor(&self, kind: TagKind) -> Tag {
+        let semver = self.semver();
+        let identifier = semver
+            .parsed_identifier()
+            .filter(|identifier| !matches!(identifier, Identifier::ReleaseCandidate(_)))
+            .cloned();
+        let next = match kind {
+            TagKind::Proton => SemVer::new(semver.major().saturating_add(1), 1, 0, identifier),
+            TagKind::Wine { .. } => SemVer::new(semver.major(), semver.minor().saturating_add(1), 1, identifier),
+        };
+        Tag::from_semver(&next, kind)
+    }
+
+    fn canonical_str(semver: &SemVer, kind: TagKind) -> String {
+        let release_candidate = semver
+            .release_candidate()
+            .map(|rc| format!("{}{}", RELEASE_CANDIDATE_MARKER, rc));
+
+        let mut normalized = match kind {
+            TagKind::Proton if semver.patch() == 0 && semver.build().is_empty() => {
+                format!("GE-Proton{}-{}", semver.major(), semver.minor())
+            }
+            TagKind::Proton => format!("GE-Proton{}-{}-{}", semver.major(), semver.minor(), semver.patch()),
+            TagKind::Wine { .. } => format!(
+                "{}.{}{}-GE-{}",
+                semver.major(),
+                semver.minor(),
+                release_candidate.as_deref().unwrap_or_default(),
+                semver.patch()
+            ),
+        };
+        for number in semver.build() {
+            normalized.push_str(&format!("-{}", number));
+        }
+
+        let suffix = match kind {
+            TagKind::Proton => semver.parsed_identifier(),
+            TagKind::Wine { .. } => semver.parsed_identifier().filter(|_| release_candidate.is_none()),
+        };
+        if let Some(suffix) = suffix {
+            normalized.push('-');
+            normalized.push_str(&suffix.to_string());
+        }
+        normalized
+    }
+}
+
+impl Default for Tag {
+    fn default() -> Self {
+        Tag::new("")
+    }
+}
+
+impl From<String> for Tag {
+    fn from(s: String) -> Self {
+        Tag::new(&s)
+    }
+}
+
+impl From<&str> for Tag {
+    fn from(s: &str) -> Self {
+        Tag::new(s)
+    }
+}
+
+impl From<Option<String>> for Tag {
+    fn from(opt: Option<String>) -> Self {
+        match opt {
+            Some(str) => Tag::new(str),
+            None => Tag::default(),
+        }
+    }
+}
+
+impl From<Option<&str>> for Tag {
+    fn from(opt: Option<&str>) -> Self {
+        match opt {
+            Some(str) => Tag::new(str),
+            None => Tag::default(),
+        }
+    }
+}
+
+impl AsRef<Path> for Tag {
+    fn as_ref(&self) -> &Path {
+        self.str.as_ref()
+    }
+}
+
+impl AsRef<str> for Tag {
+    fn as_ref(&self) -> &str {
+        self.str.as_ref()
+    }
+}
+
+/// Formats the tag string. The alternate flag (`{:#}`) appends the semantic version in parentheses, e.g.
+/// `GE-Proton7-8 (7.8.0)`.
+impl Display for Tag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            write!(f, "{} ({})", self.str, self.semver())
+        } else {
+            write!(f, "{}", self.str)
+        }
+    }
+}
+
+/// Tags are equal if their tag strings are equal, consistent with `Ord` and `Hash`. Different spellings of the same
+/// version are different tags; use `Tag::semver_eq` to compare only the versions.
+impl PartialEq<Tag> for Tag {
+    fn eq(&self, other: &Tag) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl PartialOrd<Tag> for Tag {
+    fn partial_cmp(&self, other: &Tag) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Tag {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Equal strings always have an equal semantic version, so no parsing is needed.
+        if self.str == other.str {
+            return Ordering::Equal;
+        }
+        self.cmp_semver_only(other).then_with(|| self.str.cmp(&other.str))
+    }
+}
+
+impl Eq for Tag {}
+
+/// Compare the raw tag string with a string.
+///
+/// This is plain string equality, the string is not parsed. Because different spellings of the same version are
+/// different tags, this is consistent with `PartialEq<Tag>`: `Tag::new("6.16-GE-0-LoL")` equals `"6.16-GE-0-LoL"` but
+/// neither `"6.16-0-GE-LoL"` nor `Tag::new("6.16-0-GE-LoL")`. Use `Tag::semver_eq` to compare versions.
+impl PartialEq<str> for Tag {
+    fn eq(&self, other: &str) -> bool {
+        self.str == other
+    }
+}
+
+/// Compare the raw tag string with a string, see `PartialEq<str>` for `Tag`.
+impl PartialEq<&str> for Tag {
+    fn eq(&self, other: &&str) -> bool {
+        self.str == *other
+    }
+}
+
+/// Compare the raw tag string with a string, see `PartialEq<str>` for `Tag`.
+impl PartialEq<String> for Tag {
+    fn eq(&self, other: &String) -> bool {
+        &self.str == other
+    }
+}
+
+/// Compare a string with the raw tag string, see `PartialEq<str>` for `Tag`.
+impl PartialEq<Tag> for str {
+    fn eq(&self, other: &Tag) -> bool {
+        other == self
+    }
+}
+
+/// Compare a string with the raw tag string, see `PartialEq<str>` for `Tag`.
+impl PartialEq<Tag> for &str {
+    fn eq(&self, other: &Tag) -> bool {
+        other == self
+    }
+}
+
+/// Compare a string with the raw tag string, see `PartialEq<str>` for `Tag`.
+impl PartialEq<Tag> for String {
+    fn eq(&self, other: &Tag) -> bool {
+        other == self
+    }
+}
+
+impl From<Tag> for String {
+    fn from(tag: Tag) -> Self {
+        String::from(tag.str())
+    }
+}
+
+/// Hashes the tag string, consistent with `PartialEq`. To deduplicate tags by their version, use `Tag::semver_eq`.
+impl Hash for Tag {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Tags are equal if their strings are equal. The semantic version is not hashed, because a deserialized tag
+        // can carry a version that differs from the version parsed from its string.
+        self.str.hash(state)
+    }
+}
+
+/// An element of an iterator that `TagIterExt` can pick the latest `Tag` from.
+///
+/// This is implemented for owned and borrowed `Tag`s, for which the bound kind is used or otherwise inferred with
+/// `TagKind::from_tag`, and for `(Tag, TagKind)` pairs, which provide the kind explicitly.
+pub trait TagItem {
+    fn tag(&self) -> &Tag;
+    fn kind(&self) -> Option<TagKind>;
+}
+
+impl TagItem for Tag {
+    fn tag(&self) -> &Tag {
+        self
+    }
+
+    fn kind(&self) -> Option<TagKind> {
+        self.kind.or_else(|| TagKind::from_tag(self))
+    }
+}
+
+impl TagItem for &Tag {
+    fn tag(&self) -> &Tag {
+        self
+    }
+
+    fn kind(&self) -> Option<TagKind> {
+        self.kind.or_else(|| TagKind::from_tag(self))
+    }
+}
+
+impl TagItem for (Tag, TagKind) {
+    fn tag(&self) -> &Tag {
+        &self.0
+    }
+
+    fn kind(&self) -> Option<TagKind> {
+        Some(self.1)
+    }
+}
+
+impl TagItem for (&Tag, TagKind) {
+    fn tag(&self) -> &Tag {
+        self.0
+    }
+
+    fn kind(&self) -> Option<TagKind> {
+        Some(self.1)
+    }
+}
+
+impl TagItem for &(Tag, TagKind) {
+    fn tag(&self) -> &Tag {
+        &self.0
+    }
+
+    fn kind(&self) -> Option<TagKind> {
+        Some(self.1)
+    }
+}
+
+/// Extension methods to pick the latest `Tag` from an iterator.
+///
+/// The methods return the element of the iterator, so a borrowing iterator returns `Option<&Tag>` and an owning
+/// iterator returns `Option<Tag>`. Elements are compared with `Ord for Tag`, so if several elements have the same
+/// version, the element with the greatest tag string is returned. Of elements with equal tag strings, the last one is
+/// returned.
+///
+/// # Examples
+///
+/// ```
+/// use ge_man_lib::tag::{Tag, TagIterExt};
+///
+/// let tags = vec![Tag::new("GE-Proton7-8"), Tag::new("GE-Proton8-1rc1"), Tag::new("GE-Proton7-20")];
+/// assert_eq!(tags.iter().latest_stable(), Some(&Tag::new("GE-Proton7-20")));
+/// ```
+pub trait TagIterExt: Iterator
+where
+    Self::Item: TagItem,
+    Self: Sized,
+{
+    /// Get the element with the highest version.
+    fn latest(self) -> Option<Self::Item> {
+        self.max_by(|a, b| a.tag().cmp(b.tag()))
+    }
+
+    /// Get the element with the highest version that is not a release candidate.
+    fn latest_stable(self) -> Option<Self::Item> {
+        self.filter(|item| item.tag().semver().release_candidate().is_none())
+            .latest()
+    }
+
+    /// Get the element with the highest version of `kind`.
+    ///
+    /// For plain `Tag`s the kind is inferred with `TagKind::from_tag`, so tags with an ambiguous kind are skipped.
+    fn latest_of_kind(self, kind: TagKind) -> Option<Self::Item> {
+        self.filter(|item| item.kind() == Some(kind)).latest()
+    }
+
+    /// Get the element with the highest version like `latest`, but check that the elements do not mix kinds.
+    ///
+    /// Elements whose kind is unknown (see `TagItem::kind`) are ignored by the check. In debug builds this method
+    /// panics if the elements contain different kinds, which usually means that `latest_of_kind` should have been
+    /// used. In release builds it behaves like `latest`.
+    fn latest_checked(self) -> Option<Self::Item> {
+        let items: Vec<Self::Item> = self.collect();
+        if cfg!(debug_assertions) {
+            let mut kinds = items.iter().filter_map(TagItem::kind);
+            if let Some(first) = kinds.next() {
+                if let Some(other) = kinds.find(|kind| *kind != first) {
+                    panic!("Tags of different kinds are compared: {} and {}", first, other);
+                }
+            }
+        }
+        items.into_iter().latest()
+    }
+}
+
+impl<I> TagIterExt for I
+where
+    I: Iterator,
+    I::Item: TagItem,
+{
+}
+
+/// Compact `serde` representation of a `Tag` as a plain string.
+///
+/// By default a `Tag` is serialized with its string and its semantic version. Since the semantic version can always
+/// be created from the string, this module allows to serialize a `Tag` as its string only. For deserialization both
+/// the plain string and the default representation (including the `value` alias used before version 0.2.0) are
+/// accepted, so existing files can still be read.
+///
+/// # Examples
+///
+/// ```
+/// use ge_man_lib::tag::Tag;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct ManagedVersion {
+///     #[serde(with = "ge_man_lib::tag::serde_string")]
+///     tag: Tag,
+/// }
+///
+/// let version = ManagedVersion { tag: Tag::new("GE-Proton7-8") };
+/// assert_eq!(serde_json::to_string(&version).unwrap(), r#"{"tag":"GE-Proton7-8"}"#);
+/// ```
+pub mod serde_string {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::Tag;
+
+    pub fn serialize<S: Serializer>(tag: &Tag, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(tag.str())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Tag, D::Error> {
+        Tag::deserialize(deserializer)
+    }
+}
+
+/// Represents the kind of version for a `Tag`.
+///
+/// GE versions exists for both Proton and Wine. Additionally, for Wine also League of Legends specific versions
+/// exist. Therefore, all possible version kinds are represented by this enum.
+///
+/// This enum supports `serde`'s serialization and deserialization traits.
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug)]
+#[serde(tag = "type")]
+pub enum TagKind {
+    Proton,
+    Wine { kind: WineTagKind },
+}
+
+impl TagKind {
+    /// Create a Wine GE `TagKind`.
+    pub fn wine() -> TagKind {
+        TagKind::Wine {
+            kind: WineTagKind::WineGe,
+        }
+    }
+
+    /// Create a Wine GE LoL `TagKind`.
+    pub fn lol() -> TagKind {
+        TagKind::Wine {
+            kind: WineTagKind::LolWineGe,
+        }
+    }
+
+    /// Get all possible values.
+    pub fn values() -> Vec<TagKind> {
+        vec![TagKind::Proton, TagKind::wine(), TagKind::lol()]
+    }
+
+    /// Get a "human readable" compatibility tool name for the `TagKind`.
+    pub fn compatibility_tool_name(&self) -> String {
+        let name = match self {
+            TagKind::Proton => "Proton GE",
+            TagKind::Wine { kind } => match kind {
+                WineTagKind::WineGe => "Wine GE",
+                WineTagKind::LolWineGe => "Wine GE (LoL)",
+            },
+        };
+        String::from(name)
+    }
+
+    /// Get a "human readable" compatibility tool kind text for the `TagKind`.
+    pub fn compatibility_tool_kind(&self) -> String {
+        let name = match self {
+            TagKind::Proton => "Proton",
+            TagKind::Wine { .. } => "Wine",
+        };
+        String::from(name)
+    }
+
+    /// Get a 1:1 string representation of the enum name.
+    pub fn str(&self) -> String {
+        let name = match self {
+            TagKind::Proton => PROTON,
+            TagKind::Wine { kind } => match kind {
+                WineTagKind::WineGe => WINE,
+                WineTagKind::LolWineGe => LOL_WINE,
+            },
+        };
+        String::from(name)
+    }
+
+    /// Infer the `TagKind` from the string of a `Tag`.
+    ///
+    /// This applies the heuristics of `TagKind::guess` to the tag string.
+    pub fn from_tag(tag: &Tag) -> Option<TagKind> {
+        TagKind::guess(tag.str())
+    }
+
+    /// Guess the `TagKind` of an arbitrary string, e.g. the name of an installed compatibility tool directory.
+    ///
+    /// Surrounding whitespace and everything up to the last path separator are ignored, so
+    /// `~/.steam/root/compatibilitytools.d/GE-Proton7-43` is treated like `GE-Proton7-43`. The following heuristics are
+    /// then applied in order:
+    /// 1. Strings containing `LoL` (e.g. `6.16-GE-3-LoL`) are Wine GE (LoL) releases
+    /// 2. Strings containing `wine` or `lutris` (e.g. `wine-ge-8-26` or `lutris-GE-Proton8-26-x86_64`) are Wine GE
+    ///    releases
+    /// 3. Strings containing `Proton` (e.g. `GE-Proton7-8` or `Proton-6.20-GE-1`) are Proton GE releases
+    /// 4. Strings with the `MF` suffix (e.g. `5.11-GE-1-MF`) are Proton GE releases
+    ///
+    /// All comparisons are case-insensitive. If no heuristic matches `None` is returned. This is the case for strings
+    /// like `6.20-GE-1`, because both the Proton GE and the Wine GE repository used this naming scheme.
+    pub fn guess(s: &str) -> Option<TagKind> {
+        let name = s.trim().trim_end_matches('/');
+        let name = name.rsplit('/').next().unwrap_or(name).to_lowercase();
+
+        if name.contains("lol") {
+            Some(TagKind::lol())
+        } else if name.contains("wine") || name.contains("lutris") {
+            Some(TagKind::wine())
+        } else if name.contains("proton") || name.ends_with("-mf") {
+            Some(TagKind::Proton)
+        } else {
+            None
+        }
+    }
+
+    fn parse(str: &str) -> Result<Self, TagKindError> {
+        let kind = match str.trim().to_lowercase().replace('_', "-").as_str() {
+            "proton" | "proton-ge" | "ge-proton" => TagKind::Proton,
+            "wine" | "wine-ge" | "ge-wine" | "lutris" | "lutris-ge" => TagKind::wine(),
+            "lol-wine" | "lol" | "lol-wine-ge" | "wine-lol" | "wine-ge-lol" => TagKind::lol(),
+            _ => return Err(TagKindError::UnknownString(String::from(str))),
+        };
+        Ok(kind)
+    }
+}
+
+impl From<&WineTagKind> for TagKind {
+    fn from(kind: &WineTagKind) -> Self {
+        TagKind::Wine { kind: *kind }
+    }
+}
+
+impl From<WineTagKind> for TagKind {
+    fn from(kind: WineTagKind) -> Self {
+        TagKind::Wine { kind }
+    }
+}
+
+impl FromStr for TagKind {
+    type Err = TagKindError;
+
+    /// Parse a `TagKind` from its `str` representation or one of its aliases.
+    ///
+    /// Parsing is case-insensitive and treats `_` and `-` the same. Surrounding whitespace is ignored. The following
+    /// strings are accepted:
+    /// * Proton GE: `PROTON`, `proton-ge`, `ge-proton`
+    /// * Wine GE: `WINE`, `wine-ge`, `ge-wine`, `lutris`, `lutris-ge`
+    /// * Wine GE (LoL): `LOL_WINE`, `lol`, `lol-wine-ge`, `wine-lol`, `wine-ge-lol`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TagKind::parse(s)
+    }
+}
+
+impl TryFrom<&str> for TagKind {
+    type Error = TagKindError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        TagKind::parse(value)
+    }
+}
+
+impl TryFrom<String> for TagKind {
+    type Error = TagKindError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        TagKind::parse(&value)
+    }
+}
+
+impl Display for TagKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.str())
+    }
+}
+
+/// Represents a Wine GE or Wine GE LoL version.
 ///
 /// Wine GE versions come in two flavours. One ist the normal Wine version with GE's patches and the other is a
 /// specific version for League of Legends.
@@ -397,18 +1626,341 @@ pub enum WineTagKind {
     LolWineGe,
 }
 
-impl From<&str> for WineTagKind {
-    fn from(string: &str) -> Self {
-        match string {
-            s if s.eq(WINE) => WineTagKind::WineGe,
-            s if s.eq(LOL_WINE) => WineTagKind::LolWineGe,
-            _ => panic!("Cannot map string to LutrisVersionKind"),
-        }
+impl WineTagKind {
+    fn parse(str: &str) -> Result<Self, TagKindError> {
+        match TagKind::parse(str)? {
+            TagKind::Wine { kind } => Ok(kind),
+            TagKind::Proton => Err(TagKindError::UnknownString(String::from(str))),
+        }
+    }
+}
+
+impl FromStr for WineTagKind {
+    type Err = TagKindError;
+
+    /// Parse a `WineTagKind` from the `str` representation of its `TagKind` or one of its aliases.
+    ///
+    /// This accepts the same Wine GE and Wine GE (LoL) strings as `TagKind::from_str`. Proton GE strings are rejected.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        WineTagKind::parse(s)
+    }
+}
+
+impl TryFrom<&str> for WineTagKind {
+    type Error = TagKindError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        WineTagKind::parse(value)
+    }
+}
+
+impl TryFrom<String> for WineTagKind {
+    type Error = TagKindError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        WineTagKind::parse(&value)
+    }
+}
+
+#[cfg(test)]
+mod semver_tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case("6.20.1" => SemVer::new(6, 20, 1, None))]
+    #[test_case("7.0.0-rc3" => SemVer::new(7, 0, 0, Some(Identifier::ReleaseCandidate(3))))]
+    #[test_case("6.16.0-LoL" => SemVer::new(6, 16, 0, Some(Identifier::Lol)))]
+    #[test_case("5.11.1-MF" => SemVer::new(5, 11, 1, Some(Identifier::Mf)))]
+    fn parse_from_string(version: &str) -> SemVer {
+        version.parse().unwrap()
+    }
+
+    #[test_case("1.2" => SemVerError::MissingComponent { input: String::from("1.2"), component: "patch" })]
+    #[test_case("" => SemVerError::InvalidComponent { input: String::new(), component: "major" })]
+    #[test_case("1.2.3-" => SemVerError::EmptyIdentifier { input: String::from("1.2.3-") })]
+    #[test_case("1.2.3-r c" => SemVerError::InvalidIdentifier { input: String::from("1.2.3-r c") })]
+    #[test_case("1.2.3.4" => SemVerError::InvalidComponent { input: String::from("1.2.3.4"), component: "patch" })]
+    #[test_case("1.x.3" => SemVerError::InvalidComponent { input: String::from("1.x.3"), component: "minor" })]
+    #[test_case("1.+2.3" => SemVerError::InvalidComponent { input: String::from("1.+2.3"), component: "minor" })]
+    #[test_case("256.0.0" => SemVerError::InvalidComponent { input: String::from("256.0.0"), component: "major" })]
+    fn parse_from_invalid_string(version: &str) -> SemVerError {
+        version.parse::<SemVer>().unwrap_err()
+    }
+
+    #[test_case("7.0.0-rc2", "7.0.0-rc3" => Ordering::Less)]
+    #[test_case("7.0.0-rc9", "7.0.0-rc10" => Ordering::Less)]
+    #[test_case("7.0.0-rc3", "7.0.0" => Ordering::Less)]
+    #[test_case("7.0.0", "7.0.0-LoL" => Ordering::Less)]
+    #[test_case("7.0.0-LoL", "7.0.0-MF" => Ordering::Less)]
+    #[test_case("7.0.0-MF", "7.0.0-hotfix" => Ordering::Less)]
+    #[test_case("7.0.0-rc3", "7.0.0-RC3" => Ordering::Equal)]
+    #[test_case("7.0.0-hotfix", "6.20.0" => Ordering::Greater)]
+    fn compare_identifiers(version: &str, other: &str) -> Ordering {
+        let version: SemVer = version.parse().unwrap();
+        version.cmp(&other.parse().unwrap())
+    }
+
+    #[test_case("rc3" => Identifier::ReleaseCandidate(3))]
+    #[test_case("RC12" => Identifier::ReleaseCandidate(12))]
+    #[test_case("LoL" => Identifier::Lol)]
+    #[test_case("MF" => Identifier::Mf)]
+    #[test_case("hotfix" => Identifier::Other(String::from("hotfix")))]
+    #[test_case("rc" => Identifier::Other(String::from("rc")); "rc without number")]
+    #[test_case("lol" => Identifier::Other(String::from("lol")); "lowercase lol")]
+    fn create_identifier_from_string(identifier: &str) -> Identifier {
+        Identifier::from(identifier)
+    }
+
+    #[test_case(Identifier::ReleaseCandidate(3) => "rc3")]
+    #[test_case(Identifier::Lol => "LoL")]
+    #[test_case(Identifier::Mf => "MF")]
+    #[test_case(Identifier::Other(String::from("hotfix")) => "hotfix")]
+    fn display_identifier(identifier: Identifier) -> String {
+        identifier.to_string()
+    }
+
+    #[test]
+    fn deserialize_identifier_from_string() {
+        let semver: SemVer =
+            serde_json::from_str(r#"{"major": 7, "minor": 0, "patch": 1, "identifier": "rc3"}"#).unwrap();
+        assert_eq!(semver.parsed_identifier(), Some(&Identifier::ReleaseCandidate(3)));
+        assert_eq!(
+            serde_json::to_string(&semver).unwrap(),
+            r#"{"major":7,"minor":0,"patch":1,"identifier":"rc3"}"#
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn get_identifier_as_string() {
+        let semver: SemVer = "6.16.3-LoL".parse().unwrap();
+        assert_eq!(semver.identifier(), Some(String::from("LoL")));
+    }
+
+    #[test_case("6.20.1", "7.0.0" => VersionBump::Major)]
+    #[test_case("7.8.0", "7.20.0" => VersionBump::Minor)]
+    #[test_case("6.20.1", "6.20.2" => VersionBump::Patch)]
+    #[test_case("7.0.0-rc3", "7.0.0" => VersionBump::IdentifierOnly)]
+    #[test_case("7.0.0-rc2", "7.0.0-rc3" => VersionBump::IdentifierOnly)]
+    #[test_case("6.16.0", "6.16.0-LoL" => VersionBump::IdentifierOnly)]
+    #[test_case("6.16.0-LoL", "6.16.0" => VersionBump::IdentifierOnly)]
+    #[test_case("7.0.0", "7.0.0-rc3" => VersionBump::Downgrade(VersionPart::Identifier))]
+    #[test_case("7.0.0-rc3", "7.0.0-rc2" => VersionBump::Downgrade(VersionPart::Identifier))]
+    #[test_case("7.0.0", "6.20.1" => VersionBump::Downgrade(VersionPart::Major))]
+    #[test_case("7.20.0", "7.8.0" => VersionBump::Downgrade(VersionPart::Minor))]
+    #[test_case("6.20.2", "6.20.1-rc1" => VersionBump::Downgrade(VersionPart::Patch))]
+    #[test_case("6.20.1", "6.20.1" => VersionBump::None)]
+    fn diff_versions(from: &str, to: &str) -> VersionBump {
+        from.parse::<SemVer>().unwrap().diff(&to.parse().unwrap())
+    }
+
+    #[test_case(VersionBump::Minor => "minor update")]
+    #[test_case(VersionBump::IdentifierOnly => "identifier update")]
+    #[test_case(VersionBump::Downgrade(VersionPart::Major) => "major downgrade")]
+    #[test_case(VersionBump::None => "no change")]
+    fn display_version_bump(bump: VersionBump) -> String {
+        bump.to_string()
+    }
+
+    #[test_case("8.26.0+2" => (SemVer::new(8, 26, 0, None), vec![2]))]
+    #[test_case("8.26.0-rc1+2.3" => (SemVer::new(8, 26, 0, Some(Identifier::ReleaseCandidate(1))), vec![2, 3]))]
+    fn parse_with_build_numbers(version: &str) -> (SemVer, Vec<u32>) {
+        let mut semver: SemVer = version.parse().unwrap();
+        let build = std::mem::take(&mut semver.build);
+        (semver, build)
+    }
+
+    #[test_case("8.26.0+")]
+    #[test_case("8.26.0+2.")]
+    #[test_case("8.26.0+x")]
+    fn parse_with_invalid_build_numbers(version: &str) {
+        let err = version.parse::<SemVer>().unwrap_err();
+        assert_eq!(
+            err,
+            SemVerError::InvalidBuild {
+                input: String::from(version)
+            }
+        );
+    }
+
+    #[test]
+    fn create_from_tag_with_build_numbers() {
+        let semver = Tag::new("GE-Proton8-26-0-2").semver().clone();
+        assert_eq!(semver.build(), &[2]);
+        assert_eq!(semver.str(), "8.26.0+2");
+    }
+
+    #[test_case("lutris-ge-6.21-1-x86_64")]
+    #[test_case("lutris-ge-6.21-1-i686")]
+    fn create_from_tag_without_architecture_numbers(tag: &str) {
+        let semver = Tag::new(tag).semver().clone();
+        assert!(semver.build().is_empty());
+        assert_eq!(semver.str(), "6.21.1");
+    }
+
+    #[test_case("GE-Proton8-25-hotfix" => Some(String::from("hotfix")))]
+    #[test_case("GE-Proton8-25" => None)]
+    #[test_case("GE-Proton8-25.tar.gz" => None)]
+    #[test_case("lutris-ge-lol-6.16-3-x86_64" => None)]
+    #[test_case("proton-3.16-5" => None)]
+    #[test_case("wine ge (custom)+8" => None)]
+    #[test_case("6.16-GE-3-LoL" => Some(String::from("LoL")))]
+    fn create_from_tag_with_suffix(tag: &str) -> Option<String> {
+        Tag::new(tag).semver().parsed_identifier().map(Identifier::to_string)
+    }
+
+    #[test]
+    fn compare_hotfix_after_base_release() {
+        let release = Tag::new("GE-Proton8-25");
+        let hotfix = Tag::new("GE-Proton8-25-hotfix");
+        let next_release = Tag::new("GE-Proton8-26");
+
+        assert_ne!(release, hotfix);
+        assert!(release < hotfix);
+        assert!(hotfix < next_release);
+        assert_eq!(hotfix.semver().str(), "8.25.0-hotfix");
+        assert_eq!(hotfix.semver().identifier_kind(), Some(IdentifierKind::Other));
+    }
+
+    #[test]
+    fn compare_build_numbers_after_patch() {
+        let release = Tag::new("GE-Proton8-26");
+        let build = Tag::new("GE-Proton8-26-0-2");
+        let next_patch = Tag::new("GE-Proton8-26-1");
+
+        assert!(release < build);
+        assert!(build < next_patch);
+        assert_eq!(release.diff(&build), VersionBump::Build);
+        assert_eq!(build.diff(&release), VersionBump::Downgrade(VersionPart::Build));
+    }
+
+    #[test]
+    fn deserialize_without_build_numbers() {
+        let semver: SemVer = serde_json::from_str(r#"{"major":8,"minor":26,"patch":0,"identifier":null}"#).unwrap();
+        assert!(semver.build().is_empty());
+        assert_eq!(
+            serde_json::to_string(&semver).unwrap(),
+            r#"{"major":8,"minor":26,"patch":0,"identifier":null}"#
+        );
+    }
+
+    #[test]
+    fn serialization_round_trip_with_build_numbers() {
+        let semver = Tag::new("GE-Proton8-26-0-2").semver().clone();
+
+        let json = serde_json::to_string(&semver).unwrap();
+        assert_eq!(
+            json,
+            r#"{"major":8,"minor":26,"patch":0,"build":[2],"identifier":null}"#
+        );
+        assert_eq!(serde_json::from_str::<SemVer>(&json).unwrap(), semver);
+    }
+
+    #[test_case("7.8" => "7.8.0")]
+    #[test_case("7" => "7.0.0")]
+    #[test_case("6.20.1-rc3" => "6.20.1-rc3")]
+    #[test_case("7-rc3" => "7.0.0-rc3")]
+    #[test_case("6.16-LoL" => "6.16.0-LoL")]
+    #[test_case("8.26+2" => "8.26.0+2")]
+    fn parse_lenient(version: &str) -> String {
+        SemVer::parse_lenient(version).unwrap().str()
+    }
+
+    #[test]
+    fn parse_lenient_reports_inferred_numbers() {
+        let semver = SemVer::parse_lenient("7.8").unwrap();
+        assert!(!semver.inferred_major());
+        assert!(!semver.inferred_minor());
+        assert!(semver.inferred_patch());
+        assert_eq!(semver, SemVer::new(7, 8, 0, None));
+    }
+
+    #[test_case("" => SemVerError::InvalidComponent { input: String::new(), component: "major" })]
+    #[test_case("-rc3" => SemVerError::InvalidComponent { input: String::from("-rc3"), component: "major" })]
+    #[test_case("7." => SemVerError::InvalidComponent { input: String::from("7."), component: "minor" })]
+    #[test_case("7.x" => SemVerError::InvalidComponent { input: String::from("7.x"), component: "minor" })]
+    #[test_case("7.8-" => SemVerError::EmptyIdentifier { input: String::from("7.8-") })]
+    fn parse_lenient_from_invalid_string(version: &str) -> SemVerError {
+        SemVer::parse_lenient(version).unwrap_err()
+    }
+
+    #[test_case("6.20.1")]
+    #[test_case("7.0.0-rc3")]
+    #[test_case("8.26.0+2")]
+    fn try_from_string(version: &str) {
+        let from_str = SemVer::try_from(version).unwrap();
+        let from_string = SemVer::try_from(String::from(version)).unwrap();
+        assert_eq!(from_str.str(), version);
+        assert_eq!(from_string.str(), version);
+    }
+
+    #[test]
+    fn try_from_is_strict() {
+        let err = SemVer::try_from("7.8").unwrap_err();
+        assert_eq!(
+            err,
+            SemVerError::MissingComponent {
+                input: String::from("7.8"),
+                component: "patch"
+            }
+        );
+    }
+
+    #[test]
+    fn parse_error_message() {
+        let err = "1.2".parse::<SemVer>().unwrap_err();
+        assert_eq!(err.to_string(), r#"Version "1.2" has no patch version number"#);
+    }
+
+    #[test_case("6.20-GE-1")]
+    #[test_case("7.0rc3-GE-1")]
+    #[test_case("6.16-GE-3-LoL")]
+    #[test_case("5.11-GE-1-MF")]
+    #[test_case("GE-Proton7-8")]
+    fn parse_round_trip(tag_str: &str) {
+        let semver = Tag::new(tag_str).semver().clone();
+        assert_eq!(semver.str().parse::<SemVer>().unwrap(), semver);
+        assert_eq!(SemVer::try_from(semver.str()).unwrap().str(), semver.str());
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct CompactSemVer {
+        #[serde(with = "semver_serde_string")]
+        version: SemVer,
+    }
+
+    #[test_case("6.20-GE-1", r#"{"version":"6.20.1"}"#)]
+    #[test_case("7.0rc3-GE-1", r#"{"version":"7.0.1-rc3"}"#)]
+    #[test_case("6.16-GE-LoL", r#"{"version":"6.16.0-LoL"}"#)]
+    fn serialize_as_string_round_trip(tag_str: &str, expected_json: &str) {
+        let compact = CompactSemVer {
+            version: Tag::new(tag_str).semver().clone(),
+        };
+
+        let json = serde_json::to_string(&compact).unwrap();
+        assert_eq!(json, expected_json);
+
+        let deserialized: CompactSemVer = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.version, compact.version);
+    }
+
+    #[test_case(r#"{"version":"1.2"}"#)]
+    #[test_case(r#"{"version":"1.2.3-"}"#)]
+    fn deserialize_as_string_from_malformed_version(json: &str) {
+        let err = serde_json::from_str::<CompactSemVer>(json).err().unwrap();
+        assert!(
+            err.to_string().starts_with("Version \"1.2"),
+            "Unexpected error: {}",
+            err
+        );
     }
 }
 
 #[cfg(test)]
 mod tag_tests {
+    use std::collections::HashSet;
+
     use test_case::test_case;
 
     use super::*;
@@ -431,11 +1983,95 @@ mod tag_tests {
     #[test_case("5.11-GE-1-MF" => String::from("5.11.1-MF"))]
     #[test_case("proton-3.16-5" => String::from("3.16.5"))]
     #[test_case("5.0-rc5-GE-1" => String::from("5.0.1-rc5"))]
+    #[test_case("lutris-GE-Proton8-26-x86_64" => String::from("8.26.0"))]
+    #[test_case("wine-ge-8-26-x86_64" => String::from("8.26.0"))]
+    #[test_case("lutris-ge-6.21-1-x86_64" => String::from("6.21.1"))]
+    #[test_case("lutris-ge-6.16-3-lol-i386" => String::from("6.16.3"))]
+    #[test_case("lutris-GE-Proton7-35-amd64" => String::from("7.35.0"))]
     fn get_semver_format(tag_str: &str) -> String {
         let tag = Tag::new(tag_str);
         tag.semver().to_string()
     }
 
+    #[test_case("GE-Proton007-08" => String::from("7.8.0"); "leading zeros")]
+    #[test_case("7.0rc03-GE-01" => String::from("7.0.1-rc3"); "leading zeros in rc")]
+    #[test_case("GE-Proton300-99999999999999999999" => String::from("255.255.0"); "numbers above u8")]
+    #[test_case("6.20-GE-1-99999999999999999999" => String::from("6.20.1+4294967295"); "build number above u32")]
+    #[test_case("GE-Proton" => String::from("0.0.0"); "no numbers")]
+    #[test_case("GE-Pröton8-25" => String::from("8.25.0"); "non-ASCII letters")]
+    fn get_semver_format_of_adversarial_tag(tag_str: &str) -> String {
+        Tag::new(tag_str).semver().to_string()
+    }
+
+    #[test]
+    fn get_semver_of_very_long_digit_run() {
+        let tag_str = format!("GE-Proton{}-{}", "9".repeat(10_000), "0".repeat(10_000));
+        assert_eq!(Tag::new(tag_str).semver().to_string(), "255.0.0");
+    }
+
+    #[test_case("7.0rc3-GE-1" => Some(IdentifierKind::ReleaseCandidate))]
+    #[test_case("5.0-rc5-GE-1" => Some(IdentifierKind::ReleaseCandidate))]
+    #[test_case("6.16-2-GE-LoL" => Some(IdentifierKind::Lol))]
+    #[test_case("6.16-GE-LoL" => Some(IdentifierKind::Lol))]
+    #[test_case("5.11-GE-1-MF" => Some(IdentifierKind::Mf))]
+    #[test_case("GE-Proton7-8" => None)]
+    #[test_case("6.20-GE-1" => None)]
+    fn get_identifier_kind(tag_str: &str) -> Option<IdentifierKind> {
+        Tag::new(tag_str).identifier_kind()
+    }
+
+    #[test_case("7.0rc3-GE-1" => Some(3))]
+    #[test_case("5.0-rc5-GE-1" => Some(5))]
+    #[test_case("6.16-GE-3-LoL" => None)]
+    #[test_case("5.11-GE-1-MF" => None)]
+    #[test_case("GE-Proton7-8" => None)]
+    fn get_release_candidate(tag_str: &str) -> Option<u32> {
+        Tag::new(tag_str).semver().release_candidate()
+    }
+
+    #[test_case("RC3" => Some(3))]
+    #[test_case("Rc12" => Some(12))]
+    #[test_case("rc" => None)]
+    #[test_case("rcX" => None)]
+    #[test_case("LoL" => None)]
+    fn get_release_candidate_from_identifier(identifier: &str) -> Option<u32> {
+        SemVer::new(7, 0, 0, Some(Identifier::from(identifier))).release_candidate()
+    }
+
+    #[test_case("7.0rc3-GE-1" => (true, false, false))]
+    #[test_case("5.0-rc5-GE-1" => (true, false, false))]
+    #[test_case("6.16-2-GE-LoL" => (false, true, false))]
+    #[test_case("6.16-GE-3-LoL" => (false, true, false))]
+    #[test_case("5.11-GE-1-MF" => (false, false, true))]
+    #[test_case("GE-Proton7-8" => (false, false, false))]
+    fn identifier_predicates(tag_str: &str) -> (bool, bool, bool) {
+        let tag = Tag::new(tag_str);
+        (tag.is_release_candidate(), tag.is_lol(), tag.is_mf())
+    }
+
+    #[test_case("GE-Proton7-8.tar.gz" => (String::from("GE-Proton7-8"), String::from("7.8.0")))]
+    #[test_case("GE-Proton9-1.tar.zst" => (String::from("GE-Proton9-1"), String::from("9.1.0")))]
+    #[test_case("Proton-6.20-GE-1.sha512sum" => (String::from("Proton-6.20-GE-1"), String::from("6.20.1")))]
+    #[test_case("wine ge (custom)+8.tar.xz" => (String::from("wine ge (custom)+8"), String::from("8.0.0")))]
+    #[test_case("wine%20ge%20%28custom%29%2B8.tar.xz" => (String::from("wine ge (custom)+8"), String::from("8.0.0")))]
+    #[test_case("Wein-GE-Übersetzung-7-2.tar.gz" => (String::from("Wein-GE-Übersetzung-7-2"), String::from("7.2.0")); "non-ASCII file name")]
+    fn create_from_file_name(file_name: &str) -> (String, String) {
+        let tag = Tag::from_file_name(file_name);
+        (tag.str().clone(), tag.semver().str())
+    }
+
+    #[test_case("6.20-GE-1", TagKind::Proton => (String::from("Proton-6.20-GE-1.tar.gz"), String::from("Proton-6.20-GE-1.sha512sum")); "Proton before renaming")]
+    #[test_case("5.11-GE-1-MF", TagKind::Proton => (String::from("Proton-5.11-GE-1-MF.tar.gz"), String::from("Proton-5.11-GE-1-MF.sha512sum")); "Proton MF before renaming")]
+    #[test_case("GE-Proton7-8", TagKind::Proton => (String::from("GE-Proton7-8.tar.gz"), String::from("GE-Proton7-8.sha512sum")); "Proton after renaming")]
+    #[test_case("6.20-GE-1", TagKind::wine() => (String::from("wine-lutris-ge-6.20-1-x86_64.tar.xz"), String::from("wine-lutris-ge-6.20-1-x86_64.sha512sum")); "Wine before renaming")]
+    #[test_case("7.0rc3-GE-1", TagKind::wine() => (String::from("wine-lutris-ge-7.0rc3-1-x86_64.tar.xz"), String::from("wine-lutris-ge-7.0rc3-1-x86_64.sha512sum")); "Wine release candidate")]
+    #[test_case("GE-Proton8-26", TagKind::wine() => (String::from("wine-lutris-GE-Proton8-26-x86_64.tar.xz"), String::from("wine-lutris-GE-Proton8-26-x86_64.sha512sum")); "Wine after renaming")]
+    #[test_case("6.16-GE-3-LoL", TagKind::lol() => (String::from("wine-lutris-ge-6.16-3-lol-x86_64.tar.xz"), String::from("wine-lutris-ge-6.16-3-lol-x86_64.sha512sum")); "Wine LoL before renaming")]
+    fn get_asset_file_names(tag_str: &str, kind: TagKind) -> (String, String) {
+        let tag = Tag::new(tag_str);
+        (tag.archive_file_name(kind), tag.checksum_file_name(kind))
+    }
+
     #[test]
     fn create_from_json_before_release_0_2_0() {
         let tag: Tag = serde_json::from_str(
@@ -464,12 +2100,294 @@ mod tag_tests {
         assert_eq!(tag.str(), "6.20-GE-1");
     }
 
+    #[test]
+    fn create_from_json_uses_serialized_semver() {
+        // Parsing "7.0rc-GE" panics because the release candidate has no number.
+        let tag: Tag = serde_json::from_str(
+            r###"{
+            "str": "7.0rc-GE",
+            "semver": {
+                "major": 7, "minor": 0, "patch": 0, "identifier": "rc1"
+            }
+        }"###,
+        )
+        .unwrap();
+
+        assert_eq!(tag.semver().str(), "7.0.0-rc1");
+        assert!(tag < Tag::new("7.0-GE-1"));
+    }
+
+    #[test]
+    fn create_from_json_without_semver() {
+        let tag: Tag = serde_json::from_str(r#"{"str": "6.20-GE-1"}"#).unwrap();
+        assert_eq!(tag.semver().str(), "6.20.1");
+    }
+
+    #[test]
+    fn create_without_parsing_semver() {
+        let tag = Tag::new("7.0rc-GE");
+        assert_eq!(tag.str(), "7.0rc-GE");
+        assert_eq!(tag, Tag::new("7.0rc-GE"));
+    }
+
+    #[test]
+    fn hash_set_contains_one_tag_per_string() {
+        let tags: HashSet<Tag> = [
+            Tag::new("6.16-GE-0-LoL"),
+            Tag::new("6.16-0-GE-LoL"),
+            Tag::new("6.16-0-GE-LoL"),
+            Tag::new("6.16-GE-1-LoL"),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(tags.len(), 3);
+        assert!(tags.contains(&Tag::new("6.16-GE-0-LoL")));
+        assert!(tags.contains(&Tag::new("6.16-0-GE-LoL")));
+        assert!(tags.contains(&Tag::new("6.16-GE-1-LoL")));
+    }
+
+    #[test]
+    fn merged_spellings_are_deduplicated_by_semver() {
+        let first_source = vec![Tag::new("6.16-GE-0-LoL"), Tag::new("6.16-GE-1-LoL")];
+        let second_source = vec![Tag::new("6.16-0-GE-LoL"), Tag::new("6.16-GE-1-LoL")];
+
+        let mut merged: Vec<Tag> = first_source.into_iter().chain(second_source).collect();
+        merged.sort();
+        merged.dedup_by(|a, b| a.semver_eq(b));
+
+        assert_eq!(merged, vec![Tag::new("6.16-0-GE-LoL"), Tag::new("6.16-GE-1-LoL")]);
+        assert!(Tag::new("6.16-GE-0-LoL").semver_eq(&Tag::new("6.16-0-GE-LoL")));
+        assert!(!Tag::new("6.16-GE-0-LoL").semver_eq(&Tag::new("6.16-GE-1-LoL")));
+    }
+
+    #[test]
+    fn hash_set_contains_deserialized_tag_with_divergent_semver_once() {
+        let deserialized: Tag = serde_json::from_str(
+            r#"{"str": "lutris-GE-Proton8-26-x86_64", "semver": {"major": 8, "minor": 26, "patch": 86, "identifier": null}}"#,
+        )
+        .unwrap();
+        assert_ne!(deserialized.semver(), Tag::new("lutris-GE-Proton8-26-x86_64").semver());
+
+        let tags: HashSet<Tag> = [deserialized, Tag::new("lutris-GE-Proton8-26-x86_64")]
+            .into_iter()
+            .collect();
+
+        assert_eq!(tags.len(), 1);
+    }
+
+    #[test_case("6.16-GE-0-LoL", "6.16-0-GE-LoL" => (Ordering::Greater, Ordering::Equal); "different spellings")]
+    #[test_case("6.16-GE-0-LoL", "6.16-GE-0-LoL" => (Ordering::Equal, Ordering::Equal); "same spelling")]
+    #[test_case("6.16-GE-0-LoL", "6.16-GE-1-LoL" => (Ordering::Less, Ordering::Less); "different versions")]
+    fn compare_with_tie_break(tag: &str, other: &str) -> (Ordering, Ordering) {
+        let (tag, other) = (Tag::new(tag), Tag::new(other));
+        (tag.cmp(&other), tag.cmp_semver_only(&other))
+    }
+
+    #[test]
+    fn sort_is_independent_of_input_order() {
+        let expected = vec![
+            Tag::new("6.16-0-GE-LoL"),
+            Tag::new("6.16-GE-0-LoL"),
+            Tag::new("6.16-GE-LoL"),
+            Tag::new("6.16-GE-1-LoL"),
+            Tag::new("6.20-GE"),
+            Tag::new("6.20-GE-0"),
+            Tag::new("GE-Proton6-20"),
+        ];
+
+        for rotation in 0..expected.len() {
+            for reverse in [false, true] {
+                let mut tags = expected.clone();
+                tags.rotate_left(rotation);
+                if reverse {
+                    tags.reverse();
+                }
+
+                tags.sort();
+                let strings: Vec<&String> = tags.iter().map(Tag::str).collect();
+                let expected_strings: Vec<&String> = expected.iter().map(Tag::str).collect();
+                assert_eq!(strings, expected_strings);
+            }
+        }
+    }
+
+    #[test]
+    fn serialize_lazily_created_semver() {
+        let json = serde_json::to_string(&Tag::new("6.20-GE-1")).unwrap();
+        assert_eq!(
+            json,
+            r#"{"str":"6.20-GE-1","semver":{"major":6,"minor":20,"patch":1,"identifier":null}}"#
+        );
+    }
+
+    #[test_case("GE-Proton7-8", TagKind::Proton)]
+    #[test_case("5.11-GE-1-MF", TagKind::Proton)]
+    #[test_case("lutris-GE-Proton8-26-x86_64", TagKind::wine())]
+    #[test_case("lutris-ge-6.21-1-x86_64", TagKind::wine())]
+    #[test_case("6.16-GE-3-LoL", TagKind::lol())]
+    #[test_case("6.20-GE-1", TagKind::Proton; "ambiguous tag as Proton")]
+    #[test_case("6.20-GE-1", TagKind::wine(); "ambiguous tag as Wine")]
+    fn create_with_kind(tag_str: &str, kind: TagKind) {
+        let tag = Tag::with_kind(tag_str, kind).unwrap();
+        assert_eq!(tag.str(), tag_str);
+        assert_eq!(tag.kind(), Some(kind));
+    }
+
+    #[test_case("GE-Proton7-8", TagKind::wine())]
+    #[test_case("6.16-GE-3-LoL", TagKind::wine())]
+    #[test_case("lutris-ge-6.21-1-x86_64", TagKind::Proton)]
+    #[test_case("5.11-GE-1-MF", TagKind::lol())]
+    fn create_with_wrong_kind(tag_str: &str, kind: TagKind) {
+        let result = Tag::with_kind(tag_str, kind);
+        assert_eq!(
+            result.unwrap_err(),
+            TagParseError::KindMismatch {
+                tag: String::from(tag_str),
+                kind
+            }
+        );
+    }
+
+    #[test]
+    fn serialize_with_kind_round_trip() {
+        let tag = Tag::with_kind("6.20-GE-1", TagKind::wine()).unwrap();
+
+        let json = serde_json::to_string(&tag).unwrap();
+        assert_eq!(
+            json,
+            r#"{"str":"6.20-GE-1","semver":{"major":6,"minor":20,"patch":1,"identifier":null},"kind":{"type":"Wine","kind":{"type":"WineGe"}}}"#
+        );
+
+        let deserialized: Tag = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.kind(), Some(TagKind::wine()));
+    }
+
+    #[test]
+    fn deserialize_without_kind() {
+        let tag: Tag = serde_json::from_str(r#"{"str": "6.20-GE-1"}"#).unwrap();
+        assert_eq!(tag.kind(), None);
+    }
+
+    #[test_case(r#"{"str": "GE-Proton7-8", "semver": {"major": 7, "minor": 8, "patch": 0, "identifier": null}}"#; "struct form")]
+    #[test_case(r#"{"value": "GE-Proton7-8", "semver": {"major": 7, "minor": 8, "patch": 0, "identifier": null}}"#; "struct with value alias")]
+    #[test_case(r#""GE-Proton7-8""#; "plain string")]
+    fn deserialize_accepted_forms(json: &str) {
+        let tag: Tag = serde_json::from_str(json).unwrap();
+        assert_eq!(tag.str(), "GE-Proton7-8");
+        assert_eq!(tag.semver().str(), "7.8.0");
+        assert_eq!(tag.kind(), None);
+    }
+
+    #[test]
+    fn deserialize_from_plain_string_in_struct() {
+        #[derive(Deserialize)]
+        struct OldConfig {
+            tag: Tag,
+        }
+
+        let config: OldConfig = serde_json::from_str(r#"{"tag": "6.16-GE-3-LoL"}"#).unwrap();
+        assert_eq!(config.tag.semver().str(), "6.16.3-LoL");
+        assert!(serde_json::to_string(&config.tag)
+            .unwrap()
+            .starts_with(r#"{"str":"6.16-GE-3-LoL","semver":"#));
+    }
+
+    #[test]
+    fn deserialize_from_unparsable_string() {
+        let err = serde_json::from_str::<Tag>(r#""7.0rc-GE""#).unwrap_err();
+        assert!(err.to_string().contains("release candidate marker without a number"));
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct CompactTag {
+        #[serde(with = "serde_string")]
+        tag: Tag,
+    }
+
+    #[test_case("GE-Proton7-8")]
+    #[test_case("6.16-GE-3-LoL")]
+    #[test_case("7.0rc3-GE-1")]
+    fn serialize_as_string_round_trip(tag_str: &str) {
+        let compact = CompactTag { tag: Tag::new(tag_str) };
+
+        let json = serde_json::to_string(&compact).unwrap();
+        assert_eq!(json, format!(r#"{{"tag":"{}"}}"#, tag_str));
+
+        let deserialized: CompactTag = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.tag.str(), tag_str);
+        assert_eq!(deserialized.tag.semver(), compact.tag.semver());
+    }
+
+    #[test_case(
+        r#"{"tag": {"str": "6.20-GE-1", "semver": {"major": 6, "minor": 20, "patch": 1, "identifier": null}}}"#
+    )]
+    #[test_case(
+        r#"{"tag": {"value": "6.20-GE-1", "semver": {"major": 6, "minor": 20, "patch": 1, "identifier": null}}}"#
+    )]
+    fn deserialize_as_string_from_verbose_format(json: &str) {
+        let compact: CompactTag = serde_json::from_str(json).unwrap();
+
+        assert_eq!(compact.tag.str(), "6.20-GE-1");
+        assert_eq!(compact.tag.semver().str(), "6.20.1");
+
+        let reserialized = serde_json::to_string(&compact).unwrap();
+        assert_eq!(reserialized, r#"{"tag":"6.20-GE-1"}"#);
+    }
+
+    #[test]
+    fn deserialize_as_string_from_invalid_value() {
+        let result = serde_json::from_str::<CompactTag>(r#"{"tag": 7}"#);
+        assert!(result.is_err());
+    }
+
     #[test_case(Tag::new("6.20-GE-1"), Tag::new("6.20-GE-1") => true)]
     #[test_case(Tag::new("6.20-GE-1"), Tag::new("6.21-GE-1") => false)]
     fn equality_tests(a: Tag, b: Tag) -> bool {
         a.eq(&b)
     }
 
+    #[test_case("GE-Proton7-8", "GE-Proton7-8", "GE-Proton7-8 (7.8.0)")]
+    #[test_case("7.0rc3-GE-1", "7.0rc3-GE-1", "7.0rc3-GE-1 (7.0.1-rc3)")]
+    #[test_case("6.16-GE-3-LoL", "6.16-GE-3-LoL", "6.16-GE-3-LoL (6.16.3-LoL)")]
+    #[test_case("GE-Proton8-26-0-2", "GE-Proton8-26-0-2", "GE-Proton8-26-0-2 (8.26.0+2)")]
+    fn display_tag(tag: &str, plain: &str, verbose: &str) {
+        let tag = Tag::new(tag);
+        assert_eq!(format!("{}", tag), plain);
+        assert_eq!(format!("{:#}", tag), verbose);
+        assert_eq!(tag.display_verbose(), verbose);
+    }
+
+    #[test_case("GE-Proton7-8", "GE-Proton7-8" => true)]
+    #[test_case("GE-Proton7-8", "GE-Proton7-9" => false)]
+    #[test_case("GE-Proton7-8", "ge-proton7-8" => false; "case-sensitive")]
+    #[test_case("6.16-GE-0-LoL", "6.16-0-GE-LoL" => false; "other spelling of the same version")]
+    fn equality_with_strings(tag: &str, other: &str) -> bool {
+        let tag = Tag::new(tag);
+        let owned = String::from(other);
+        let results = [
+            tag == *other,
+            tag == other,
+            tag == owned,
+            *other == tag,
+            other == tag,
+            owned == tag,
+        ];
+        assert!(results.iter().all(|result| *result == results[0]));
+        results[0]
+    }
+
+    #[test]
+    fn string_equality_ignores_semver() {
+        let tag = Tag::new("6.16-GE-0-LoL");
+        let other_spelling = Tag::new("6.16-0-GE-LoL");
+
+        assert!(tag == "6.16-GE-0-LoL");
+        assert!(tag != "6.16-0-GE-LoL");
+        assert_ne!(tag, other_spelling);
+        assert_eq!(tag.cmp_semver_only(&other_spelling), Ordering::Equal);
+    }
+
     #[test_case(Tag::new("6.20-GE-1"), Tag::new("6.20-GE-1") => Ordering::Equal)]
     #[test_case(Tag::new("6.20-GE-1"), Tag::new("6.21-GE-1") => Ordering::Less)]
     #[test_case(Tag::new("6.20-GE-1"), Tag::new("6.19-GE-1") => Ordering::Greater)]
@@ -481,6 +2399,379 @@ mod tag_tests {
     }
 }
 
+#[cfg(test)]
+mod tag_diff_tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case("GE-Proton7-8", "GE-Proton7-20" => VersionBump::Minor)]
+    #[test_case("6.21-GE-2", "GE-Proton7-8" => VersionBump::Major)]
+    #[test_case("7.0rc3-GE-1", "7.0-GE-1" => VersionBump::IdentifierOnly)]
+    #[test_case("GE-Proton7-20", "GE-Proton7-8" => VersionBump::Downgrade(VersionPart::Minor))]
+    #[test_case("GE-Proton7-8", "GE-Proton7-8.tar.gz" => VersionBump::None)]
+    fn diff_tags(from: &str, to: &str) -> VersionBump {
+        Tag::new(from).diff(&Tag::new(to))
+    }
+}
+
+#[cfg(test)]
+mod tag_parse_options_tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    const STRICT: TagParseOptions = TagParseOptions { strict: true };
+
+    #[test_case("6.20-GE-1")]
+    #[test_case("lutris-ge-6.21-1-x86_64")]
+    #[test_case("7.0rc3-GE-1")]
+    #[test_case("GE-Proton8-26-0-2")]
+    fn parse_strict_with_all_version_numbers(tag: &str) {
+        let parsed = Tag::parse_with(tag, &STRICT).unwrap();
+        assert_eq!(parsed, Tag::new(tag));
+        assert!(!parsed.semver().inferred_patch());
+    }
+
+    #[test_case("GE-Proton7-8" => TagParseError::MissingVersionNumbers { tag: String::from("GE-Proton7-8"), found: 2 })]
+    #[test_case("6.20-GE" => TagParseError::MissingVersionNumbers { tag: String::from("6.20-GE"), found: 2 })]
+    #[test_case("7.0rc3-GE" => TagParseError::MissingVersionNumbers { tag: String::from("7.0rc3-GE"), found: 2 })]
+    #[test_case("" => TagParseError::NoVersionNumbers { tag: String::new() })]
+    #[test_case("GE-Proton" => TagParseError::NoVersionNumbers { tag: String::from("GE-Proton") })]
+    #[test_case("lutris-ge-x86_64" => TagParseError::NoVersionNumbers { tag: String::from("lutris-ge-x86_64") })]
+    #[test_case("7.0rc-GE" => TagParseError::MissingReleaseCandidateNumber { tag: String::from("7.0rc-GE"), position: 3 })]
+    #[test_case("6.300-GE-1" => TagParseError::VersionNumberOverflow {
+        tag: String::from("6.300-GE-1"),
+        position: 2,
+        component: "minor",
+        number: String::from("300"),
+    })]
+    #[test_case("8.26-GE-1-99999999999" => TagParseError::VersionNumberOverflow {
+        tag: String::from("8.26-GE-1-99999999999"),
+        position: 10,
+        component: "build",
+        number: String::from("99999999999"),
+    })]
+    fn parse_strict_with_missing_version_numbers(tag: &str) -> TagParseError {
+        Tag::parse_with(tag, &STRICT).unwrap_err()
+    }
+
+    #[test_case("6.20-GE-1" => (false, false, false))]
+    #[test_case("6.20-GE" => (false, false, true))]
+    #[test_case("GE-Proton7" => (false, true, true))]
+    #[test_case("GE-Proton" => (true, true, true))]
+    fn parse_lenient_records_inferred_numbers(tag: &str) -> (bool, bool, bool) {
+        let tag = Tag::parse_with(tag, &TagParseOptions::default()).unwrap();
+        let semver = tag.semver();
+        (
+            semver.inferred_major(),
+            semver.inferred_minor(),
+            semver.inferred_patch(),
+        )
+    }
+
+    #[test]
+    fn inferred_numbers_are_ignored_in_comparison() {
+        let inferred = Tag::new("6.20-GE");
+        let explicit = Tag::new("6.20-GE-0");
+
+        assert!(inferred.semver().inferred_patch());
+        assert!(!explicit.semver().inferred_patch());
+        assert_eq!(inferred.semver(), explicit.semver());
+        assert_eq!(inferred.cmp_semver_only(&explicit), Ordering::Equal);
+    }
+
+    #[test]
+    fn strict_error_message() {
+        let err = Tag::parse_with("6.20-GE", &STRICT).unwrap_err();
+        assert_eq!(err.to_string(), r#"Tag "6.20-GE" contains only 2 of 3 version numbers"#);
+    }
+
+    #[test_case("7.0rc-GE")]
+    #[test_case("GE-Proton7-rc")]
+    fn lenient_parse_rejects_release_candidate_without_number(tag: &str) {
+        let err = Tag::parse_with(tag, &TagParseOptions::default()).unwrap_err();
+        assert!(matches!(err, TagParseError::MissingReleaseCandidateNumber { .. }));
+    }
+
+    #[test_case("" => "0.0.0")]
+    #[test_case("6.300-GE-1" => "6.255.1")]
+    fn lenient_parse_fills_in_and_caps_numbers(tag: &str) -> String {
+        Tag::parse_with(tag, &TagParseOptions::default())
+            .unwrap()
+            .semver()
+            .str()
+    }
+
+    #[test_case("GE-Proton7-8" => Ok(String::from("7.8.0")); "proton")]
+    #[test_case("6.20-GE" => Ok(String::from("6.20.0")); "missing patch")]
+    #[test_case("lol" => Err(TagParseError::NoVersionNumbers { tag: String::from("lol") }); "no version numbers")]
+    #[test_case("" => Err(TagParseError::NoVersionNumbers { tag: String::new() }); "empty")]
+    #[test_case("7.0rc-GE" => Err(TagParseError::MissingReleaseCandidateNumber { tag: String::from("7.0rc-GE"), position: 3 }); "release candidate without number")]
+    fn try_new(tag: &str) -> Result<String, TagParseError> {
+        Tag::try_new(tag).map(|tag| tag.semver().str())
+    }
+
+    #[test]
+    fn parse_error_diagnostics() {
+        let err = Tag::parse_with("lutris-x86_64-7.0rc-GE", &STRICT).unwrap_err();
+        assert_eq!(err.tag(), "lutris-x86_64-7.0rc-GE");
+        assert_eq!(err.position(), Some(17));
+        assert_eq!(
+            err.to_string(),
+            r#"Tag "lutris-x86_64-7.0rc-GE" has a release candidate marker without a number at position 17"#
+        );
+    }
+}
+
+#[cfg(test)]
+mod tag_iter_tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    fn tags() -> Vec<Tag> {
+        vec![
+            Tag::new("GE-Proton7-8"),
+            Tag::new("6.21-GE-2-LoL"),
+            Tag::new("7.0rc3-GE-1"),
+            Tag::new("lutris-ge-6.21-1-x86_64"),
+            Tag::new("6.20-GE-1"),
+        ]
+    }
+
+    #[test]
+    fn latest_of_borrowed_tags() {
+        let tags = tags();
+        assert_eq!(tags.iter().latest(), Some(&Tag::new("GE-Proton7-8")));
+    }
+
+    #[test]
+    fn latest_of_owned_tags() {
+        let latest: Option<Tag> = tags().into_iter().latest();
+        assert_eq!(latest, Some(Tag::new("GE-Proton7-8")));
+    }
+
+    #[test]
+    fn latest_of_empty_iterator() {
+        assert_eq!(Vec::<Tag>::new().iter().latest(), None);
+    }
+
+    #[test]
+    fn latest_of_same_version_picks_greatest_tag_string() {
+        let tags = [Tag::new("6.16-GE-0-LoL"), Tag::new("6.16-0-GE-LoL")];
+        assert_eq!(tags.iter().latest(), Some(&Tag::new("6.16-GE-0-LoL")));
+        assert_eq!(tags.iter().rev().latest(), Some(&Tag::new("6.16-GE-0-LoL")));
+    }
+
+    #[test]
+    fn latest_stable_skips_release_candidates() {
+        let tags = [Tag::new("6.21-GE-2"), Tag::new("7.0rc3-GE-1"), Tag::new("6.20-GE-1")];
+        assert_eq!(tags.iter().latest(), Some(&Tag::new("7.0rc3-GE-1")));
+        assert_eq!(tags.iter().latest_stable(), Some(&Tag::new("6.21-GE-2")));
+    }
+
+    #[test]
+    fn latest_of_inferred_kind() {
+        let tags = tags();
+        assert_eq!(
+            tags.iter().latest_of_kind(TagKind::Proton),
+            Some(&Tag::new("GE-Proton7-8"))
+        );
+        assert_eq!(
+            tags.iter().latest_of_kind(TagKind::lol()),
+            Some(&Tag::new("6.21-GE-2-LoL"))
+        );
+        assert_eq!(
+            tags.iter().latest_of_kind(TagKind::wine()),
+            Some(&Tag::new("lutris-ge-6.21-1-x86_64"))
+        );
+    }
+
+    #[test]
+    fn latest_of_bound_kind() {
+        let tags = [
+            Tag::with_kind("6.20-GE-1", TagKind::wine()).unwrap(),
+            Tag::new("6.19-GE-2"),
+            Tag::with_kind("6.21-GE-1", TagKind::Proton).unwrap(),
+        ];
+        assert_eq!(
+            tags.iter().latest_of_kind(TagKind::wine()),
+            Some(&Tag::new("6.20-GE-1"))
+        );
+    }
+
+    #[test]
+    fn latest_of_explicit_kind() {
+        let tags = vec![
+            (Tag::new("6.20-GE-1"), TagKind::wine()),
+            (Tag::new("GE-Proton7-8"), TagKind::Proton),
+            (Tag::new("6.19-GE-2"), TagKind::wine()),
+        ];
+
+        assert_eq!(
+            tags.iter().latest_of_kind(TagKind::wine()),
+            Some(&(Tag::new("6.20-GE-1"), TagKind::wine()))
+        );
+        assert_eq!(
+            tags.into_iter().latest_of_kind(TagKind::Proton),
+            Some((Tag::new("GE-Proton7-8"), TagKind::Proton))
+        );
+    }
+
+    #[test]
+    fn latest_checked_of_same_kind() {
+        let tags = [
+            Tag::new("6.20-GE-1"),
+            Tag::new("lutris-ge-6.21-1-x86_64"),
+            Tag::with_kind("6.19-GE-2", TagKind::wine()).unwrap(),
+        ];
+        assert_eq!(tags.iter().latest_checked(), Some(&Tag::new("lutris-ge-6.21-1-x86_64")));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "Tags of different kinds are compared: PROTON and LOL_WINE")]
+    fn latest_checked_of_mixed_kinds() {
+        let tags = tags();
+        tags.iter().latest_checked();
+    }
+
+    #[test_case("GE-Proton7-8", TagKind::Proton, "GE-Proton7-9", TagKind::Proton => Some(Ordering::Less))]
+    #[test_case("6.20-GE-1", TagKind::wine(), "6.20-GE-1", TagKind::wine() => Some(Ordering::Equal))]
+    #[test_case("6.21-GE-1", TagKind::wine(), "6.20-GE-1", TagKind::wine() => Some(Ordering::Greater))]
+    #[test_case("6.20-GE-1", TagKind::wine(), "GE-Proton7-8", TagKind::Proton => None; "wine and proton")]
+    #[test_case("6.16-GE-3-LoL", TagKind::lol(), "6.16-GE-3", TagKind::wine() => None; "lol and wine")]
+    #[test_case("6.20-GE-1", TagKind::wine(), "6.20-GE-1", TagKind::Proton => None; "same tag of different kinds")]
+    fn compare_with_kind(tag: &str, kind: TagKind, other: &str, other_kind: TagKind) -> Option<Ordering> {
+        Tag::new(tag).compare_with_kind(kind, &Tag::new(other), other_kind)
+    }
+}
+
+#[cfg(test)]
+mod tag_normalization_tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case("GE-Proton7-8", TagKind::Proton => "GE-Proton7-8")]
+    #[test_case("GE-Proton7-8.tar.gz", TagKind::Proton => "GE-Proton7-8")]
+    #[test_case("6.20-GE-1", TagKind::Proton => "GE-Proton6-20-1")]
+    #[test_case("Proton-6.20-GE-1", TagKind::Proton => "GE-Proton6-20-1")]
+    #[test_case("proton-3.16-5", TagKind::Proton => "GE-Proton3-16-5")]
+    #[test_case("5.11-GE-1-MF", TagKind::Proton => "GE-Proton5-11-1-MF")]
+    #[test_case("7.0rc3-GE-1", TagKind::Proton => "GE-Proton7-0-1-rc3")]
+    #[test_case("6.20-GE-1", TagKind::wine() => "6.20-GE-1")]
+    #[test_case("lutris-ge-6.21-1-x86_64", TagKind::wine() => "6.21-GE-1")]
+    #[test_case("7.0rc3-GE-1", TagKind::wine() => "7.0rc3-GE-1")]
+    #[test_case("6.16-GE-0-LoL", TagKind::lol() => "6.16-GE-0-LoL")]
+    #[test_case("6.16-0-GE-LoL", TagKind::lol() => "6.16-GE-0-LoL")]
+    #[test_case("6.16-GE-3-LoL", TagKind::lol() => "6.16-GE-3-LoL")]
+    #[test_case("GE-Proton8-26-0-2", TagKind::Proton => "GE-Proton8-26-0-2")]
+    #[test_case("8.26-GE-0-2", TagKind::wine() => "8.26-GE-0-2")]
+    fn normalize_tag(tag: &str, kind: TagKind) -> String {
+        Tag::new(tag).normalized(kind)
+    }
+
+    #[test_case("GE-Proton7-8", TagKind::Proton)]
+    #[test_case("6.20-GE-1", TagKind::Proton)]
+    #[test_case("5.11-GE-1-MF", TagKind::Proton)]
+    #[test_case("7.0rc3-GE-1", TagKind::Proton)]
+    #[test_case("7.0rc3-GE-1", TagKind::wine())]
+    #[test_case("6.16-0-GE-LoL", TagKind::lol())]
+    fn normalized_tag_has_same_semver(tag: &str, kind: TagKind) {
+        let tag = Tag::new(tag);
+        let normalized = Tag::new(tag.normalized(kind));
+        assert_eq!(normalized.semver(), tag.semver());
+        assert_eq!(normalized.normalized(kind), tag.normalized(kind));
+    }
+
+    #[test_case("7.0.1-rc3", TagKind::wine() => "7.0rc3-GE-1")]
+    #[test_case("7.0.1-rc3", TagKind::Proton => "GE-Proton7-0-1-rc3")]
+    #[test_case("6.16.3-LoL", TagKind::lol() => "6.16-GE-3-LoL")]
+    #[test_case("5.11.1-MF", TagKind::Proton => "GE-Proton5-11-1-MF")]
+    #[test_case("7.8.0", TagKind::Proton => "GE-Proton7-8")]
+    #[test_case("8.26.0+2", TagKind::wine() => "8.26-GE-0-2")]
+    fn create_from_semver(semver: &str, kind: TagKind) -> String {
+        let semver: SemVer = semver.parse().unwrap();
+        Tag::from_semver(&semver, kind).str().clone()
+    }
+
+    #[test_case("GE-Proton7-8", TagKind::Proton => "GE-Proton7-9")]
+    #[test_case("GE-Proton8-26-0-2", TagKind::Proton => "GE-Proton8-27"; "build numbers are dropped")]
+    #[test_case("Proton-6.5-GE-2", TagKind::Proton => "GE-Proton6-6"; "patch is reset")]
+    #[test_case("5.11-GE-1-MF", TagKind::Proton => "GE-Proton5-12-MF")]
+    #[test_case("6.20-GE-1", TagKind::wine() => "6.20-GE-2")]
+    #[test_case("lutris-ge-6.21-1-x86_64", TagKind::wine() => "6.21-GE-2")]
+    #[test_case("6.16-GE-3-LoL", TagKind::lol() => "6.16-GE-4-LoL")]
+    #[test_case("6.16-2-GE-LoL", TagKind::lol() => "6.16-GE-3-LoL"; "lol with swapped patch")]
+    #[test_case("7.0rc3-GE-1", TagKind::wine() => "7.0-GE-1"; "wine release candidate")]
+    #[test_case("GE-Proton7-0-1-rc3", TagKind::Proton => "GE-Proton7-0-1"; "proton release candidate")]
+    #[test_case("GE-Proton7-255", TagKind::Proton => "GE-Proton7-255"; "saturates")]
+    fn next_patch_tag(tag: &str, kind: TagKind) -> String {
+        Tag::new(tag).next_patch(kind).str().clone()
+    }
+
+    #[test_case("GE-Proton7-8", TagKind::Proton => "GE-Proton8-1")]
+    #[test_case("GE-Proton8-26-0-2", TagKind::Proton => "GE-Proton9-1"; "build numbers are dropped")]
+    #[test_case("5.11-GE-1-MF", TagKind::Proton => "GE-Proton6-1-MF")]
+    #[test_case("6.20-GE-1", TagKind::wine() => "6.21-GE-1")]
+    #[test_case("6.16-GE-3-LoL", TagKind::lol() => "6.17-GE-1-LoL")]
+    #[test_case("7.0rc3-GE-1", TagKind::wine() => "7.1-GE-1"; "release candidate")]
+    fn next_minor_tag(tag: &str, kind: TagKind) -> String {
+        Tag::new(tag).next_minor(kind).str().clone()
+    }
+
+    #[test_case("GE-Proton7-8", TagKind::Proton)]
+    #[test_case("6.20-GE-1", TagKind::wine())]
+    #[test_case("6.16-GE-3-LoL", TagKind::lol())]
+    #[test_case("7.0rc3-GE-1", TagKind::wine())]
+    fn next_tags_are_newer(tag: &str, kind: TagKind) {
+        let tag = Tag::new(tag);
+        assert_eq!(tag.cmp_semver_only(&tag.next_patch(kind)), Ordering::Less);
+        assert_eq!(tag.cmp_semver_only(&tag.next_minor(kind)), Ordering::Less);
+    }
+
+    #[test_case("6.20-GE-1")]
+    #[test_case("6.20-GE-0")]
+    #[test_case("6.20-GE")]
+    #[test_case("6.16-GE-3-LoL")]
+    #[test_case("6.16-2-GE-LoL")]
+    #[test_case("6.16-GE-LoL")]
+    #[test_case("6.16-GE-0-LoL")]
+    #[test_case("6.16-0-GE-LoL")]
+    #[test_case("7.0rc3-GE-1")]
+    #[test_case("7.0rc3-GE-0")]
+    #[test_case("7.0rc3-GE")]
+    #[test_case("7.0-GE")]
+    #[test_case("7.0-GE-1")]
+    #[test_case("GE-Proton7-8")]
+    #[test_case("GE-Proton7-4")]
+    #[test_case("5.11-GE-1-MF")]
+    #[test_case("proton-3.16-5")]
+    #[test_case("5.0-rc5-GE-1")]
+    #[test_case("lutris-GE-Proton8-26-x86_64")]
+    #[test_case("wine-ge-8-26-x86_64")]
+    #[test_case("lutris-ge-6.21-1-x86_64")]
+    #[test_case("lutris-ge-6.16-3-lol-i386")]
+    #[test_case("lutris-GE-Proton7-35-amd64")]
+    #[test_case("GE-Proton8-25-hotfix")]
+    #[test_case("GE-Proton8-26-0-2")]
+    fn create_from_semver_round_trip(tag: &str) {
+        let tag = Tag::new(tag);
+        for kind in TagKind::values() {
+            let created = Tag::from_semver(tag.semver(), kind);
+            assert_eq!(
+                Tag::new(created.str()).semver(),
+                tag.semver(),
+                "{} as {}",
+                created,
+                kind
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tag_kind_tests {
     use test_case::test_case;
@@ -533,10 +2824,103 @@ mod tag_kind_tests {
         kind.compatibility_tool_name()
     }
 
+    #[test_case("GE-Proton7-8" => Some(TagKind::Proton))]
+    #[test_case("proton-3.16-5" => Some(TagKind::Proton))]
+    #[test_case("Proton-6.20-GE-1" => Some(TagKind::Proton))]
+    #[test_case("5.11-GE-1-MF" => Some(TagKind::Proton))]
+    #[test_case("6.16-GE-3-LoL" => Some(TagKind::lol()))]
+    #[test_case("6.16-2-GE-LoL" => Some(TagKind::lol()))]
+    #[test_case("lutris-ge-6.16-3-lol-x86_64" => Some(TagKind::lol()))]
+    #[test_case("lutris-ge-6.21-1-x86_64" => Some(TagKind::wine()))]
+    #[test_case("lutris-GE-Proton8-26-x86_64" => Some(TagKind::wine()))]
+    #[test_case("6.20-GE-1" => None)]
+    #[test_case("7.0rc3-GE-1" => None)]
+    fn infer_kind_from_tag(tag: &str) -> Option<TagKind> {
+        TagKind::from_tag(&Tag::new(tag))
+    }
+
+    #[test_case("Proton-6.20-GE-1" => Some(TagKind::Proton))]
+    #[test_case("GE-Proton7-43" => Some(TagKind::Proton))]
+    #[test_case("lutris-GE-Proton8-26-x86_64" => Some(TagKind::wine()); "lutris wins over proton")]
+    #[test_case("wine-ge-8-26" => Some(TagKind::wine()))]
+    #[test_case("wine-ge-lol-6.16-3" => Some(TagKind::lol()); "lol wins over wine")]
+    #[test_case("Proton-6.16-GE-3-LoL" => Some(TagKind::lol()); "lol wins over proton")]
+    #[test_case("GE-PROTON7-43" => Some(TagKind::Proton); "upper case")]
+    #[test_case("  GE-Proton7-43\n" => Some(TagKind::Proton); "surrounding whitespace")]
+    #[test_case("/home/lol/.steam/root/compatibilitytools.d/GE-Proton7-43" => Some(TagKind::Proton); "path")]
+    #[test_case("/home/user/.local/share/lutris/runners/wine/" => Some(TagKind::wine()); "trailing separator")]
+    #[test_case("/home/user/.steam/root/compatibilitytools.d/6.20-GE-1" => None; "ambiguous directory")]
+    #[test_case("5.11-GE-1-MF" => Some(TagKind::Proton))]
+    #[test_case("6.20-GE-1" => None)]
+    #[test_case("" => None; "empty")]
+    #[test_case("steamapps" => None)]
+    fn guess_kind(s: &str) -> Option<TagKind> {
+        TagKind::guess(s)
+    }
+
     #[test_case(TagKind::Proton => "PROTON"; "Correct type name should be returned for Proton")]
     #[test_case(TagKind::wine() => "WINE"; "Correct type name should be returned for Wine")]
     #[test_case(TagKind::lol() => "LOL_WINE"; "Correct type name should be returned for Wine (LoL)")]
     fn get_type_name(kind: TagKind) -> String {
         kind.str()
     }
+
+    #[test_case("proton" => TagKind::Proton)]
+    #[test_case("Proton-GE" => TagKind::Proton)]
+    #[test_case("ge_proton" => TagKind::Proton)]
+    #[test_case("WINE" => TagKind::wine())]
+    #[test_case("wine-ge" => TagKind::wine())]
+    #[test_case(" lutris " => TagKind::wine())]
+    #[test_case("lol-wine" => TagKind::lol())]
+    #[test_case("LoL" => TagKind::lol())]
+    #[test_case("wine-ge-lol" => TagKind::lol())]
+    fn parse_from_string(str: &str) -> TagKind {
+        TagKind::try_from(str).unwrap()
+    }
+
+    #[test]
+    fn parse_with_from_str() {
+        assert_eq!("wine_ge".parse::<TagKind>().unwrap(), TagKind::wine());
+        assert_eq!(TagKind::try_from(String::from("proton")).unwrap(), TagKind::Proton);
+    }
+
+    #[test]
+    fn parse_every_str_representation() {
+        for kind in TagKind::values() {
+            assert_eq!(kind.str().parse::<TagKind>().unwrap(), kind);
+        }
+    }
+
+    #[test_case("")]
+    #[test_case("steam")]
+    #[test_case("proton ge")]
+    fn parse_from_unknown_string(str: &str) {
+        let err = str.parse::<TagKind>().unwrap_err();
+        assert_eq!(err, TagKindError::UnknownString(String::from(str)));
+    }
+
+    #[test_case("WINE" => WineTagKind::WineGe)]
+    #[test_case("wine-ge" => WineTagKind::WineGe)]
+    #[test_case("LOL_WINE" => WineTagKind::LolWineGe)]
+    #[test_case("lol" => WineTagKind::LolWineGe)]
+    fn parse_wine_tag_kind_from_string(str: &str) -> WineTagKind {
+        WineTagKind::try_from(str).unwrap()
+    }
+
+    #[test_case("PROTON")]
+    #[test_case("proton-ge")]
+    #[test_case("LutrisVersionKind")]
+    fn parse_wine_tag_kind_from_unknown_string(str: &str) {
+        let err = str.parse::<WineTagKind>().unwrap_err();
+        assert_eq!(err, TagKindError::UnknownString(String::from(str)));
+    }
+
+    #[test]
+    fn unknown_string_error_message_contains_string() {
+        let err = TagKind::try_from("steam").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"Could not create TagKind from provided string "steam"."#
+        );
+    }
 }
@@ -0,0 +1,510 @@
+//! Parse and format timestamps.
+//!
+//! All timestamps of this crate are UTC instants represented by `SystemTime`. They are serialized as RFC 3339
+//! date-times in UTC (e.g. `2022-03-20T18:30:12Z`) and used in file names in the fixed format `20220320T183012Z`, which
+//! sorts lexicographically in chronological order. No other module parses or formats timestamps itself.
+//!
+//! Parsing accepts the forms the GitHub API emits: a `Z` suffix, numeric offsets like `+02:00` or `-0500` and optional
+//! fractional seconds. Offsets are applied, so the parsed `SystemTime` is always the UTC instant.
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::TimestampError;
+
+const SECONDS_PER_MINUTE: i64 = 60;
+const SECONDS_PER_HOUR: i64 = 60 * SECONDS_PER_MINUTE;
+const SECONDS_PER_DAY: i64 = 24 * SECONDS_PER_HOUR;
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+
+/// A UTC date-time split into its calendar fields.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct DateTime {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    nanos: u32,
+}
+
+impl DateTime {
+    fn from_system_time(time: SystemTime) -> Self {
+        let (mut seconds, mut nanos) = match time.duration_since(UNIX_EPOCH) {
+            Ok(duration) => (duration.as_secs() as i64, i64::from(duration.subsec_nanos())),
+            Err(err) => {
+                let duration = err.duration();
+                (-(duration.as_secs() as i64), -i64::from(duration.subsec_nanos()))
+            }
+        };
+        if nanos < 0 {
+            seconds -= 1;
+            nanos += NANOS_PER_SECOND;
+        }
+
+        let days = seconds.div_euclid(SECONDS_PER_DAY);
+        let second_of_day = seconds.rem_euclid(SECONDS_PER_DAY);
+        let (year, month, day) = civil_from_days(days);
+        DateTime {
+            year,
+            month,
+            day,
+            hour: (second_of_day / SECONDS_PER_HOUR) as u32,
+            minute: (second_of_day % SECONDS_PER_HOUR / SECONDS_PER_MINUTE) as u32,
+            second: (second_of_day % SECONDS_PER_MINUTE) as u32,
+            nanos: nanos as u32,
+        }
+    }
+
+    fn to_system_time(self, offset_seconds: i64) -> SystemTime {
+        let seconds = days_from_civil(self.year, self.month, self.day) * SECONDS_PER_DAY
+            + i64::from(self.hour) * SECONDS_PER_HOUR
+            + i64::from(self.minute) * SECONDS_PER_MINUTE
+            + i64::from(self.second)
+            - offset_seconds;
+        let nanos = Duration::from_nanos(u64::from(self.nanos));
+
+        if seconds >= 0 {
+            UNIX_EPOCH + Duration::from_secs(seconds as u64) + nanos
+        } else {
+            UNIX_EPOCH - Duration::from_secs(seconds.unsigned_abs()) + nanos
+        }
+    }
+}
+
+/// Parse an RFC 3339 date-time like `2022-03-20T18:30:12Z` or `2022-03-20T20:30:12.5+02:00`.
+///
+/// # Errors
+///
+/// This function returns an error if `input` is not an RFC 3339 date-time or contains an invalid date or time.
+pub fn parse_rfc3339(input: &str) -> Result<SystemTime, TimestampError> {
+    let invalid = |reason: &'static str| TimestampError::InvalidFormat {
+        input: String::from(input),
+        reason,
+    };
+    let mut scanner = Scanner::new(input);
+
+    let year = scanner.number(4).ok_or_else(|| invalid("expected a four digit year"))?;
+    scanner
+        .expect(b'-')
+        .ok_or_else(|| invalid("expected '-' after the year"))?;
+    let month = scanner.number(2).ok_or_else(|| invalid("expected a two digit month"))?;
+    scanner
+        .expect(b'-')
+        .ok_or_else(|| invalid("expected '-' after the month"))?;
+    let day = scanner.number(2).ok_or_else(|| invalid("expected a two digit day"))?;
+    scanner
+        .expect_any(b"Tt ")
+        .ok_or_else(|| invalid("expected 'T' between date and time"))?;
+    let hour = scanner.number(2).ok_or_else(|| invalid("expected a two digit hour"))?;
+    scanner
+        .expect(b':')
+        .ok_or_else(|| invalid("expected ':' after the hour"))?;
+    let minute = scanner
+        .number(2)
+        .ok_or_else(|| invalid("expected a two digit minute"))?;
+    scanner
+        .expect(b':')
+        .ok_or_else(|| invalid("expected ':' after the minute"))?;
+    let second = scanner.number(2).ok_or_else(|| invalid("expected two digit seconds"))?;
+    let nanos = scanner.fraction().ok_or_else(|| invalid("expected digits after '.'"))?;
+    let offset_seconds = scanner
+        .offset()
+        .ok_or_else(|| invalid("expected 'Z' or a numeric offset"))?;
+    if !scanner.is_done() {
+        return Err(invalid("unexpected characters after the offset"));
+    }
+
+    if !(1..=12).contains(&month) {
+        return Err(invalid("month is out of range"));
+    }
+    if day == 0 || day > days_in_month(year, month) {
+        return Err(invalid("day is out of range"));
+    }
+    // A leap second (`:60`) is accepted and folds into the following second.
+    if hour > 23 || minute > 59 || second > 60 {
+        return Err(invalid("time is out of range"));
+    }
+
+    let date_time = DateTime {
+        year: i64::from(year),
+        month,
+        day,
+        hour,
+        minute,
+        second,
+        nanos,
+    };
+    Ok(date_time.to_system_time(offset_seconds))
+}
+
+/// Format `time` as an RFC 3339 date-time in UTC, e.g. `2022-03-20T18:30:12Z`.
+///
+/// Fractional seconds are only included if `time` is not a whole second. Trailing zeros of the fraction are omitted.
+pub fn format_rfc3339(time: SystemTime) -> String {
+    let date_time = DateTime::from_system_time(time);
+    let mut formatted = format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        date_time.year, date_time.month, date_time.day, date_time.hour, date_time.minute, date_time.second
+    );
+    if date_time.nanos > 0 {
+        let fraction = format!("{:09}", date_time.nanos);
+        formatted.push('.');
+        formatted.push_str(fraction.trim_end_matches('0'));
+    }
+    formatted.push('Z');
+    formatted
+}
+
+/// Format `time` for use in a file name, e.g. `20220320T183012Z`.
+///
+/// The format has a fixed width and only contains characters that are valid in file names on all platforms. File names
+/// with this format sort lexicographically in chronological order. Fractional seconds are dropped.
+pub fn format_file_name(time: SystemTime) -> String {
+    let date_time = DateTime::from_system_time(time);
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        date_time.year, date_time.month, date_time.day, date_time.hour, date_time.minute, date_time.second
+    )
+}
+
+/// Serialize a `SystemTime` as an RFC 3339 string with `#[serde(with = "...")]`.
+pub mod rfc3339 {
+    use std::time::SystemTime;
+
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(time: &SystemTime, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&super::format_rfc3339(*time))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<SystemTime, D::Error> {
+        let str = String::deserialize(deserializer)?;
+        super::parse_rfc3339(&str).map_err(D::Error::custom)
+    }
+}
+
+/// Serialize an `Option<SystemTime>` as an RFC 3339 string or `null` with `#[serde(with = "...")]`.
+pub mod option_rfc3339 {
+    use std::time::SystemTime;
+
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(time: &Option<SystemTime>, serializer: S) -> Result<S::Ok, S::Error> {
+        match time {
+            Some(time) => serializer.serialize_some(&super::format_rfc3339(*time)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<SystemTime>, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(str) => super::parse_rfc3339(&str).map(Some).map_err(D::Error::custom),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Like `option_rfc3339`, but a missing, malformed or non-string timestamp is deserialized as `None` instead of failing.
+///
+/// This is meant for data from external APIs, where an unexpected timestamp should not make the whole value unusable.
+pub mod lenient_option_rfc3339 {
+    use std::time::SystemTime;
+
+    use serde::de::IgnoredAny;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Timestamp {
+        String(String),
+        Other(IgnoredAny),
+    }
+
+    pub fn serialize<S: Serializer>(time: &Option<SystemTime>, serializer: S) -> Result<S::Ok, S::Error> {
+        super::option_rfc3339::serialize(time, serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<SystemTime>, D::Error> {
+        let time = match Timestamp::deserialize(deserializer)? {
+            Timestamp::String(str) => super::parse_rfc3339(&str).ok(),
+            Timestamp::Other(_) => None,
+        };
+        Ok(time)
+    }
+}
+
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    idx: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(input: &'a str) -> Self {
+        Scanner {
+            bytes: input.as_bytes(),
+            idx: 0,
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.idx == self.bytes.len()
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.idx).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        self.expect_any(&[byte])
+    }
+
+    fn expect_any(&mut self, bytes: &[u8]) -> Option<()> {
+        self.peek().filter(|next| bytes.contains(next))?;
+        self.idx += 1;
+        Some(())
+    }
+
+    /// Read exactly `digits` ASCII digits.
+    fn number(&mut self, digits: usize) -> Option<u32> {
+        let digits = self.bytes.get(self.idx..self.idx + digits)?;
+        if !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        self.idx += digits.len();
+        Some(
+            digits
+                .iter()
+                .fold(0, |number, digit| number * 10 + u32::from(digit - b'0')),
+        )
+    }
+
+    /// Read optional fractional seconds as nanoseconds. Digits beyond nanosecond precision are truncated.
+    fn fraction(&mut self) -> Option<u32> {
+        if self.expect(b'.').is_none() {
+            return Some(0);
+        }
+        let start = self.idx;
+        while self.peek().is_some_and(|byte| byte.is_ascii_digit()) {
+            self.idx += 1;
+        }
+        let digits = &self.bytes[start..self.idx];
+        if digits.is_empty() {
+            return None;
+        }
+        let nanos = (0..9).fold(0, |nanos, idx| {
+            nanos * 10 + digits.get(idx).map_or(0, |digit| u32::from(digit - b'0'))
+        });
+        Some(nanos)
+    }
+
+    /// Read the UTC offset in seconds: `Z`, `+HH:MM` or `+HHMM`.
+    fn offset(&mut self) -> Option<i64> {
+        if self.expect_any(b"Zz").is_some() {
+            return Some(0);
+        }
+        let sign = match self.peek()? {
+            b'+' => 1,
+            b'-' => -1,
+            _ => return None,
+        };
+        self.idx += 1;
+        let hours = self.number(2)?;
+        let _ = self.expect(b':');
+        let minutes = self.number(2)?;
+        if hours > 23 || minutes > 59 {
+            return None;
+        }
+        Some(sign * (i64::from(hours) * SECONDS_PER_HOUR + i64::from(minutes) * SECONDS_PER_MINUTE))
+    }
+}
+
+fn is_leap_year(year: u32) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Get the number of days since 1970-01-01 for a date of the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Get the date of the proleptic Gregorian calendar for a number of days since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod timeutil_tests {
+    use serde::{Deserialize, Serialize};
+    use test_case::test_case;
+
+    use super::*;
+
+    // 2022-03-20T18:30:12Z
+    const PUBLISHED_AT: u64 = 1_647_801_012;
+
+    fn time(seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    #[test_case("2022-03-20T18:30:12Z" => time(PUBLISHED_AT); "utc")]
+    #[test_case("2022-03-20t18:30:12z" => time(PUBLISHED_AT); "lowercase separators")]
+    #[test_case("2022-03-20 18:30:12Z" => time(PUBLISHED_AT); "space separator")]
+    #[test_case("2022-03-20T18:30:12+00:00" => time(PUBLISHED_AT); "zero offset")]
+    #[test_case("2022-03-20T20:30:12+02:00" => time(PUBLISHED_AT); "positive offset")]
+    #[test_case("2022-03-20T13:30:12-05:00" => time(PUBLISHED_AT); "negative offset")]
+    #[test_case("2022-03-20T13:30:12-0500" => time(PUBLISHED_AT); "offset without colon")]
+    #[test_case("2022-03-21T00:00:12+05:30" => time(PUBLISHED_AT); "offset crossing the date")]
+    #[test_case("2022-03-20T18:30:12.250Z" => time(PUBLISHED_AT) + Duration::from_millis(250); "fractional seconds")]
+    #[test_case("1970-01-01T00:00:00Z" => UNIX_EPOCH; "unix epoch")]
+    #[test_case("1969-12-31T23:59:59Z" => UNIX_EPOCH - Duration::from_secs(1); "before unix epoch")]
+    #[test_case("2024-02-29T00:00:00Z" => time(1_709_164_800); "leap day")]
+    fn parse_github_timestamp(input: &str) -> SystemTime {
+        parse_rfc3339(input).unwrap()
+    }
+
+    #[test_case("" ; "empty")]
+    #[test_case("2022-03-20" ; "date only")]
+    #[test_case("2022-03-20T18:30:12" ; "missing offset")]
+    #[test_case("2022-03-20T18:30:12.Z" ; "empty fraction")]
+    #[test_case("2022-13-20T18:30:12Z" ; "invalid month")]
+    #[test_case("2023-02-29T18:30:12Z" ; "invalid leap day")]
+    #[test_case("2022-03-20T24:30:12Z" ; "invalid hour")]
+    #[test_case("2022-03-20T18:30:12+24:00" ; "invalid offset")]
+    #[test_case("2022-03-20T18:30:12Z trailing" ; "trailing characters")]
+    #[test_case("２０２２-03-20T18:30:12Z" ; "non-ASCII digits")]
+    fn parse_invalid_timestamp(input: &str) {
+        let err = parse_rfc3339(input).unwrap_err();
+        assert!(matches!(err, TimestampError::InvalidFormat { input: ref err_input, .. } if err_input == input));
+    }
+
+    #[test_case(time(PUBLISHED_AT) => "2022-03-20T18:30:12Z"; "whole seconds")]
+    #[test_case(time(PUBLISHED_AT) + Duration::from_millis(250) => "2022-03-20T18:30:12.25Z"; "fractional seconds")]
+    #[test_case(UNIX_EPOCH - Duration::from_millis(500) => "1969-12-31T23:59:59.5Z"; "before unix epoch")]
+    fn format_timestamp(time: SystemTime) -> String {
+        format_rfc3339(time)
+    }
+
+    #[test_case("2022-03-20T18:30:12Z")]
+    #[test_case("2022-03-20T18:30:12.123456789Z")]
+    #[test_case("1901-01-01T00:00:00Z")]
+    #[test_case("2400-02-29T23:59:59Z")]
+    fn format_parsed_timestamp(input: &str) {
+        assert_eq!(format_rfc3339(parse_rfc3339(input).unwrap()), input);
+    }
+
+    #[test]
+    fn format_timestamp_for_file_name() {
+        assert_eq!(format_file_name(time(PUBLISHED_AT)), "20220320T183012Z");
+    }
+
+    #[test]
+    fn file_names_sort_chronologically() {
+        let times = [
+            time(0),
+            time(9),
+            time(10),
+            time(PUBLISHED_AT),
+            time(PUBLISHED_AT + 59),
+            time(PUBLISHED_AT + 60),
+            time(PUBLISHED_AT + 86_400 * 300),
+            time(4_102_444_800),
+        ];
+        let names: Vec<String> = times.iter().map(|time| format_file_name(*time)).collect();
+
+        let mut sorted = names.clone();
+        sorted.sort();
+        assert_eq!(names, sorted);
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Release {
+        #[serde(with = "rfc3339")]
+        created_at: SystemTime,
+        #[serde(default, with = "option_rfc3339")]
+        published_at: Option<SystemTime>,
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let release = Release {
+            created_at: time(PUBLISHED_AT),
+            published_at: Some(time(PUBLISHED_AT) + Duration::from_millis(250)),
+        };
+
+        let json = serde_json::to_string(&release).unwrap();
+        assert_eq!(
+            json,
+            r#"{"created_at":"2022-03-20T18:30:12Z","published_at":"2022-03-20T18:30:12.25Z"}"#
+        );
+        assert_eq!(serde_json::from_str::<Release>(&json).unwrap(), release);
+    }
+
+    #[test]
+    fn deserialize_offset_as_utc() {
+        let release: Release =
+            serde_json::from_str(r#"{"created_at":"2022-03-20T20:30:12+02:00","published_at":null}"#).unwrap();
+        assert_eq!(release.created_at, time(PUBLISHED_AT));
+        assert_eq!(release.published_at, None);
+        assert_eq!(
+            serde_json::to_string(&release).unwrap(),
+            r#"{"created_at":"2022-03-20T18:30:12Z","published_at":null}"#
+        );
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct LenientRelease {
+        #[serde(default, with = "lenient_option_rfc3339")]
+        published_at: Option<SystemTime>,
+    }
+
+    #[test_case(r#"{"published_at":"2022-03-20T18:30:12Z"}"# => Some(time(PUBLISHED_AT)); "valid")]
+    #[test_case(r#"{"published_at":"yesterday"}"# => None; "malformed")]
+    #[test_case(r#"{"published_at":1647801012}"# => None; "number")]
+    #[test_case(r#"{"published_at":null}"# => None; "null")]
+    #[test_case(r#"{}"# => None; "missing")]
+    fn deserialize_lenient_timestamp(json: &str) -> Option<SystemTime> {
+        serde_json::from_str::<LenientRelease>(json).unwrap().published_at
+    }
+
+    #[test]
+    fn deserialize_missing_optional_timestamp() {
+        let release: Release = serde_json::from_str(r#"{"created_at":"2022-03-20T18:30:12Z"}"#).unwrap();
+        assert_eq!(release.published_at, None);
+    }
+
+    #[test]
+    fn deserialize_invalid_timestamp() {
+        let result = serde_json::from_str::<Release>(r#"{"created_at":"yesterday"}"#);
+        assert!(result.is_err());
+    }
+}
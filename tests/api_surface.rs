@@ -0,0 +1,354 @@
+//! Compile checks for the public API.
+//!
+//! Every function pointer below is assigned to an explicitly typed binding. If the signature of one of these functions
+//! changes, this file stops compiling. See the crate documentation for the covered parts of the API.
+
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use ge_man_lib::archive;
+use ge_man_lib::digest::Digest;
+use ge_man_lib::download::response::{DownloadedArchive, DownloadedChecksum};
+use ge_man_lib::download::retry::RetryPolicy;
+use ge_man_lib::error::{TagRangeError, TimestampError};
+use ge_man_lib::prelude::*;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+fn fetch_first_page(downloader: &dyn GeDownload, kind: TagKind) -> Result<Vec<GeRelease>, GithubError> {
+    downloader.fetch_releases(kind, 1)
+}
+
+fn stream_archive(
+    downloader: &dyn GeDownload,
+    request: DownloadRequest,
+    sink: &mut dyn Write,
+) -> Result<ArchiveMetadata, GithubError> {
+    downloader.download_archive_to(request, sink)
+}
+
+#[test]
+fn tag_signatures() {
+    let _: fn(&'static str) -> Tag = Tag::new::<&str>;
+    let _: fn(String) -> Tag = Tag::new::<String>;
+    let _: fn(String, TagKind) -> Result<Tag, TagParseError> = Tag::with_kind::<String>;
+    let _: fn(String, &TagParseOptions) -> Result<Tag, TagParseError> = Tag::parse_with::<String>;
+    let _: fn(&str) -> Tag = Tag::from_file_name;
+    let _: fn(&SemVer, TagKind) -> Tag = Tag::from_semver;
+    let _: fn(&Tag, TagKind) -> Tag = Tag::next_patch;
+    let _: fn(&Tag, TagKind) -> Tag = Tag::next_minor;
+    let _: fn(&Tag) -> &SemVer = Tag::semver;
+    let _: fn(&Tag) -> &String = Tag::str;
+    let _: fn(&Tag) -> Option<TagKind> = Tag::kind;
+    let _: fn(&Tag, &Tag) -> VersionBump = Tag::diff;
+    let _: fn(&Tag, &Tag) -> std::cmp::Ordering = Tag::cmp_semver_only;
+    let _: fn(&Tag, &Tag) -> bool = Tag::semver_eq;
+    let _: fn(&Tag, TagKind, &Tag, TagKind) -> Option<std::cmp::Ordering> = Tag::compare_with_kind;
+    let _: fn(&Tag, TagKind) -> String = Tag::normalized;
+    let _: fn(&Tag, TagKind) -> String = Tag::archive_file_name;
+    let _: fn(&Tag, TagKind) -> String = Tag::checksum_file_name;
+    let _: fn(&Tag) -> Option<TagKind> = TagKind::from_tag;
+    let _: fn(&str) -> Option<TagKind> = TagKind::guess;
+}
+
+#[test]
+fn semver_signatures() {
+    let _: fn(&SemVer) -> u8 = SemVer::major;
+    let _: fn(&SemVer) -> u8 = SemVer::minor;
+    let _: fn(&SemVer) -> u8 = SemVer::patch;
+    let _: fn(&SemVer) -> &[u32] = SemVer::build;
+    let _: fn(&SemVer) -> Option<u32> = SemVer::release_candidate;
+    let _: fn(&SemVer, &SemVer) -> VersionBump = SemVer::diff;
+    let _: Result<SemVer, SemVerError> = "7.8.0".parse();
+    let _: Result<SemVer, SemVerError> = SemVer::try_from("7.8.0");
+    let _: fn(&str) -> Result<SemVer, SemVerError> = SemVer::parse_lenient;
+}
+
+#[test]
+fn tag_range_signatures() {
+    let _: fn(&TagRange, &Tag) -> bool = TagRange::satisfies;
+    let _: Result<TagRange, TagRangeError> = ">=7.20, <8".parse();
+    let _: fn(&TagPattern, &Tag) -> bool = TagPattern::matches;
+    let _: fn(&TagPattern, &SemVer) -> bool = TagPattern::matches_semver;
+    let _: Result<TagPattern, TagPatternError> = "7.*".parse();
+}
+
+#[test]
+fn archive_signatures() {
+    let _: fn(&[u8], &[u8]) -> bool = archive::checksums_match;
+    let _: fn(&[u8], &[u8]) -> Result<(), ChecksumError> = archive::verify;
+    let _ = |file_name: &str, compressed_archive: &[u8], checksum_file: &[u8]| -> Result<(), ChecksumError> {
+        archive::verify_named(file_name, compressed_archive, checksum_file)
+    };
+    let _ = |file_name: &str, digest: &Digest, checksum_file: &[u8]| -> Result<(), ChecksumError> {
+        archive::verify_named_digest(file_name, digest, checksum_file)
+    };
+    let _: fn(&DownloadedAssets) -> Result<(), ChecksumError> = DownloadedAssets::verify_checksum;
+    let _: fn(&str) -> Result<ChecksumFile, ChecksumParseError> = ChecksumFile::parse;
+    let _: fn(&ChecksumFile) -> Option<&str> = ChecksumFile::file_name;
+    // Functions with `impl Trait` arguments can not be turned into function pointers, so they are called instead.
+    let _ = |kind: &TagKind, compressed_tar: &[u8], destination: &Path| -> Result<PathBuf, io::Error> {
+        archive::extract_compressed(kind, compressed_tar, destination)
+    };
+    let _ = |kind: &TagKind,
+             compressed_tar: Box<dyn Read>,
+             destination: &Path,
+             options: &ExtractOptions|
+     -> Result<PathBuf, io::Error> {
+        archive::extract_compressed_with_options(kind, compressed_tar, destination, options)
+    };
+    let _ = ExtractOptions {
+        sanitize_modes: true,
+        preserve_mtime: false,
+    };
+    let _ = |compression: Compression,
+             compressed_tar: &[u8],
+             destination: &Path,
+             options: &ExtractOptions|
+     -> Result<PathBuf, ExtractError> {
+        archive::extract_with_compression(compression, compressed_tar, destination, options)
+    };
+    let _: fn(&str) -> Option<Compression> = Compression::from_file_name;
+    let _: fn(&str) -> Option<Compression> = Compression::from_content_type;
+    let _: fn(&GeAsset) -> Option<Compression> = GeAsset::compression;
+    let _: fn(DownloadedArchive, Compression) -> DownloadedArchive = DownloadedArchive::with_compression;
+    let _: fn(&DownloadedArchive, &Path) -> Result<ExtractedTool, ExtractError> = DownloadedArchive::extract_to;
+}
+
+#[test]
+fn config_signatures() {
+    let _: fn(&Path) -> Result<SteamConfig, SteamConfigError> = SteamConfig::create_copy;
+    let _: fn(&mut SteamConfig, &str) = SteamConfig::set_proton_version;
+    let _: fn(&Path) -> Result<LutrisConfig, LutrisConfigError> = LutrisConfig::create_copy;
+    let _: fn(&mut LutrisConfig, &str) = LutrisConfig::set_wine_version;
+}
+
+#[test]
+fn downloader_is_usable_as_trait_object() {
+    let downloader: Box<dyn GeDownload> = Box::new(GeDownloader::default());
+    let _: fn(&dyn GeDownload, TagKind) -> Result<Vec<GeRelease>, GithubError> = fetch_first_page;
+    let _: fn(&dyn GeDownload, DownloadRequest, &mut dyn Write) -> Result<ArchiveMetadata, GithubError> =
+        stream_archive;
+    drop(downloader);
+}
+
+#[test]
+fn release_asset_signatures() {
+    let _: fn(&GeRelease) -> Result<&GeAsset, GithubError> = GeRelease::tar_asset;
+    let _: fn(&GeRelease) -> Result<&GeAsset, GithubError> = GeRelease::checksum_asset;
+    let _: fn(GeAsset, u64) -> GeAsset = GeAsset::with_size;
+    let _: fn(GeAsset, SystemTime) -> GeAsset = GeAsset::with_created_at;
+}
+
+#[test]
+fn resume_download_signatures() {
+    let _ = |downloader: &dyn GeDownload, request: DownloadRequest| -> Option<u64> {
+        downloader
+            .resume_archive_download(request, Path::new("GE-Proton7-8.tar.gz"))
+            .ok()
+            .and_then(|metadata| metadata.resumed_from)
+    };
+}
+
+#[test]
+fn release_notes_signatures() {
+    let _: fn(&GeRelease) -> Option<&str> = GeRelease::changelog;
+    let _: fn(GeRelease) -> GeRelease = GeRelease::without_notes;
+    let _ = |downloader: &dyn GeDownload| -> Result<GeRelease, GithubError> {
+        downloader.fetch_release_without_notes(None, TagKind::Proton)
+    };
+    let _ = |downloader: &dyn GeDownload| -> Result<Vec<GeRelease>, GithubError> {
+        downloader.fetch_releases_without_notes(TagKind::Proton, 1)
+    };
+}
+
+#[test]
+fn fetch_latest_for_all_kinds_signature() {
+    let _ = |downloader: &dyn GeDownload| -> Vec<(TagKind, Result<GeRelease, GithubError>)> {
+        downloader.fetch_latest_for_all_kinds()
+    };
+}
+
+#[test]
+fn repository_source_signatures() {
+    let _: fn(&TagKind) -> RepositorySource = RepositorySource::official;
+    let _: fn(GeDownloader, [(TagKind, RepositorySource); 1]) -> GeDownloader = GeDownloader::with_sources;
+    let _: fn(GeDownloaderBuilder, Vec<(TagKind, RepositorySource)>) -> GeDownloaderBuilder =
+        GeDownloaderBuilder::with_sources;
+    assert_send_sync::<RepositorySource>();
+}
+
+#[test]
+fn downloaded_archive_helper_signatures() {
+    let _: fn(&DownloadedArchive, &Path) -> io::Result<PathBuf> = DownloadedArchive::write_to_dir;
+    let _: fn(&DownloadedArchive) -> String = DownloadedArchive::sha512;
+    let _: fn(&DownloadedArchive, &DownloadedChecksum) -> bool = DownloadedArchive::matches_checksum;
+}
+
+#[test]
+fn asset_content_type_signatures() {
+    let _ = |content_type: &str| -> AssetContentType { AssetContentType::from(content_type) };
+    let _: fn(&AssetContentType) -> Option<Compression> = AssetContentType::compression;
+    let _: fn(&AssetContentType) -> bool = AssetContentType::is_archive;
+    let _: fn(&GeAsset) -> AssetContentType = GeAsset::parsed_content_type;
+}
+
+#[test]
+fn asset_digest_signatures() {
+    let _: fn(&str) -> Option<AssetDigest> = AssetDigest::parse;
+    let _: fn(&AssetDigest, &Digest) -> Result<(), ChecksumError> = AssetDigest::verify;
+    let _: fn(&AssetDigest) -> Algorithm = AssetDigest::algorithm;
+    let _: fn(&GeAsset) -> Option<AssetDigest> = GeAsset::parsed_digest;
+    let _: fn(GeAsset, String) -> GeAsset = GeAsset::with_digest::<String>;
+    let _: fn(&[u8]) -> Digest = ge_man_lib::digest::sha256;
+    let _: fn(Algorithm, &[u8]) -> Digest = ge_man_lib::digest::digest;
+    let _: fn(DownloadedAssets, Verification) -> DownloadedAssets = DownloadedAssets::with_verification;
+    let _: fn(&Verification) -> bool = Verification::is_verified;
+}
+
+#[test]
+fn checksum_algorithm_signatures() {
+    let _: fn(&str) -> Option<Algorithm> = Algorithm::from_checksum_file_name;
+    let _: fn(&str, Algorithm) -> Result<ChecksumFile, ChecksumParseError> = ChecksumFile::parse_with;
+    let _: fn(&ChecksumFile) -> Algorithm = ChecksumFile::algorithm;
+    let _: fn(DownloadedChecksum, Algorithm) -> DownloadedChecksum = DownloadedChecksum::with_algorithm;
+    let _: fn(&DownloadedChecksum, &str, &[u8]) -> Result<(), ChecksumError> = DownloadedChecksum::verify;
+    let _: fn(&DownloadedChecksum) -> Result<ChecksumFile, ChecksumError> = DownloadedChecksum::parsed;
+    let _ = |checksum: &DownloadedChecksum| -> Option<Algorithm> { checksum.algorithm };
+    let _ = ChecksumError::UnknownAlgorithm {
+        file_name: String::from("GE-Proton7-8.md5sum"),
+    };
+}
+
+#[test]
+fn prerelease_filter_signatures() {
+    let _ = |downloader: &dyn GeDownload| -> Result<Vec<GeRelease>, GithubError> {
+        downloader.list_latest_releases_filtered(TagKind::Proton, 5, false)
+    };
+    let _ = |downloader: &dyn GeDownload| -> Result<GeRelease, GithubError> {
+        downloader.fetch_latest_release(TagKind::Proton, false)
+    };
+    let _: fn(&GeRelease) -> bool = GeRelease::is_stable;
+}
+
+#[test]
+fn resolve_assets_signatures() {
+    let _ = |downloader: &dyn GeDownload, tag: &Tag| -> Result<ResolvedAssets, GithubError> {
+        downloader.resolve_assets(tag, TagKind::Proton)
+    };
+    let _: fn(&GeRelease) -> Result<ResolvedAssets, GithubError> = ResolvedAssets::from_release;
+}
+
+#[test]
+fn download_and_extract_signature() {
+    let _ = |downloader: &dyn GeDownload, request: DownloadRequest| -> Result<ExtractedTool, GithubError> {
+        downloader.download_and_extract(request, Path::new("compatibilitytools.d"))
+    };
+}
+
+#[test]
+fn api_url_signatures() {
+    let _: fn(GeDownloader, String) -> GeDownloader = GeDownloader::with_api_url::<String>;
+    let _: fn(GeDownloaderBuilder, String) -> GeDownloaderBuilder = GeDownloaderBuilder::with_api_url::<String>;
+}
+
+#[test]
+fn availability_signatures() {
+    let _: fn(&GeDownloader, &Tag, TagKind) -> Result<bool, GithubError> = GeDownloader::release_exists;
+    let _: fn(&GeDownloader, &GeRelease) -> Result<bool, GithubError> = GeDownloader::asset_available;
+}
+
+#[test]
+fn download_request_builder_signatures() {
+    let _: fn() -> DownloadRequestBuilder = DownloadRequest::builder;
+    let _: fn(DownloadRequestBuilder, String) -> DownloadRequestBuilder = DownloadRequestBuilder::with_tag::<String>;
+    let _: fn(DownloadRequestBuilder, TagKind) -> DownloadRequestBuilder = DownloadRequestBuilder::with_kind;
+    let _: fn(DownloadRequestBuilder, Box<dyn ReadProgressWrapper>) -> DownloadRequestBuilder =
+        DownloadRequestBuilder::with_progress_wrapper;
+    let _: fn(DownloadRequestBuilder) -> DownloadRequestBuilder = DownloadRequestBuilder::without_checksum;
+    let _: fn(DownloadRequestBuilder, bool) -> DownloadRequestBuilder = DownloadRequestBuilder::with_overwrite;
+    let _: fn(DownloadRequestBuilder) -> DownloadRequestBuilder = DownloadRequestBuilder::without_verification;
+    let _: fn(DownloadRequestBuilder, MissingChecksumPolicy) -> DownloadRequestBuilder =
+        DownloadRequestBuilder::with_missing_checksum;
+    let _: fn(DownloadRequest, bool) -> DownloadRequest = DownloadRequest::with_verify;
+    let _: fn(DownloadRequest, MissingChecksumPolicy) -> DownloadRequest = DownloadRequest::with_missing_checksum;
+    let _: fn(DownloadPlan, bool) -> DownloadPlan = DownloadPlan::with_verify;
+    let _: fn(DownloadRequestBuilder, u64) -> DownloadRequestBuilder = DownloadRequestBuilder::with_max_bytes_per_sec;
+    let _: fn(DownloadRequest, Option<u64>) -> DownloadRequest = DownloadRequest::with_max_bytes_per_sec;
+    let _: fn(DownloadPlan, Option<u64>) -> DownloadPlan = DownloadPlan::with_max_bytes_per_sec;
+    let _: fn(DownloadRequestBuilder) -> Result<DownloadRequest, DownloadRequestError> = DownloadRequestBuilder::build;
+    let _: Box<dyn ReadProgressWrapper> = Box::new(NoProgress);
+}
+
+#[test]
+fn fetch_all_tags_signatures() {
+    let _: fn(String) -> Result<Tag, TagParseError> = Tag::try_new::<String>;
+    let _ = |downloader: &dyn GeDownload, kind: TagKind| -> Result<FetchedTags, GithubError> {
+        downloader.fetch_all_tags(kind)
+    };
+}
+
+#[test]
+fn batch_download_signatures() {
+    let _: fn(Vec<DownloadRequest>) -> BatchDownload = BatchDownload::new;
+    let _: fn(BatchDownload, usize) -> BatchDownload = BatchDownload::with_concurrency;
+    let _: fn(BatchDownload, bool) -> BatchDownload = BatchDownload::with_fail_fast;
+    let _: fn(&BatchDownload) -> BatchCancelHandle = BatchDownload::cancel_handle;
+    let _: fn(BatchDownload, &GeDownloader) -> Vec<BatchItem> = BatchDownload::run::<GeDownloader>;
+    let _ = |batch: BatchDownload, downloader: &(dyn GeDownload + Sync)| -> Vec<BatchItem> { batch.run(downloader) };
+    let _: fn(&BatchCancelHandle) = BatchCancelHandle::cancel;
+    let _: fn(&BatchCancelHandle) -> bool = BatchCancelHandle::is_cancelled;
+}
+
+#[test]
+fn thread_safety() {
+    assert_send_sync::<GeDownloader>();
+    assert_send_sync::<Tag>();
+    assert_send_sync::<SemVer>();
+    assert_send_sync::<TagKind>();
+    assert_send_sync::<TagRange>();
+    assert_send_sync::<TagPattern>();
+    assert_send_sync::<DownloadPlan>();
+    assert_send_sync::<RetryPolicy>();
+    assert_send_sync::<GeDownloaderBuilder>();
+    assert_send_sync::<GithubError>();
+    assert_send_sync::<SteamConfigError>();
+    assert_send_sync::<LutrisConfigError>();
+    assert_send_sync::<TagKindError>();
+    assert_send_sync::<TagParseError>();
+    assert_send_sync::<SemVerError>();
+    assert_send_sync::<TagRangeError>();
+    assert_send_sync::<TagPatternError>();
+    assert_send_sync::<TimestampError>();
+    assert_send_sync::<DownloadRequestError>();
+    assert_send_sync::<BatchCancelHandle>();
+}
+
+#[test]
+fn tag_as_map_key() {
+    let mut installed: HashMap<Tag, TagKind> = HashMap::new();
+    installed.insert(Tag::new("6.16-GE-0-LoL"), TagKind::lol());
+    installed.insert(Tag::new("6.16-0-GE-LoL"), TagKind::lol());
+
+    assert_eq!(installed.len(), 2);
+    assert_eq!(installed.keys().latest(), Some(&Tag::new("6.16-GE-0-LoL")));
+}
+
+#[test]
+fn status_not_ok_fields() {
+    let _ = |err: GithubError| -> Option<(reqwest::StatusCode, String, String)> {
+        match err {
+            GithubError::StatusNotOk { status, url, body } => Some((status, url, body)),
+            _ => None,
+        }
+    };
+}
+
+#[test]
+fn error_categories() {
+    let err = TagKindError::UnknownString(String::from("unknown"));
+    let _: ErrorCategory = err.category();
+    let _: &dyn std::error::Error = &err;
+}